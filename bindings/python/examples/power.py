@@ -15,7 +15,9 @@ coal = model.add_var(name="Coal", lb=0.0, ub=180.0)
 wind = model.add_var(name="Wind", lb=0.0, ub=120.0)
 
 # Total generation must meet demand
-model.add_constraint((gas.expr().__add__(coal.expr()).__add__(wind.expr())).eq(300.0))
+model.add_constraint(
+    (gas.expr().__add__(coal.expr()).__add__(wind.expr())).eq(300.0), name="Demand"
+)
 
 # Gas emissions <= 0.5 * coal
 model.add_constraint(gas.leq(coal.__mul__(0.5)))
@@ -33,3 +35,10 @@ print(f"Optimal cost:     ${solution.objective_value:.1f}")
 print(f"Gas generation:   {solution.value(gas):.1f} MW")
 print(f"Coal generation:  {solution.value(coal):.1f} MW")
 print(f"Wind generation:  {solution.value(wind):.1f} MW")
+
+# How far can demand and the price of gas move before the generation mix changes?
+report = model.sensitivity()
+demand = report.rhs("Demand")
+gas_price = report.objective(gas)
+print(f"\nMarginal cost of demand: ${demand.rate:.1f}/MW for {demand.lower:.1f}-{demand.upper:.1f} MW")
+print(f"Gas price range:         ${gas_price.lower:.1f}-{gas_price.upper:.1f}")
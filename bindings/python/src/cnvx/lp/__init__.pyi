@@ -26,6 +26,21 @@ class Solution:
     def value(self, var: Var) -> float: ...
     def __repr__(self) -> str: ...
 
+class Range:
+    @property
+    def value(self) -> float: ...
+    @property
+    def lower(self) -> float: ...
+    @property
+    def upper(self) -> float: ...
+    @property
+    def rate(self) -> float: ...
+    def __repr__(self) -> str: ...
+
+class Sensitivity:
+    def objective(self, var: Var) -> Optional[Range]: ...
+    def rhs(self, constraint: int | str) -> Optional[Range]: ...
+
 class Model:
     def __new__(cls) -> Model: ...
     def add_var(
@@ -36,5 +51,6 @@ class Model:
     ) -> Var: ...
     def minimize(self, expr: LinExprPy, name: Optional[str] = None) -> None: ...
     def maximize(self, expr: LinExprPy, name: Optional[str] = None) -> None: ...
-    def add_constraint(self, c: ConstraintPy) -> None: ...
+    def add_constraint(self, c: ConstraintPy, name: str | None = None) -> None: ...
     def solve(self) -> Solution: ...
+    def sensitivity(self) -> Sensitivity: ...
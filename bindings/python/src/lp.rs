@@ -1,3 +1,5 @@
+use std::sync::Mutex;
+
 use cnvx_lp::*;
 use pyo3::prelude::*;
 
@@ -10,13 +12,15 @@ pub struct Var {
 #[pyclass]
 pub struct Model {
     inner: LpModel,
+    /// Solver used by the most recent `solve()`, kept for sensitivity analysis.
+    solver: Option<Mutex<LpSolver>>,
 }
 
 #[pymethods]
 impl Model {
     #[new]
     pub fn new() -> Self {
-        Self { inner: LpModel::new() }
+        Self { inner: LpModel::new(), solver: None }
     }
 
     /// model.add_var(name="Gas", lb=0.0, ub=200.0)
@@ -60,16 +64,36 @@ impl Model {
         self.inner.add_objective(obj);
     }
 
-    /// model.add_constraint(expr.eq(300.0))
-    pub fn add_constraint(&mut self, c: &ConstraintPy) {
-        self.inner += c.inner.clone();
+    /// model.add_constraint(expr.eq(300.0), name="Demand")
+    pub fn add_constraint(&mut self, c: &ConstraintPy, name: Option<&str>) {
+        let cons = c.inner.clone();
+        self.inner += match name {
+            Some(n) => cons.named(n),
+            None => cons,
+        };
     }
 
     pub fn solve(&mut self) -> PyResult<Solution> {
         let mut solver = LpSolver::new();
-        solver
+        let solution = solver
             .solve(&self.inner)
             .map(|s| Solution { inner: s })
+            .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+        self.solver = Some(Mutex::new(solver));
+        Ok(solution)
+    }
+
+    /// model.sensitivity() - ranging of the most recent solve
+    pub fn sensitivity(&self) -> PyResult<SensitivityPy> {
+        self.solver
+            .as_ref()
+            .ok_or_else(|| {
+                pyo3::exceptions::PyRuntimeError::new_err("model has not been solved")
+            })?
+            .lock()
+            .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?
+            .sensitivity(&self.inner)
+            .map(|s| SensitivityPy { inner: s })
             .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
     }
 }
@@ -182,6 +206,70 @@ impl Solution {
     }
 }
 
+/// A constraint given by index or by name.
+#[derive(FromPyObject)]
+pub enum ConstraintKey {
+    Index(usize),
+    Name(String),
+}
+
+#[pyclass(name = "Sensitivity")]
+pub struct SensitivityPy {
+    inner: Sensitivity,
+}
+
+#[pymethods]
+impl SensitivityPy {
+    /// sensitivity.objective(var) - Range of the objective coefficient
+    pub fn objective(&self, var: &Var) -> Option<RangePy> {
+        self.inner.objective_range(var.inner).map(|r| RangePy { inner: r })
+    }
+
+    /// sensitivity.rhs(0) or sensitivity.rhs("Demand") - Range of the right-hand side
+    pub fn rhs(&self, constraint: ConstraintKey) -> Option<RangePy> {
+        match constraint {
+            ConstraintKey::Index(i) => self.inner.rhs_range(i),
+            ConstraintKey::Name(n) => self.inner.rhs_range(n.as_str()),
+        }
+        .map(|r| RangePy { inner: r })
+    }
+}
+
+#[pyclass(name = "Range")]
+pub struct RangePy {
+    inner: SensitivityRange,
+}
+
+#[pymethods]
+impl RangePy {
+    #[getter]
+    pub fn value(&self) -> f64 {
+        self.inner.value
+    }
+
+    #[getter]
+    pub fn lower(&self) -> f64 {
+        self.inner.lower
+    }
+
+    #[getter]
+    pub fn upper(&self) -> f64 {
+        self.inner.upper
+    }
+
+    #[getter]
+    pub fn rate(&self) -> f64 {
+        self.inner.rate
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!(
+            "Range(value={}, lower={}, upper={}, rate={})",
+            self.inner.value, self.inner.lower, self.inner.upper, self.inner.rate
+        )
+    }
+}
+
 pub fn register(parent: &Bound<'_, PyModule>) -> PyResult<()> {
     let py = parent.py();
     let m = PyModule::new(py, "lp")?;
@@ -191,6 +279,8 @@ pub fn register(parent: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<LinExprPy>()?;
     m.add_class::<ConstraintPy>()?;
     m.add_class::<Solution>()?;
+    m.add_class::<SensitivityPy>()?;
+    m.add_class::<RangePy>()?;
 
     parent.add_submodule(&m)?;
     py.import("sys")?.getattr("modules")?.set_item("cnvx.lp", &m)?;
@@ -73,6 +73,17 @@ pub struct SolveArgs {
          value_hint = ValueHint::FilePath,
      )]
     pub language_type: Option<LanguageType>,
+
+    /// Print objective and right-hand side ranging after an optimal solve.
+    #[clap(long)]
+    pub sensitivity: bool,
+
+    /// How to scale the constraint matrix before solving [default: geometric-mean].
+    ///
+    /// Only the simplex and branch-and-bound solvers scale the model; a warning
+    /// is printed if the selected solver ignores this option.
+    #[clap(long, value_enum)]
+    pub scaling: Option<ScalingMethod>,
 }
 
 /// An input that is either stdin or a real path.
@@ -126,3 +137,39 @@ impl Display for LanguageType {
         }
     }
 }
+
+/// Scaling of the constraint matrix, see [`cnvx_lp::Scaling`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, ValueEnum)]
+pub enum ScalingMethod {
+    /// Solve the model as given.
+    #[value(name = "none")]
+    None,
+
+    /// Geometric-mean row and column scaling.
+    #[value(name = "geometric-mean")]
+    GeometricMean,
+
+    /// Row and column equilibration.
+    #[value(name = "equilibration")]
+    Equilibration,
+}
+
+impl Display for ScalingMethod {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ScalingMethod::None => f.write_str("none"),
+            ScalingMethod::GeometricMean => f.write_str("geometric-mean"),
+            ScalingMethod::Equilibration => f.write_str("equilibration"),
+        }
+    }
+}
+
+impl From<ScalingMethod> for cnvx_lp::Scaling {
+    fn from(method: ScalingMethod) -> Self {
+        match method {
+            ScalingMethod::None => cnvx_lp::Scaling::None,
+            ScalingMethod::GeometricMean => cnvx_lp::Scaling::GeometricMean,
+            ScalingMethod::Equilibration => cnvx_lp::Scaling::Equilibration,
+        }
+    }
+}
@@ -1,4 +1,7 @@
-use cnvx_lp::{LpSolver, Solver};
+use cnvx_core::SolveStatus;
+use cnvx_lp::{LpModel, LpSolver, Scaling, Sensitivity, SensitivityRange, Solver};
+
+use crate::args::ScalingMethod;
 
 /// Entry point for the `cnvx solve` command.
 ///
@@ -34,13 +37,74 @@ pub fn solve(
     let model = cnvx_parse::parse(&contents, &ext)
         .map_err(|e| format!("Failed to parse model: {e}"))?;
 
-    let mut solver = LpSolver::new();
-    println!("Using solver: {}", solver.name());
+    let scaling = command.args.scaling.map_or_else(Scaling::default, Scaling::from);
+    let mut solver = LpSolver::with_scaling(scaling);
+    match solver.selection_for(&model) {
+        Some(selection) => println!("Using solver: {selection}"),
+        None => println!("Using solver: {}", solver.name()),
+    }
+    if let Some(method) = command.args.scaling
+        && method != ScalingMethod::None
+        && let Some(selected) = solver.get_selected_solver(&model)
+        && !selected.capabilities().scaling
+    {
+        println!(
+            "Warning: {} does not scale the model, ignoring --scaling {method}",
+            selected.name()
+        );
+    }
 
     let solution = solver.solve(&model).map_err(|e| format!("Solver error: {e}"))?;
 
     // TODO: Also support writing to a file.
     println!("{}", solution);
 
+    if solution.status == SolveStatus::Infeasible {
+        match cnvx_lp::compute_iis(&model) {
+            Ok(iis) => print!("\nIrreducible infeasible subsystem:\n{iis}"),
+            Err(e) => println!("\nNo irreducible infeasible subsystem found: {e}"),
+        }
+    }
+
+    if command.args.sensitivity && solution.status != SolveStatus::Optimal {
+        println!("\nNo sensitivity analysis: ranging needs an optimal solution.");
+    } else if command.args.sensitivity {
+        let report = solver
+            .sensitivity(&model)
+            .map_err(|e| format!("Sensitivity analysis failed: {e}"))?;
+        print_sensitivity(&model, &report);
+    }
+
     Ok(())
 }
+
+/// Print the objective and right-hand side ranging tables of `report`.
+fn print_sensitivity(model: &LpModel, report: &Sensitivity) {
+    println!("\nObjective ranging:");
+    print_header();
+    for (var, range) in model.vars().iter().zip(&report.objective) {
+        let name = var.name.clone().unwrap_or_else(|| format!("x{}", var.id.0));
+        print_range(&name, range);
+    }
+
+    println!("\nRight-hand side ranging:");
+    print_header();
+    for (i, range) in report.rhs.iter().enumerate() {
+        let name = report.constraint_names[i].clone().unwrap_or_else(|| format!("c{i}"));
+        print_range(&name, range);
+    }
+}
+
+fn print_header() {
+    println!(
+        "{:<16} {:>12} {:>12} {:>12} {:>12}",
+        "name", "value", "lower", "upper", "rate"
+    );
+}
+
+fn print_range(name: &str, range: &SensitivityRange) {
+    println!(
+        "{:<16} {:>12.6} {:>12.6} {:>12.6} {:>12.6}",
+        name, range.value, range.lower, range.upper, range.rate
+    );
+}
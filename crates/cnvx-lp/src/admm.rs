@@ -0,0 +1,1192 @@
+//! Convex QPs and LPs, solved by the alternating direction method of
+//! multipliers (ADMM).
+//!
+//! The [`AdmmSolver`] is a first-order operator-splitting method in the style
+//! of OSQP. Each iteration costs one solve with a sparse factorisation that is
+//! computed once, so it scales to models whose dense simplex tableau would not
+//! fit in memory, at the price of moderately accurate solutions.
+
+use std::collections::BTreeMap;
+
+use cnvx_core::{Sense, SolveError, SolveStatus};
+use cnvx_math::{
+    Factorisation, Matrix, Regularisation, SparseCholesky, SparseMatrix, SymbolicCholesky,
+};
+
+use crate::qp::check_convex;
+use crate::{Capabilities, Certificate, Cmp, LpModel, LpSolution, Solver};
+
+/// Regularisation `σ` of the `x` block of the KKT matrix, which keeps it
+/// positive definite for LPs.
+const SIGMA: f64 = 1e-6;
+
+/// Over-relaxation parameter `α` of the iterations.
+const RELAXATION: f64 = 1.6;
+
+/// Equality rows use this multiple of the step size `ρ`, as their duals are
+/// never clipped at a bound.
+const EQUALITY_RHO_SCALE: f64 = 1e3;
+
+/// Smallest step size `ρ` the adaptive rule may choose.
+const RHO_MIN: f64 = 1e-6;
+
+/// Largest step size `ρ` the adaptive rule may choose.
+const RHO_MAX: f64 = 1e6;
+
+/// The KKT matrix is only refactored once the adaptive `ρ` has changed by
+/// more than this factor.
+const RHO_UPDATE_FACTOR: f64 = 5.0;
+
+/// The residuals are checked every this many iterations.
+const CHECK_INTERVAL: usize = 10;
+
+/// Iterations before the first update of the adaptive `ρ`.
+const RHO_UPDATE_INTERVAL: usize = 50;
+
+/// Rounds of Ruiz equilibration applied to the problem data.
+const SCALING_STEPS: usize = 10;
+
+/// Bounds of each equilibration factor.
+const SCALING_RANGE: (f64, f64) = (1e-4, 1e4);
+
+/// Regularisation `δ` of the KKT matrix `[P + δI, Aᵀ; A, -δI]` of the active
+/// rows solved when polishing. Iterative refinement against the unregularised
+/// matrix removes its effect.
+const POLISH_DELTA: f64 = 1e-6;
+
+/// Rounds of iterative refinement of each polishing solve.
+const REFINEMENT_STEPS: usize = 3;
+
+/// Relative tolerance of the certificates of primal and dual infeasibility.
+const INFEASIBILITY_TOL: f64 = 1e-5;
+
+/// A certificate is only accepted once it passes [`Certificate::verify`]
+/// against the model with this tolerance; until then the iterations go on.
+const CERTIFICATE_TOL: f64 = 1e-6;
+
+/// ADMM solver for convex quadratic programs and LPs, in the style of OSQP.
+///
+/// Minimizes `½ xᵀPx + qᵀx`, or maximizes its negation, subject to
+/// `l <= Ax <= u`, where the rows of `A` are the constraints of the model
+/// followed by one row for each bounded variable. Each iteration solves the
+/// quasi-definite KKT system `[P + σI, Aᵀ; A, -ρ⁻¹I]` with a
+/// [`SparseCholesky`] factorisation, projects onto the bounds, and updates the
+/// duals. The problem is equilibrated first, and the factorisation is computed
+/// once: it is only refactored when the adaptive step size `ρ`, which balances
+/// the primal and dual residuals, changes by more than a factor of five.
+///
+/// The iterations stop once the residuals fall below
+/// [`tolerance`](Self::tolerance), in absolute and relative terms, so the
+/// solutions are moderately accurate and not basic. With
+/// [`polish`](Self::polish) set, the solver then guesses the active
+/// constraints from the duals and solves for them exactly, which usually
+/// recovers a highly accurate solution. Differences of successive
+/// iterates converge to certificates of primal or dual infeasibility, which
+/// the solution carries as its [`Certificate`] once they verify against the
+/// model. If [`max_iter`](Self::max_iter) iterations pass first, the solution
+/// holds the last iterate, with status [`Other`](SolveStatus::Other).
+///
+/// [`warm_start`](Self::warm_start) starts the next solve from any
+/// [`LpSolution`] of a related model, and [`resolve`](Solver::resolve) from
+/// the previous solution of this solver.
+///
+/// # Examples
+///
+/// ```rust
+/// # use cnvx_lp::*;
+/// let mut model = LpModel::new();
+/// let x = model.add_var().name("x").finish();
+/// let y = model.add_var().name("y").finish();
+/// model += (x + y).leq(4.0).named("capacity");
+/// model += (x + 3.0 * y).leq(6.0).named("labour");
+/// model.add_objective(Objective::maximize(3.0 * x + 2.0 * y).name("profit"));
+///
+/// let solution = AdmmSolver::new().solve(&model).unwrap();
+/// assert!((solution.value(x) - 4.0).abs() < 1e-4);
+/// assert!(solution.value(y).abs() < 1e-4);
+/// assert!((solution.objective_value.unwrap() - 12.0).abs() < 1e-4);
+/// assert!((solution.dual("capacity").unwrap() - 3.0).abs() < 1e-4);
+/// ```
+///
+/// Re-solving a mean-variance portfolio with a higher required return,
+/// starting from the previous solution:
+///
+/// ```rust
+/// # use cnvx_lp::*;
+/// let mut model = LpModel::new();
+/// let stocks = model.add_var().name("stocks").finish();
+/// let bonds = model.add_var().name("bonds").finish();
+/// model += (stocks + bonds).eq(1.0).named("budget");
+/// model += (0.10 * stocks + 0.04 * bonds).geq(0.07).named("return");
+///
+/// let variance = 0.04 * (stocks * stocks) + 0.01 * (bonds * bonds)
+///     + 0.004 * (stocks * bonds);
+/// model.add_objective(Objective::minimize(variance).name("risk"));
+///
+/// let mut solver = AdmmSolver::new();
+/// solver.solve(&model).unwrap();
+/// assert!(solver.has_warm_start());
+///
+/// model.constraints[1].rhs = 0.08;
+/// let solution = solver.resolve(&model).unwrap();
+/// assert!((solution.value(stocks) - 2.0 / 3.0).abs() < 1e-4);
+/// ```
+///
+/// [`LpSolver`](crate::LpSolver) routes LPs to it once their dense simplex
+/// tableau would hold more than 10⁸ entries:
+///
+/// ```rust
+/// # use cnvx_lp::*;
+/// let mut model = LpModel::new();
+/// let vars: Vec<_> = (0..10_000).map(|_| model.add_var().finish()).collect();
+/// for pair in vars.windows(2) {
+///     model += (pair[0] + pair[1]).geq(1.0);
+/// }
+/// model.add_objective(Objective::minimize(1.0 * vars[0]).name("cost"));
+///
+/// let selection = LpSolver::new().selection_for(&model).unwrap();
+/// assert_eq!(selection.solver, "admm");
+/// ```
+pub struct AdmmSolver {
+    /// Absolute and relative tolerance on the primal and dual residuals.
+    pub tolerance: f64,
+    /// The maximum number of ADMM iterations before terminating.
+    pub max_iter: usize,
+    /// Initial step size `ρ` of the inequality rows.
+    pub rho: f64,
+    /// Whether to adapt `ρ` to the ratio of the primal and dual residuals.
+    pub adaptive_rho: bool,
+    /// Whether to polish the solution by solving for the active constraints.
+    pub polish: bool,
+    /// Whether to log iteration details during solving.
+    pub logging: bool,
+
+    /// Starting point for the next solve, set by [`warm_start`](Self::warm_start).
+    start: Option<Start>,
+    /// Solution of the most recent optimal solve, the starting point of
+    /// [`resolve`](Solver::resolve).
+    previous: Option<Start>,
+    /// Cached objective value from the most recent solve.
+    last_objective: Option<f64>,
+    /// Cached solution vector from the most recent solve.
+    last_solution: Vec<f64>,
+}
+
+impl AdmmSolver {
+    /// Creates a new ADMM solver with default settings.
+    pub fn new() -> Self {
+        Self {
+            tolerance: 1e-4,
+            max_iter: 20_000,
+            rho: 0.1,
+            adaptive_rho: true,
+            polish: true,
+            logging: false,
+            start: None,
+            previous: None,
+            last_objective: None,
+            last_solution: Vec::new(),
+        }
+    }
+
+    /// Start the next solve from `solution`, e.g. the solution of a closely
+    /// related model found by any solver.
+    ///
+    /// The primal values give the starting `x`, and the shadow prices and
+    /// reduced costs, if present, the starting duals. Entries for variables or
+    /// constraints the model no longer has are ignored, and new ones start at
+    /// zero.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use cnvx_lp::*;
+    /// let mut model = LpModel::new();
+    /// let x = model.add_var().finish();
+    /// let y = model.add_var().finish();
+    /// model += (x + 2.0 * y).geq(2.0);
+    /// model += (3.0 * x + y).geq(3.0);
+    /// model.add_objective(Objective::minimize(x + y).name("cost"));
+    ///
+    /// let simplex = PrimalSimplexSolver::new().solve(&model).unwrap();
+    ///
+    /// let mut admm = AdmmSolver::new();
+    /// admm.warm_start(&simplex);
+    /// let solution = admm.solve(&model).unwrap();
+    /// assert!((solution.objective_value.unwrap() - 1.4).abs() < 1e-4);
+    /// ```
+    pub fn warm_start(&mut self, solution: &LpSolution) {
+        self.start = Some(Start::from(solution));
+    }
+
+    /// Solve `model`, starting from `start` if given.
+    fn run(
+        &mut self,
+        model: &LpModel,
+        start: Option<Start>,
+    ) -> Result<LpSolution, SolveError> {
+        crate::validate::check_qp(model)?;
+
+        let mut state = AdmmState::new(model, self.rho)?;
+        state.logging = self.logging;
+        state.polish = self.polish;
+        if let Some(start) = &start {
+            state.start_from(start);
+        }
+        state.solve(model, self.max_iter, self.tolerance, self.adaptive_rho)?;
+
+        let solution = match state.status {
+            SolveStatus::Optimal | SolveStatus::Other(_) => {
+                let values = state.values();
+                let objective = model.objective().expect("checked by check_qp");
+                let value = objective.evaluate(&values) - objective.expr.constant;
+                let mut solution =
+                    LpSolution::new(values, Some(value), state.status.clone())
+                        .with_activities(model);
+                state.attach_duals(model, &mut solution);
+                if solution.status == SolveStatus::Optimal {
+                    self.previous = Some(Start::from(&solution));
+                }
+                solution
+            }
+            status => {
+                let mut solution = LpSolution::new(Vec::new(), None, status);
+                solution.certificate = state.certificate.take();
+                solution
+            }
+        };
+        Ok(self.finish(state.iteration, solution))
+    }
+
+    /// Record the outcome of a solve and return its [`LpSolution`].
+    fn finish(&mut self, iterations: usize, solution: LpSolution) -> LpSolution {
+        if self.logging {
+            println!(
+                "ADMM finished with status {:?} in {} iterations. Objective value: {}",
+                solution.status,
+                iterations,
+                solution.objective_value.unwrap_or_default()
+            );
+        }
+
+        self.last_objective = solution.objective_value;
+        self.last_solution = solution.values.clone();
+        solution
+    }
+}
+
+impl Default for AdmmSolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Solver for AdmmSolver {
+    fn name(&self) -> &str {
+        "admm"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            warm_start: true,
+            quadratic_objective: true,
+            ..Capabilities::default()
+        }
+    }
+
+    /// Solve the convex QP or LP `model`, starting from the solution passed
+    /// to [`warm_start`](AdmmSolver::warm_start), if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`check_qp`](crate::validate::check_qp),
+    /// [`SolveError::InvalidModel`] if the objective is not convex (or, when
+    /// maximized, concave), and [`SolveError::NumericalFailure`] if the KKT
+    /// matrix cannot be factored.
+    fn solve(&mut self, model: &LpModel) -> Result<LpSolution, SolveError> {
+        let start = self.start.take();
+        self.run(model, start)
+    }
+
+    /// Re-solve `model`, starting from the solution passed to
+    /// [`warm_start`](AdmmSolver::warm_start), or else from the solution of
+    /// the previous solve.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`solve`](Solver::solve).
+    fn resolve(&mut self, model: &LpModel) -> Result<LpSolution, SolveError> {
+        let start = self.start.take().or_else(|| self.previous.clone());
+        self.run(model, start)
+    }
+
+    fn has_warm_start(&self) -> bool {
+        self.start.is_some() || self.previous.is_some()
+    }
+
+    fn objective_value(&self) -> Option<f64> {
+        self.last_objective
+    }
+
+    fn solution_vector(&self) -> Vec<f64> {
+        self.last_solution.clone()
+    }
+}
+
+/// A starting point: primal values, shadow prices and reduced costs, indexed
+/// like those of an [`LpSolution`].
+#[derive(Clone)]
+struct Start {
+    values: Vec<f64>,
+    duals: Vec<f64>,
+    reduced_costs: Vec<f64>,
+}
+
+impl From<&LpSolution> for Start {
+    fn from(solution: &LpSolution) -> Self {
+        Self {
+            values: solution.values.clone(),
+            duals: solution.duals.clone(),
+            reduced_costs: solution.reduced_costs.clone(),
+        }
+    }
+}
+
+/// The KKT matrix `[P + σI, Aᵀ; A, -ρ⁻¹I]`, of which only the `ρ` block
+/// changes, factored with one symbolic analysis.
+struct Kkt {
+    /// Number of columns `n` of `A`.
+    n: usize,
+    /// Lower triangle of the matrix.
+    matrix: SparseMatrix,
+    /// Storage position in `matrix` of the diagonal entry of each row of `A`.
+    diag: Vec<usize>,
+    /// The symbolic analysis of the pattern.
+    symbolic: SymbolicCholesky,
+    /// The factorisation, once the matrix has been factored.
+    chol: Option<SparseCholesky>,
+}
+
+impl Kkt {
+    /// Analyse the KKT pattern for the columns `cols` of an `A` with `m` rows
+    /// and the quadratic form with diagonal `p_diag` and strictly lower
+    /// triangle `p_lower`.
+    fn new(
+        cols: &[Vec<(usize, f64)>],
+        m: usize,
+        p_diag: &[f64],
+        p_lower: &[(usize, usize, f64)],
+    ) -> Result<Self, SolveError> {
+        let n = cols.len();
+        let mut triplets: Vec<(usize, usize, f64)> =
+            p_diag.iter().enumerate().map(|(j, p)| (j, j, p + SIGMA)).collect();
+        // Unit entries keep the `ρ` diagonal in the pattern until it is set.
+        triplets.extend((n..n + m).map(|k| (k, k, -1.0)));
+        triplets.extend_from_slice(p_lower);
+        for (j, col) in cols.iter().enumerate() {
+            triplets.extend(col.iter().map(|&(i, a)| (n + i, j, a)));
+        }
+        let matrix = SparseMatrix::from_triplets(n + m, n + m, &triplets);
+
+        // The diagonal entry leads each column of the lower triangle.
+        let mut diag = Vec::with_capacity(m);
+        let mut pos = 0;
+        for k in 0..n + m {
+            if k >= n {
+                diag.push(pos);
+            }
+            pos += matrix.col(k).count();
+        }
+
+        let symbolic =
+            SymbolicCholesky::analyse(&matrix).map_err(SolveError::NumericalFailure)?;
+        Ok(Self { n, matrix, diag, symbolic, chol: None })
+    }
+
+    /// Set the `ρ` block to `-ρ⁻¹` and factor the matrix.
+    fn factor(&mut self, rho: &[f64]) -> Result<(), SolveError> {
+        let values = self.matrix.values_mut();
+        for (&p, r) in self.diag.iter().zip(rho) {
+            values[p] = -1.0 / r;
+        }
+
+        let chol = match self.chol.take() {
+            Some(mut chol) => {
+                chol.refactor(&self.matrix).map_err(SolveError::NumericalFailure)?;
+                chol
+            }
+            None => {
+                let reg = Regularisation::quasi_definite(self.n, self.diag.len());
+                SparseCholesky::factor_regularised(&self.symbolic, &self.matrix, reg)
+                    .map_err(SolveError::NumericalFailure)?
+            }
+        };
+        self.chol = Some(chol);
+        Ok(())
+    }
+
+    /// Solve the KKT system for `rhs`, in place.
+    fn solve(&self, rhs: &mut [f64]) {
+        self.chol.as_ref().expect("factored before solving").solve(rhs);
+    }
+}
+
+/// Internal state of the ADMM iterations.
+///
+/// The model is converted to `min ½ xᵀPx + qᵀx` subject to `l <= Ax <= u`,
+/// with a row of `A` for each constraint and then for each variable with a
+/// bound, and equilibrated to `P̄ = c D P D`, `q̄ = c D q`, `Ā = E A D`,
+/// `l̄ = E l` and `ū = E u`, for diagonal `D` and `E` and a scalar `c`. The
+/// iterates `x`, `z` (an estimate of `Ax` within the bounds) and `y` are those
+/// of the scaled problem; the original ones are `D x`, `E⁻¹ z` and `E y / c`.
+/// The dual is `Px + q + Aᵀy = 0`, with `y_i >= 0` at an upper bound and
+/// `y_i <= 0` at a lower bound.
+#[derive(Clone)]
+struct AdmmState {
+    /// Current iteration count.
+    iteration: usize,
+    /// Outcome of the iterations.
+    status: SolveStatus,
+    /// Certificate of infeasibility or unboundedness, if one was found.
+    certificate: Option<Certificate>,
+
+    /// Scaled primal iterate `x`.
+    x: Vec<f64>,
+    /// Scaled projection `z` of `Ax` onto the bounds.
+    z: Vec<f64>,
+    /// Scaled row duals `y`.
+    y: Vec<f64>,
+    /// Step size `ρ` of each row.
+    rho: Vec<f64>,
+    /// Step size `ρ` of the inequality rows.
+    rho_base: f64,
+
+    /// Sparse columns of the scaled constraint matrix, as `(row, coefficient)`
+    /// pairs.
+    cols: Vec<Vec<(usize, f64)>>,
+    /// Scaled linear objective coefficients `q` (minimisation form).
+    q: Vec<f64>,
+    /// Diagonal of the scaled `P` (minimisation form).
+    p_diag: Vec<f64>,
+    /// Strictly lower triangle of the scaled `P` as `(row, column, value)`
+    /// triplets.
+    p_lower: Vec<(usize, usize, f64)>,
+    /// Scaled lower bound of each row (`-inf` if absent).
+    lower: Vec<f64>,
+    /// Scaled upper bound of each row (`inf` if absent).
+    upper: Vec<f64>,
+    /// Column scaling `D`.
+    col_scale: Vec<f64>,
+    /// Row scaling `E`.
+    row_scale: Vec<f64>,
+    /// Objective scaling `c`.
+    cost_scale: f64,
+    /// Row of `A` holding the bounds of each variable, if it has any.
+    bound_rows: Vec<Option<usize>>,
+    /// Number of rows of `A` that are constraints of the model.
+    n_cons: usize,
+    /// Whether the QP is a minimization problem.
+    minimise: bool,
+    /// Whether to polish the iterate once the active set settles.
+    polish: bool,
+    /// Whether to log iteration details.
+    logging: bool,
+}
+
+impl AdmmState {
+    /// Initialize the equilibrated state from `model`, starting at zero with
+    /// step size `rho`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SolveError::InvalidModel`] if the objective is not convex in
+    /// its sense.
+    fn new(model: &LpModel, rho: f64) -> Result<Self, SolveError> {
+        let n = model.vars().len();
+        let objective = model.objective().expect("checked by check_qp");
+        let minimise = objective.sense == Sense::Minimize;
+        let sign = if minimise { 1.0 } else { -1.0 };
+
+        let mut cols: Vec<Vec<(usize, f64)>> = vec![Vec::new(); n];
+        let mut lower = Vec::new();
+        let mut upper = Vec::new();
+        for (i, cons) in model.constraints().iter().enumerate() {
+            let rhs = cons.rhs - cons.expr.constant;
+            let (l, u) = match cons.cmp {
+                Cmp::LEQ => (f64::NEG_INFINITY, rhs),
+                Cmp::GEQ => (rhs, f64::INFINITY),
+                Cmp::EQ => (rhs, rhs),
+            };
+            lower.push(l);
+            upper.push(u);
+            for term in &cons.expr.terms {
+                let col = &mut cols[term.var.0];
+                match col.last_mut() {
+                    Some((row, coeff)) if *row == i => *coeff += term.coeff,
+                    _ => col.push((i, term.coeff)),
+                }
+            }
+        }
+        let n_cons = lower.len();
+
+        let mut bound_rows = vec![None; n];
+        for var in model.vars() {
+            if var.lb.is_none() && var.ub.is_none() {
+                continue;
+            }
+            let j = var.id.0;
+            bound_rows[j] = Some(lower.len());
+            cols[j].push((lower.len(), 1.0));
+            lower.push(var.lb.unwrap_or(f64::NEG_INFINITY));
+            upper.push(var.ub.unwrap_or(f64::INFINITY));
+        }
+
+        let mut q = vec![0.0; n];
+        for term in &objective.expr.terms {
+            q[term.var.0] += sign * term.coeff;
+        }
+
+        // `coeff * x_i * x_j` contributes `coeff` to both `P_ij` and `P_ji`,
+        // and `2 * coeff` to `P_ii`.
+        let mut p_diag = vec![0.0; n];
+        let mut off: BTreeMap<(usize, usize), f64> = BTreeMap::new();
+        for term in &objective.quad {
+            let (i, j) = (term.var1.0, term.var2.0);
+            if i == j {
+                p_diag[i] += sign * 2.0 * term.coeff;
+            } else {
+                *off.entry((i.max(j), i.min(j))).or_default() += sign * term.coeff;
+            }
+        }
+        let p_lower: Vec<_> = off
+            .into_iter()
+            .filter(|&(_, v)| v != 0.0)
+            .map(|((i, j), v)| (i, j, v))
+            .collect();
+        check_convex(&p_diag, &p_lower, minimise)?;
+
+        let m = lower.len();
+        let mut state = Self {
+            iteration: 0,
+            status: SolveStatus::NotSolved,
+            certificate: None,
+            x: vec![0.0; n],
+            z: vec![0.0; m],
+            y: vec![0.0; m],
+            rho: Vec::new(),
+            rho_base: rho,
+            cols,
+            q,
+            p_diag,
+            p_lower,
+            lower,
+            upper,
+            col_scale: vec![1.0; n],
+            row_scale: vec![1.0; m],
+            cost_scale: 1.0,
+            bound_rows,
+            n_cons,
+            minimise,
+            polish: true,
+            logging: false,
+        };
+        state.equilibrate();
+        state.rho = state.row_rho(rho);
+        Ok(state)
+    }
+
+    /// Scale the problem data with Ruiz equilibration, which divides each row
+    /// and column of `[P, Aᵀ; A, 0]` by the square root of its largest entry
+    /// until all of them are close to one, and then scale the objective.
+    fn equilibrate(&mut self) {
+        let n = self.x.len();
+        let m = self.lower.len();
+        let factor = |norm: f64| {
+            if norm > 0.0 {
+                (1.0 / norm.sqrt()).clamp(SCALING_RANGE.0, SCALING_RANGE.1)
+            } else {
+                1.0
+            }
+        };
+
+        for _ in 0..SCALING_STEPS {
+            let mut col_norm: Vec<f64> = self.p_diag.iter().map(|p| p.abs()).collect();
+            for &(i, j, p) in &self.p_lower {
+                col_norm[i] = col_norm[i].max(p.abs());
+                col_norm[j] = col_norm[j].max(p.abs());
+            }
+            let mut row_norm = vec![0.0f64; m];
+            for (j, col) in self.cols.iter().enumerate() {
+                for &(i, a) in col {
+                    col_norm[j] = col_norm[j].max(a.abs());
+                    row_norm[i] = row_norm[i].max(a.abs());
+                }
+            }
+            let d: Vec<f64> = col_norm.into_iter().map(factor).collect();
+            let e: Vec<f64> = row_norm.into_iter().map(factor).collect();
+
+            for (j, &d_j) in d.iter().enumerate() {
+                self.p_diag[j] *= d_j * d_j;
+                self.q[j] *= d_j;
+                self.col_scale[j] *= d_j;
+                for (i, a) in &mut self.cols[j] {
+                    *a *= e[*i] * d_j;
+                }
+            }
+            for (i, j, p) in &mut self.p_lower {
+                *p *= d[*i] * d[*j];
+            }
+            for (i, &e_i) in e.iter().enumerate() {
+                self.lower[i] *= e_i;
+                self.upper[i] *= e_i;
+                self.row_scale[i] *= e_i;
+            }
+        }
+
+        // Bring the largest of the mean column norm of `P` and of `q` to one.
+        let mut col_norm: Vec<f64> = self.p_diag.iter().map(|p| p.abs()).collect();
+        for &(i, j, p) in &self.p_lower {
+            col_norm[i] = col_norm[i].max(p.abs());
+            col_norm[j] = col_norm[j].max(p.abs());
+        }
+        let mean = col_norm.iter().sum::<f64>() / n.max(1) as f64;
+        let scale = mean.max(norm(&self.q));
+        let cost = if scale > 0.0 {
+            (1.0 / scale).clamp(SCALING_RANGE.0, SCALING_RANGE.1)
+        } else {
+            1.0
+        };
+        self.p_diag.iter_mut().for_each(|p| *p *= cost);
+        self.p_lower.iter_mut().for_each(|(_, _, p)| *p *= cost);
+        self.q.iter_mut().for_each(|q| *q *= cost);
+        self.cost_scale = cost;
+    }
+
+    /// Step size of each row for the inequality step size `rho`.
+    fn row_rho(&self, rho: f64) -> Vec<f64> {
+        self.lower
+            .iter()
+            .zip(&self.upper)
+            .map(|(l, u)| if l == u { EQUALITY_RHO_SCALE * rho } else { rho })
+            .collect()
+    }
+
+    /// Start from the primal values, shadow prices and reduced costs of
+    /// `start`, and the projection of `Ax` onto the bounds.
+    fn start_from(&mut self, start: &Start) {
+        let sign = if self.minimise { 1.0 } else { -1.0 };
+        for (j, x) in self.x.iter_mut().enumerate() {
+            *x = start.values.get(j).copied().unwrap_or_default() / self.col_scale[j];
+        }
+
+        // Original duals `y = -sign * dual` of the constraints, and
+        // `y = -sign * reduced cost` of the bound rows.
+        let mut y = vec![0.0; self.y.len()];
+        for (i, dual) in start.duals.iter().take(self.n_cons).enumerate() {
+            y[i] = -sign * dual;
+        }
+        for (j, row) in self.bound_rows.iter().enumerate() {
+            if let (Some(row), Some(cost)) = (row, start.reduced_costs.get(j)) {
+                y[*row] = -sign * cost;
+            }
+        }
+        for (i, y) in y.into_iter().enumerate() {
+            self.y[i] = self.cost_scale * y / self.row_scale[i];
+        }
+
+        let ax = self.a_mul(&self.x);
+        self.z = (0..ax.len())
+            .map(|i| ax[i].clamp(self.lower[i], self.upper[i]))
+            .collect();
+    }
+
+    /// Run the ADMM iterations until the residuals fall below `tol` or the
+    /// iterates certify infeasibility or unboundedness of `model`, the model
+    /// the state was built from. The status is [`Other`](SolveStatus::Other)
+    /// if neither happens within `max_iter` iterations.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SolveError::NumericalFailure`] if the KKT matrix cannot be
+    /// factored.
+    fn solve(
+        &mut self,
+        model: &LpModel,
+        max_iter: usize,
+        tol: f64,
+        adaptive_rho: bool,
+    ) -> Result<(), SolveError> {
+        let n = self.x.len();
+        let m = self.z.len();
+        let mut kkt = Kkt::new(&self.cols, m, &self.p_diag, &self.p_lower)?;
+        kkt.factor(&self.rho)?;
+        // Changing `ρ` too often keeps the iterations from converging, so the
+        // wait between updates doubles after each one.
+        let mut rho_wait = RHO_UPDATE_INTERVAL;
+        let mut next_rho_update = rho_wait;
+        // Active sets of the last check and of the last polishing attempt.
+        let mut settled = Vec::new();
+        let mut polished = Vec::new();
+
+        for iter in 0..max_iter {
+            self.iteration = iter;
+
+            let mut rhs: Vec<f64> =
+                (0..n).map(|j| SIGMA * self.x[j] - self.q[j]).collect();
+            rhs.extend((0..m).map(|i| self.z[i] - self.y[i] / self.rho[i]));
+            kkt.solve(&mut rhs);
+
+            let mut dx = Vec::with_capacity(n);
+            for (x, &x_tilde) in self.x.iter_mut().zip(&rhs[..n]) {
+                let relaxed = RELAXATION * x_tilde + (1.0 - RELAXATION) * *x;
+                dx.push(relaxed - *x);
+                *x = relaxed;
+            }
+            let mut dy = Vec::with_capacity(m);
+            for i in 0..m {
+                let z_tilde = self.z[i] + (rhs[n + i] - self.y[i]) / self.rho[i];
+                let relaxed = RELAXATION * z_tilde + (1.0 - RELAXATION) * self.z[i];
+                let z = (relaxed + self.y[i] / self.rho[i])
+                    .clamp(self.lower[i], self.upper[i]);
+                let step = self.rho[i] * (relaxed - z);
+                dy.push(step);
+                self.y[i] += step;
+                self.z[i] = z;
+            }
+
+            if (iter + 1) % CHECK_INTERVAL != 0 && iter + 1 != max_iter {
+                continue;
+            }
+
+            let res = self.residuals();
+            if self.logging {
+                println!(
+                    "Iteration {:>5}: primal = {:>10.3e}, dual = {:>10.3e}, rho = {:>10.3e}",
+                    iter + 1,
+                    self.primal_residual(&res),
+                    self.dual_residual(&res),
+                    self.rho_base
+                );
+            }
+
+            // Polish once the active set has settled, and not twice for the
+            // same one.
+            let active = self.active_set();
+            if self.polish && active == settled && active != polished {
+                if self.polish(&active, tol)? {
+                    self.status = SolveStatus::Optimal;
+                    return Ok(());
+                }
+                polished = active.clone();
+            }
+            settled = active;
+
+            if let Some(status) = self.check_termination(model, &res, &dx, &dy, tol) {
+                let unbounded = status == SolveStatus::Unbounded;
+                if self.polish && status == SolveStatus::Optimal {
+                    // Keep the polished iterate unless it is less accurate.
+                    let residual = self.relative_residual(&res);
+                    self.polish(&self.active_set(), residual)?;
+                }
+                self.status = status;
+                if unbounded {
+                    self.confirm_unbounded(model, max_iter, tol)?;
+                }
+                return Ok(());
+            }
+
+            if adaptive_rho && iter + 1 >= next_rho_update && self.adapt_rho(&res) {
+                kkt.factor(&self.rho)?;
+                rho_wait *= 2;
+                next_rho_update = iter + 1 + rho_wait;
+            }
+        }
+
+        self.status = SolveStatus::Other("iteration limit reached".into());
+        Ok(())
+    }
+
+    /// Decide whether the iterate is optimal to within `tol`, or whether the
+    /// last steps `dx` and `dy` certify dual infeasibility
+    /// ([`Unbounded`](SolveStatus::Unbounded)) or primal infeasibility
+    /// ([`Infeasible`](SolveStatus::Infeasible)) of `model`, and keep the
+    /// certificate.
+    fn check_termination(
+        &mut self,
+        model: &LpModel,
+        res: &Residuals,
+        dx: &[f64],
+        dy: &[f64],
+        tol: f64,
+    ) -> Option<SolveStatus> {
+        if self.is_optimal(res, tol) {
+            return Some(SolveStatus::Optimal);
+        }
+        let n = self.x.len();
+        let m = self.z.len();
+        let e = &self.row_scale;
+        let dc: Vec<f64> = self.col_scale.iter().map(|d| d * self.cost_scale).collect();
+        let unscale_rows =
+            |v: &[f64]| -> Vec<f64> { (0..m).map(|i| v[i] / e[i]).collect() };
+        let unscale_cols =
+            |v: &[f64]| -> Vec<f64> { (0..n).map(|j| v[j] / dc[j]).collect() };
+
+        // Certificate of primal infeasibility: `Aᵀδy = 0` with
+        // `uᵀ max(δy, 0) + lᵀ min(δy, 0) < 0`, where `δy` may only move
+        // towards the finite bounds.
+        let dy: Vec<f64> = (0..m)
+            .map(|i| match (self.lower[i].is_finite(), self.upper[i].is_finite()) {
+                (true, true) => dy[i],
+                (true, false) => dy[i].min(0.0),
+                (false, true) => dy[i].max(0.0),
+                (false, false) => 0.0,
+            })
+            .collect();
+        let farkas: Vec<f64> = (0..m).map(|i| e[i] * dy[i]).collect();
+        let dy_norm = norm(&farkas);
+        if dy_norm > 0.0 {
+            let support: f64 = (0..m)
+                .map(|i| match dy[i] {
+                    d if d > 0.0 => self.upper[i] * d,
+                    d if d < 0.0 => self.lower[i] * d,
+                    _ => 0.0,
+                })
+                .sum();
+            let aty: Vec<f64> = self
+                .at_mul(&dy)
+                .iter()
+                .zip(&self.col_scale)
+                .map(|(v, d)| v / d)
+                .collect();
+            let certificate = Certificate::infeasible(farkas[..self.n_cons].to_vec());
+            if norm(&aty) <= INFEASIBILITY_TOL * dy_norm
+                && support < -INFEASIBILITY_TOL * dy_norm
+                && certificate.verify(model, CERTIFICATE_TOL).is_ok()
+            {
+                self.certificate = Some(certificate);
+                return Some(SolveStatus::Infeasible);
+            }
+        }
+
+        // Certificate of dual infeasibility: `Pδx = 0`, `qᵀδx < 0` and `Aδx`
+        // within the recession cone of the bounds.
+        let ray: Vec<f64> = (0..n).map(|j| self.col_scale[j] * dx[j]).collect();
+        let dx_norm = norm(&ray);
+        if dx_norm > 0.0 {
+            let eps = INFEASIBILITY_TOL * dx_norm;
+            let slope = dot(&self.q, dx) / self.cost_scale;
+            let adx = unscale_rows(&self.a_mul(dx));
+            let recedes = (0..m).all(|i| {
+                (!self.upper[i].is_finite() || adx[i] <= eps)
+                    && (!self.lower[i].is_finite() || adx[i] >= -eps)
+            });
+            let certificate = Certificate::unbounded(ray);
+            if slope < -eps
+                && recedes
+                && norm(&unscale_cols(&self.p_mul(dx))) <= eps
+                && certificate.verify(model, CERTIFICATE_TOL).is_ok()
+            {
+                self.certificate = Some(certificate);
+                return Some(SolveStatus::Unbounded);
+            }
+        }
+        None
+    }
+
+    /// Whether the original primal and dual residuals of the iterate are below
+    /// `tol`, in absolute and relative terms.
+    fn is_optimal(&self, res: &Residuals, tol: f64) -> bool {
+        self.relative_residual(res) <= tol
+    }
+
+    /// The larger of the original primal and dual residuals, each relative to
+    /// one plus the size of the terms it is made of.
+    fn relative_residual(&self, res: &Residuals) -> f64 {
+        let rows = |v: &[f64]| {
+            v.iter()
+                .zip(&self.row_scale)
+                .map(|(v, e)| (v / e).abs())
+                .fold(0.0, f64::max)
+        };
+        let cols = |v: &[f64]| {
+            v.iter()
+                .zip(&self.col_scale)
+                .map(|(v, d)| (v / (d * self.cost_scale)).abs())
+                .fold(0.0, f64::max)
+        };
+        let primal_scale = rows(&res.ax).max(rows(&self.z));
+        let dual_scale = cols(&res.px).max(cols(&res.aty)).max(cols(&self.q));
+        (self.primal_residual(res) / (1.0 + primal_scale))
+            .max(self.dual_residual(res) / (1.0 + dual_scale))
+    }
+
+    /// The rows the duals mark as active: `-1` at the lower bound, `1` at the
+    /// upper bound, and `0` for the others.
+    fn active_set(&self) -> Vec<i8> {
+        (0..self.z.len())
+            .map(|i| {
+                if self.z[i] - self.lower[i] < -self.y[i] {
+                    -1
+                } else if self.upper[i] - self.z[i] < self.y[i] {
+                    1
+                } else {
+                    0
+                }
+            })
+            .collect()
+    }
+
+    /// Polish the iterate by solving the equality-constrained QP whose
+    /// constraints are the rows of `active` held at their active bound, and
+    /// keep the result if its [relative residual](Self::relative_residual) is
+    /// at most `threshold`.
+    ///
+    /// Returns whether the polished iterate was kept.
+    fn polish(&mut self, active: &[i8], threshold: f64) -> Result<bool, SolveError> {
+        let n = self.x.len();
+        let rows: Vec<usize> = (0..active.len()).filter(|&i| active[i] != 0).collect();
+        let k = rows.len();
+        let mut index = vec![None; active.len()];
+        for (r, &i) in rows.iter().enumerate() {
+            index[i] = Some(r);
+        }
+
+        let mut triplets: Vec<(usize, usize, f64)> = self
+            .p_diag
+            .iter()
+            .enumerate()
+            .map(|(j, p)| (j, j, p + POLISH_DELTA))
+            .collect();
+        triplets.extend((n..n + k).map(|r| (r, r, -POLISH_DELTA)));
+        triplets.extend_from_slice(&self.p_lower);
+        for (j, col) in self.cols.iter().enumerate() {
+            for &(i, a) in col {
+                if let Some(r) = index[i] {
+                    triplets.push((n + r, j, a));
+                }
+            }
+        }
+        let matrix = SparseMatrix::from_triplets(n + k, n + k, &triplets);
+        let symbolic =
+            SymbolicCholesky::analyse(&matrix).map_err(SolveError::NumericalFailure)?;
+        let reg = Regularisation::quasi_definite(n, k);
+        let Ok(chol) = SparseCholesky::factor_regularised(&symbolic, &matrix, reg) else {
+            return Ok(false);
+        };
+
+        let mut rhs: Vec<f64> = self.q.iter().map(|q| -q).collect();
+        rhs.extend(
+            rows.iter().map(
+                |&i| {
+                    if active[i] < 0 { self.lower[i] } else { self.upper[i] }
+                },
+            ),
+        );
+        let mut sol = rhs.clone();
+        chol.solve(&mut sol);
+        for _ in 0..REFINEMENT_STEPS {
+            // Residual `rhs - K v` of the unregularised system.
+            let mut r = rhs.clone();
+            let px = self.p_mul(&sol[..n]);
+            for j in 0..n {
+                r[j] -= px[j];
+                for &(i, a) in &self.cols[j] {
+                    if let Some(row) = index[i] {
+                        r[j] -= a * sol[n + row];
+                        r[n + row] -= a * sol[j];
+                    }
+                }
+            }
+            chol.solve(&mut r);
+            sol.iter_mut().zip(&r).for_each(|(v, d)| *v += d);
+        }
+
+        // Duals of the wrong sign for their bound are dropped.
+        let mut y = vec![0.0; active.len()];
+        for (r, &i) in rows.iter().enumerate() {
+            y[i] = match active[i] {
+                _ if self.lower[i] == self.upper[i] => sol[n + r],
+                -1 => sol[n + r].min(0.0),
+                _ => sol[n + r].max(0.0),
+            };
+        }
+        let x = sol[..n].to_vec();
+        let z = self
+            .a_mul(&x)
+            .iter()
+            .enumerate()
+            .map(|(i, v)| v.clamp(self.lower[i], self.upper[i]))
+            .collect();
+
+        let previous = (
+            std::mem::replace(&mut self.x, x),
+            std::mem::replace(&mut self.z, z),
+            std::mem::replace(&mut self.y, y),
+        );
+        if self.relative_residual(&self.residuals()) <= threshold {
+            return Ok(true);
+        }
+        (self.x, self.z, self.y) = previous;
+        Ok(false)
+    }
+
+    /// Dual infeasibility means the problem is unbounded if it has a feasible
+    /// point at all; decide by solving it with a zero objective, and report
+    /// it infeasible otherwise. If that solve reaches the iteration limit, so
+    /// does this one.
+    fn confirm_unbounded(
+        &mut self,
+        model: &LpModel,
+        max_iter: usize,
+        tol: f64,
+    ) -> Result<(), SolveError> {
+        let mut feasibility = self.clone();
+        feasibility.q.fill(0.0);
+        feasibility.x.fill(0.0);
+        feasibility.z.fill(0.0);
+        feasibility.y.fill(0.0);
+        feasibility.logging = false;
+        feasibility.solve(model, max_iter, tol, false)?;
+        if feasibility.status != SolveStatus::Optimal {
+            self.status = feasibility.status;
+            self.certificate = feasibility.certificate;
+        }
+        Ok(())
+    }
+
+    /// Balance the primal and dual residuals by scaling `ρ` with the square
+    /// root of their ratio. Returns whether `ρ` changed enough to refactor.
+    fn adapt_rho(&mut self, res: &Residuals) -> bool {
+        let m = self.z.len();
+        let rp: Vec<f64> = (0..m).map(|i| res.ax[i] - self.z[i]).collect();
+        let rd: Vec<f64> = (0..self.x.len())
+            .map(|j| res.px[j] + self.q[j] + res.aty[j])
+            .collect();
+        let primal = norm(&rp) / norm(&res.ax).max(norm(&self.z)).max(f64::MIN_POSITIVE);
+        let dual = norm(&rd)
+            / norm(&res.px)
+                .max(norm(&res.aty))
+                .max(norm(&self.q))
+                .max(f64::MIN_POSITIVE);
+        let ratio = (primal / dual.max(f64::MIN_POSITIVE)).sqrt().clamp(0.1, 10.0);
+        let rho = (self.rho_base * ratio).clamp(RHO_MIN, RHO_MAX);
+        if rho > RHO_UPDATE_FACTOR * self.rho_base
+            || rho * RHO_UPDATE_FACTOR < self.rho_base
+        {
+            self.rho_base = rho;
+            self.rho = self.row_rho(rho);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Products of the scaled data with the current iterate.
+    fn residuals(&self) -> Residuals {
+        Residuals {
+            ax: self.a_mul(&self.x),
+            px: self.p_mul(&self.x),
+            aty: self.at_mul(&self.y),
+        }
+    }
+
+    /// Largest entry of the original primal residual `Ax - z`.
+    fn primal_residual(&self, res: &Residuals) -> f64 {
+        (0..self.z.len())
+            .map(|i| ((res.ax[i] - self.z[i]) / self.row_scale[i]).abs())
+            .fold(0.0, f64::max)
+    }
+
+    /// Largest entry of the original dual residual `Px + q + Aᵀy`.
+    fn dual_residual(&self, res: &Residuals) -> f64 {
+        (0..self.x.len())
+            .map(|j| {
+                let r = res.px[j] + self.q[j] + res.aty[j];
+                (r / (self.col_scale[j] * self.cost_scale)).abs()
+            })
+            .fold(0.0, f64::max)
+    }
+
+    /// The original primal values `D x`.
+    fn values(&self) -> Vec<f64> {
+        self.x.iter().zip(&self.col_scale).map(|(x, d)| d * x).collect()
+    }
+
+    /// Fill in the shadow prices and reduced costs of `solution`, in the sense
+    /// of the original objective. The reduced cost of a variable is the
+    /// gradient of the objective at the solution, minus the shadow prices
+    /// times its constraint coefficients.
+    fn attach_duals(&self, model: &LpModel, solution: &mut LpSolution) {
+        let sign = if self.minimise { 1.0 } else { -1.0 };
+        solution.duals = (0..self.n_cons)
+            .map(|i| -sign * self.row_scale[i] * self.y[i] / self.cost_scale)
+            .collect();
+
+        let mut reduced_costs = vec![0.0; model.vars().len()];
+        if let Some(obj) = model.objective() {
+            for term in &obj.expr.terms {
+                reduced_costs[term.var.0] += term.coeff;
+            }
+            for term in &obj.quad {
+                reduced_costs[term.var1.0] += term.coeff * solution.values[term.var2.0];
+                reduced_costs[term.var2.0] += term.coeff * solution.values[term.var1.0];
+            }
+        }
+        for (cons, dual) in model.constraints().iter().zip(&solution.duals) {
+            for term in &cons.expr.terms {
+                reduced_costs[term.var.0] -= dual * term.coeff;
+            }
+        }
+        solution.reduced_costs = reduced_costs;
+    }
+
+    /// The product `A v` of the scaled constraint matrix.
+    fn a_mul(&self, v: &[f64]) -> Vec<f64> {
+        let mut out = vec![0.0; self.z.len()];
+        for (col, v) in self.cols.iter().zip(v) {
+            for &(i, a) in col {
+                out[i] += a * v;
+            }
+        }
+        out
+    }
+
+    /// The product `Aᵀ v` of the scaled constraint matrix.
+    fn at_mul(&self, v: &[f64]) -> Vec<f64> {
+        self.cols
+            .iter()
+            .map(|col| col.iter().map(|&(i, a)| a * v[i]).sum())
+            .collect()
+    }
+
+    /// The product `P v` of the scaled quadratic form.
+    fn p_mul(&self, v: &[f64]) -> Vec<f64> {
+        let mut out: Vec<f64> = self.p_diag.iter().zip(v).map(|(p, v)| p * v).collect();
+        for &(i, j, p) in &self.p_lower {
+            out[i] += p * v[j];
+            out[j] += p * v[i];
+        }
+        out
+    }
+}
+
+/// Products of the scaled data with an iterate, shared by the termination
+/// checks and the `ρ` update.
+struct Residuals {
+    /// `A x`.
+    ax: Vec<f64>,
+    /// `P x`.
+    px: Vec<f64>,
+    /// `Aᵀ y`.
+    aty: Vec<f64>,
+}
+
+/// Dot product of `a` and `b`.
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
+}
+
+/// Largest magnitude of the entries of `v`.
+fn norm(v: &[f64]) -> f64 {
+    v.iter().fold(0.0f64, |m, x| m.max(x.abs()))
+}
@@ -0,0 +1,1212 @@
+//! Second-order cone programs, solved by a primal-dual interior-point method.
+//!
+//! The [`ConicSolver`] handles models with [`ConeConstraint`](crate::ConeConstraint)s
+//! next to their linear constraints, such as norm minimisation, robust LPs
+//! with ellipsoidal uncertainty, or the epigraphs of convex quadratics written
+//! as rotated cones. Its solutions carry shadow prices and reduced costs of
+//! the linear constraints like those of the LP solvers.
+
+use cnvx_core::{Sense, SolveError, SolveStatus};
+use cnvx_math::{
+    Factorisation, Matrix, Regularisation, SparseCholesky, SparseMatrix, SymbolicCholesky,
+};
+
+use crate::{Capabilities, Certificate, Cmp, LpModel, LpSolution, Solver};
+
+/// Fraction of the distance to the boundary of the cone taken by each step.
+const STEP_FRACTION: f64 = 0.99;
+
+/// The iterations stop once the step length falls below this value.
+const MIN_STEP: f64 = 1e-12;
+
+/// The iterations stop once an iterate exceeds this magnitude.
+const DIVERGENCE: f64 = 1e12;
+
+/// Static regularisation `δ` of the KKT matrix
+/// `[δI, Aᵀ, Gᵀ; A, -δI, 0; G, 0, -W² - δI]`. Iterative refinement against the
+/// unregularised matrix removes its effect.
+const KKT_DELTA: f64 = 1e-9;
+
+/// Largest regularisation `δ` tried before giving up. When an iteration
+/// fails, typically because small pivots of free columns cancel, `δ` grows a
+/// hundredfold and the iteration is retried.
+const MAX_KKT_DELTA: f64 = 1e-5;
+
+/// Largest relative residual of the KKT solves of the starting point.
+const START_TOL: f64 = 1e-6;
+
+/// Rounds of iterative refinement of each KKT solve.
+const REFINEMENT_STEPS: usize = 3;
+
+/// Primal-dual interior-point solver for second-order cone programs.
+///
+/// Minimizes or maximizes a linear objective subject to the linear
+/// constraints, variable bounds and [cone constraints](crate::ConeConstraint)
+/// of the model. Rotated cones are solved in their equivalent
+/// [Lorentz form](crate::ConeConstraint::lorentz).
+///
+/// Follows the central path of the homogeneous self-dual embedding of the
+/// problem with Mehrotra predictor-corrector steps, scaled symmetrically
+/// with the Nesterov–Todd scaling `W` of each cone. Like the
+/// [`InteriorPointSolver`](crate::InteriorPointSolver), it needs no feasible
+/// starting point and reports infeasible and unbounded problems from the
+/// iterates themselves, with a [`Certificate`] for linear programs and
+/// unbounded cone programs. A linear program whose embedding reaches neither
+/// a solution nor a certificate is classified by the simplex method instead.
+///
+/// Each iteration factors the quasi-definite KKT matrix
+/// `[0, Aᵀ, Gᵀ; A, 0, 0; G, 0, -W²]` with a [`SparseCholesky`] factorisation,
+/// whose symbolic analysis is shared by all iterations. `W²` is dense within
+/// each cone, so a cone with `k` arguments adds `k²/2` entries to the matrix.
+///
+/// # Examples
+///
+/// The point of the half-plane `x + y <= 1` closest to `(3, 4)`:
+///
+/// ```rust
+/// # use cnvx_lp::*;
+/// let mut model = LpModel::new();
+/// let x = model.add_var().free().name("x").finish();
+/// let y = model.add_var().free().name("y").finish();
+/// let t = model.add_var().name("t").finish();
+/// model += (x + y).leq(1.0).named("half-plane");
+/// model += norm2([1.0 * x + -3.0, 1.0 * y + -4.0]).leq(t);
+/// model.add_objective(Objective::minimize(1.0 * t).name("distance"));
+///
+/// let solution = ConicSolver::new().solve(&model).unwrap();
+/// assert!((solution.value(x) - 0.0).abs() < 1e-6);
+/// assert!((solution.value(y) - 1.0).abs() < 1e-6);
+/// assert!((solution.objective_value.unwrap() - 3.0 * 2f64.sqrt()).abs() < 1e-6);
+///
+/// // Moving the half-plane out by one brings it 1/√2 closer.
+/// let dual = solution.dual("half-plane").unwrap();
+/// assert!((dual + 0.5f64.sqrt()).abs() < 1e-6);
+/// ```
+///
+/// A robust LP, whose constraint `x + y <= 2` must hold for every perturbation
+/// of its coefficients of norm at most one half:
+///
+/// ```rust
+/// # use cnvx_lp::*;
+/// let mut model = LpModel::new();
+/// let x = model.add_var().finish();
+/// let y = model.add_var().finish();
+/// model += norm2([0.5 * x, 0.5 * y]).leq(LinExpr::constant(2.0) - (x + y));
+/// model.add_objective(Objective::maximize(x + y).name("Z"));
+///
+/// let selection = LpSolver::new().selection_for(&model).unwrap();
+/// assert_eq!(selection.solver, "conic-interior-point");
+///
+/// let solution = LpSolver::new().solve(&model).unwrap();
+/// let expected = 4.0 / (2.0 + 0.5f64.sqrt());
+/// assert!((solution.objective_value.unwrap() - expected).abs() < 1e-6);
+/// ```
+///
+/// The unit disc does not reach `x >= 2`:
+///
+/// ```rust
+/// # use cnvx_core::SolveStatus;
+/// # use cnvx_lp::*;
+/// let mut model = LpModel::new();
+/// let x = model.add_var().lower_bound(2.0).finish();
+/// let y = model.add_var().free().finish();
+/// model += norm2([x, y]).leq(1.0);
+/// model.add_objective(Objective::minimize(1.0 * y).name("Z"));
+///
+/// let solution = ConicSolver::new().solve(&model).unwrap();
+/// assert_eq!(solution.status, SolveStatus::Infeasible);
+/// ```
+pub struct ConicSolver {
+    /// Relative tolerance on the primal and dual residuals and the duality gap.
+    pub tolerance: f64,
+    /// The maximum number of interior-point iterations before terminating.
+    pub max_iter: usize,
+    /// Whether to log iteration details during solving.
+    pub logging: bool,
+
+    /// Cached objective value from the most recent solve.
+    last_objective: Option<f64>,
+    /// Cached solution vector from the most recent solve.
+    last_solution: Vec<f64>,
+}
+
+impl ConicSolver {
+    /// Creates a new conic solver with default settings.
+    pub fn new() -> Self {
+        Self {
+            tolerance: 1e-8,
+            max_iter: 100,
+            logging: false,
+            last_objective: None,
+            last_solution: Vec::new(),
+        }
+    }
+
+    /// Record the outcome of a solve and return its [`LpSolution`].
+    fn finish(&mut self, iterations: usize, solution: LpSolution) -> LpSolution {
+        if self.logging {
+            println!(
+                "Conic interior point finished with status {:?} in {} iterations. Objective value: {}",
+                solution.status,
+                iterations,
+                solution.objective_value.unwrap_or_default()
+            );
+        }
+
+        self.last_objective = solution.objective_value;
+        self.last_solution = solution.values.clone();
+        solution
+    }
+}
+
+impl Default for ConicSolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Solver for ConicSolver {
+    fn name(&self) -> &str {
+        "conic-interior-point"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities { conic_constraints: true, ..Capabilities::default() }
+    }
+
+    /// Solve the second-order cone program `model`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`check_socp`](crate::validate::check_socp), and
+    /// [`SolveError::NumericalFailure`] or [`SolveError::Other`] if the
+    /// iterations fail before reaching a solution or a certificate of
+    /// infeasibility.
+    fn solve(&mut self, model: &LpModel) -> Result<LpSolution, SolveError> {
+        crate::validate::check_socp(model)?;
+
+        let mut state = ConicState::new(model);
+        state.logging = self.logging;
+        if let Err(err) = state.solve(self.max_iter, self.tolerance) {
+            // The embedding of an ill-posed LP can approach neither a solution
+            // nor a certificate; the simplex method decides those instead.
+            if model.cones().is_empty()
+                && let Some(solution) = crate::qp::classify(model)?
+            {
+                return Ok(self.finish(state.iteration, solution));
+            }
+            return Err(err);
+        }
+
+        if state.status != SolveStatus::Optimal {
+            let mut solution = LpSolution::new(Vec::new(), None, state.status.clone());
+            solution.certificate = state.certificate;
+            return Ok(self.finish(state.iteration, solution));
+        }
+
+        let values: Vec<f64> = state.x.iter().map(|x| x / state.tau).collect();
+        let objective = model.objective().expect("checked by check_socp");
+        let value = objective.evaluate(&values) - objective.expr.constant;
+        let mut solution = LpSolution::new(values, Some(value), SolveStatus::Optimal)
+            .with_activities(model);
+        state.attach_duals(model, &mut solution);
+        Ok(self.finish(state.iteration, solution))
+    }
+
+    fn objective_value(&self) -> Option<f64> {
+        self.last_objective
+    }
+
+    fn solution_vector(&self) -> Vec<f64> {
+        self.last_solution.clone()
+    }
+}
+
+/// The cone `K` of the slacks `s = h - Gx`: a non-negative orthant over the
+/// first rows, followed by a second-order cone over each block of rows.
+#[derive(Clone)]
+struct Blocks {
+    /// Number of rows of the orthant.
+    orthant: usize,
+    /// First row and number of rows of each second-order cone.
+    cones: Vec<(usize, usize)>,
+}
+
+impl Blocks {
+    /// Number of rows of `K`.
+    fn len(&self) -> usize {
+        self.cones.last().map_or(self.orthant, |&(start, dim)| start + dim)
+    }
+
+    /// Degree of `K`: one for each orthant row and each cone.
+    fn degree(&self) -> usize {
+        self.orthant + self.cones.len()
+    }
+
+    /// The identity `e` of the Jordan product, at the centre of `K`.
+    fn identity(&self) -> Vec<f64> {
+        let mut e = vec![1.0; self.orthant];
+        e.resize(self.len(), 0.0);
+        for &(start, _) in &self.cones {
+            e[start] = 1.0;
+        }
+        e
+    }
+
+    /// The Jordan product `u ∘ v`: elementwise on the orthant, and
+    /// `(uᵀv, u₀ v₁ + v₀ u₁)` on each cone.
+    fn product(&self, u: &[f64], v: &[f64]) -> Vec<f64> {
+        let mut out: Vec<f64> =
+            u[..self.orthant].iter().zip(v).map(|(u, v)| u * v).collect();
+        for &(start, dim) in &self.cones {
+            let (u, v) = (&u[start..start + dim], &v[start..start + dim]);
+            out.push(dot(u, v));
+            out.extend((1..dim).map(|k| u[0] * v[k] + v[0] * u[k]));
+        }
+        out
+    }
+
+    /// The solution `x` of `l ∘ x = d`, for `l` inside `K`.
+    fn divide(&self, l: &[f64], d: &[f64]) -> Vec<f64> {
+        let mut out: Vec<f64> =
+            d[..self.orthant].iter().zip(l).map(|(d, l)| d / l).collect();
+        for &(start, dim) in &self.cones {
+            let (l, d) = (&l[start..start + dim], &d[start..start + dim]);
+            let x0 = (l[0] * d[0] - dot(&l[1..], &d[1..])) / lorentz_det(l);
+            out.push(x0);
+            out.extend((1..dim).map(|k| (d[k] - x0 * l[k]) / l[0]));
+        }
+        out
+    }
+
+    /// Longest step `α` with `u + α du` in `K`, for `u` inside `K`.
+    fn max_step(&self, u: &[f64], du: &[f64]) -> f64 {
+        let mut alpha = f64::INFINITY;
+        for (u, du) in u[..self.orthant].iter().zip(du) {
+            if *du < 0.0 {
+                alpha = alpha.min(-u / du);
+            }
+        }
+        for &(start, dim) in &self.cones {
+            let (u, du) = (&u[start..start + dim], &du[start..start + dim]);
+            alpha = alpha.min(cone_step(u, du));
+        }
+        alpha
+    }
+
+    /// Move `v` inside `K` along the identity if it is not already.
+    fn shift_inside(&self, mut v: Vec<f64>) -> Vec<f64> {
+        let mut outside = f64::NEG_INFINITY;
+        for &v in &v[..self.orthant] {
+            outside = outside.max(-v);
+        }
+        for &(start, dim) in &self.cones {
+            let v = &v[start..start + dim];
+            outside = outside.max(euclidean(&v[1..]) - v[0]);
+        }
+        if outside >= 0.0 {
+            let e = self.identity();
+            v.iter_mut().zip(e).for_each(|(v, e)| *v += (1.0 + outside) * e);
+        }
+        v
+    }
+}
+
+/// Largest step `α` with `u + α du` in the second-order cone, for `u` inside
+/// it. The boundary is the smallest positive root of
+/// `(u₀ + α du₀)² - ||u₁ + α du₁||² = 0`.
+fn cone_step(u: &[f64], du: &[f64]) -> f64 {
+    let a = du[0] * du[0] - dot(&du[1..], &du[1..]);
+    let b = 2.0 * (u[0] * du[0] - dot(&u[1..], &du[1..]));
+    let c = lorentz_det(u);
+    if a.abs() <= f64::EPSILON * (du[0] * du[0] + dot(&du[1..], &du[1..])) {
+        return if b < 0.0 { -c / b } else { f64::INFINITY };
+    }
+    let disc = b * b - 4.0 * a * c;
+    if disc < 0.0 {
+        return f64::INFINITY;
+    }
+    // Roots `q / a` and `c / q`, computed without cancellation.
+    let q = -0.5 * (b + b.signum() * disc.sqrt());
+    let roots = [q / a, c / q];
+    roots.into_iter().filter(|r| *r > 0.0).fold(f64::INFINITY, f64::min)
+}
+
+/// The Nesterov–Todd scaling `W` of a pair `s`, `z` inside `K`, the
+/// symmetric matrix with `W z = W⁻¹ s`.
+///
+/// On the orthant `W` is diagonal. On each cone it is `η W̄` with
+/// `W̄ = [w₀, w₁ᵀ; w₁, I + w₁ w₁ᵀ / (1 + w₀)]`, whose square is `2 w wᵀ - J`
+/// for `J = diag(1, -I)`.
+struct Scaling {
+    /// `√(s_i / z_i)` of each orthant row.
+    diag: Vec<f64>,
+    /// `η` and `w` of each cone.
+    cones: Vec<(f64, Vec<f64>)>,
+}
+
+impl Scaling {
+    /// The scaling `W = I`.
+    fn identity(blocks: &Blocks) -> Self {
+        let cones = blocks
+            .cones
+            .iter()
+            .map(|&(_, dim)| {
+                let mut w = vec![0.0; dim];
+                w[0] = 1.0;
+                (1.0, w)
+            })
+            .collect();
+        Self { diag: vec![1.0; blocks.orthant], cones }
+    }
+
+    /// The scaling of `s` and `z`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SolveError::NumericalFailure`] if either is not strictly
+    /// inside `K`.
+    fn new(blocks: &Blocks, s: &[f64], z: &[f64]) -> Result<Self, SolveError> {
+        let outside = || {
+            SolveError::NumericalFailure(
+                "conic iterates left the interior of the cone".into(),
+            )
+        };
+        let mut diag = Vec::with_capacity(blocks.orthant);
+        for (s, z) in s[..blocks.orthant].iter().zip(z) {
+            if !(*s > 0.0 && *z > 0.0) {
+                return Err(outside());
+            }
+            diag.push((s / z).sqrt());
+        }
+
+        let mut cones = Vec::with_capacity(blocks.cones.len());
+        for &(start, dim) in &blocks.cones {
+            let (s, z) = (&s[start..start + dim], &z[start..start + dim]);
+            let (s_det, z_det) = (lorentz_det(s), lorentz_det(z));
+            if !(s_det > 0.0 && z_det > 0.0 && s[0] > 0.0 && z[0] > 0.0) {
+                return Err(outside());
+            }
+            let (s_norm, z_norm) = (s_det.sqrt(), z_det.sqrt());
+            let gamma = ((1.0 + dot(s, z) / (s_norm * z_norm)) / 2.0).sqrt();
+            let mut w: Vec<f64> =
+                (0..dim).map(|k| s[k] / s_norm - z[k] / z_norm).collect();
+            w[0] = s[0] / s_norm + z[0] / z_norm;
+            w.iter_mut().for_each(|w| *w /= 2.0 * gamma);
+            cones.push(((s_norm / z_norm).sqrt(), w));
+        }
+        Ok(Self { diag, cones })
+    }
+
+    /// The product `W v`, or `W⁻¹ v` if `inverse`.
+    fn apply(&self, blocks: &Blocks, v: &[f64], inverse: bool) -> Vec<f64> {
+        let mut out: Vec<f64> = v[..blocks.orthant]
+            .iter()
+            .zip(&self.diag)
+            .map(|(v, d)| if inverse { v / d } else { v * d })
+            .collect();
+        // `W⁻¹ = J W̄ J / η`, which flips the sign of `w₁`.
+        let sign = if inverse { -1.0 } else { 1.0 };
+        for (&(start, dim), (eta, w)) in blocks.cones.iter().zip(&self.cones) {
+            let v = &v[start..start + dim];
+            let scale = if inverse { 1.0 / eta } else { *eta };
+            let w1v1 = sign * dot(&w[1..], &v[1..]);
+            out.push(scale * (w[0] * v[0] + w1v1));
+            let coeff = v[0] + w1v1 / (1.0 + w[0]);
+            out.extend((1..dim).map(|k| scale * (v[k] + sign * coeff * w[k])));
+        }
+        out
+    }
+}
+
+/// The KKT matrix `[δI, Aᵀ, Gᵀ; A, -δI, 0; G, 0, -W² - δI]`, whose
+/// off-diagonal blocks are fixed, factored with one symbolic analysis for all iterations.
+struct Kkt {
+    /// Number of columns of `A` and `G`.
+    n: usize,
+    /// Number of rows of `A`.
+    p: usize,
+    /// Lower triangle of the matrix.
+    matrix: SparseMatrix,
+    /// Storage position in `matrix` of the diagonal of the `x` and `y` blocks
+    /// and of each orthant row.
+    diag: Vec<usize>,
+    /// Storage positions in `matrix` of the lower triangle of each cone
+    /// block of `W²`, column by column.
+    cones: Vec<Vec<usize>>,
+    /// The symbolic analysis of the pattern.
+    symbolic: SymbolicCholesky,
+    /// The factorisation, once the matrix has been factored.
+    chol: Option<SparseCholesky>,
+    /// The regularisation `δ`.
+    delta: f64,
+}
+
+impl Kkt {
+    /// Analyse the KKT pattern of `A`, `G` and the cone blocks.
+    fn new(
+        a: &SparseMatrix,
+        g: &SparseMatrix,
+        blocks: &Blocks,
+    ) -> Result<Self, SolveError> {
+        let (n, p, m) = (a.cols(), a.rows(), g.rows());
+        let dim = n + p + m;
+        // Unit entries keep the whole diagonal and every cone block in the
+        // pattern.
+        let mut triplets: Vec<(usize, usize, f64)> =
+            (0..n + p + blocks.orthant).map(|k| (k, k, 1.0)).collect();
+        for &(start, size) in &blocks.cones {
+            let first = n + p + start;
+            for j in first..first + size {
+                triplets.extend((j..first + size).map(|i| (i, j, 1.0)));
+            }
+        }
+        for j in 0..n {
+            triplets.extend(a.col(j).map(|(i, v)| (n + i, j, v)));
+            triplets.extend(g.col(j).map(|(i, v)| (n + p + i, j, v)));
+        }
+        let matrix = SparseMatrix::from_triplets(dim, dim, &triplets);
+
+        let mut starts = Vec::with_capacity(dim);
+        let mut pos = 0;
+        for k in 0..dim {
+            starts.push(pos);
+            pos += matrix.col(k).count();
+        }
+        let position = |i: usize, j: usize| {
+            starts[j] + matrix.col(j).position(|(r, _)| r == i).expect("in the pattern")
+        };
+        let diag = (0..n + p + blocks.orthant).map(|k| position(k, k)).collect();
+        let cones = blocks
+            .cones
+            .iter()
+            .map(|&(start, size)| {
+                let first = n + p + start;
+                (first..first + size)
+                    .flat_map(|j| (j..first + size).map(move |i| (i, j)))
+                    .map(|(i, j)| position(i, j))
+                    .collect()
+            })
+            .collect();
+
+        let symbolic =
+            SymbolicCholesky::analyse(&matrix).map_err(SolveError::NumericalFailure)?;
+        Ok(Self {
+            n,
+            p,
+            matrix,
+            diag,
+            cones,
+            symbolic,
+            chol: None,
+            delta: KKT_DELTA,
+        })
+    }
+
+    /// Grow the regularisation `δ` a hundredfold, unless it has reached
+    /// [`MAX_KKT_DELTA`]. Returns whether it grew.
+    fn strengthen(&mut self) -> bool {
+        if self.delta >= MAX_KKT_DELTA {
+            return false;
+        }
+        self.delta *= 100.0;
+        true
+    }
+
+    /// Set the `-W²` block to that of `scaling` and factor the matrix.
+    fn factor(&mut self, scaling: &Scaling) -> Result<(), SolveError> {
+        let (n, p, delta) = (self.n, self.p, self.delta);
+        let values = self.matrix.values_mut();
+        for (k, &pos) in self.diag.iter().enumerate() {
+            values[pos] = if k < n {
+                delta
+            } else if k < n + p {
+                -delta
+            } else {
+                -scaling.diag[k - n - p].powi(2) - delta
+            };
+        }
+        // `W² = η² (2 w wᵀ - J)` on each cone.
+        for (positions, (eta, w)) in self.cones.iter().zip(&scaling.cones) {
+            let mut positions = positions.iter();
+            for j in 0..w.len() {
+                for i in j..w.len() {
+                    let (j_entry, shift) = match (i, j) {
+                        (0, 0) => (-1.0, delta),
+                        _ if i == j => (1.0, delta),
+                        _ => (0.0, 0.0),
+                    };
+                    let pos = *positions.next().expect("one position per entry");
+                    values[pos] = -eta * eta * (2.0 * w[i] * w[j] + j_entry) - shift;
+                }
+            }
+        }
+
+        let chol = match self.chol.take() {
+            Some(mut chol) => {
+                chol.refactor(&self.matrix).map_err(SolveError::NumericalFailure)?;
+                chol
+            }
+            None => {
+                let dim = self.matrix.rows();
+                let reg = Regularisation::quasi_definite(n, dim - n);
+                SparseCholesky::factor_regularised(&self.symbolic, &self.matrix, reg)
+                    .map_err(SolveError::NumericalFailure)?
+            }
+        };
+        self.chol = Some(chol);
+        Ok(())
+    }
+
+    /// Solve `K v = rhs` for the unregularised matrix `K`, with the
+    /// regularised factor and iterative refinement. A refinement step is only
+    /// kept if it reduces the residual, as it can diverge once `K` is nearly
+    /// singular.
+    fn solve(&self, rhs: &[f64]) -> Vec<f64> {
+        let chol = self.chol.as_ref().expect("factored before solving");
+        let residual = |sol: &[f64]| -> Vec<f64> {
+            rhs.iter().zip(self.mul(sol)).map(|(b, k)| b - k).collect()
+        };
+        let mut sol = rhs.to_vec();
+        chol.solve(&mut sol);
+        let mut r = residual(&sol);
+        for _ in 0..REFINEMENT_STEPS {
+            if norm(&r) <= f64::EPSILON * norm(rhs) {
+                break;
+            }
+            let mut refined = r.clone();
+            chol.solve(&mut refined);
+            refined.iter_mut().zip(&sol).for_each(|(d, v)| *d += v);
+            let refined_r = residual(&refined);
+            if norm(&refined_r) >= norm(&r) {
+                break;
+            }
+            (sol, r) = (refined, refined_r);
+        }
+        sol
+    }
+
+    /// Largest entry of the residual `rhs - K v` of the unregularised matrix.
+    fn residual(&self, rhs: &[f64], v: &[f64]) -> f64 {
+        rhs.iter()
+            .zip(self.mul(v))
+            .fold(0.0, |m, (b, k)| m.max((b - k).abs()))
+    }
+
+    /// The product `K v` with the unregularised matrix.
+    fn mul(&self, v: &[f64]) -> Vec<f64> {
+        let mut out = vec![0.0; v.len()];
+        for (j, &vj) in v.iter().enumerate() {
+            for (i, a) in self.matrix.col(j) {
+                out[i] += a * vj;
+                if i != j {
+                    out[j] += a * v[i];
+                }
+            }
+        }
+        for k in 0..self.n {
+            out[k] -= self.delta * v[k];
+        }
+        for k in self.n..v.len() {
+            out[k] += self.delta * v[k];
+        }
+        out
+    }
+}
+
+/// Where the row of a model constraint ended up in the standard form.
+#[derive(Copy, Clone)]
+enum Row {
+    /// Row of `Ax = b`.
+    Equality(usize),
+    /// Orthant row of `Gx + s = h`, holding the constraint times the sign.
+    Inequality(usize, f64),
+}
+
+/// A search direction for all iterates.
+struct Direction {
+    dx: Vec<f64>,
+    dy: Vec<f64>,
+    dz: Vec<f64>,
+    ds: Vec<f64>,
+    dtau: f64,
+    dkappa: f64,
+}
+
+impl Direction {
+    /// Whether every entry of the direction is finite.
+    fn is_finite(&self) -> bool {
+        [&self.dx, &self.dy, &self.dz, &self.ds]
+            .iter()
+            .all(|v| v.iter().all(|d| d.is_finite()))
+            && self.dtau.is_finite()
+            && self.dkappa.is_finite()
+    }
+}
+
+/// Residuals of the homogeneous self-dual embedding at the current iterate.
+struct Residuals {
+    /// `Aᵀy + Gᵀz + cτ`.
+    rx: Vec<f64>,
+    /// `bτ - Ax`.
+    ry: Vec<f64>,
+    /// `hτ - Gx - s`.
+    rz: Vec<f64>,
+    /// `-cᵀx - bᵀy - hᵀz - κ`.
+    rtau: f64,
+}
+
+/// Internal state of the conic interior-point method.
+///
+/// The model is converted to `min cᵀx` subject to `Ax = b` and
+/// `Gx + s = h` with `s` in the cone `K` of [`Blocks`]. Inequality rows and
+/// variable bounds go to the orthant and fixed variables to `Ax = b`. The
+/// dual is `max -bᵀy - hᵀz` subject to `Aᵀy + Gᵀz + c = 0` with `z` in `K`.
+///
+/// The iterates `(x, y, z, s, τ, κ)` are those of the homogeneous self-dual
+/// embedding, whose solutions with `τ > 0` scale down to an optimal pair, and
+/// whose solutions with `κ > 0` are certificates of infeasibility.
+#[derive(Clone)]
+struct ConicState {
+    /// Current iteration count.
+    iteration: usize,
+    /// Status reached by the iterations.
+    status: SolveStatus,
+    /// Certificate of infeasibility or unboundedness, if one was found.
+    certificate: Option<Certificate>,
+
+    x: Vec<f64>,
+    y: Vec<f64>,
+    z: Vec<f64>,
+    s: Vec<f64>,
+    tau: f64,
+    kappa: f64,
+
+    /// Equality constraint matrix `A`.
+    a: SparseMatrix,
+    /// Right-hand side `b` of the equalities.
+    b: Vec<f64>,
+    /// Conic constraint matrix `G`.
+    g: SparseMatrix,
+    /// Right-hand side `h` of the conic constraints.
+    h: Vec<f64>,
+    /// Objective coefficients `c` (minimisation form).
+    c: Vec<f64>,
+    /// The cone of the slacks.
+    blocks: Blocks,
+    /// Row of each model constraint.
+    rows: Vec<Row>,
+    /// Whether the problem is a minimization problem.
+    minimise: bool,
+    /// Whether to log iteration details.
+    logging: bool,
+}
+
+impl ConicState {
+    /// Convert `model` to standard form. The iterates are set by
+    /// [`solve`](Self::solve).
+    fn new(model: &LpModel) -> Self {
+        let n = model.vars().len();
+        let objective = model.objective().expect("checked by check_socp");
+        let minimise = objective.sense == Sense::Minimize;
+        let sign = if minimise { 1.0 } else { -1.0 };
+
+        let mut c = vec![0.0; n];
+        for term in &objective.expr.terms {
+            c[term.var.0] += sign * term.coeff;
+        }
+
+        let (mut a, mut b) = (Vec::new(), Vec::new());
+        let (mut g, mut h) = (Vec::new(), Vec::new());
+        let mut rows = Vec::with_capacity(model.constraints().len());
+        for cons in model.constraints() {
+            let rhs = cons.rhs - cons.expr.constant;
+            let dir = match cons.cmp {
+                Cmp::EQ => {
+                    a.extend(cons.expr.terms.iter().map(|t| (b.len(), t.var.0, t.coeff)));
+                    rows.push(Row::Equality(b.len()));
+                    b.push(rhs);
+                    continue;
+                }
+                Cmp::LEQ => 1.0,
+                Cmp::GEQ => -1.0,
+            };
+            g.extend(cons.expr.terms.iter().map(|t| (h.len(), t.var.0, dir * t.coeff)));
+            rows.push(Row::Inequality(h.len(), dir));
+            h.push(dir * rhs);
+        }
+
+        for var in model.vars() {
+            let j = var.id.0;
+            match (var.lb, var.ub) {
+                (Some(lb), Some(ub)) if lb == ub => {
+                    a.push((b.len(), j, 1.0));
+                    b.push(lb);
+                }
+                (lb, ub) => {
+                    if let Some(lb) = lb {
+                        g.push((h.len(), j, -1.0));
+                        h.push(-lb);
+                    }
+                    if let Some(ub) = ub {
+                        g.push((h.len(), j, 1.0));
+                        h.push(ub);
+                    }
+                }
+            }
+        }
+
+        // `s = h - Gx = (t, w)` for each cone `||w||₂ <= t`.
+        let orthant = h.len();
+        let mut cones = Vec::with_capacity(model.cones().len());
+        for cone in model.cones() {
+            let (t, w) = cone.lorentz();
+            cones.push((h.len(), w.len() + 1));
+            for expr in std::iter::once(&t).chain(&w) {
+                g.extend(
+                    expr.terms.iter().map(|term| (h.len(), term.var.0, -term.coeff)),
+                );
+                h.push(expr.constant);
+            }
+        }
+
+        let (p, m) = (b.len(), h.len());
+        Self {
+            iteration: 0,
+            status: SolveStatus::NotSolved,
+            certificate: None,
+            x: vec![0.0; n],
+            y: vec![0.0; p],
+            z: vec![0.0; m],
+            s: vec![0.0; m],
+            tau: 1.0,
+            kappa: 1.0,
+            a: SparseMatrix::from_triplets(p, n, &a),
+            b,
+            g: SparseMatrix::from_triplets(m, n, &g),
+            h,
+            c,
+            blocks: Blocks { orthant, cones },
+            rows,
+            minimise,
+            logging: false,
+        }
+    }
+
+    /// Run the predictor-corrector iterations until the iterates are optimal
+    /// or certify infeasibility or unboundedness to within `tol`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SolveError::NumericalFailure`] if the steps stall or the
+    /// iterates diverge, and [`SolveError::Other`] if `max_iter` iterations
+    /// are reached.
+    fn solve(&mut self, max_iter: usize, tol: f64) -> Result<(), SolveError> {
+        let mut kkt = Kkt::new(&self.a, &self.g, &self.blocks)?;
+        self.initialise(&mut kkt)?;
+
+        // Close to a certificate of infeasibility the KKT matrix turns singular
+        // along the certificate itself, so the steps can break down, or stall
+        // until `max_iter`, just short of `tol`. The latest looser certificate
+        // (but never a looser optimum) is kept to fall back on then.
+        let mut fallback = None;
+        for iter in 0..max_iter {
+            self.iteration = iter;
+
+            let res = self.residuals();
+            if let Some(status) = self.check_termination(&res, tol) {
+                let certificate = self.certificate(&status);
+                return self.conclude(status, certificate, max_iter, tol);
+            }
+            if self.tau <= tol.sqrt() * self.kappa
+                && let Some(status) = self.check_termination(&res, tol.sqrt())
+                && status != SolveStatus::Optimal
+            {
+                fallback = Some((self.certificate(&status), status));
+            }
+
+            if let Err(err) = self.iterate(&mut kkt, &res) {
+                if kkt.strengthen() {
+                    continue;
+                }
+                return match fallback {
+                    Some((certificate, status)) => {
+                        self.conclude(status, certificate, max_iter, tol)
+                    }
+                    None => Err(err),
+                };
+            }
+        }
+
+        match fallback {
+            Some((certificate, status)) => {
+                self.conclude(status, certificate, max_iter, tol)
+            }
+            None => Err(SolveError::Other("max iterations reached".into())),
+        }
+    }
+
+    /// Decide whether the iterate is optimal, or certifies infeasibility
+    /// ([`Infeasible`](SolveStatus::Infeasible)) or dual infeasibility
+    /// ([`Unbounded`](SolveStatus::Unbounded)), to within `tol`.
+    fn check_termination(&self, res: &Residuals, tol: f64) -> Option<SolveStatus> {
+        let b_norm = 1.0 + norm(&self.b).max(norm(&self.h));
+        let c_norm = 1.0 + norm(&self.c);
+        let cx = dot(&self.c, &self.x);
+        let gap = dot(&self.s, &self.z);
+
+        let tau = self.tau;
+        if norm(&res.ry).max(norm(&res.rz)) <= tol * b_norm * tau
+            && norm(&res.rx) <= tol * c_norm * tau
+            && gap <= tol * (tau * tau + cx.abs() * tau)
+        {
+            return Some(SolveStatus::Optimal);
+        }
+        // Only once `κ` dominates `τ` do the iterates approach a certificate.
+        if self.kappa <= tau {
+            return None;
+        }
+
+        // Certificate of primal infeasibility: `Aᵀy + Gᵀz = 0` with
+        // `bᵀy + hᵀz < 0`.
+        let by_hz = dot(&self.b, &self.y) + dot(&self.h, &self.z);
+        let aty_gtz: Vec<f64> =
+            res.rx.iter().zip(&self.c).map(|(r, c)| r - c * tau).collect();
+        if by_hz < 0.0 && norm(&aty_gtz) <= tol * -by_hz {
+            return Some(SolveStatus::Infeasible);
+        }
+
+        // Certificate of dual infeasibility: `Ax = 0` and `Gx + s = 0` with
+        // `cᵀx < 0`.
+        let ax = self.a.mul_vec(&self.x);
+        let gx = self.g.mul_vec(&self.x);
+        let gx_s: Vec<f64> = gx.iter().zip(&self.s).map(|(g, s)| g + s).collect();
+        if cx < 0.0 && norm(&ax).max(norm(&gx_s)) <= tol * -cx {
+            return Some(SolveStatus::Unbounded);
+        }
+        None
+    }
+
+    /// Record `status` and its `certificate` as the outcome of the solve.
+    fn conclude(
+        &mut self,
+        status: SolveStatus,
+        certificate: Option<Certificate>,
+        max_iter: usize,
+        tol: f64,
+    ) -> Result<(), SolveError> {
+        self.status = status;
+        self.certificate = certificate;
+        // Dual infeasibility means the problem is unbounded if it has a
+        // feasible point at all.
+        if self.status == SolveStatus::Unbounded {
+            let feasibility = self.feasibility(max_iter, tol)?;
+            if feasibility.status != SolveStatus::Optimal {
+                self.status = SolveStatus::Infeasible;
+                self.certificate = feasibility.certificate;
+            }
+        }
+        Ok(())
+    }
+
+    /// The certificate of `status` at the current iterate: the ray `x` of an
+    /// unbounded problem, or the Farkas multipliers `y` and `z` of the linear
+    /// constraints of an infeasible one. Infeasibility that involves cones
+    /// has no such certificate.
+    fn certificate(&self, status: &SolveStatus) -> Option<Certificate> {
+        match status {
+            SolveStatus::Unbounded => Some(Certificate::unbounded(self.x.clone())),
+            SolveStatus::Infeasible if self.blocks.cones.is_empty() => {
+                let farkas = self
+                    .rows
+                    .iter()
+                    .map(|&row| match row {
+                        Row::Equality(i) => self.y[i],
+                        Row::Inequality(i, dir) => dir * self.z[i],
+                    })
+                    .collect();
+                Some(Certificate::infeasible(farkas))
+            }
+            _ => None,
+        }
+    }
+
+    /// Take one predictor-corrector step from the iterate with residuals
+    /// `res`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SolveError::NumericalFailure`] if the iterates have diverged,
+    /// the KKT matrix cannot be factored, or the step length vanishes.
+    fn iterate(&mut self, kkt: &mut Kkt, res: &Residuals) -> Result<(), SolveError> {
+        let largest = norm(&self.x)
+            .max(norm(&self.y))
+            .max(norm(&self.z))
+            .max(norm(&self.s))
+            .max(self.tau)
+            .max(self.kappa);
+        if !largest.is_finite() || largest > DIVERGENCE {
+            return Err(SolveError::NumericalFailure(
+                "conic interior-point iterates diverged".into(),
+            ));
+        }
+
+        let gap = dot(&self.s, &self.z) + self.tau * self.kappa;
+        let mu = gap / (self.blocks.degree() + 1) as f64;
+
+        let scaling = Scaling::new(&self.blocks, &self.s, &self.z)?;
+        let lambda = scaling.apply(&self.blocks, &self.z, false);
+        kkt.factor(&scaling)?;
+
+        // The response of `(x, y, z)` to a unit change of `τ`.
+        let rhs: Vec<f64> = self
+            .c
+            .iter()
+            .map(|c| -c)
+            .chain(self.b.iter().copied())
+            .chain(self.h.iter().copied())
+            .collect();
+        let unit = kkt.solve(&rhs);
+
+        // Predictor (affine-scaling) direction.
+        let lambda_sq = self.blocks.product(&lambda, &lambda);
+        let ds: Vec<f64> = lambda_sq.iter().map(|v| -v).collect();
+        let dk = -self.tau * self.kappa;
+        let aff = self.direction(kkt, &scaling, &lambda, &unit, res, 1.0, &ds, dk);
+        let alpha = self.max_step(&aff).min(1.0);
+
+        // Centring parameter from the predicted reduction of complementarity.
+        let s_aff: Vec<f64> =
+            self.s.iter().zip(&aff.ds).map(|(s, d)| s + alpha * d).collect();
+        let z_aff: Vec<f64> =
+            self.z.iter().zip(&aff.dz).map(|(z, d)| z + alpha * d).collect();
+        let gap_aff = dot(&s_aff, &z_aff)
+            + (self.tau + alpha * aff.dtau) * (self.kappa + alpha * aff.dkappa);
+        let sigma = (gap_aff / gap).clamp(0.0, 1.0).powi(3);
+
+        // Corrector direction, with Mehrotra's second-order term.
+        let ds_scaled = scaling.apply(&self.blocks, &aff.ds, true);
+        let dz_scaled = scaling.apply(&self.blocks, &aff.dz, false);
+        let second = self.blocks.product(&ds_scaled, &dz_scaled);
+        let e = self.blocks.identity();
+        let ds: Vec<f64> = (0..ds.len())
+            .map(|k| -lambda_sq[k] - second[k] + sigma * mu * e[k])
+            .collect();
+        let dk = -self.tau * self.kappa - aff.dtau * aff.dkappa + sigma * mu;
+        let dir =
+            self.direction(kkt, &scaling, &lambda, &unit, res, 1.0 - sigma, &ds, dk);
+
+        let alpha = (STEP_FRACTION * self.max_step(&dir)).min(1.0);
+        if alpha < MIN_STEP || !aff.is_finite() || !dir.is_finite() {
+            return Err(SolveError::NumericalFailure(
+                "conic interior-point step length vanished".into(),
+            ));
+        }
+        self.step(&dir, alpha);
+
+        if self.logging {
+            println!(
+                "Iteration {:>3}: mu = {:>10.3e}, primal = {:>10.3e}, dual = {:>10.3e}, tau = {:>10.3e}, step = {:>6.4}",
+                self.iteration + 1,
+                mu,
+                norm(&res.ry).max(norm(&res.rz)),
+                norm(&res.rx),
+                self.tau,
+                alpha
+            );
+        }
+        Ok(())
+    }
+
+    /// Set the starting point: `x` fitting `Gx + s = h` and `Ax = b` in the
+    /// least-squares sense and `(y, z)` the least-norm solution of the dual
+    /// equations, with `s` and `z` moved inside the cone and `τ = κ = 1`.
+    fn initialise(&mut self, kkt: &mut Kkt) -> Result<(), SolveError> {
+        let (n, p) = (self.x.len(), self.y.len());
+        let primal_rhs: Vec<f64> = std::iter::repeat_n(0.0, n)
+            .chain(self.b.iter().copied())
+            .chain(self.h.iter().copied())
+            .collect();
+        let mut dual_rhs = vec![0.0; primal_rhs.len()];
+        dual_rhs.iter_mut().zip(&self.c).for_each(|(r, c)| *r = -c);
+
+        // A factorisation that broke down leaves large residuals; it is
+        // repeated with more regularisation then.
+        let (primal, dual) = loop {
+            kkt.factor(&Scaling::identity(&self.blocks))?;
+            let primal = kkt.solve(&primal_rhs);
+            let dual = kkt.solve(&dual_rhs);
+            let accurate = kkt.residual(&primal_rhs, &primal)
+                <= START_TOL * (1.0 + norm(&primal_rhs))
+                && kkt.residual(&dual_rhs, &dual) <= START_TOL * (1.0 + norm(&dual_rhs));
+            if accurate || !kkt.strengthen() {
+                break (primal, dual);
+            }
+        };
+        self.x = primal[..n].to_vec();
+        self.s = self.blocks.shift_inside(primal[n + p..].iter().map(|z| -z).collect());
+        self.y = dual[n..n + p].to_vec();
+        self.z = self.blocks.shift_inside(dual[n + p..].to_vec());
+
+        self.tau = 1.0;
+        self.kappa = 1.0;
+        Ok(())
+    }
+
+    /// The problem solved with a zero objective, which is optimal exactly
+    /// when the constraints admit a feasible point.
+    fn feasibility(&self, max_iter: usize, tol: f64) -> Result<Self, SolveError> {
+        let mut feasibility = self.clone();
+        feasibility.c.fill(0.0);
+        feasibility.logging = false;
+        feasibility.solve(max_iter, tol)?;
+        Ok(feasibility)
+    }
+
+    /// Residuals of the embedding at the current iterate.
+    fn residuals(&self) -> Residuals {
+        let aty = self.a.tr_mul_vec(&self.y);
+        let gtz = self.g.tr_mul_vec(&self.z);
+        let rx = (0..self.x.len())
+            .map(|j| aty[j] + gtz[j] + self.c[j] * self.tau)
+            .collect();
+        let ax = self.a.mul_vec(&self.x);
+        let ry = self.b.iter().zip(&ax).map(|(b, ax)| b * self.tau - ax).collect();
+        let gx = self.g.mul_vec(&self.x);
+        let rz = (0..self.s.len())
+            .map(|i| self.h[i] * self.tau - gx[i] - self.s[i])
+            .collect();
+        let rtau = -dot(&self.c, &self.x)
+            - dot(&self.b, &self.y)
+            - dot(&self.h, &self.z)
+            - self.kappa;
+        Residuals { rx, ry, rz, rtau }
+    }
+
+    /// Solve the Newton system of the embedding for the residuals `res`
+    /// scaled by `eta`, and the complementarity targets `ds` of `λ ∘ λ` and
+    /// `dk` of `τκ`.
+    ///
+    /// Eliminating `s` and `κ` leaves the KKT system
+    /// `Aᵀdy + Gᵀdz = -η rx - c dτ`, `A dx = η ry + b dτ`,
+    /// `G dx - W² dz = η rz - W (λ \ ds) + h dτ`, whose solution is affine in
+    /// `dτ`; `unit` is its response to a unit `dτ`, and the last row of the
+    /// embedding fixes `dτ`.
+    #[allow(clippy::too_many_arguments)]
+    fn direction(
+        &self,
+        kkt: &Kkt,
+        scaling: &Scaling,
+        lambda: &[f64],
+        unit: &[f64],
+        res: &Residuals,
+        eta: f64,
+        ds: &[f64],
+        dk: f64,
+    ) -> Direction {
+        let (n, p) = (self.x.len(), self.y.len());
+        let quotient = self.blocks.divide(lambda, ds);
+        let corr = scaling.apply(&self.blocks, &quotient, false);
+        let rhs: Vec<f64> = res
+            .rx
+            .iter()
+            .map(|r| -eta * r)
+            .chain(res.ry.iter().map(|r| eta * r))
+            .chain(res.rz.iter().zip(&corr).map(|(r, c)| eta * r - c))
+            .collect();
+        let sol = kkt.solve(&rhs);
+
+        let along = |v: &[f64]| {
+            dot(&self.c, &v[..n]) + dot(&self.b, &v[n..n + p]) + dot(&self.h, &v[n + p..])
+        };
+        let dtau = (-eta * res.rtau + dk / self.tau + along(&sol))
+            / (self.kappa / self.tau - along(unit));
+        let d: Vec<f64> = sol.iter().zip(unit).map(|(s, u)| s + dtau * u).collect();
+
+        let dz = d[n + p..].to_vec();
+        let wdz = scaling.apply(&self.blocks, &dz, false);
+        let inner: Vec<f64> = quotient.iter().zip(&wdz).map(|(q, w)| q - w).collect();
+        Direction {
+            dx: d[..n].to_vec(),
+            dy: d[n..n + p].to_vec(),
+            dz,
+            ds: scaling.apply(&self.blocks, &inner, false),
+            dtau,
+            dkappa: (dk - self.kappa * dtau) / self.tau,
+        }
+    }
+
+    /// Longest step along `dir` that keeps `s`, `z`, `τ` and `κ` inside their
+    /// cones.
+    fn max_step(&self, dir: &Direction) -> f64 {
+        let ratio = |value: f64, d: f64| if d < 0.0 { -value / d } else { f64::INFINITY };
+        self.blocks
+            .max_step(&self.s, &dir.ds)
+            .min(self.blocks.max_step(&self.z, &dir.dz))
+            .min(ratio(self.tau, dir.dtau))
+            .min(ratio(self.kappa, dir.dkappa))
+    }
+
+    /// Move every iterate by `alpha` along `dir`.
+    fn step(&mut self, dir: &Direction, alpha: f64) {
+        let update = |v: &mut [f64], d: &[f64]| {
+            v.iter_mut().zip(d).for_each(|(v, d)| *v += alpha * d);
+        };
+        update(&mut self.x, &dir.dx);
+        update(&mut self.y, &dir.dy);
+        update(&mut self.z, &dir.dz);
+        update(&mut self.s, &dir.ds);
+        self.tau += alpha * dir.dtau;
+        self.kappa += alpha * dir.dkappa;
+    }
+
+    /// Fill in the shadow prices of the linear constraints and the reduced
+    /// costs of `solution`, in the sense of the original objective.
+    fn attach_duals(&self, model: &LpModel, solution: &mut LpSolution) {
+        // The optimal value changes by `-y` per unit of `b` and by `-z` per
+        // unit of `h`.
+        let sign = if self.minimise { 1.0 } else { -1.0 };
+        solution.duals = self
+            .rows
+            .iter()
+            .map(|&row| match row {
+                Row::Equality(i) => -sign * self.y[i] / self.tau,
+                Row::Inequality(i, dir) => -sign * dir * self.z[i] / self.tau,
+            })
+            .collect();
+
+        let mut reduced_costs = vec![0.0; model.vars().len()];
+        if let Some(obj) = model.objective() {
+            for term in &obj.expr.terms {
+                reduced_costs[term.var.0] += term.coeff;
+            }
+        }
+        for (cons, dual) in model.constraints().iter().zip(&solution.duals) {
+            for term in &cons.expr.terms {
+                reduced_costs[term.var.0] -= dual * term.coeff;
+            }
+        }
+        solution.reduced_costs = reduced_costs;
+    }
+}
+
+/// `u₀² - ||u₁||²`, positive inside the second-order cone.
+fn lorentz_det(u: &[f64]) -> f64 {
+    let rest = euclidean(&u[1..]);
+    (u[0] - rest) * (u[0] + rest)
+}
+
+/// Inner product `uᵀv`.
+fn dot(u: &[f64], v: &[f64]) -> f64 {
+    u.iter().zip(v).map(|(u, v)| u * v).sum()
+}
+
+/// Euclidean norm of `v`.
+fn euclidean(v: &[f64]) -> f64 {
+    dot(v, v).sqrt()
+}
+
+/// Largest magnitude of the entries of `v`.
+fn norm(v: &[f64]) -> f64 {
+    v.iter().fold(0.0f64, |m, x| m.max(x.abs()))
+}
@@ -0,0 +1,216 @@
+//! Certificates proving that a linear program is infeasible or unbounded.
+
+use cnvx_core::Sense;
+
+use crate::{Cmp, LpModel, Var};
+
+/// Evidence that a model has no optimal solution.
+///
+/// Solvers that can produce one attach it to the
+/// [`certificate`](crate::LpSolution::certificate) of an infeasible or unbounded
+/// [`LpSolution`](crate::LpSolution), normalised so that its largest entry has
+/// magnitude one. [`verify`](Self::verify) checks it against the model, without
+/// trusting the solver.
+///
+/// # Examples
+///
+/// ```rust
+/// # use cnvx_core::SolveStatus;
+/// # use cnvx_lp::*;
+/// let mut model = LpModel::new();
+/// let x = model.add_var().finish();
+/// let y = model.add_var().finish();
+/// model += (x + y).leq(1.0).named("capacity");
+/// model += (x + 2.0 * y).geq(3.0).named("demand");
+/// model.add_objective(Objective::minimize(x + y).name("cost"));
+///
+/// let solution = PrimalSimplexSolver::new().solve(&model).unwrap();
+/// assert_eq!(solution.status, SolveStatus::Infeasible);
+///
+/// let certificate = solution.certificate.unwrap();
+/// assert!(certificate.verify(&model, 1e-9).is_ok());
+///
+/// // Both constraints take part in the conflict.
+/// let Certificate::Infeasible { farkas } = certificate else { unreachable!() };
+/// assert!(farkas.iter().all(|y| y.abs() > 1e-9));
+///
+/// // The capacity constraint alone is satisfiable.
+/// let partial = Certificate::infeasible(vec![farkas[0], 0.0]);
+/// assert!(partial.verify(&model, 1e-9).is_err());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub enum Certificate {
+    /// A Farkas ray proving that the constraints and variable bounds cannot
+    /// all hold.
+    ///
+    /// `farkas` holds a multiplier `y_i` for each constraint, indexed like
+    /// [`LpModel::constraints`]: non-negative for `<=` rows, non-positive for
+    /// `>=` rows and free for `==` rows. Every point satisfying the constraints
+    /// then satisfies `(Aᵀy)ᵀx <= yᵀb`, yet every point within the variable
+    /// bounds has `(Aᵀy)ᵀx > yᵀb`. The constraints with a nonzero multiplier
+    /// are the ones in conflict.
+    Infeasible { farkas: Vec<f64> },
+
+    /// A direction along which the objective improves without bound.
+    ///
+    /// `ray` holds a component for each variable, indexed by variable ID.
+    /// Moving any feasible point along it keeps every constraint and variable
+    /// bound satisfied, and strictly improves the objective. The quadratic
+    /// terms of a quadratic objective are constant along it.
+    Unbounded { ray: Vec<f64> },
+}
+
+impl Certificate {
+    /// A Farkas certificate with multipliers `farkas`, normalised.
+    pub fn infeasible(farkas: Vec<f64>) -> Self {
+        Certificate::Infeasible { farkas: normalise(farkas) }
+    }
+
+    /// An unboundedness certificate along `ray`, normalised.
+    pub fn unbounded(ray: Vec<f64>) -> Self {
+        Certificate::Unbounded { ray: normalise(ray) }
+    }
+
+    /// Check the certificate against `model`, allowing each condition to be
+    /// violated by up to `tol`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a description of the first condition the certificate violates.
+    pub fn verify(&self, model: &LpModel, tol: f64) -> Result<(), String> {
+        match self {
+            Certificate::Infeasible { farkas } => verify_farkas(model, farkas, tol),
+            Certificate::Unbounded { ray } => verify_ray(model, ray, tol),
+        }
+    }
+}
+
+/// Check that `farkas` proves `model` infeasible.
+fn verify_farkas(model: &LpModel, farkas: &[f64], tol: f64) -> Result<(), String> {
+    let cons = model.constraints();
+    if farkas.len() != cons.len() {
+        return Err(format!(
+            "certificate has {} multipliers for {} constraints",
+            farkas.len(),
+            cons.len()
+        ));
+    }
+
+    // Combine the constraints into the single inequality `gᵀx <= rhs`.
+    let mut g = vec![0.0; model.vars().len()];
+    let mut rhs = 0.0;
+    for (i, (con, &y)) in cons.iter().zip(farkas).enumerate() {
+        let wrong_sign = match con.cmp {
+            Cmp::LEQ => y < -tol,
+            Cmp::GEQ => y > tol,
+            Cmp::EQ => false,
+        };
+        if wrong_sign {
+            return Err(format!(
+                "{} has a multiplier of the wrong sign ({y})",
+                constraint_name(model, i)
+            ));
+        }
+        for term in &con.expr.terms {
+            g[term.var.0] += y * term.coeff;
+        }
+        rhs += y * (con.rhs - con.expr.constant);
+    }
+
+    // The smallest value of `gᵀx` within the variable bounds.
+    let mut lowest = 0.0;
+    for var in model.vars() {
+        let g = g[var.id.0];
+        match if g > 0.0 { var.lb } else { var.ub } {
+            Some(bound) => lowest += g * bound,
+            None if g.abs() <= tol => {}
+            None => {
+                return Err(format!(
+                    "{} is unbounded in the combined constraint",
+                    var_name(var)
+                ));
+            }
+        }
+    }
+
+    if lowest - rhs <= tol {
+        return Err(format!(
+            "the combined constraint can be satisfied: {lowest} <= {rhs}"
+        ));
+    }
+    Ok(())
+}
+
+/// Check that `ray` is an improving direction of unboundedness of `model`.
+fn verify_ray(model: &LpModel, ray: &[f64], tol: f64) -> Result<(), String> {
+    if ray.len() != model.vars().len() {
+        return Err(format!(
+            "certificate has {} components for {} variables",
+            ray.len(),
+            model.vars().len()
+        ));
+    }
+
+    for var in model.vars() {
+        let r = ray[var.id.0];
+        if r < -tol && var.lb.is_some() || r > tol && var.ub.is_some() {
+            return Err(format!("ray leaves the bounds of {}", var_name(var)));
+        }
+    }
+
+    for (i, con) in model.constraints().iter().enumerate() {
+        let change: f64 = con.expr.terms.iter().map(|t| t.coeff * ray[t.var.0]).sum();
+        let violated = match con.cmp {
+            Cmp::LEQ => change > tol,
+            Cmp::GEQ => change < -tol,
+            Cmp::EQ => change.abs() > tol,
+        };
+        if violated {
+            return Err(format!("ray leaves {} ({change})", constraint_name(model, i)));
+        }
+    }
+
+    let objective = model.objective().ok_or("model has no objective")?;
+    let slope: f64 = objective.expr.terms.iter().map(|t| t.coeff * ray[t.var.0]).sum();
+    let improving = match objective.sense {
+        Sense::Maximize => slope > tol,
+        Sense::Minimize => slope < -tol,
+    };
+    if !improving {
+        return Err(format!("ray does not improve the objective ({slope})"));
+    }
+
+    // For a convex objective, `rᵀQr = 0` means the gradient stays constant
+    // along the ray.
+    let curvature: f64 = objective
+        .quad
+        .iter()
+        .map(|t| t.coeff * ray[t.var1.0] * ray[t.var2.0])
+        .sum();
+    if curvature.abs() > tol {
+        return Err(format!("the objective curves along the ray ({curvature})"));
+    }
+    Ok(())
+}
+
+/// Scale `v` so that its largest entry has magnitude one.
+fn normalise(mut v: Vec<f64>) -> Vec<f64> {
+    let largest = v.iter().fold(0.0f64, |m, x| m.max(x.abs()));
+    if largest > 0.0 {
+        v.iter_mut().for_each(|x| *x /= largest);
+    }
+    v
+}
+
+/// Name of variable `var` for messages.
+fn var_name(var: &Var) -> String {
+    var.name.clone().unwrap_or_else(|| format!("x{}", var.id.0))
+}
+
+/// Name of constraint `i` of `model` for messages.
+fn constraint_name(model: &LpModel, i: usize) -> String {
+    model.constraints()[i]
+        .name
+        .clone()
+        .unwrap_or_else(|| format!("constraint {i}"))
+}
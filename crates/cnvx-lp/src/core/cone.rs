@@ -0,0 +1,170 @@
+//! Second-order cone constraints for optimization models.
+
+use std::fmt::Display;
+
+use crate::LinExpr;
+
+/// The cone a [`ConeConstraint`] keeps its arguments in.
+#[derive(Clone, Debug)]
+pub enum Cone {
+    /// The second-order (Lorentz) cone `||args||₂ <= bound`.
+    SecondOrder { bound: LinExpr },
+
+    /// The rotated second-order cone `||args||₂² <= 2 u v`, with `u, v >= 0`.
+    Rotated { u: LinExpr, v: LinExpr },
+}
+
+/// A second-order cone constraint, either `||Ax + b||₂ <= cᵀx + d` or its
+/// rotated form `||Ax + b||₂² <= 2 (cᵀx + d) (eᵀx + f)`.
+///
+/// Each row of `Ax + b` is one of the [`args`](Self::args). Models with cone
+/// constraints are solved by the [`ConicSolver`](crate::ConicSolver).
+///
+/// # Examples
+///
+/// ```rust
+/// # use cnvx_lp::*;
+/// let mut model = LpModel::new();
+/// let x = model.add_var().free().finish();
+/// let y = model.add_var().free().finish();
+/// let t = model.add_var().finish();
+///
+/// // sqrt((x - 1)² + y²) <= t
+/// model += norm2([1.0 * x + -1.0, 1.0 * y]).leq(t).named("distance");
+///
+/// let cone = &model.cones()[0];
+/// assert_eq!(cone.violation(&[4.0, 4.0, 5.0]), 0.0);
+/// assert_eq!(cone.violation(&[4.0, 4.0, 3.0]), 2.0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ConeConstraint {
+    /// The expressions whose Euclidean norm is bounded.
+    pub args: Vec<LinExpr>,
+
+    /// The cone and the expressions bounding the norm.
+    pub cone: Cone,
+
+    /// Optional human-readable name for the constraint, used in diagnostics.
+    pub name: Option<String>,
+}
+
+impl ConeConstraint {
+    /// Creates the constraint `||args||₂ <= bound`.
+    pub fn second_order(args: Vec<LinExpr>, bound: LinExpr) -> Self {
+        Self {
+            args,
+            cone: Cone::SecondOrder { bound },
+            name: None,
+        }
+    }
+
+    /// Creates the rotated cone constraint `||args||₂² <= 2 u v`, with
+    /// `u, v >= 0`.
+    ///
+    /// # Examples
+    ///
+    /// The epigraph `x² <= t` of a square, solved for the smallest `t` with
+    /// `x >= 2`:
+    ///
+    /// ```rust
+    /// # use cnvx_lp::*;
+    /// let mut model = LpModel::new();
+    /// let x = model.add_var().lower_bound(2.0).finish();
+    /// let t = model.add_var().finish();
+    /// model += ConeConstraint::rotated(vec![x.into()], t.into(), LinExpr::constant(0.5));
+    /// model.add_objective(Objective::minimize(1.0 * t).name("t"));
+    ///
+    /// let solution = ConicSolver::new().solve(&model).unwrap();
+    /// assert!((solution.value(t) - 4.0).abs() < 1e-6);
+    /// ```
+    pub fn rotated(args: Vec<LinExpr>, u: LinExpr, v: LinExpr) -> Self {
+        Self { args, cone: Cone::Rotated { u, v }, name: None }
+    }
+
+    /// Attaches a human-readable name to this constraint (builder-style).
+    pub fn named(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    /// Number of rows of the cone, counting the bounding expressions.
+    pub fn dim(&self) -> usize {
+        match self.cone {
+            Cone::SecondOrder { .. } => self.args.len() + 1,
+            Cone::Rotated { .. } => self.args.len() + 2,
+        }
+    }
+
+    /// The constraint as `||w||₂ <= t`, returned as `(t, w)`.
+    ///
+    /// A rotated cone becomes `||(√2 args, u - v)||₂ <= u + v`, which holds
+    /// exactly when `||args||₂² <= 2 u v` and `u, v >= 0`.
+    pub fn lorentz(&self) -> (LinExpr, Vec<LinExpr>) {
+        match &self.cone {
+            Cone::SecondOrder { bound } => (bound.clone(), self.args.clone()),
+            Cone::Rotated { u, v } => {
+                let mut args: Vec<LinExpr> =
+                    self.args.iter().map(|a| a.clone() * 2f64.sqrt()).collect();
+                args.push(u.clone() - v.clone());
+                (u.clone() + v.clone(), args)
+            }
+        }
+    }
+
+    /// How far `values`, indexed by variable ID, are from satisfying the
+    /// constraint: `max(0, ||w||₂ - t)` for its [`lorentz`](Self::lorentz)
+    /// form `||w||₂ <= t`.
+    pub fn violation(&self, values: &[f64]) -> f64 {
+        let (t, w) = self.lorentz();
+        let norm = w.iter().map(|e| e.evaluate(values).powi(2)).sum::<f64>().sqrt();
+        (norm - t.evaluate(values)).max(0.0)
+    }
+}
+
+impl Display for ConeConstraint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(name) = &self.name {
+            write!(f, "[{}] ", name)?;
+        }
+        let args: Vec<String> = self.args.iter().map(|a| a.to_string()).collect();
+        match &self.cone {
+            Cone::SecondOrder { bound } => {
+                write!(f, "||({})||_2 <= {}", args.join(", "), bound)
+            }
+            Cone::Rotated { u, v } => {
+                write!(f, "||({})||_2^2 <= 2 ({}) ({})", args.join(", "), u, v)
+            }
+        }
+    }
+}
+
+/// The Euclidean norm `||args||₂` of linear expressions, to be bounded with
+/// [`leq`](Self::leq). Created by [`norm2`].
+#[derive(Clone, Debug, Default)]
+pub struct Norm2 {
+    /// The expressions whose norm is taken.
+    pub args: Vec<LinExpr>,
+}
+
+impl Norm2 {
+    /// Creates the second-order cone constraint `||args||₂ <= bound`.
+    pub fn leq<T: Into<LinExpr>>(self, bound: T) -> ConeConstraint {
+        ConeConstraint::second_order(self.args, bound.into())
+    }
+}
+
+/// The Euclidean norm of `args`, for building second-order cone constraints.
+///
+/// # Example
+///
+/// ```rust
+/// # use cnvx_lp::*;
+/// let mut model = LpModel::new();
+/// let x = model.add_var().finish();
+/// let y = model.add_var().finish();
+/// model += norm2([x, y]).leq(1.0); // x² + y² <= 1
+/// assert_eq!(model.cones()[0].dim(), 3);
+/// ```
+pub fn norm2<T: Into<LinExpr>>(args: impl IntoIterator<Item = T>) -> Norm2 {
+    Norm2 { args: args.into_iter().map(Into::into).collect() }
+}
@@ -1,7 +1,7 @@
 use crate::{LinearConstraint, VarId};
 use std::{
     fmt::Display,
-    ops::{Add, AddAssign},
+    ops::{Add, AddAssign, Mul},
 };
 
 /// A single term in a linear expression: `coeff * var`.
@@ -50,6 +50,24 @@ impl LinExpr {
         Self { terms: vec![], constant: c }
     }
 
+    /// Evaluates the expression at `values`, indexed by variable ID.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use cnvx_lp::{LinExpr, VarId};
+    /// let expr = LinExpr::new(VarId(0), 3.0) + LinExpr::new(VarId(1), -1.0) + 2.0;
+    /// assert_eq!(expr.evaluate(&[1.0, 4.0]), 1.0);
+    /// ```
+    pub fn evaluate(&self, values: &[f64]) -> f64 {
+        self.constant
+            + self
+                .terms
+                .iter()
+                .map(|t| t.coeff * values.get(t.var.0).copied().unwrap_or(0.0))
+                .sum::<f64>()
+    }
+
     /// Creates a `<=` constraint from this linear expression.
     pub fn leq(self, rhs: f64) -> LinearConstraint {
         LinearConstraint::leq(self, rhs)
@@ -179,6 +197,26 @@ impl std::ops::Sub for LinExpr {
     }
 }
 
+/// LinExpr * f64
+impl Mul<f64> for LinExpr {
+    type Output = LinExpr;
+
+    fn mul(mut self, rhs: f64) -> LinExpr {
+        self.terms.iter_mut().for_each(|t| t.coeff *= rhs);
+        self.constant *= rhs;
+        self
+    }
+}
+
+/// f64 * LinExpr
+impl Mul<LinExpr> for f64 {
+    type Output = LinExpr;
+
+    fn mul(self, rhs: LinExpr) -> LinExpr {
+        rhs * self
+    }
+}
+
 /// Allows converting a single variable into a linear expression with coefficient 1.0.
 impl From<VarId> for LinExpr {
     fn from(var: VarId) -> Self {
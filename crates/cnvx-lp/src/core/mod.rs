@@ -1,15 +1,23 @@
+pub mod certificate;
+pub mod cone;
 pub mod constraint;
 pub mod expr;
 pub mod model;
 pub mod objective;
+pub mod quad_expr;
+pub mod sensitivity;
 pub mod solution;
 pub mod solver;
 pub mod var;
 
+pub use certificate::*;
+pub use cone::*;
 pub use constraint::*;
 pub use expr::*;
 pub use model::*;
 pub use objective::*;
+pub use quad_expr::*;
+pub use sensitivity::*;
 pub use solution::*;
 pub use solver::*;
 pub use var::*;
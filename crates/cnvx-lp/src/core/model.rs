@@ -34,11 +34,14 @@ pub struct LpModel {
     /// List of constraints in the model.
     pub constraints: Vec<LinearConstraint>,
 
-    /// Optional objective function.
+    /// Second-order cone constraints of the model, see [`ConeConstraint`].
+    pub cones: Vec<ConeConstraint>,
+
+    /// Objective functions, highest priority first.
     ///
-    /// Currently supports only a single objective.
-    /// TODO: Replace with `Vec<Objective>` for multi-objective optimization.
-    pub objective: Option<Objective>,
+    /// Holds at most one objective unless objectives are given a
+    /// [`priority`](Objective::priority), see [`add_objective`](Self::add_objective).
+    pub objectives: Vec<Objective>,
 }
 
 impl LpModel {
@@ -76,7 +79,13 @@ impl LpModel {
         VarBuilder { model: self, var: id }
     }
 
-    /// Sets the objective function of the model, replacing any existing one.
+    /// Sets the objective function of the model.
+    ///
+    /// An objective without a [`priority`](Objective::priority) replaces any
+    /// existing ones. An objective with a priority is added to the other
+    /// prioritised objectives, replacing one of the same priority, so that the
+    /// model can be optimized lexicographically (see
+    /// [`solve_lexicographic`]).
     ///
     /// # Example
     ///
@@ -85,9 +94,20 @@ impl LpModel {
     /// let mut model = LpModel::new();
     /// let x = model.add_var().finish();
     /// model.add_objective(Objective::maximize(1.0 * x).name("Profit"));
+    ///
+    /// let y = model.add_var().finish();
+    /// model.add_objective(Objective::minimize(1.0 * y).priority(1).name("Waste"));
+    /// model.add_objective(Objective::maximize(x + y).priority(2).name("Output"));
+    /// let names = model.objectives().iter().map(|o| o.name.as_deref().unwrap());
+    /// assert_eq!(names.collect::<Vec<_>>(), ["Output", "Waste"]);
     /// ```
     pub fn add_objective(&mut self, obj: Objective) {
-        self.objective = Some(obj);
+        match obj.priority {
+            None => self.objectives.clear(),
+            Some(p) => self.objectives.retain(|o| o.priority.is_some_and(|q| q != p)),
+        }
+        let at = self.objectives.partition_point(|o| o.priority > obj.priority);
+        self.objectives.insert(at, obj);
     }
 
     /// Returns a read-only slice of all variables.
@@ -100,9 +120,21 @@ impl LpModel {
         &self.constraints
     }
 
+    /// Returns a read-only slice of all second-order cone constraints.
+    pub fn cones(&self) -> &[ConeConstraint] {
+        &self.cones
+    }
+
     /// Returns a reference to the model's objective function, if one is set.
+    ///
+    /// If the model has several objectives, this is the one of highest priority.
     pub fn objective(&self) -> Option<&Objective> {
-        self.objective.as_ref()
+        self.objectives.first()
+    }
+
+    /// Returns a read-only slice of all objectives, highest priority first.
+    pub fn objectives(&self) -> &[Objective] {
+        &self.objectives
     }
 }
 
@@ -121,3 +153,21 @@ impl AddAssign<LinearConstraint> for LpModel {
         self.constraints.push(rhs);
     }
 }
+
+/// Allows adding second-order cone constraints to the model using the `+=`
+/// operator.
+///
+/// # Example
+///
+/// ```rust
+/// # use cnvx_lp::*;
+/// let mut model = LpModel::new();
+/// let x = model.add_var().finish();
+/// let t = model.add_var().finish();
+/// model += norm2([x]).leq(t);
+/// ```
+impl AddAssign<ConeConstraint> for LpModel {
+    fn add_assign(&mut self, rhs: ConeConstraint) {
+        self.cones.push(rhs);
+    }
+}
@@ -4,12 +4,13 @@ use std::fmt::Display;
 
 use cnvx_core::Sense;
 
-use crate::LinExpr;
+use crate::{LinExpr, QuadExpr, QuadTerm, VarId};
 
 /// Represents an objective function in a model.
 ///
-/// Contains the linear expression to optimize, the optimization sense (min/max),
-/// an optional name, and an optional priority (useful for multi-objective problems).
+/// Contains the expression to optimize, linear or quadratic, the optimization
+/// sense (min/max), an optional name, and an optional priority and
+/// [`Degradation`] for multi-objective problems.
 ///
 /// # Examples
 ///
@@ -25,14 +26,51 @@ pub struct Objective {
     /// Whether to minimize or maximize the objective.
     pub sense: Sense,
 
-    /// The linear expression representing the objective.
+    /// The linear part of the objective, including its constant.
     pub expr: LinExpr,
 
+    /// The quadratic terms of the objective, empty for a linear objective.
+    ///
+    /// Models with quadratic terms are solved by the [`QpSolver`](crate::QpSolver).
+    pub quad: Vec<QuadTerm>,
+
     /// Optional human-readable name.
     pub name: Option<String>,
 
-    /// Optional priority for multi-objective optimization (not used)
+    /// Optional priority for multi-objective optimization.
+    ///
+    /// Objectives with a higher priority are optimized first, see
+    /// [`solve_lexicographic`](crate::solve_lexicographic).
     pub priority: Option<u32>,
+
+    /// How far the objective may move away from its optimal value while
+    /// lower-priority objectives are optimized.
+    ///
+    /// [`None`] keeps it at its optimal value.
+    pub degradation: Option<Degradation>,
+}
+
+/// How far an objective may degrade from its optimal value once it has been
+/// optimized, to leave room for the objectives of lower priority.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Degradation {
+    /// The objective may be worse than its optimal value by at most this
+    /// amount.
+    Absolute(f64),
+
+    /// The objective may be worse than its optimal value by at most this
+    /// fraction of the magnitude of the optimal value.
+    Relative(f64),
+}
+
+impl Degradation {
+    /// The amount an objective with optimal value `optimum` may degrade by.
+    pub fn allowance(&self, optimum: f64) -> f64 {
+        match *self {
+            Degradation::Absolute(tol) => tol,
+            Degradation::Relative(tol) => tol * optimum.abs(),
+        }
+    }
 }
 
 impl Clone for Objective {
@@ -40,8 +78,10 @@ impl Clone for Objective {
         Self {
             sense: self.sense,
             expr: self.expr.clone(),
+            quad: self.quad.clone(),
             name: self.name.clone(),
             priority: self.priority,
+            degradation: self.degradation,
         }
     }
 }
@@ -61,17 +101,49 @@ impl ObjectiveBuilder {
     ///
     /// # Example
     ///
-    /// ```rust, no_run
+    /// ```rust
     /// # use cnvx_lp::{Objective, LinExpr, LpModel};
     /// # let mut model = LpModel::new();
     /// let x = model.add_var().finish(); // VarId
-    /// let obj = Objective::maximize(2.0 * x).priority(1);
+    /// let obj = Objective::maximize(2.0 * x).priority(1).name("Profit");
     /// ```
     pub fn priority(mut self, p: u32) -> Self {
         self.objective.priority = Some(p);
-        unimplemented!(
-            "Multi-objective optimization is not yet implemented, so priority has no effect"
-        );
+        self
+    }
+
+    /// Lets the objective be worse than its optimal value by up to `tol` while
+    /// lower-priority objectives are optimized.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use cnvx_lp::{Degradation, Objective, LinExpr, LpModel};
+    /// # let mut model = LpModel::new();
+    /// let x = model.add_var().finish(); // VarId
+    /// let obj = Objective::maximize(2.0 * x).priority(1).abs_tol(0.5).name("Profit");
+    /// assert_eq!(obj.degradation, Some(Degradation::Absolute(0.5)));
+    /// ```
+    pub fn abs_tol(mut self, tol: f64) -> Self {
+        self.objective.degradation = Some(Degradation::Absolute(tol));
+        self
+    }
+
+    /// Lets the objective be worse than its optimal value by up to the fraction
+    /// `tol` of its magnitude while lower-priority objectives are optimized.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use cnvx_lp::{Degradation, Objective, LinExpr, LpModel};
+    /// # let mut model = LpModel::new();
+    /// let x = model.add_var().finish(); // VarId
+    /// let obj = Objective::minimize(3.0 * x).priority(2).rel_tol(0.05).name("Cost");
+    /// assert_eq!(obj.degradation, Some(Degradation::Relative(0.05)));
+    /// ```
+    pub fn rel_tol(mut self, tol: f64) -> Self {
+        self.objective.degradation = Some(Degradation::Relative(tol));
+        self
     }
 
     /// Sets a human-readable name for the objective and returns the final [`Objective`].
@@ -91,7 +163,7 @@ impl ObjectiveBuilder {
 }
 
 impl Objective {
-    /// Creates a minimization objective.
+    /// Creates a minimization objective from a linear or quadratic expression.
     ///
     /// Returns an [`ObjectiveBuilder`] to optionally set name or priority.
     ///
@@ -103,18 +175,21 @@ impl Objective {
     /// let x = model.add_var().finish(); // VarId
     /// let obj = Objective::minimize(3.0 * x).name("Cost");
     /// ```
-    pub fn minimize(expr: LinExpr) -> ObjectiveBuilder {
+    pub fn minimize(expr: impl Into<QuadExpr>) -> ObjectiveBuilder {
+        let QuadExpr { quad, linear } = expr.into();
         ObjectiveBuilder {
             objective: Objective {
                 sense: Sense::Minimize,
-                expr,
+                expr: linear,
+                quad,
                 name: None,
                 priority: None,
+                degradation: None,
             },
         }
     }
 
-    /// Creates a maximization objective.
+    /// Creates a maximization objective from a linear or quadratic expression.
     ///
     /// Returns an [`ObjectiveBuilder`] to optionally set name or priority.
     ///
@@ -126,16 +201,45 @@ impl Objective {
     /// let x = model.add_var().finish(); // VarId
     /// let obj = Objective::maximize(3.0 * x).name("Profit");
     /// ```
-    pub fn maximize(expr: LinExpr) -> ObjectiveBuilder {
+    pub fn maximize(expr: impl Into<QuadExpr>) -> ObjectiveBuilder {
+        let QuadExpr { quad, linear } = expr.into();
         ObjectiveBuilder {
             objective: Objective {
                 sense: Sense::Maximize,
-                expr,
+                expr: linear,
+                quad,
                 name: None,
                 priority: None,
+                degradation: None,
             },
         }
     }
+
+    /// Whether the objective has quadratic terms.
+    pub fn is_quadratic(&self) -> bool {
+        !self.quad.is_empty()
+    }
+
+    /// Evaluates the objective at `values`, indexed by variable ID.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use cnvx_lp::{Objective, LpModel};
+    /// # let mut model = LpModel::new();
+    /// let x = model.add_var().finish();
+    /// let obj = Objective::minimize(x * x + 2.0 * x).name("Cost");
+    /// assert_eq!(obj.evaluate(&[3.0]), 15.0);
+    /// ```
+    pub fn evaluate(&self, values: &[f64]) -> f64 {
+        let value = |v: VarId| values.get(v.0).copied().unwrap_or(0.0);
+        self.expr.evaluate(values)
+            + self
+                .quad
+                .iter()
+                .map(|t| t.coeff * value(t.var1) * value(t.var2))
+                .sum::<f64>()
+    }
 }
 
 impl Display for Objective {
@@ -144,10 +248,11 @@ impl Display for Objective {
             Sense::Minimize => "Minimize",
             Sense::Maximize => "Maximize",
         };
+        let expr = QuadExpr { quad: self.quad.clone(), linear: self.expr.clone() };
         if let Some(name) = &self.name {
-            write!(f, "{} {}: {}", sense_str, name, self.expr)
+            write!(f, "{} {}: {}", sense_str, name, expr)
         } else {
-            write!(f, "{}: {}", sense_str, self.expr)
+            write!(f, "{}: {}", sense_str, expr)
         }
     }
 }
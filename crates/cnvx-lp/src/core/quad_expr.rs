@@ -0,0 +1,316 @@
+use crate::{LinExpr, LinTerm, VarId};
+use std::{
+    fmt::Display,
+    ops::{Add, AddAssign, Mul, Neg, Sub},
+};
+
+/// A single term in a quadratic expression: `coeff * var1 * var2`.
+#[derive(Clone, Debug)]
+pub struct QuadTerm {
+    /// The first variable of the product.
+    pub var1: VarId,
+    /// The second variable of the product, equal to `var1` for a square.
+    pub var2: VarId,
+    /// The coefficient for the product.
+    pub coeff: f64,
+}
+
+/// Represents a quadratic expression of the form
+/// `q1*xi*xj + ... + a1*x1 + a2*x2 + ... + c`.
+///
+/// Quadratic expressions come from multiplying variables or linear
+/// expressions, and serve as quadratic objectives (see
+/// [`Objective::minimize`](crate::Objective::minimize)).
+///
+/// # Examples
+///
+/// ```rust
+/// # use cnvx_lp::LpModel;
+/// let mut model = LpModel::new();
+/// let x = model.add_var().finish();
+/// let y = model.add_var().finish();
+///
+/// // (x - y - 1)^2 + x*y
+/// let residual = 1.0 * x - 1.0 * y + -1.0;
+/// let expr = residual.clone() * residual + x * y;
+/// assert_eq!(expr.evaluate(&[3.0, 1.0]), 4.0);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct QuadExpr {
+    /// All quadratic terms in the expression.
+    pub quad: Vec<QuadTerm>,
+    /// Linear terms and constant of the expression.
+    pub linear: LinExpr,
+}
+
+impl QuadExpr {
+    /// Creates a quadratic expression from a single product of two variables.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use cnvx_lp::{QuadExpr, VarId};
+    /// let expr = QuadExpr::new(VarId(0), VarId(1), 2.0); // 2*VarId(0)*VarId(1)
+    /// ```
+    pub fn new(var1: VarId, var2: VarId, coeff: f64) -> Self {
+        Self {
+            quad: vec![QuadTerm { var1, var2, coeff }],
+            linear: LinExpr::default(),
+        }
+    }
+
+    /// Whether the expression has no quadratic terms.
+    pub fn is_linear(&self) -> bool {
+        self.quad.is_empty()
+    }
+
+    /// Evaluates the expression at `values`, indexed by variable ID.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use cnvx_lp::{QuadExpr, VarId};
+    /// let expr = QuadExpr::new(VarId(0), VarId(0), 2.0) + VarId(1) + 1.0;
+    /// assert_eq!(expr.evaluate(&[3.0, 4.0]), 23.0);
+    /// ```
+    pub fn evaluate(&self, values: &[f64]) -> f64 {
+        let value = |v: VarId| values.get(v.0).copied().unwrap_or(0.0);
+        self.linear.evaluate(values)
+            + self
+                .quad
+                .iter()
+                .map(|t| t.coeff * value(t.var1) * value(t.var2))
+                .sum::<f64>()
+    }
+}
+
+impl Display for QuadExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut parts = Vec::new();
+        for term in &self.quad {
+            parts.push(format!(
+                "{}*VarId({})*VarId({})",
+                term.coeff, term.var1.0, term.var2.0
+            ));
+        }
+        if !self.linear.terms.is_empty()
+            || self.linear.constant != 0.0
+            || parts.is_empty()
+        {
+            parts.push(self.linear.to_string());
+        }
+        write!(f, "{}", parts.join(" + "))
+    }
+}
+
+/////////////////////////////////////////////////////////////////////////////
+// Operator Overloads for QuadExpr
+/////////////////////////////////////////////////////////////////////////////
+
+/// Allows multiplying two variables to create a quadratic expression.
+///
+/// # Examples
+///
+/// ```rust
+/// # use cnvx_lp::LpModel;
+/// let mut model = LpModel::new();
+/// let x = model.add_var().finish();
+/// let y = model.add_var().finish();
+/// let expr = x * y; // QuadExpr representing x*y
+/// ```
+impl Mul for VarId {
+    type Output = QuadExpr;
+
+    fn mul(self, rhs: VarId) -> QuadExpr {
+        QuadExpr::new(self, rhs, 1.0)
+    }
+}
+
+/// LinExpr * LinExpr
+impl Mul for LinExpr {
+    type Output = QuadExpr;
+
+    fn mul(self, rhs: LinExpr) -> QuadExpr {
+        let mut quad = Vec::with_capacity(self.terms.len() * rhs.terms.len());
+        for a in &self.terms {
+            for b in &rhs.terms {
+                quad.push(QuadTerm {
+                    var1: a.var,
+                    var2: b.var,
+                    coeff: a.coeff * b.coeff,
+                });
+            }
+        }
+        let mut terms = Vec::with_capacity(self.terms.len() + rhs.terms.len());
+        for term in &self.terms {
+            terms.push(LinTerm { var: term.var, coeff: term.coeff * rhs.constant });
+        }
+        for term in &rhs.terms {
+            terms.push(LinTerm { var: term.var, coeff: term.coeff * self.constant });
+        }
+        terms.retain(|t| t.coeff != 0.0);
+        QuadExpr {
+            quad,
+            linear: LinExpr { terms, constant: self.constant * rhs.constant },
+        }
+    }
+}
+
+/// LinExpr * VarId
+impl Mul<VarId> for LinExpr {
+    type Output = QuadExpr;
+
+    fn mul(self, rhs: VarId) -> QuadExpr {
+        self * LinExpr::from(rhs)
+    }
+}
+
+/// VarId * LinExpr
+impl Mul<LinExpr> for VarId {
+    type Output = QuadExpr;
+
+    fn mul(self, rhs: LinExpr) -> QuadExpr {
+        LinExpr::from(self) * rhs
+    }
+}
+
+/// QuadExpr * f64
+impl Mul<f64> for QuadExpr {
+    type Output = QuadExpr;
+
+    fn mul(mut self, rhs: f64) -> QuadExpr {
+        self.quad.iter_mut().for_each(|t| t.coeff *= rhs);
+        self.linear = self.linear * rhs;
+        self
+    }
+}
+
+/// f64 * QuadExpr
+impl Mul<QuadExpr> for f64 {
+    type Output = QuadExpr;
+
+    fn mul(self, rhs: QuadExpr) -> QuadExpr {
+        rhs * self
+    }
+}
+
+/// -QuadExpr
+impl Neg for QuadExpr {
+    type Output = QuadExpr;
+
+    fn neg(self) -> QuadExpr {
+        self * -1.0
+    }
+}
+
+/// QuadExpr + QuadExpr
+impl Add for QuadExpr {
+    type Output = QuadExpr;
+
+    fn add(mut self, rhs: QuadExpr) -> QuadExpr {
+        self += rhs;
+        self
+    }
+}
+
+/// QuadExpr + LinExpr
+impl Add<LinExpr> for QuadExpr {
+    type Output = QuadExpr;
+
+    fn add(mut self, rhs: LinExpr) -> QuadExpr {
+        self += rhs;
+        self
+    }
+}
+
+/// LinExpr + QuadExpr
+impl Add<QuadExpr> for LinExpr {
+    type Output = QuadExpr;
+
+    fn add(self, rhs: QuadExpr) -> QuadExpr {
+        rhs + self
+    }
+}
+
+/// QuadExpr + VarId
+impl Add<VarId> for QuadExpr {
+    type Output = QuadExpr;
+
+    fn add(mut self, rhs: VarId) -> QuadExpr {
+        self.linear += rhs;
+        self
+    }
+}
+
+/// QuadExpr + f64
+impl Add<f64> for QuadExpr {
+    type Output = QuadExpr;
+
+    fn add(mut self, rhs: f64) -> QuadExpr {
+        self.linear.constant += rhs;
+        self
+    }
+}
+
+/// QuadExpr += QuadExpr
+impl AddAssign for QuadExpr {
+    fn add_assign(&mut self, rhs: QuadExpr) {
+        self.quad.extend(rhs.quad);
+        self.linear += rhs.linear;
+    }
+}
+
+/// QuadExpr += LinExpr
+impl AddAssign<LinExpr> for QuadExpr {
+    fn add_assign(&mut self, rhs: LinExpr) {
+        self.linear += rhs;
+    }
+}
+
+/// QuadExpr - QuadExpr
+impl Sub for QuadExpr {
+    type Output = QuadExpr;
+
+    fn sub(self, rhs: QuadExpr) -> QuadExpr {
+        self + -rhs
+    }
+}
+
+/// QuadExpr - LinExpr
+impl Sub<LinExpr> for QuadExpr {
+    type Output = QuadExpr;
+
+    fn sub(mut self, rhs: LinExpr) -> QuadExpr {
+        self.linear = self.linear - rhs;
+        self
+    }
+}
+
+/// LinExpr - QuadExpr
+impl Sub<QuadExpr> for LinExpr {
+    type Output = QuadExpr;
+
+    fn sub(self, rhs: QuadExpr) -> QuadExpr {
+        -rhs + self
+    }
+}
+
+/// Allows converting a linear expression into a quadratic expression without
+/// quadratic terms.
+impl From<LinExpr> for QuadExpr {
+    fn from(linear: LinExpr) -> Self {
+        QuadExpr { quad: Vec::new(), linear }
+    }
+}
+
+impl From<VarId> for QuadExpr {
+    fn from(var: VarId) -> Self {
+        LinExpr::from(var).into()
+    }
+}
+
+impl From<f64> for QuadExpr {
+    fn from(c: f64) -> Self {
+        LinExpr::constant(c).into()
+    }
+}
@@ -0,0 +1,75 @@
+//! Sensitivity analysis (ranging) of an optimal LP solution.
+
+use crate::{ConstraintRef, VarId};
+
+/// The interval over which the optimal basis stays optimal when a single
+/// objective coefficient or right-hand side changes.
+///
+/// Inside `[lower, upper]` the objective value changes linearly with slope
+/// [`rate`](Self::rate): the value of the variable for objective ranging, and
+/// the shadow price of the constraint for right-hand side ranging.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SensitivityRange {
+    /// Current value of the coefficient or right-hand side.
+    pub value: f64,
+    /// Smallest value keeping the basis optimal, or `-inf` if unbounded below.
+    pub lower: f64,
+    /// Largest value keeping the basis optimal, or `inf` if unbounded above.
+    pub upper: f64,
+    /// Rate of change of the objective value inside the interval.
+    pub rate: f64,
+}
+
+/// Objective and right-hand side ranging of an optimal basis.
+///
+/// Obtained from [`Solver::sensitivity`](crate::Solver::sensitivity) after an
+/// optimal solve.
+///
+/// # Examples
+///
+/// ```rust
+/// # use cnvx_lp::*;
+/// let mut model = LpModel::new();
+/// let x = model.add_var().finish();
+/// let y = model.add_var().finish();
+/// model += (x + y).leq(4.0).named("capacity");
+/// model += x.leq(3.0);
+/// model.add_objective(Objective::maximize(3.0 * x + 2.0 * y).name("profit"));
+///
+/// let mut solver = LpSolver::new();
+/// solver.solve(&model).unwrap();
+/// let report = solver.sensitivity(&model).unwrap();
+///
+/// // The profit of `x` can fall to 2 before `y` becomes more attractive.
+/// let cx = report.objective_range(x).unwrap();
+/// assert!((cx.lower - 2.0).abs() < 1e-9 && cx.upper.is_infinite());
+///
+/// // Each unit of capacity is worth 2 while capacity stays within [3, inf).
+/// let cap = report.rhs_range("capacity").unwrap();
+/// assert!((cap.rate - 2.0).abs() < 1e-9 && (cap.lower - 3.0).abs() < 1e-9);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Sensitivity {
+    /// Ranging of each objective coefficient, indexed by variable ID.
+    pub objective: Vec<SensitivityRange>,
+    /// Ranging of each constraint right-hand side, indexed like
+    /// [`LpModel::constraints`](crate::LpModel::constraints).
+    pub rhs: Vec<SensitivityRange>,
+    /// Name of each constraint, used to look constraints up by name.
+    pub constraint_names: Vec<Option<String>>,
+}
+
+impl Sensitivity {
+    /// Returns the ranging of the objective coefficient of `var`.
+    pub fn objective_range(&self, var: VarId) -> Option<SensitivityRange> {
+        self.objective.get(var.0).copied()
+    }
+
+    /// Returns the ranging of a constraint's right-hand side, looked up by index
+    /// or name.
+    pub fn rhs_range(&self, constraint: impl ConstraintRef) -> Option<SensitivityRange> {
+        constraint
+            .index_in(&self.constraint_names)
+            .and_then(|i| self.rhs.get(i).copied())
+    }
+}
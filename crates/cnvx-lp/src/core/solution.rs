@@ -1,12 +1,62 @@
 use cnvx_core::SolveStatus;
 
-use crate::VarId;
+use crate::{Certificate, LpModel, VarId};
 use std::fmt::Display;
 
+/// Position of a variable relative to the final simplex basis.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BasisStatus {
+    /// The variable is basic.
+    Basic,
+    /// The variable is non-basic at its lower bound.
+    AtLower,
+    /// The variable is non-basic at its upper bound.
+    AtUpper,
+    /// The variable is non-basic but not at one of its bounds (e.g. a free
+    /// variable held at zero).
+    Superbasic,
+}
+
+/// Identifies a constraint of the solved model, either by its index in
+/// [`LpModel::constraints`] or by its name.
+///
+/// # Examples
+///
+/// ```rust
+/// # use cnvx_lp::*;
+/// let mut model = LpModel::new();
+/// let x = model.add_var().finish();
+/// model += x.leq(4.0).named("capacity");
+/// model.add_objective(Objective::maximize(3.0 * x).name("profit"));
+///
+/// let solution = PrimalSimplexSolver::new().solve(&model).unwrap();
+/// assert_eq!(solution.dual(0), solution.dual("capacity"));
+/// ```
+pub trait ConstraintRef {
+    /// Returns the index of the constraint among `names`, the constraint names of
+    /// the solved model, if it exists.
+    fn index_in(&self, names: &[Option<String>]) -> Option<usize>;
+}
+
+impl ConstraintRef for usize {
+    fn index_in(&self, names: &[Option<String>]) -> Option<usize> {
+        (*self < names.len()).then_some(*self)
+    }
+}
+
+impl ConstraintRef for &str {
+    fn index_in(&self, names: &[Option<String>]) -> Option<usize> {
+        names.iter().position(|n| n.as_deref() == Some(*self))
+    }
+}
+
 /// Represents the result of solving an optimization problem.
 ///
 /// Contains the values assigned to each variable, the value of the objective function,
-/// and the solver status.
+/// and the solver status. Solvers that finish on an optimal basis also report the
+/// shadow price of every constraint, the reduced cost and basis status of every
+/// variable, and the activity and slack of every constraint. Solvers that prove
+/// a model infeasible or unbounded may attach a [`Certificate`] of it.
 ///
 /// # Examples
 ///
@@ -15,11 +65,7 @@ use std::fmt::Display;
 /// use cnvx_lp::{LpSolution, VarId};
 ///
 /// // Example solution with 3 variables
-/// let solution = LpSolution {
-///     values: vec![1.0, 2.0, 3.0],
-///     objective_value: Some(10.0),
-///     status: SolveStatus::Optimal,
-/// };
+/// let solution = LpSolution::new(vec![1.0, 2.0, 3.0], Some(10.0), SolveStatus::Optimal);
 ///
 /// assert_eq!(solution.value(VarId(0)), 1.0);
 /// assert_eq!(solution.value(VarId(2)), 3.0);
@@ -36,11 +82,83 @@ pub struct LpSolution {
     /// [`None`] if the solver did not produce an objective value (e.g. infeasible or unbounded problem).
     pub objective_value: Option<f64>,
 
+    /// Value of each objective of the model at the solution, highest priority
+    /// first, as in [`LpModel::objectives`].
+    ///
+    /// Filled in by lexicographic solves of models with several objectives,
+    /// and empty otherwise.
+    pub objective_values: Vec<f64>,
+
     /// The solver status indicating whether the solution is optimal, feasible, infeasible, or unbounded.
     pub status: SolveStatus,
+
+    /// Shadow price of each constraint, indexed like [`LpModel::constraints`].
+    ///
+    /// The shadow price is the rate of change of the objective value per unit
+    /// increase of the constraint's right-hand side. Empty if the solver did not
+    /// produce dual information.
+    pub duals: Vec<f64>,
+
+    /// Reduced cost of each variable, indexed by variable ID.
+    ///
+    /// Computed as `c_j - Σ_i y_i a_ij` over the model's constraints, so it also
+    /// carries the shadow price of an active variable bound. For a quadratic
+    /// objective, `c_j` is the gradient of the objective at the solution. Empty if the solver
+    /// did not produce dual information.
+    pub reduced_costs: Vec<f64>,
+
+    /// Status of each variable in the final basis, indexed by variable ID.
+    ///
+    /// Empty if the solver did not finish on a basis.
+    pub basis_status: Vec<BasisStatus>,
+
+    /// Value of the left-hand side of each constraint at the solution.
+    pub activities: Vec<f64>,
+
+    /// Slack `rhs - activity` of each constraint at the solution.
+    pub slacks: Vec<f64>,
+
+    /// Name of each constraint, used to look constraints up by name.
+    pub constraint_names: Vec<Option<String>>,
+
+    /// Proof that the model is infeasible or unbounded, if the solver produced
+    /// one.
+    pub certificate: Option<Certificate>,
 }
 
 impl LpSolution {
+    /// Creates a solution holding only primal values.
+    pub fn new(
+        values: Vec<f64>,
+        objective_value: Option<f64>,
+        status: SolveStatus,
+    ) -> Self {
+        Self {
+            values,
+            objective_value,
+            objective_values: Vec::new(),
+            status,
+            duals: Vec::new(),
+            reduced_costs: Vec::new(),
+            basis_status: Vec::new(),
+            activities: Vec::new(),
+            slacks: Vec::new(),
+            constraint_names: Vec::new(),
+            certificate: None,
+        }
+    }
+
+    /// Evaluates the constraints of `model` at the solution values, filling in
+    /// [`activities`](Self::activities), [`slacks`](Self::slacks) and
+    /// [`constraint_names`](Self::constraint_names).
+    pub fn with_activities(mut self, model: &LpModel) -> Self {
+        let cons = model.constraints();
+        self.activities = cons.iter().map(|c| c.expr.evaluate(&self.values)).collect();
+        self.slacks = cons.iter().zip(&self.activities).map(|(c, a)| c.rhs - a).collect();
+        self.constraint_names = cons.iter().map(|c| c.name.clone()).collect();
+        self
+    }
+
     /// Returns the value assigned to the variable with the given [`VarId`].
     ///
     /// # Example
@@ -50,16 +168,62 @@ impl LpSolution {
     /// # use cnvx_lp::{LpModel, LpSolution, VarId};
     /// # let mut model = LpModel::new();
     /// let x1: VarId = model.add_var().finish();
-    /// let solution = LpSolution {
-    ///     values: vec![1.0], // Assuming x1 has ID 0
-    ///     objective_value: Some(10.0),
-    ///     status: SolveStatus::Optimal,
-    /// };
+    /// // Assuming x1 has ID 0
+    /// let solution = LpSolution::new(vec![1.0], Some(10.0), SolveStatus::Optimal);
     /// let value = solution.value(x1);
     /// ```
     pub fn value(&self, var: VarId) -> f64 {
         self.values[var.0]
     }
+
+    /// Returns the shadow price of a constraint, looked up by index or name.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use cnvx_lp::*;
+    /// let mut model = LpModel::new();
+    /// let x = model.add_var().finish();
+    /// let y = model.add_var().finish();
+    /// model += (x + y).leq(4.0).named("capacity");
+    /// model += x.leq(3.0);
+    /// model.add_objective(Objective::maximize(3.0 * x + 2.0 * y).name("profit"));
+    ///
+    /// let solution = PrimalSimplexSolver::new().solve(&model).unwrap();
+    /// assert!((solution.dual("capacity").unwrap() - 2.0).abs() < 1e-9);
+    /// assert!((solution.dual(1).unwrap() - 1.0).abs() < 1e-9);
+    /// ```
+    pub fn dual(&self, constraint: impl ConstraintRef) -> Option<f64> {
+        constraint
+            .index_in(&self.constraint_names)
+            .and_then(|i| self.duals.get(i).copied())
+    }
+
+    /// Returns the value of the left-hand side of a constraint, looked up by index
+    /// or name.
+    pub fn activity(&self, constraint: impl ConstraintRef) -> Option<f64> {
+        constraint
+            .index_in(&self.constraint_names)
+            .and_then(|i| self.activities.get(i).copied())
+    }
+
+    /// Returns the slack `rhs - activity` of a constraint, looked up by index or
+    /// name.
+    pub fn slack(&self, constraint: impl ConstraintRef) -> Option<f64> {
+        constraint
+            .index_in(&self.constraint_names)
+            .and_then(|i| self.slacks.get(i).copied())
+    }
+
+    /// Returns the reduced cost of the variable with the given [`VarId`].
+    pub fn reduced_cost(&self, var: VarId) -> Option<f64> {
+        self.reduced_costs.get(var.0).copied()
+    }
+
+    /// Returns the basis status of the variable with the given [`VarId`].
+    pub fn basis_status(&self, var: VarId) -> Option<BasisStatus> {
+        self.basis_status.get(var.0).copied()
+    }
 }
 
 impl Display for LpSolution {
@@ -1,6 +1,98 @@
 use cnvx_core::SolveError;
 
-use crate::{LpModel, LpSolution};
+use crate::{LpModel, LpSolution, Sensitivity};
+
+/// The kinds of models a [`Solver`] can handle, as reported by
+/// [`Solver::capabilities`].
+///
+/// The default describes a plain LP solver: continuous variables with any
+/// bounds, no size limits, no warm starts and no scaling.
+///
+/// # Examples
+///
+/// ```rust
+/// # use cnvx_lp::*;
+/// let mut model = LpModel::new();
+/// let x = model.add_var().integer().finish();
+/// model += x.leq(3.0);
+/// model.add_objective(Objective::maximize(1.0 * x).name("Z"));
+///
+/// let caps = PrimalSimplexSolver::new().capabilities();
+/// assert!(caps.check(&model).is_err());
+/// assert!(MipSolver::new().capabilities().check(&model).is_ok());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Capabilities {
+    /// Whether the solver enforces integrality of integer variables.
+    pub integer_vars: bool,
+    /// Whether the solver accepts variables without a lower bound.
+    pub free_vars: bool,
+    /// Largest number of variables the solver accepts, if limited.
+    pub max_vars: Option<usize>,
+    /// Largest number of constraints the solver accepts, if limited.
+    pub max_constraints: Option<usize>,
+    /// Whether [`Solver::resolve`] can reuse the state of the previous solve.
+    pub warm_start: bool,
+    /// Whether the solver accepts objectives with quadratic terms.
+    pub quadratic_objective: bool,
+    /// Whether the solver accepts second-order cone constraints.
+    pub conic_constraints: bool,
+    /// Whether the solver scales the constraint matrix as set by its
+    /// [`Scaling`](crate::Scaling) option.
+    pub scaling: bool,
+}
+
+impl Default for Capabilities {
+    fn default() -> Self {
+        Self {
+            integer_vars: false,
+            free_vars: true,
+            max_vars: None,
+            max_constraints: None,
+            warm_start: false,
+            quadratic_objective: false,
+            conic_constraints: false,
+            scaling: false,
+        }
+    }
+}
+
+impl Capabilities {
+    /// Check whether a solver with these capabilities can solve `model`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a description of the first model feature the solver cannot
+    /// handle.
+    pub fn check(&self, model: &LpModel) -> Result<(), String> {
+        let (rows, cols) = model.shape();
+        if !self.integer_vars && model.vars().iter().any(|v| v.is_integer) {
+            return Err("model has integer variables".to_string());
+        }
+        if !self.quadratic_objective
+            && model.objectives().iter().any(|o| o.is_quadratic())
+        {
+            return Err("model has a quadratic objective".to_string());
+        }
+        if !self.conic_constraints && !model.cones().is_empty() {
+            return Err("model has second-order cone constraints".to_string());
+        }
+        if !self.free_vars && model.vars().iter().any(|v| v.lb.is_none()) {
+            return Err("model has variables without a lower bound".to_string());
+        }
+        if let Some(max) = self.max_vars
+            && cols > max
+        {
+            return Err(format!("model has {cols} variables, more than {max}"));
+        }
+        if let Some(max) = self.max_constraints
+            && rows > max
+        {
+            return Err(format!("model has {rows} constraints, more than {max}"));
+        }
+        Ok(())
+    }
+}
 
 /// Trait for optimization solvers.
 ///
@@ -49,6 +141,59 @@ pub trait Solver: Send {
     /// on internal assertion failures that indicate a programming error.
     fn solve(&mut self, model: &LpModel) -> Result<LpSolution, SolveError>;
 
+    /// Re-solve `model` after it has been edited, reusing the internal state
+    /// retained by the previous call to [`solve`](Self::solve) where possible.
+    ///
+    /// Intended for sequences of closely related models, e.g. after adding
+    /// constraints, tightening bounds, or changing right-hand sides or
+    /// objective coefficients. The default implementation simply calls
+    /// [`solve`](Self::solve); solvers that support warm starts override it.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`solve`](Self::solve).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use cnvx_lp::*;
+    /// let mut model = LpModel::new();
+    /// let x = model.add_var().finish();
+    /// let y = model.add_var().finish();
+    /// model += (x + y).leq(4.0);
+    /// model.add_objective(Objective::maximize(x + 2.0 * y).name("Z"));
+    ///
+    /// let mut solver = DualSimplexSolver::new();
+    /// solver.solve(&model).unwrap();
+    ///
+    /// // Add a constraint and re-optimise from the previous basis.
+    /// model += y.leq(1.0);
+    /// let solution = solver.resolve(&model).unwrap();
+    /// assert!((solution.objective_value.unwrap() - 5.0).abs() < 1e-6);
+    /// ```
+    fn resolve(&mut self, model: &LpModel) -> Result<LpSolution, SolveError> {
+        self.solve(model)
+    }
+
+    /// Objective and right-hand side ranging of the basis found by the most
+    /// recent call to [`solve`](Self::solve) or [`resolve`](Self::resolve).
+    ///
+    /// `model` must be the model that was solved. See [`Sensitivity`] for an
+    /// example.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SolveError::Unsupported`] if the solver does not provide
+    /// sensitivity analysis (the default), and [`SolveError::Other`] if the most
+    /// recent solve did not end on an optimal basis.
+    fn sensitivity(&self, model: &LpModel) -> Result<Sensitivity, SolveError> {
+        _ = model;
+        Err(SolveError::Unsupported(format!(
+            "{} does not provide sensitivity analysis",
+            self.name()
+        )))
+    }
+
     /// Returns the objective value from the most recent call to [`solve`](Self::solve).
     ///
     /// Returns `None` if `solve` has not been called yet, or if the most
@@ -67,6 +212,28 @@ pub trait Solver: Send {
     /// is more expressive.
     fn solution_vector(&self) -> Vec<f64>;
 
+    /// Returns the kinds of models this solver can handle.
+    ///
+    /// The default implementation returns [`Capabilities::default`], a plain
+    /// LP solver.
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::default()
+    }
+
+    /// Whether this solver can solve `model`, according to its
+    /// [`capabilities`](Self::capabilities).
+    fn supports(&self, model: &LpModel) -> bool {
+        self.capabilities().check(model).is_ok()
+    }
+
+    /// Whether the next call to [`resolve`](Self::resolve) can start from state
+    /// retained by the previous solve (e.g. an optimal basis).
+    ///
+    /// The default implementation returns `false`.
+    fn has_warm_start(&self) -> bool {
+        false
+    }
+
     /// Returns a human-readable name for this solver.
     ///
     /// Used in diagnostics, error messages, and logging.  The default
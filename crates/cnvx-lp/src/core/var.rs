@@ -116,6 +116,30 @@ impl<'a> VarBuilder<'a> {
         self
     }
 
+    /// Removes both bounds, making the variable free.
+    ///
+    /// Variables are non-negative by default; use this for variables that may
+    /// take any sign.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use cnvx_lp::*;
+    /// let mut model = LpModel::new();
+    /// let x = model.add_var().free().finish();
+    /// model += x.geq(-5.0);
+    /// model.add_objective(Objective::minimize(1.0 * x).name("min_x"));
+    ///
+    /// let solution = PrimalSimplexSolver::new().solve(&model).unwrap();
+    /// assert!((solution.value(x) + 5.0).abs() < 1e-9);
+    /// ```
+    pub fn free(self) -> Self {
+        let var = &mut self.model.vars[self.var.0];
+        var.lb = None;
+        var.ub = None;
+        self
+    }
+
     /// Mark the variable as an integer.
     ///
     /// # Examples
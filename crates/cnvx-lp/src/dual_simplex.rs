@@ -1,7 +1,12 @@
-use cnvx_core::SolveError;
-use cnvx_math::{DenseMatrix, Matrix};
+use cnvx_core::{SolveError, SolveStatus};
+use cnvx_math::{DenseMatrix, Matrix, SparseMatrix};
 
-use crate::{LpModel, LpSolution, Solver};
+use crate::scaling::{ScaleFactors, Scaling};
+use crate::simplex::{
+    self, BasisFactor, BasisView, ColumnKey, Layout, Reoptimized, StandardForm,
+    WarmStart, is_sparse,
+};
+use crate::{Capabilities, LpModel, LpSolution, Sensitivity, Solver};
 
 /// Dual simplex solver for linear programs.
 ///
@@ -10,12 +15,20 @@ use crate::{LpModel, LpSolution, Solver};
 /// adding a constraint or a cut in branch-and-bound), where the primal simplex
 /// would require an expensive Phase 1.
 ///
-/// ## Status
+/// ## Algorithm
 ///
-/// The `solve` implementation is **not yet complete** - it returns
-/// [`SolveError::Unsupported`]. The state scaffolding ([`DualSimplexState`]) is
-/// in place so that the algorithm can be filled in incrementally without
-/// interface changes.
+/// The solver starts from the slack basis (with artificial columns for equality
+/// rows) and places every non-basic column at the bound favoured by its reduced
+/// cost, which makes the basis dual feasible. Columns lacking that bound are
+/// given an artificial bound `M` for the duration of the solve. Dual iterations
+/// then pick the basic variable furthest outside its bounds to leave and use the
+/// dual ratio test to choose the entering column, terminating with
+/// [`Optimal`](SolveStatus::Optimal), [`Infeasible`](SolveStatus::Infeasible) or
+/// [`Unbounded`](SolveStatus::Unbounded) (when an artificial bound is active at
+/// the optimum).
+///
+/// The constraint matrix is stored as a [`SparseMatrix`] when the model is
+/// sparse, and as a [`DenseMatrix`] otherwise.
 ///
 /// ## Compatibility
 ///
@@ -24,20 +37,36 @@ use crate::{LpModel, LpSolution, Solver};
 ///
 /// ## Configuration
 ///
-/// ```rust,ignore
+/// ```rust
+/// # use cnvx_lp::*;
+/// let mut model = LpModel::new();
+/// let x = model.add_var().finish();
+/// let y = model.add_var().finish();
+/// model += (x + y).geq(2.0);
+/// model += (x + -1.0 * y).eq(1.0);
+/// model.add_objective(Objective::minimize(3.0 * x + y).name("cost"));
+///
 /// let mut solver = DualSimplexSolver::new();
 /// solver.tolerance = 1e-9;
 /// solver.max_iter  = 2000;
+///
+/// let solution = solver.solve(&model).unwrap();
+/// assert!((solution.objective_value.unwrap() - 5.0).abs() < 1e-6);
 /// ```
 pub struct DualSimplexSolver {
     /// Internal state retained between solve() calls for warm-starting.
     ///
     /// `None` until the first successful solve.
-    state: Option<DualSimplexState<DenseMatrix>>,
+    state: Option<State>,
     /// The numerical tolerance used for feasibility and optimality checks.
     pub tolerance: f64,
     /// The maximum number of dual simplex iterations before terminating.
     pub max_iter: usize,
+    /// How to scale the constraint matrix before solving from scratch.
+    ///
+    /// Values, duals and reduced costs are always reported for the original,
+    /// unscaled model.
+    pub scaling: Scaling,
     /// Whether to log iteration details during solving.
     pub logging: bool,
 
@@ -54,6 +83,7 @@ impl DualSimplexSolver {
             state: None,
             tolerance: 1e-8,
             max_iter: 1000,
+            scaling: Scaling::default(),
             logging: false,
             last_objective: None,
             last_solution: Vec::new(),
@@ -61,6 +91,92 @@ impl DualSimplexSolver {
     }
 }
 
+impl DualSimplexSolver {
+    /// Record the outcome of a solve and return its [`LpSolution`].
+    fn finish(&mut self, state: Option<State>, solution: LpSolution) -> LpSolution {
+        if self.logging
+            && let Some(state) = &state
+        {
+            println!(
+                "Dual simplex finished with status {:?} in {} iterations. Objective value: {}",
+                solution.status,
+                state.iteration(),
+                solution.objective_value.unwrap_or_default()
+            );
+        }
+
+        self.last_objective = solution.objective_value;
+        self.last_solution = solution.values.clone();
+        self.state = state;
+
+        solution
+    }
+
+    /// Build the [`LpSolution`] of a finished solve; the objective value is only
+    /// reported for optimal solutions.
+    fn build_solution(
+        model: &LpModel,
+        status: SolveStatus,
+        values: Vec<f64>,
+        obj: f64,
+    ) -> LpSolution {
+        let objective_value = (status == SolveStatus::Optimal).then_some(obj);
+        LpSolution::new(values, objective_value, status).with_activities(model)
+    }
+
+    /// Solve `model` from scratch with the constraint matrix stored as `A`.
+    fn solve_with<A: Matrix>(
+        &self,
+        model: &LpModel,
+    ) -> Result<(LpSolution, DualSimplexState<A>), SolveError> {
+        let mut state: DualSimplexState<A> =
+            DualSimplexState::with_scaling(model, self.scaling);
+        state.logging = self.logging;
+
+        let (values, obj) = state.solve_lp(self.max_iter, self.tolerance)?;
+
+        let mut solution = Self::build_solution(model, state.status.clone(), values, obj);
+        state.attach_duals(&mut solution)?;
+        Ok((solution, state))
+    }
+
+    /// Re-optimise `model` from `warm` with the constraint matrix stored as `A`.
+    ///
+    /// Returns `None` if the basis no longer fits the model.
+    fn resolve_with<A: Matrix>(
+        &self,
+        model: &LpModel,
+        warm: &WarmStart,
+    ) -> Result<Option<Resolved<A>>, SolveError> {
+        let Some(state) =
+            simplex::reoptimize::<A>(model, warm, self.max_iter, self.tolerance)?
+        else {
+            return Ok(None);
+        };
+
+        let (values, obj) = state.solution();
+        let mut solution = Self::build_solution(model, state.status(), values, obj);
+        state.attach_duals(&mut solution)?;
+
+        let state = match state {
+            Reoptimized::Dual(s) => Some(s),
+            Reoptimized::Primal(s) => s
+                .warm_start()
+                .and_then(|w| DualSimplexState::with_basis(model, &w))
+                .map(|mut d| {
+                    d.status = s.status.clone();
+                    d
+                }),
+        };
+
+        Ok(Some((solution, state)))
+    }
+}
+
+/// A re-optimised solution, with the dual simplex state it ended in if that
+/// could be rebuilt.
+type Resolved<A> = (LpSolution, Option<DualSimplexState<A>>);
+
 impl Default for DualSimplexSolver {
     fn default() -> Self {
         Self::new()
@@ -73,13 +189,50 @@ impl Solver for DualSimplexSolver {
     }
 
     fn solve(&mut self, model: &LpModel) -> Result<LpSolution, SolveError> {
-        _ = self.state; // Silence unused field warning until solve() is implemented
-        _ = model; // Silence unused parameter warning until solve() is implemented
+        crate::validate::check_lp(model)?;
+
+        let (solution, state) = if is_sparse(model) {
+            let (solution, state) = self.solve_with::<SparseMatrix>(model)?;
+            (solution, State::Sparse(state))
+        } else {
+            let (solution, state) = self.solve_with::<DenseMatrix>(model)?;
+            (solution, State::Dense(state))
+        };
+        Ok(self.finish(Some(state), solution))
+    }
+
+    /// Re-optimises `model` from the basis of the previous solve.
+    ///
+    /// New primal infeasibilities (added constraints, tightened bounds, changed
+    /// right-hand sides) are removed with the dual simplex; new dual
+    /// infeasibilities (changed objective coefficients) with the primal simplex.
+    /// Falls back to [`solve`](Solver::solve) when there is no previous optimal
+    /// basis or it no longer fits the model.
+    fn resolve(&mut self, model: &LpModel) -> Result<LpSolution, SolveError> {
+        crate::validate::check_lp(model)?;
 
-        // TODO: implement dual simplex iterations
-        Err(SolveError::Unsupported(
-            "DualSimplexSolver.solve() is not yet implemented".to_string(),
-        ))
+        let Some(warm) = self.state.as_ref().and_then(State::warm_start) else {
+            return self.solve(model);
+        };
+        let resolved = if is_sparse(model) {
+            self.resolve_with::<SparseMatrix>(model, &warm)?
+                .map(|(solution, state)| (solution, state.map(State::Sparse)))
+        } else {
+            self.resolve_with::<DenseMatrix>(model, &warm)?
+                .map(|(solution, state)| (solution, state.map(State::Dense)))
+        };
+        match resolved {
+            Some((solution, state)) => Ok(self.finish(state, solution)),
+            None => self.solve(model),
+        }
+    }
+
+    fn sensitivity(&self, model: &LpModel) -> Result<Sensitivity, SolveError> {
+        match &self.state {
+            Some(State::Dense(s)) => s.sensitivity(model),
+            Some(State::Sparse(s)) => s.sensitivity(model),
+            None => Err(SolveError::Other("no previous solve to analyse".into())),
+        }
     }
 
     fn objective_value(&self) -> Option<f64> {
@@ -89,13 +242,50 @@ impl Solver for DualSimplexSolver {
     fn solution_vector(&self) -> Vec<f64> {
         self.last_solution.clone()
     }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            warm_start: true,
+            scaling: true,
+            ..Capabilities::default()
+        }
+    }
+
+    fn has_warm_start(&self) -> bool {
+        self.state.as_ref().and_then(State::warm_start).is_some()
+    }
+}
+
+/// The state of the last solve, with the constraint matrix in the format
+/// chosen for the model.
+enum State {
+    Dense(DualSimplexState<DenseMatrix>),
+    Sparse(DualSimplexState<SparseMatrix>),
+}
+
+impl State {
+    fn iteration(&self) -> usize {
+        match self {
+            State::Dense(s) => s.iteration,
+            State::Sparse(s) => s.iteration,
+        }
+    }
+
+    fn warm_start(&self) -> Option<WarmStart> {
+        match self {
+            State::Dense(s) => s.warm_start(),
+            State::Sparse(s) => s.warm_start(),
+        }
+    }
 }
 
 /// State used internally by the dual simplex solver.
 ///
 /// Mirrors the fields in [`PrimalSimplexState`](crate::primal_simplex::PrimalSimplexState)
-/// so that the two implementations share a common structure and can eventually
-/// share helper utilities (e.g. pivot, basis update, dual computation).
+/// and shares its basis and pivot helpers through the [`simplex`] module.
+///
+/// Equality rows have no slack column, so they are started with an artificial
+/// column that is fixed at zero: it may only leave the basis and never re-enters.
 #[derive(Clone)]
 pub struct DualSimplexState<A: Matrix> {
     /// Current iteration count of the dual simplex algorithm.
@@ -106,34 +296,505 @@ pub struct DualSimplexState<A: Matrix> {
     pub non_basis: Vec<usize>,
     /// Values of the basic variables.
     pub x_b: Vec<f64>,
+    /// Values of the non-basic columns, indexed by column (entries of basic
+    /// columns are unused).
+    pub x_n: Vec<f64>,
     /// Constraint matrix `A`.
     pub a: A,
     /// Right-hand side vector `b`.
     pub b: Vec<f64>,
     /// Objective coefficients vector `c`.
     pub c: Vec<f64>,
+    /// Lower bound of each column.
+    pub lower: Vec<f64>,
+    /// Upper bound of each column.
+    pub upper: Vec<f64>,
     /// Current objective value.
     pub objective: f64,
+    /// Solution status after solving (Optimal, Infeasible, Unbounded, etc.).
+    pub status: SolveStatus,
+
+    /// Origin of the standard-form rows and columns.
+    layout: Layout,
+
+    /// Factors the standard form was scaled by.
+    scale: ScaleFactors,
+
+    /// Rows owning the artificial columns, in column order starting at `layout.n_cols`.
+    art_rows: Vec<usize>,
+
+    /// Columns given an artificial finite bound to make the starting basis dual
+    /// feasible, with their original lower and upper bounds.
+    art_bounds: Vec<(usize, f64, f64)>,
+
+    /// Whether the LP is a minimization problem.
+    minimise: bool,
+
+    /// Whether to log iteration details during the dual simplex algorithm.
+    logging: bool,
+
+    /// Interval at which to log iteration details if logging is enabled.
+    log_interval: usize,
 }
 
 impl<A: Matrix> DualSimplexState<A> {
-    /// Initialises an empty dual simplex state.
-    pub fn new() -> Self {
+    /// Initialise a new dual simplex state from a given `Model`.
+    ///
+    /// Builds the standard form and the starting slack basis, adding an
+    /// artificial column for every equality row.
+    pub fn new(model: &LpModel) -> Self {
+        Self::with_scaling(model, Scaling::None)
+    }
+
+    /// Initialise a new dual simplex state from a given `Model`, with its
+    /// standard form scaled by `scaling`.
+    ///
+    /// The fields of the state refer to the scaled problem;
+    /// [`solution`](Self::solution), [`attach_duals`](Self::attach_duals) and
+    /// [`sensitivity`](Self::sensitivity) report values of the original model.
+    pub fn with_scaling(model: &LpModel, scaling: Scaling) -> Self {
+        let mut form = StandardForm::<A>::new(model);
+        let scale = form.scale(scaling);
+        let basis = form.layout.slacks.iter().flatten().map(|&(col, _)| col).collect();
+        let art_rows = (0..form.layout.rows.len())
+            .filter(|&r| form.layout.slacks[r].is_none())
+            .collect();
+        let x_n = form.initial_values();
+        Self::from_form(form, scale, basis, art_rows, x_n)
+    }
+
+    /// Initialise a dual simplex state for `model` from a previous optimal basis.
+    ///
+    /// Returns `None` if the basis cannot be mapped onto the model or is singular.
+    pub fn with_basis(model: &LpModel, warm: &WarmStart) -> Option<Self> {
+        let form = StandardForm::<A>::new(model);
+        let (basis, art_rows) = warm.map_onto(&form.layout)?;
+        let mut x_n = form.initial_values();
+        warm.restore_upper(&form.layout, &form.upper, &mut x_n);
+
+        let scale = ScaleFactors::identity(form.a.rows(), form.a.cols());
+        let mut state = Self::from_form(form, scale, basis, art_rows, x_n);
+        let factor = state.factor_or_repair().ok()?;
+        state.x_b = state.compute_basic_solution(&factor);
+        state.update_objective();
+        Some(state)
+    }
+
+    /// Build the state from a standard form scaled by `scale`, a set of basic
+    /// columns, the rows that complete the basis with an artificial column, and
+    /// the starting values of the non-basic columns.
+    fn from_form(
+        mut form: StandardForm<A>,
+        scale: ScaleFactors,
+        mut basis: Vec<usize>,
+        art_rows: Vec<usize>,
+        mut x_n: Vec<f64>,
+    ) -> Self {
+        let n = form.a.cols();
+        let n_art = art_rows.len();
+        form.add_artificials(&art_rows.iter().map(|&r| (r, 1.0)).collect::<Vec<_>>());
+        basis.extend(n..n + n_art);
+        x_n.resize(n + n_art, 0.0);
+
+        let mut in_basis = vec![false; n];
+        for &j in basis.iter().filter(|&&j| j < n) {
+            in_basis[j] = true;
+        }
+
         Self {
             iteration: 0,
-            basis: vec![],
-            non_basis: vec![],
-            x_b: vec![],
-            a: A::new(0, 0),
-            b: vec![],
-            c: vec![],
+            basis,
+            non_basis: (0..n).filter(|&j| !in_basis[j]).collect(),
+            x_b: vec![0.0; form.a.rows()],
+            x_n,
+            a: form.a,
+            b: form.b,
+            c: form.c,
+            lower: form.lower,
+            upper: form.upper,
             objective: 0.0,
+            status: SolveStatus::NotSolved,
+            layout: form.layout,
+            scale,
+            art_rows,
+            art_bounds: Vec::new(),
+            minimise: form.minimise,
+            logging: false,
+            log_interval: 100,
         }
     }
-}
 
-impl<A: Matrix> Default for DualSimplexState<A> {
-    fn default() -> Self {
-        Self::new()
+    /// Returns the optimal basis for warm-starting a later re-solve, or `None`
+    /// if the last solve did not reach an optimum.
+    pub fn warm_start(&self) -> Option<WarmStart> {
+        if self.status != SolveStatus::Optimal {
+            return None;
+        }
+        let basis = self
+            .basis
+            .iter()
+            .filter_map(|&j| match self.artificial_row(j) {
+                Some(row) => Some(ColumnKey::Artificial(self.layout.rows[row])),
+                None => self.layout.column_key(j),
+            })
+            .collect();
+        let at_upper = simplex::upper_keys(
+            &self.layout,
+            &self.non_basis,
+            &self.x_n,
+            &self.lower,
+            &self.upper,
+        );
+        Some(WarmStart { rows: self.layout.rows.clone(), basis, at_upper })
+    }
+
+    /// Fill in the shadow prices, reduced costs and basis status of `solution`
+    /// from the final basis. Does nothing unless the last solve was optimal.
+    pub fn attach_duals(&self, solution: &mut LpSolution) -> Result<(), SolveError> {
+        match self.final_basis() {
+            Some(view) => view.attach_duals(solution),
+            None => Ok(()),
+        }
+    }
+
+    /// Compute objective and right-hand side ranging of the optimal basis.
+    ///
+    /// `model` must be the model this state was solved from.
+    pub fn sensitivity(&self, model: &LpModel) -> Result<Sensitivity, SolveError> {
+        self.final_basis()
+            .ok_or_else(|| {
+                SolveError::Other("sensitivity analysis requires an optimal basis".into())
+            })?
+            .sensitivity(model)
+    }
+
+    /// Returns a view of the basis if the last solve ended on an optimal basis.
+    fn final_basis(&self) -> Option<BasisView<'_, A>> {
+        if self.status != SolveStatus::Optimal {
+            return None;
+        }
+        Some(BasisView {
+            layout: &self.layout,
+            a: &self.a,
+            b: &self.b,
+            c: &self.c,
+            lower: &self.lower,
+            upper: &self.upper,
+            x_n: &self.x_n,
+            basis: &self.basis,
+            non_basis: &self.non_basis,
+            minimise: self.minimise,
+            scale: Some(&self.scale),
+        })
+    }
+
+    /// Check the current basis for primal and dual feasibility.
+    ///
+    /// The basis is dual feasible when no non-basic column can improve the
+    /// objective by moving away from its current value within its bounds.
+    pub fn feasibility(&self, tol: f64) -> Result<(bool, bool), SolveError> {
+        let pi = simplex::compute_duals(&self.factor_basis()?, &self.c, &self.basis);
+        let dual_feasible = self.non_basis.iter().all(|&j| {
+            let rc = simplex::reduced_cost(&self.a, &self.c, &pi, j);
+            (rc <= tol || self.x_n[j] >= self.upper[j])
+                && (rc >= -tol || self.x_n[j] <= self.lower[j])
+        });
+        Ok((self.choose_leaving(tol).is_none(), dual_feasible))
+    }
+
+    /// Solve the LP using the dual simplex method.
+    ///
+    /// Returns the solution vector and the objective value.
+    pub fn solve_lp(
+        &mut self,
+        max_iter: usize,
+        tol: f64,
+    ) -> Result<(Vec<f64>, f64), SolveError> {
+        self.make_dual_feasible(tol)?;
+        loop {
+            self.run_dual(max_iter, tol)?;
+            if self.status != SolveStatus::Optimal || !self.release_bounds(tol)? {
+                break;
+            }
+        }
+
+        Ok(self.solution())
+    }
+
+    /// Returns the solution vector (structural and slack columns) and the
+    /// objective value of the current basis.
+    pub fn solution(&self) -> (Vec<f64>, f64) {
+        let (mut x, obj) = simplex::extract_solution(
+            &self.c,
+            &self.basis,
+            &self.x_b,
+            &self.x_n,
+            self.layout.n_cols,
+            self.minimise,
+        );
+        for (j, x) in x.iter_mut().enumerate() {
+            *x = self.scale.value(j, *x);
+        }
+        (x, self.scale.objective_value(obj))
+    }
+
+    /// Make the starting basis dual feasible.
+    ///
+    /// Every non-basic column is moved to the bound its reduced cost favours:
+    /// the upper bound if it is positive, the lower bound if it is negative. A
+    /// column without that bound is given an artificial one of magnitude `M`,
+    /// which [`release_bounds`](Self::release_bounds) removes again once the
+    /// dual simplex has finished.
+    fn make_dual_feasible(&mut self, tol: f64) -> Result<(), SolveError> {
+        let pi = simplex::compute_duals(&self.factor_basis()?, &self.c, &self.basis);
+
+        let big_m = BOUND_SCALE
+            * self
+                .b
+                .iter()
+                .chain(&self.lower)
+                .chain(&self.upper)
+                .filter(|v| v.is_finite())
+                .fold(1.0_f64, |acc, v| acc.max(v.abs()));
+
+        for &j in &self.non_basis {
+            let rc = simplex::reduced_cost(&self.a, &self.c, &pi, j);
+            let (lower, upper) = (self.lower[j], self.upper[j]);
+            if rc > tol {
+                if !upper.is_finite() {
+                    self.art_bounds.push((j, lower, upper));
+                    self.upper[j] = big_m;
+                }
+                self.x_n[j] = self.upper[j];
+            } else if rc < -tol {
+                if !lower.is_finite() {
+                    self.art_bounds.push((j, lower, upper));
+                    self.lower[j] = -big_m;
+                }
+                self.x_n[j] = self.lower[j];
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Restore the bounds replaced by [`make_dual_feasible`](Self::make_dual_feasible).
+    ///
+    /// If a column still rests at an artificial bound with a non-zero reduced
+    /// cost, relaxing that bound would keep improving the objective and the LP
+    /// is [`Unbounded`](SolveStatus::Unbounded). Columns resting there with a
+    /// zero reduced cost are moved back to their default value, which may make
+    /// the basis primal infeasible again; returns whether the dual simplex
+    /// needs to run again.
+    fn release_bounds(&mut self, tol: f64) -> Result<bool, SolveError> {
+        if self.art_bounds.is_empty() {
+            return Ok(false);
+        }
+
+        let pi = simplex::compute_duals(&self.factor_basis()?, &self.c, &self.basis);
+        let mut moved = false;
+        for (j, lower, upper) in std::mem::take(&mut self.art_bounds) {
+            let at_art_bound = self.non_basis.contains(&j)
+                && (self.x_n[j] == self.lower[j] && lower != self.lower[j]
+                    || self.x_n[j] == self.upper[j] && upper != self.upper[j]);
+            self.lower[j] = lower;
+            self.upper[j] = upper;
+            if !at_art_bound {
+                continue;
+            }
+            if simplex::reduced_cost(&self.a, &self.c, &pi, j).abs() > tol {
+                self.status = SolveStatus::Unbounded;
+                return Ok(false);
+            }
+            self.x_n[j] = simplex::initial_value(lower, upper);
+            moved = true;
+        }
+
+        Ok(moved)
+    }
+
+    /// Run the main dual simplex iteration loop.
+    fn run_dual(&mut self, max_iter: usize, tol: f64) -> Result<(), SolveError> {
+        let mut factor = self.factor_basis()?;
+
+        let current_iter = self.iteration;
+        for iter in current_iter..max_iter {
+            self.iteration = iter;
+
+            self.x_b = self.compute_basic_solution(&factor);
+            self.update_objective();
+
+            let Some((leave_row, bound)) = self.choose_leaving(tol) else {
+                self.status = SolveStatus::Optimal;
+                return Ok(());
+            };
+
+            let pi = simplex::compute_duals(&factor, &self.c, &self.basis);
+            let rho = simplex::compute_row(&factor, leave_row);
+            let increase = bound > self.x_b[leave_row];
+            let Some((nb_pos, entering)) = self.choose_entering(&pi, &rho, increase, tol)
+            else {
+                self.status = SolveStatus::Infeasible;
+                return Ok(());
+            };
+
+            let d = simplex::compute_direction(&self.a, &factor, entering);
+            self.pivot(&mut factor, &d, nb_pos, leave_row, entering, bound)?;
+
+            if self.logging && (iter + 1) % self.log_interval == 0 {
+                println!(
+                    "Iteration {:>4}: Objective = {:>12.6}",
+                    iter + 1,
+                    if self.minimise { -self.objective } else { self.objective }
+                );
+            }
+        }
+
+        Err(SolveError::Other("max iterations reached".into()))
+    }
+
+    /// Factor the current basis matrix `B` from scratch.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SolveError::NumericalFailure`] if `B` is singular.
+    pub fn factor_basis(&self) -> Result<BasisFactor<A>, SolveError> {
+        BasisFactor::new(&self.a, &self.basis).map_err(SolveError::NumericalFailure)
+    }
+
+    /// Factor the current basis matrix, first repairing it with
+    /// [`simplex::repair_basis`] if it is singular.
+    ///
+    /// Columns swapped out of the basis become non-basic at their current
+    /// value in `x_n`; artificial columns are dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SolveError::NumericalFailure`] if the basis cannot be repaired.
+    pub fn factor_or_repair(&mut self) -> Result<BasisFactor<A>, SolveError> {
+        if let Ok(factor) = self.factor_basis() {
+            return Ok(factor);
+        }
+
+        let swaps = simplex::repair_basis(&self.a, &self.layout, &mut self.basis)
+            .map_err(SolveError::NumericalFailure)?;
+        for (slack, leaving) in swaps {
+            let pos = self.non_basis.iter().position(|&j| j == slack).unwrap();
+            if self.artificial_row(leaving).is_some() {
+                self.non_basis.remove(pos);
+            } else {
+                self.non_basis[pos] = leaving;
+            }
+        }
+        self.factor_basis()
+    }
+
+    /// Compute the values of the basic variables by solving `B x_B = b - N x_N`.
+    pub fn compute_basic_solution(&self, factor: &BasisFactor<A>) -> Vec<f64> {
+        let rhs = simplex::nonbasic_rhs(&self.a, &self.b, &self.non_basis, &self.x_n);
+        simplex::basic_solution(factor, &rhs)
+    }
+
+    /// Choose the leaving row as the basic variable furthest outside its
+    /// bounds, together with the bound it is moved to.
+    ///
+    /// Artificial columns are fixed at zero, so they are infeasible whenever
+    /// they are non-zero.
+    fn choose_leaving(&self, tol: f64) -> Option<(usize, f64)> {
+        self.x_b
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &v)| {
+                let j = self.basis[i];
+                if self.lower[j] - v > tol {
+                    Some((i, self.lower[j], self.lower[j] - v))
+                } else if v - self.upper[j] > tol {
+                    Some((i, self.upper[j], v - self.upper[j]))
+                } else {
+                    None
+                }
+            })
+            .max_by(|a, b| a.2.partial_cmp(&b.2).unwrap())
+            .map(|(i, bound, _)| (i, bound))
+    }
+
+    /// Choose the entering column with the dual ratio test on row `ρᵀ A`.
+    ///
+    /// The leaving variable changes by `-α_j` per unit increase of non-basic
+    /// column `j`, so raising it needs a column with `α_j < 0` that can increase
+    /// or one with `α_j > 0` that can decrease (and conversely for lowering it).
+    /// Ties are broken in favour of the larger pivot element.
+    fn choose_entering(
+        &self,
+        pi: &[f64],
+        rho: &[f64],
+        increase: bool,
+        tol: f64,
+    ) -> Option<(usize, usize)> {
+        let sign = if increase { -1.0 } else { 1.0 };
+        self.non_basis
+            .iter()
+            .enumerate()
+            .filter_map(|(pos, &j)| {
+                let alpha = simplex::column_dot(&self.a, j, rho);
+                let movable = if sign * alpha > tol {
+                    self.x_n[j] < self.upper[j]
+                } else if sign * alpha < -tol {
+                    self.x_n[j] > self.lower[j]
+                } else {
+                    false
+                };
+                movable.then(|| {
+                    let rc = simplex::reduced_cost(&self.a, &self.c, pi, j);
+                    (pos, j, rc.abs() / alpha.abs(), alpha.abs())
+                })
+            })
+            .min_by(|a, b| {
+                a.2.partial_cmp(&b.2).unwrap().then(b.3.partial_cmp(&a.3).unwrap())
+            })
+            .map(|(pos, j, _, _)| (pos, j))
+    }
+
+    /// Perform pivot operations on the basis and non-basis sets.
+    ///
+    /// The leaving column becomes non-basic at `bound`. Artificial columns are
+    /// dropped once they leave the basis.
+    fn pivot(
+        &mut self,
+        factor: &mut BasisFactor<A>,
+        d: &[f64],
+        enter_pos: usize,
+        leave_row: usize,
+        entering: usize,
+        bound: f64,
+    ) -> Result<(), SolveError> {
+        let leaving = self.basis[leave_row];
+        self.basis[leave_row] = entering;
+        self.x_n[leaving] = bound;
+        if self.artificial_row(leaving).is_some() {
+            self.non_basis.remove(enter_pos);
+        } else {
+            self.non_basis[enter_pos] = leaving;
+        }
+
+        factor
+            .replace_column(&self.a, leave_row, entering, d)
+            .map_err(SolveError::NumericalFailure)
+    }
+
+    /// Returns the row owning column `j` if it is an artificial column.
+    fn artificial_row(&self, j: usize) -> Option<usize> {
+        j.checked_sub(self.layout.n_cols)
+            .and_then(|k| self.art_rows.get(k).copied())
+    }
+
+    /// Update the current objective value.
+    fn update_objective(&mut self) {
+        self.objective =
+            simplex::basis_objective(&self.c, &self.basis, &self.x_b, &self.x_n);
     }
 }
+
+/// Magnitude of the artificial bounds, relative to the largest finite `|b_i|`
+/// or variable bound.
+const BOUND_SCALE: f64 = 1e6;
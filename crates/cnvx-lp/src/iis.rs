@@ -0,0 +1,272 @@
+//! Irreducible infeasible subsystems of infeasible LPs.
+//!
+//! An irreducible infeasible subsystem (IIS) is a set of constraints and
+//! variable bounds that cannot all hold together, but can as soon as any one of
+//! them is dropped. It points at the part of an infeasible model that has to
+//! change.
+//!
+//! [`compute_iis`] finds one with a deletion filter on top of the
+//! [`PrimalSimplexSolver`]: each member of a candidate set is dropped in turn,
+//! and stays dropped if the rest is still infeasible. The candidates start as
+//! the constraints and bounds that the Farkas [`Certificate`] of the model
+//! combines, which is usually far fewer than the whole model.
+
+use std::fmt::{Display, Formatter};
+
+use cnvx_core::{SolveError, SolveStatus};
+
+use crate::{
+    Certificate, LinExpr, LpModel, Objective, PrimalSimplexSolver, Solver, Var, VarId,
+};
+
+/// Multipliers of a Farkas certificate up to this magnitude are treated as zero.
+const SUPPORT_TOL: f64 = 1e-9;
+
+/// Which bound of a variable belongs to an [`Iis`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BoundKind {
+    /// The lower bound [`Var::lb`].
+    Lower,
+    /// The upper bound [`Var::ub`].
+    Upper,
+}
+
+/// A constraint of an [`Iis`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct IisConstraint {
+    /// Index of the constraint in [`LpModel::constraints`].
+    pub index: usize,
+    /// Name of the constraint, or `c<index>` if it has none.
+    pub name: String,
+}
+
+/// A variable bound of an [`Iis`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct IisBound {
+    /// The bounded variable.
+    pub var: VarId,
+    /// Name of the variable, or `x<id>` if it has none.
+    pub name: String,
+    /// Which of its bounds is part of the subsystem.
+    pub kind: BoundKind,
+    /// Value of the bound.
+    pub value: f64,
+}
+
+/// An irreducible infeasible subsystem of an [`LpModel`], see [`compute_iis`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Iis {
+    /// Constraints of the subsystem, in model order.
+    pub constraints: Vec<IisConstraint>,
+    /// Variable bounds of the subsystem, in variable order.
+    pub bounds: Vec<IisBound>,
+}
+
+impl Display for Iis {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for con in &self.constraints {
+            writeln!(f, "constraint {}", con.name)?;
+        }
+        for bound in &self.bounds {
+            let cmp = match bound.kind {
+                BoundKind::Lower => ">=",
+                BoundKind::Upper => "<=",
+            };
+            writeln!(f, "bound      {} {cmp} {}", bound.name, bound.value)?;
+        }
+        Ok(())
+    }
+}
+
+/// A constraint or variable bound of the model that may belong to the IIS.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Member {
+    Lower(usize),
+    Upper(usize),
+    Constraint(usize),
+}
+
+/// Find an irreducible infeasible subsystem of `model`.
+///
+/// The objective plays no part, and integrality is ignored: the subsystem is
+/// one of the LP relaxation. Variable bounds are tried before constraints, so
+/// the subsystem favours keeping constraints over bounds.
+///
+/// # Examples
+///
+/// ```rust
+/// # use cnvx_lp::*;
+/// let mut model = LpModel::new();
+/// let x = model.add_var().name("x").finish();
+/// let y = model.add_var().name("y").finish();
+/// model += (x + y).leq(10.0).named("budget");
+/// model += x.geq(8.0).named("min_x");
+/// model += y.geq(5.0).named("min_y");
+/// model += (x + 2.0 * y).leq(40.0).named("capacity");
+///
+/// let iis = compute_iis(&model).unwrap();
+/// let names = iis.constraints.iter().map(|c| c.name.as_str()).collect::<Vec<_>>();
+/// assert_eq!(names, ["budget", "min_x", "min_y"]);
+/// assert!(iis.bounds.is_empty());
+/// ```
+///
+/// # Errors
+///
+/// Returns [`SolveError::InvalidModel`] if `model` is feasible, and any error
+/// of the [`PrimalSimplexSolver`] solving its subsystems.
+pub fn compute_iis(model: &LpModel) -> Result<Iis, SolveError> {
+    // A variable whose bounds cross is infeasible on its own.
+    if let Some(var) = model
+        .vars()
+        .iter()
+        .find(|v| v.lb.zip(v.ub).is_some_and(|(l, u)| l > u))
+    {
+        return Ok(iis_of(model, &[Member::Lower(var.id.0), Member::Upper(var.id.0)]));
+    }
+
+    let mut set = model
+        .vars()
+        .iter()
+        .flat_map(|v| {
+            let j = v.id.0;
+            [v.lb.map(|_| Member::Lower(j)), v.ub.map(|_| Member::Upper(j))]
+        })
+        .flatten()
+        .chain((0..model.constraints().len()).map(Member::Constraint))
+        .collect::<Vec<_>>();
+
+    let Some(mut support) = infeasible_support(model, &set)? else {
+        return Err(SolveError::InvalidModel("the model is feasible".into()));
+    };
+
+    // Shrink to the members the Farkas certificates combine, while they stay
+    // infeasible on their own.
+    while support.len() < set.len() {
+        match infeasible_support(model, &support)? {
+            Some(next) => set = std::mem::replace(&mut support, next),
+            None => break,
+        }
+    }
+
+    // Deletion filter.
+    let mut i = 0;
+    while i < set.len() {
+        let mut trial = set.clone();
+        trial.remove(i);
+        if infeasible_support(model, &trial)?.is_some() {
+            set = trial;
+        } else {
+            i += 1;
+        }
+    }
+
+    Ok(iis_of(model, &set))
+}
+
+/// Solve the subsystem of `model` formed by `members`.
+///
+/// Returns `None` if it is feasible. Otherwise returns the members its Farkas
+/// certificate combines, or all of `members` if the solver gave no valid
+/// certificate.
+fn infeasible_support(
+    model: &LpModel,
+    members: &[Member],
+) -> Result<Option<Vec<Member>>, SolveError> {
+    let rows = members
+        .iter()
+        .filter_map(|m| match *m {
+            Member::Constraint(i) => Some(i),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let sub = subsystem(model, members, &rows);
+
+    let solution = PrimalSimplexSolver::new().solve(&sub)?;
+    if solution.status != SolveStatus::Infeasible {
+        return Ok(None);
+    }
+
+    let certificate =
+        solution.certificate.filter(|c| c.verify(&sub, SUPPORT_TOL).is_ok());
+    let Some(Certificate::Infeasible { farkas }) = certificate else {
+        return Ok(Some(members.to_vec()));
+    };
+
+    // The bounds the certificate relies on are those of the variables with a
+    // nonzero coefficient in the combined constraint.
+    let mut g = vec![0.0; model.vars().len()];
+    for (con, &y) in sub.constraints.iter().zip(&farkas) {
+        for term in &con.expr.terms {
+            g[term.var.0] += y * term.coeff;
+        }
+    }
+
+    let support = members
+        .iter()
+        .copied()
+        .filter(|m| match *m {
+            Member::Lower(j) => g[j] > SUPPORT_TOL,
+            Member::Upper(j) => g[j] < -SUPPORT_TOL,
+            Member::Constraint(i) => {
+                let k = rows.iter().position(|&r| r == i).unwrap();
+                farkas[k].abs() > SUPPORT_TOL
+            }
+        })
+        .collect();
+    Ok(Some(support))
+}
+
+/// The feasibility problem of `model` restricted to `members`, whose
+/// constraints are `rows`.
+fn subsystem(model: &LpModel, members: &[Member], rows: &[usize]) -> LpModel {
+    let mut sub = LpModel::new();
+    sub.vars = model
+        .vars()
+        .iter()
+        .map(|v| {
+            let j = v.id.0;
+            Var {
+                lb: v.lb.filter(|_| members.contains(&Member::Lower(j))),
+                ub: v.ub.filter(|_| members.contains(&Member::Upper(j))),
+                is_integer: false,
+                ..v.clone()
+            }
+        })
+        .collect();
+    sub.constraints = rows.iter().map(|&i| model.constraints()[i].clone()).collect();
+    sub.add_objective(Objective::minimize(LinExpr::constant(0.0)).name("feasibility"));
+    sub
+}
+
+/// The [`Iis`] of `model` formed by `members`.
+fn iis_of(model: &LpModel, members: &[Member]) -> Iis {
+    let mut iis = Iis::default();
+    for &member in members {
+        let (j, kind) = match member {
+            Member::Constraint(i) => {
+                let name = model.constraints()[i].name.clone();
+                iis.constraints.push(IisConstraint {
+                    index: i,
+                    name: name.unwrap_or_else(|| format!("c{i}")),
+                });
+                continue;
+            }
+            Member::Lower(j) => (j, BoundKind::Lower),
+            Member::Upper(j) => (j, BoundKind::Upper),
+        };
+        let var = &model.vars()[j];
+        let value = match kind {
+            BoundKind::Lower => var.lb,
+            BoundKind::Upper => var.ub,
+        };
+        iis.bounds.push(IisBound {
+            var: var.id,
+            name: var.name.clone().unwrap_or_else(|| format!("x{j}")),
+            kind,
+            value: value.unwrap_or_default(),
+        });
+    }
+    iis.constraints.sort_by_key(|c| c.index);
+    iis.bounds.sort_by_key(|b| (b.var.0, b.kind == BoundKind::Upper));
+    iis
+}
@@ -0,0 +1,1072 @@
+use cnvx_core::{Sense, SolveError, SolveStatus};
+use cnvx_math::{
+    DenseMatrix, Factorisation, Matrix, Regularisation, SparseCholesky, SparseMatrix,
+    SymbolicCholesky,
+};
+
+use crate::simplex::{self, Layout, Reoptimized, WarmStart};
+use crate::{Capabilities, LpModel, LpSolution, PrimalSimplexState, Sensitivity, Solver};
+
+/// Pivots of the normal equations below this fraction of their diagonal entry
+/// are dropped (see [`Regularisation`]).
+const PIVOT_TOL: f64 = 1e-13;
+
+/// Fraction of the distance to the boundary of the positive orthant taken by
+/// each step.
+const STEP_FRACTION: f64 = 0.99;
+
+/// The solve stops with a numerical failure once the step length falls below
+/// this value.
+const MIN_STEP: f64 = 1e-12;
+
+/// Rows (and, during crossover, columns) whose eliminated entries fall below
+/// this fraction of their largest entry are treated as linearly dependent.
+const DEPENDENCE_TOL: f64 = 1e-9;
+
+/// Largest number of variables, and of constraints, accepted with crossover
+/// enabled. Its independence test keeps a dense column of length `m` for each
+/// structural and slack column, which at both limits holds about 10⁸ entries.
+const CROSSOVER_MAX_SIZE: usize = 7_000;
+
+/// Primal-dual interior-point solver for linear programs.
+///
+/// Follows the central path of the homogeneous self-dual embedding of the LP
+/// with Mehrotra's predictor-corrector steps. The embedding needs no feasible
+/// starting point, and it detects infeasible and unbounded problems from the
+/// iterates themselves: the homogenising variable `τ` tends to zero and the
+/// iterates approach a Farkas certificate.
+///
+/// Each iteration factors the normal equations `A Θ Aᵀ` once with a
+/// [`SparseCholesky`] factorisation from `cnvx-math`, whose symbolic analysis
+/// is shared by all iterations, and reuses the factor for the
+/// predictor and corrector directions. Nearly dependent rows are handled by
+/// dropping the collapsing pivots.
+///
+/// The interior-point solution lies in the relative interior of the optimal
+/// face, so it is generally not a vertex. With [`crossover`](Self::crossover)
+/// enabled, a basis is built from the interior solution and finished with the
+/// simplex method, which also makes basis statuses and
+/// [`sensitivity`](Solver::sensitivity) analysis available.
+///
+/// # Examples
+///
+/// ```rust
+/// # use cnvx_lp::*;
+/// let mut model = LpModel::new();
+/// let x = model.add_var().finish();
+/// let y = model.add_var().finish();
+/// model += (x + y).leq(4.0);
+/// model += (x + 3.0 * y).leq(6.0);
+/// model.add_objective(Objective::maximize(3.0 * x + 2.0 * y).name("profit"));
+///
+/// let mut solver = InteriorPointSolver::new();
+/// let solution = solver.solve(&model).unwrap();
+/// assert!((solution.objective_value.unwrap() - 12.0).abs() < 1e-6);
+///
+/// // Crossover to an optimal vertex.
+/// solver.crossover = true;
+/// let solution = solver.solve(&model).unwrap();
+/// assert_eq!(solution.value(x), 4.0);
+/// assert_eq!(solution.basis_status[0], BasisStatus::Basic);
+/// ```
+pub struct InteriorPointSolver {
+    /// Final basis found by crossover, retained for sensitivity analysis.
+    basis: Option<PrimalSimplexState<DenseMatrix>>,
+    /// Relative tolerance on the primal and dual residuals and the duality gap.
+    pub tolerance: f64,
+    /// The maximum number of interior-point iterations before terminating.
+    pub max_iter: usize,
+    /// Whether to convert an optimal interior solution to a basic one.
+    ///
+    /// Crossover works on dense columns, so models with more than 7000
+    /// variables or constraints are not supported with it enabled.
+    pub crossover: bool,
+    /// Whether to log iteration details during solving.
+    pub logging: bool,
+
+    /// Cached objective value from the most recent solve.
+    last_objective: Option<f64>,
+    /// Cached solution vector from the most recent solve.
+    last_solution: Vec<f64>,
+}
+
+impl InteriorPointSolver {
+    /// Creates a new interior-point solver without crossover.
+    pub fn new() -> Self {
+        Self {
+            basis: None,
+            tolerance: 1e-8,
+            max_iter: 100,
+            crossover: false,
+            logging: false,
+            last_objective: None,
+            last_solution: Vec::new(),
+        }
+    }
+
+    /// Record the outcome of a solve and return its [`LpSolution`].
+    fn finish(&mut self, iterations: usize, solution: LpSolution) -> LpSolution {
+        if self.logging {
+            println!(
+                "Interior point finished with status {:?} in {} iterations. Objective value: {}",
+                solution.status,
+                iterations,
+                solution.objective_value.unwrap_or_default()
+            );
+        }
+
+        self.last_objective = solution.objective_value;
+        self.last_solution = solution.values.clone();
+        solution
+    }
+}
+
+impl Default for InteriorPointSolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Solver for InteriorPointSolver {
+    fn name(&self) -> &str {
+        "interior-point"
+    }
+
+    fn solve(&mut self, model: &LpModel) -> Result<LpSolution, SolveError> {
+        crate::validate::check_lp(model)?;
+        self.basis = None;
+
+        let mut state = InteriorPointState::new(model);
+        state.logging = self.logging;
+        state.solve(self.max_iter, self.tolerance)?;
+
+        if state.status != SolveStatus::Optimal {
+            let solution = LpSolution::new(Vec::new(), None, state.status.clone());
+            return Ok(self.finish(state.iteration, solution));
+        }
+
+        if self.crossover {
+            let basis = state.crossover(model, self.tolerance)?;
+            let (values, obj) = basis.solution();
+            let objective_value = (basis.status == SolveStatus::Optimal).then_some(obj);
+            let mut solution =
+                LpSolution::new(values, objective_value, basis.status.clone())
+                    .with_activities(model);
+            basis.attach_duals(&mut solution)?;
+            self.basis = Some(basis);
+            return Ok(self.finish(state.iteration, solution));
+        }
+
+        let (values, obj) = state.solution();
+        let mut solution = LpSolution::new(values, Some(obj), SolveStatus::Optimal)
+            .with_activities(model);
+        state.attach_duals(model, &mut solution);
+        Ok(self.finish(state.iteration, solution))
+    }
+
+    /// Objective and right-hand side ranging of the basis found by crossover.
+    ///
+    /// Returns [`SolveError::Unsupported`] unless the most recent solve ran with
+    /// [`crossover`](InteriorPointSolver::crossover) enabled.
+    fn sensitivity(&self, model: &LpModel) -> Result<Sensitivity, SolveError> {
+        match &self.basis {
+            Some(basis) => basis.sensitivity(model),
+            None => Err(SolveError::Unsupported(
+                "interior-point provides sensitivity analysis only after crossover"
+                    .into(),
+            )),
+        }
+    }
+
+    fn objective_value(&self) -> Option<f64> {
+        self.last_objective
+    }
+
+    fn solution_vector(&self) -> Vec<f64> {
+        self.last_solution.clone()
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        let limit = self.crossover.then_some(CROSSOVER_MAX_SIZE);
+        Capabilities {
+            max_vars: limit,
+            max_constraints: limit,
+            ..Capabilities::default()
+        }
+    }
+}
+
+/// How a standard-form column (structural or slack) is represented by the
+/// non-negative columns of the interior-point problem.
+#[derive(Copy, Clone, Debug)]
+enum ColumnMap {
+    /// `x = offset + x_k`, for columns with a finite lower bound.
+    Shifted { k: usize, offset: f64 },
+    /// `x = offset - x_k`, for columns with only a finite upper bound.
+    Reflected { k: usize, offset: f64 },
+    /// `x = x_pos - x_neg`, for free columns.
+    Split { pos: usize, neg: usize },
+    /// A column fixed at the given value.
+    Fixed(f64),
+}
+
+/// A search direction for all iterates.
+struct Direction {
+    dx: Vec<f64>,
+    dw: Vec<f64>,
+    dy: Vec<f64>,
+    dz: Vec<f64>,
+    dv: Vec<f64>,
+    dtau: f64,
+    dkappa: f64,
+}
+
+/// Residuals of the homogeneous self-dual embedding at the current iterate.
+struct Residuals {
+    /// `A x - b τ`.
+    primal: Vec<f64>,
+    /// `x + w - u τ` on the columns with an upper bound.
+    upper: Vec<f64>,
+    /// `Aᵀ y + z - v - c τ`.
+    dual: Vec<f64>,
+    /// `bᵀ y - uᵀ v - cᵀ x - κ`.
+    gap: f64,
+}
+
+/// The normal equations `A Θ Aᵀ`, assembled in place into their fixed pattern
+/// and factored with one symbolic analysis for all iterations.
+struct NormalEquations {
+    /// Lower triangle of `A Θ Aᵀ`.
+    matrix: SparseMatrix,
+    /// The rows of `A`, as `(column, coefficient)` pairs.
+    rows: Vec<Vec<(usize, f64)>>,
+    /// Storage position in `matrix` of each product `a_ij a_kj` with `i >= k`,
+    /// in the order in which [`factor`](Self::factor) visits them.
+    slots: Vec<usize>,
+    /// The symbolic analysis of the pattern.
+    symbolic: SymbolicCholesky,
+    /// The factorisation, once the matrix has been factored.
+    chol: Option<SparseCholesky>,
+}
+
+impl NormalEquations {
+    /// Analyse the pattern of `A Aᵀ` for the sparse columns `cols` of an `A`
+    /// with `m` rows.
+    fn new(cols: &[Vec<(usize, f64)>], m: usize) -> Result<Self, SolveError> {
+        let mut rows = vec![Vec::new(); m];
+        for (j, col) in cols.iter().enumerate() {
+            for &(i, a) in col {
+                rows[i].push((j, a));
+            }
+        }
+
+        // Column k of the lower triangle holds the rows i >= k that share a
+        // column of A with row k. The pattern gets unit values, so that none
+        // of its entries cancels out.
+        let mut triplets = Vec::new();
+        let mut slots = Vec::new();
+        let mut pos = vec![usize::MAX; m];
+        for (k, row) in rows.iter().enumerate() {
+            let start = triplets.len();
+            let mut pattern = Vec::new();
+            for &(j, _) in row {
+                for &(i, _) in cols[j].iter().filter(|&&(i, _)| i >= k) {
+                    if pos[i] == usize::MAX {
+                        pos[i] = 0;
+                        pattern.push(i);
+                    }
+                }
+            }
+            pattern.sort_unstable();
+            for (p, &i) in pattern.iter().enumerate() {
+                pos[i] = start + p;
+                triplets.push((i, k, 1.0));
+            }
+            for &(j, _) in row {
+                slots.extend(
+                    cols[j].iter().filter(|&&(i, _)| i >= k).map(|&(i, _)| pos[i]),
+                );
+            }
+            pattern.iter().for_each(|&i| pos[i] = usize::MAX);
+        }
+
+        let matrix = SparseMatrix::from_triplets(m, m, &triplets);
+        let symbolic =
+            SymbolicCholesky::analyse(&matrix).map_err(SolveError::NumericalFailure)?;
+        Ok(Self { matrix, rows, slots, symbolic, chol: None })
+    }
+
+    /// Assemble and factor `A Θ Aᵀ`. Pivots that collapse are dropped, as
+    /// described by [`PIVOT_TOL`].
+    fn factor(
+        &mut self,
+        cols: &[Vec<(usize, f64)>],
+        theta: &[f64],
+    ) -> Result<&SparseCholesky, SolveError> {
+        let values = self.matrix.values_mut();
+        values.fill(0.0);
+        let mut slots = self.slots.iter();
+        for (k, row) in self.rows.iter().enumerate() {
+            for &(j, akj) in row {
+                let t = theta[j] * akj;
+                for &(_, aij) in cols[j].iter().filter(|&&(i, _)| i >= k) {
+                    values[*slots.next().unwrap()] += t * aij;
+                }
+            }
+        }
+
+        let chol = match self.chol.take() {
+            Some(mut chol) => {
+                chol.refactor(&self.matrix).map_err(SolveError::NumericalFailure)?;
+                chol
+            }
+            None => {
+                let reg = Regularisation {
+                    pivot_tol: PIVOT_TOL,
+                    delta: f64::INFINITY,
+                    ..Regularisation::positive_definite(self.matrix.rows())
+                };
+                SparseCholesky::factor_regularised(&self.symbolic, &self.matrix, reg)
+                    .map_err(SolveError::NumericalFailure)?
+            }
+        };
+        Ok(self.chol.insert(chol))
+    }
+}
+
+/// Internal state of the interior-point method.
+///
+/// The model is converted to `min cᵀx` subject to `Ax = b`, `0 <= x`, and
+/// `x_j <= u_j` for the columns with a finite upper bound, whose dual is
+/// `max bᵀy - uᵀv` subject to `Aᵀy + z - v = c`, `z, v >= 0`. Lower bounds are
+/// shifted to zero, columns with only an upper bound are reflected, and free
+/// columns are split into two non-negative parts.
+///
+/// The iterates are those of the homogeneous self-dual embedding, which scales
+/// the primal and dual solutions by `τ`; an optimal solution is recovered as
+/// `x / τ`, `y / τ`.
+#[derive(Clone)]
+pub struct InteriorPointState {
+    /// Current iteration count.
+    pub iteration: usize,
+    /// Solution status after solving (Optimal, Infeasible, Unbounded, etc.).
+    pub status: SolveStatus,
+
+    /// Primal columns `x`.
+    pub x: Vec<f64>,
+    /// Upper-bound slacks `w` (zero for columns without an upper bound).
+    pub w: Vec<f64>,
+    /// Row duals `y` of the linearly independent rows.
+    pub y: Vec<f64>,
+    /// Dual slacks `z` of the lower bounds.
+    pub z: Vec<f64>,
+    /// Dual slacks `v` of the upper bounds (zero for columns without one).
+    pub v: Vec<f64>,
+    /// Homogenising variable `τ`.
+    pub tau: f64,
+    /// Homogenising dual slack `κ`.
+    pub kappa: f64,
+
+    /// Sparse columns of the constraint matrix, as `(row, coefficient)` pairs.
+    cols: Vec<Vec<(usize, f64)>>,
+    /// Right-hand side vector `b`.
+    b: Vec<f64>,
+    /// Model row of each interior-point row. Linearly dependent rows are
+    /// removed before solving.
+    rows: Vec<usize>,
+    /// Whether a removed row contradicts the rows it depends on.
+    inconsistent: bool,
+    /// Objective coefficients vector `c` (minimisation form).
+    c: Vec<f64>,
+    /// Upper bound of each column (`inf` if absent).
+    upper: Vec<f64>,
+
+    /// Representation of each standard-form column.
+    map: Vec<ColumnMap>,
+    /// Bounds of each standard-form column.
+    std_bounds: Vec<(f64, f64)>,
+    /// Objective coefficient of each standard-form column (minimisation form).
+    std_cost: Vec<f64>,
+    /// Origin of the standard-form rows and columns.
+    layout: Layout,
+    /// Whether the LP is a minimization problem.
+    minimise: bool,
+    /// Whether to log iteration details.
+    logging: bool,
+}
+
+impl InteriorPointState {
+    /// Initialize a new interior-point state from `model`, starting from the
+    /// usual point `x = z = w = v = 1`, `y = 0`, `τ = κ = 1`.
+    pub fn new(model: &LpModel) -> Self {
+        let layout = Layout::new(model);
+        let n_std = layout.n_cols;
+        let m = layout.rows.len();
+        let minimise =
+            model.objective().map(|o| o.sense == Sense::Minimize).unwrap_or(false);
+
+        // Standard-form columns, bounds and costs.
+        let mut std_cols: Vec<Vec<(usize, f64)>> = vec![Vec::new(); n_std];
+        let mut std_bounds = vec![(0.0, f64::INFINITY); n_std];
+        let mut std_cost = vec![0.0; n_std];
+        let mut b = vec![0.0; m];
+        for var in model.vars() {
+            std_bounds[var.id.0] =
+                (var.lb.unwrap_or(f64::NEG_INFINITY), var.ub.unwrap_or(f64::INFINITY));
+        }
+        if let Some(obj) = model.objective() {
+            let sign = if minimise { 1.0 } else { -1.0 };
+            for term in &obj.expr.terms {
+                std_cost[term.var.0] += sign * term.coeff;
+            }
+        }
+        for (i, cons) in model.constraints().iter().enumerate() {
+            b[i] = cons.rhs - cons.expr.constant;
+            for term in &cons.expr.terms {
+                let col = &mut std_cols[term.var.0];
+                match col.last_mut() {
+                    Some((row, coeff)) if *row == i => *coeff += term.coeff,
+                    _ => col.push((i, term.coeff)),
+                }
+            }
+            if let Some((j, coeff)) = layout.slacks[i] {
+                std_cols[j].push((i, coeff));
+            }
+        }
+
+        // Interior-point columns.
+        let mut cols = Vec::new();
+        let mut c = Vec::new();
+        let mut upper = Vec::new();
+        let mut map = Vec::with_capacity(n_std);
+        let mut push = |col: Vec<(usize, f64)>, cost: f64, ub: f64| {
+            cols.push(col);
+            c.push(cost);
+            upper.push(ub);
+            cols.len() - 1
+        };
+        let negated = |col: &[(usize, f64)]| col.iter().map(|&(i, a)| (i, -a)).collect();
+
+        for j in 0..n_std {
+            let (lb, ub) = std_bounds[j];
+            let col = &std_cols[j];
+            let offset = if lb.is_finite() {
+                lb
+            } else if ub.is_finite() {
+                ub
+            } else {
+                0.0
+            };
+            for &(i, a) in col {
+                b[i] -= a * offset;
+            }
+
+            map.push(if lb == ub {
+                ColumnMap::Fixed(lb)
+            } else if lb.is_finite() {
+                let k = push(col.clone(), std_cost[j], ub - lb);
+                ColumnMap::Shifted { k, offset }
+            } else if ub.is_finite() {
+                let k = push(negated(col), -std_cost[j], f64::INFINITY);
+                ColumnMap::Reflected { k, offset }
+            } else {
+                let pos = push(col.clone(), std_cost[j], f64::INFINITY);
+                let neg = push(negated(col), -std_cost[j], f64::INFINITY);
+                ColumnMap::Split { pos, neg }
+            });
+        }
+
+        // Remove dependent rows, which make the normal equations singular.
+        let (rows, inconsistent) = independent_rows(&cols, &b);
+        let mut position = vec![None; m];
+        rows.iter().enumerate().for_each(|(k, &i)| position[i] = Some(k));
+        for col in &mut cols {
+            *col = col.iter().filter_map(|&(i, a)| Some((position[i]?, a))).collect();
+        }
+        let b: Vec<f64> = rows.iter().map(|&i| b[i]).collect();
+
+        let n = cols.len();
+        let bounded = |u: f64| if u.is_finite() { 1.0 } else { 0.0 };
+        let w = upper.iter().map(|&u| bounded(u)).collect();
+        let v = upper.iter().map(|&u| bounded(u)).collect();
+
+        Self {
+            iteration: 0,
+            status: SolveStatus::NotSolved,
+            x: vec![1.0; n],
+            w,
+            y: vec![0.0; rows.len()],
+            z: vec![1.0; n],
+            v,
+            tau: 1.0,
+            kappa: 1.0,
+            cols,
+            b,
+            rows,
+            inconsistent,
+            c,
+            upper,
+            map,
+            std_bounds,
+            std_cost,
+            layout,
+            minimise,
+            logging: false,
+        }
+    }
+
+    /// Run the predictor-corrector iterations until the embedding yields an
+    /// optimal solution or a certificate of infeasibility.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SolveError::NumericalFailure`] if the steps stall, and
+    /// [`SolveError::Other`] if `max_iter` iterations are reached.
+    pub fn solve(&mut self, max_iter: usize, tol: f64) -> Result<(), SolveError> {
+        if self.inconsistent {
+            self.status = SolveStatus::Infeasible;
+            return Ok(());
+        }
+
+        let n_pairs = self.x.len() + self.bounded().count() + 1;
+        let mut normal = NormalEquations::new(&self.cols, self.b.len())?;
+
+        for iter in 0..max_iter {
+            self.iteration = iter;
+
+            let res = self.residuals();
+            let mu = self.complementarity() / n_pairs as f64;
+            if let Some(status) = self.check_termination(&res, tol) {
+                self.status = match status {
+                    // Dual infeasibility means the problem is unbounded if it
+                    // has a feasible point at all.
+                    SolveStatus::Unbounded
+                        if !self.is_primal_feasible(max_iter, tol)? =>
+                    {
+                        SolveStatus::Infeasible
+                    }
+                    status => status,
+                };
+                return Ok(());
+            }
+
+            // Normal equations `A Θ Aᵀ` with `Θ = (Z/X + V/W)⁻¹`.
+            let theta: Vec<f64> = (0..self.x.len())
+                .map(|j| {
+                    let mut d = self.z[j] / self.x[j];
+                    if self.upper[j].is_finite() {
+                        d += self.v[j] / self.w[j];
+                    }
+                    1.0 / d
+                })
+                .collect();
+            let chol = normal.factor(&self.cols, &theta)?;
+
+            // Predictor (affine-scaling) direction.
+            let rxz: Vec<f64> = self.x.iter().zip(&self.z).map(|(x, z)| -x * z).collect();
+            let rwv: Vec<f64> = self.w.iter().zip(&self.v).map(|(w, v)| -w * v).collect();
+            let rtk = -self.tau * self.kappa;
+            let aff = self.direction(chol, &theta, &res, 1.0, &rxz, &rwv, rtk);
+            let alpha = self.max_step(&aff).min(1.0);
+
+            // Centring parameter from the predicted reduction of complementarity.
+            let mut mu_aff =
+                (self.tau + alpha * aff.dtau) * (self.kappa + alpha * aff.dkappa);
+            for j in 0..self.x.len() {
+                mu_aff +=
+                    (self.x[j] + alpha * aff.dx[j]) * (self.z[j] + alpha * aff.dz[j]);
+                if self.upper[j].is_finite() {
+                    mu_aff +=
+                        (self.w[j] + alpha * aff.dw[j]) * (self.v[j] + alpha * aff.dv[j]);
+                }
+            }
+            let sigma = (mu_aff / n_pairs as f64 / mu).clamp(0.0, 1.0).powi(3);
+
+            // Corrector direction, with Mehrotra's second-order term.
+            let target = sigma * mu;
+            let rxz: Vec<f64> = (0..self.x.len())
+                .map(|j| target - self.x[j] * self.z[j] - aff.dx[j] * aff.dz[j])
+                .collect();
+            let rwv: Vec<f64> = (0..self.x.len())
+                .map(|j| {
+                    if self.upper[j].is_finite() {
+                        target - self.w[j] * self.v[j] - aff.dw[j] * aff.dv[j]
+                    } else {
+                        0.0
+                    }
+                })
+                .collect();
+            let rtk = target - self.tau * self.kappa - aff.dtau * aff.dkappa;
+            let dir = self.direction(chol, &theta, &res, 1.0 - sigma, &rxz, &rwv, rtk);
+
+            let alpha = (STEP_FRACTION * self.max_step(&dir)).min(1.0);
+            if alpha < MIN_STEP {
+                return Err(SolveError::NumericalFailure(
+                    "interior-point step length vanished".into(),
+                ));
+            }
+            self.step(&dir, alpha);
+
+            if self.logging {
+                println!(
+                    "Iteration {:>3}: mu = {:>10.3e}, tau = {:>10.3e}, step = {:>6.4}",
+                    iter + 1,
+                    mu,
+                    self.tau,
+                    alpha
+                );
+            }
+        }
+
+        Err(SolveError::Other("max iterations reached".into()))
+    }
+
+    /// Returns the solution vector and the objective value of the current
+    /// iterate, scaled by `1 / τ`.
+    pub fn solution(&self) -> (Vec<f64>, f64) {
+        let values = self.column_values();
+        let cost: f64 = values.iter().zip(&self.std_cost).map(|(x, c)| x * c).sum();
+        (values, if self.minimise { cost } else { -cost })
+    }
+
+    /// Fill in the shadow prices and reduced costs of `solution`, in the sense
+    /// of the original objective.
+    pub fn attach_duals(&self, model: &LpModel, solution: &mut LpSolution) {
+        let sign = if self.minimise { 1.0 } else { -1.0 };
+        solution.duals = vec![0.0; self.layout.rows.len()];
+        for (&i, y) in self.rows.iter().zip(&self.y) {
+            solution.duals[i] = sign * y / self.tau;
+        }
+
+        let mut reduced_costs = vec![0.0; self.layout.n_vars];
+        if let Some(obj) = model.objective() {
+            for term in &obj.expr.terms {
+                reduced_costs[term.var.0] += term.coeff;
+            }
+        }
+        for (cons, dual) in model.constraints().iter().zip(&solution.duals) {
+            for term in &cons.expr.terms {
+                reduced_costs[term.var.0] -= dual * term.coeff;
+            }
+        }
+        solution.reduced_costs = reduced_costs;
+    }
+
+    /// Convert the (optimal) interior solution to an optimal basic solution.
+    ///
+    /// The columns furthest from their bounds are taken into the basis, skipping
+    /// columns that are linearly dependent on those already chosen, and every
+    /// other column rests at its nearest bound. The simplex method then removes
+    /// the remaining primal and dual infeasibilities of that basis. If no full
+    /// basis can be formed (e.g. `A` has dependent rows), the simplex solves the
+    /// model from scratch instead.
+    pub fn crossover(
+        &self,
+        model: &LpModel,
+        tol: f64,
+    ) -> Result<PrimalSimplexState<DenseMatrix>, SolveError> {
+        let m = self.layout.rows.len();
+        let values = self.column_values();
+        let max_iter = 1000.max(10 * (m + values.len()));
+
+        // Dense standard-form columns, for the independence test.
+        let mut dense = vec![vec![0.0; m]; values.len()];
+        for (i, cons) in model.constraints().iter().enumerate() {
+            for term in &cons.expr.terms {
+                dense[term.var.0][i] += term.coeff;
+            }
+            if let Some((j, coeff)) = self.layout.slacks[i] {
+                dense[j][i] = coeff;
+            }
+        }
+
+        let distance = |j: usize| {
+            let (lb, ub) = self.std_bounds[j];
+            (values[j] - lb).min(ub - values[j])
+        };
+        let mut order: Vec<usize> = (0..values.len()).collect();
+        order.sort_by(|&a, &b| distance(b).total_cmp(&distance(a)));
+
+        // Greedy choice of independent columns.
+        let mut elimination = Elimination::default();
+        let mut basis = Vec::with_capacity(m);
+        for &j in &order {
+            if basis.len() == m {
+                break;
+            }
+            if elimination.insert(dense[j].clone(), m) {
+                basis.push(j);
+            }
+        }
+
+        let state = if basis.len() == m {
+            let mut in_basis = vec![false; values.len()];
+            basis.iter().for_each(|&j| in_basis[j] = true);
+            let at_upper = (0..values.len())
+                .filter(|&j| {
+                    let (lb, ub) = self.std_bounds[j];
+                    !in_basis[j] && ub.is_finite() && ub - values[j] < values[j] - lb
+                })
+                .filter_map(|j| self.layout.column_key(j))
+                .collect();
+            let warm = WarmStart {
+                rows: self.layout.rows.clone(),
+                basis: basis.iter().filter_map(|&j| self.layout.column_key(j)).collect(),
+                at_upper,
+            };
+            simplex::reoptimize::<DenseMatrix>(model, &warm, max_iter, tol)?
+        } else {
+            None
+        };
+
+        let basis = match state {
+            Some(Reoptimized::Primal(s)) => Some(s),
+            Some(Reoptimized::Dual(s)) => s
+                .warm_start()
+                .and_then(|w| PrimalSimplexState::with_basis(model, &w))
+                .map(|mut p| {
+                    p.status = s.status.clone();
+                    p
+                }),
+            None => None,
+        };
+        match basis {
+            Some(basis) => Ok(basis),
+            None => {
+                let mut cold = PrimalSimplexState::new(model);
+                cold.solve_lp(max_iter, tol)?;
+                Ok(cold)
+            }
+        }
+    }
+
+    /// Values of the standard-form columns at the current iterate, scaled by
+    /// `1 / τ`.
+    fn column_values(&self) -> Vec<f64> {
+        let x = |k: usize| self.x[k] / self.tau;
+        self.map
+            .iter()
+            .map(|&col| match col {
+                ColumnMap::Shifted { k, offset } => offset + x(k),
+                ColumnMap::Reflected { k, offset } => offset - x(k),
+                ColumnMap::Split { pos, neg } => x(pos) - x(neg),
+                ColumnMap::Fixed(value) => value,
+            })
+            .collect()
+    }
+
+    /// Indices of the columns with a finite upper bound.
+    fn bounded(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.upper.len()).filter(|&j| self.upper[j].is_finite())
+    }
+
+    /// Complementarity `xᵀz + wᵀv + τκ` of the current iterate.
+    fn complementarity(&self) -> f64 {
+        let xz: f64 = self.x.iter().zip(&self.z).map(|(x, z)| x * z).sum();
+        let wv: f64 = self.bounded().map(|j| self.w[j] * self.v[j]).sum();
+        xz + wv + self.tau * self.kappa
+    }
+
+    /// `A x` for a vector `x` over the columns.
+    fn a_times(&self, x: &[f64]) -> Vec<f64> {
+        let mut ax = vec![0.0; self.b.len()];
+        for (col, &xj) in self.cols.iter().zip(x) {
+            for &(i, a) in col {
+                ax[i] += a * xj;
+            }
+        }
+        ax
+    }
+
+    /// `Aᵀ y` for a vector `y` over the rows.
+    fn a_transpose_times(&self, y: &[f64]) -> Vec<f64> {
+        self.cols
+            .iter()
+            .map(|col| col.iter().map(|&(i, a)| a * y[i]).sum())
+            .collect()
+    }
+
+    /// Residuals of the embedding at the current iterate.
+    fn residuals(&self) -> Residuals {
+        let ax = self.a_times(&self.x);
+        let primal = (0..self.b.len()).map(|i| ax[i] - self.b[i] * self.tau).collect();
+        let upper = (0..self.x.len())
+            .map(|j| {
+                if self.upper[j].is_finite() {
+                    self.x[j] + self.w[j] - self.upper[j] * self.tau
+                } else {
+                    0.0
+                }
+            })
+            .collect();
+        let aty = self.a_transpose_times(&self.y);
+        let dual = (0..self.x.len())
+            .map(|j| aty[j] + self.z[j] - self.v[j] - self.c[j] * self.tau)
+            .collect();
+        let gap = dot(&self.b, &self.y)
+            - self.bounded().map(|j| self.upper[j] * self.v[j]).sum::<f64>()
+            - dot(&self.c, &self.x)
+            - self.kappa;
+        Residuals { primal, upper, dual, gap }
+    }
+
+    /// Decide whether the iterate is optimal, or certifies infeasibility
+    /// ([`Infeasible`](SolveStatus::Infeasible)) or dual infeasibility
+    /// ([`Unbounded`](SolveStatus::Unbounded)).
+    fn check_termination(&self, res: &Residuals, tol: f64) -> Option<SolveStatus> {
+        let u_norm = norm(&self.bounded().map(|j| self.upper[j]).collect::<Vec<_>>());
+        let primal_obj = dot(&self.c, &self.x);
+        let dual_obj = dot(&self.b, &self.y)
+            - self.bounded().map(|j| self.upper[j] * self.v[j]).sum::<f64>();
+
+        let optimal = norm(&res.primal) <= tol * self.tau * (1.0 + norm(&self.b))
+            && norm(&res.upper) <= tol * self.tau * (1.0 + u_norm)
+            && norm(&res.dual) <= tol * self.tau * (1.0 + norm(&self.c))
+            && (primal_obj - dual_obj).abs() <= tol * (self.tau + primal_obj.abs());
+        if optimal {
+            return Some(SolveStatus::Optimal);
+        }
+        if self.tau > self.kappa {
+            return None;
+        }
+
+        // Farkas certificate for the primal: `Aᵀy + z - v = 0` with
+        // `bᵀy - uᵀv > 0`.
+        let ray: Vec<f64> = (0..self.x.len())
+            .map(|j| res.dual[j] + self.c[j] * self.tau)
+            .collect();
+        if dual_obj > 0.0 && norm(&ray) <= tol * dual_obj {
+            return Some(SolveStatus::Infeasible);
+        }
+
+        // Farkas certificate for the dual: `Ax = 0`, `x_U + w = 0` with
+        // `cᵀx < 0`.
+        let ax: Vec<f64> = (0..self.b.len())
+            .map(|i| res.primal[i] + self.b[i] * self.tau)
+            .collect();
+        let xw: Vec<f64> = self.bounded().map(|j| self.x[j] + self.w[j]).collect();
+        if primal_obj < 0.0 && norm(&ax).max(norm(&xw)) <= -tol * primal_obj {
+            return Some(SolveStatus::Unbounded);
+        }
+        None
+    }
+
+    /// Whether the constraints of the problem admit a feasible point, decided
+    /// by solving the problem with a zero objective.
+    fn is_primal_feasible(&self, max_iter: usize, tol: f64) -> Result<bool, SolveError> {
+        let mut feasibility = self.clone();
+        feasibility.c.fill(0.0);
+        feasibility.logging = false;
+        feasibility.x.fill(1.0);
+        feasibility.z.fill(1.0);
+        feasibility.y.fill(0.0);
+        for j in 0..self.x.len() {
+            let bounded = if self.upper[j].is_finite() { 1.0 } else { 0.0 };
+            (feasibility.w[j], feasibility.v[j]) = (bounded, bounded);
+        }
+        (feasibility.tau, feasibility.kappa) = (1.0, 1.0);
+        feasibility.solve(max_iter, tol)?;
+        Ok(feasibility.status == SolveStatus::Optimal)
+    }
+
+    /// Solve the Newton system of the embedding for the residual reduction
+    /// `eta` and the complementarity right-hand sides `rxz`, `rwv` and `rtk`.
+    ///
+    /// The primal-dual system is reduced to the normal equations: with
+    /// `Θ = (Z/X + V/W)⁻¹`, `dy = p + q dτ` for `A Θ Aᵀ p = -η r_p + A Θ h` and
+    /// `A Θ Aᵀ q = b - A Θ g`, and the remaining directions follow from `dy`
+    /// and the scalar `dτ`, which the gap equation determines.
+    #[allow(clippy::too_many_arguments)]
+    fn direction(
+        &self,
+        chol: &SparseCholesky,
+        theta: &[f64],
+        res: &Residuals,
+        eta: f64,
+        rxz: &[f64],
+        rwv: &[f64],
+        rtk: f64,
+    ) -> Direction {
+        let n = self.x.len();
+        let bounded = |j: usize| self.upper[j].is_finite();
+
+        // `g = V u / W - c` and `h = -η r_d - rxz / X + (rwv + η V r_u) / W`.
+        let g: Vec<f64> = (0..n)
+            .map(|j| {
+                let vu =
+                    if bounded(j) { self.v[j] * self.upper[j] / self.w[j] } else { 0.0 };
+                vu - self.c[j]
+            })
+            .collect();
+        let h: Vec<f64> = (0..n)
+            .map(|j| {
+                let mut h = -eta * res.dual[j] - rxz[j] / self.x[j];
+                if bounded(j) {
+                    h += (rwv[j] + eta * self.v[j] * res.upper[j]) / self.w[j];
+                }
+                h
+            })
+            .collect();
+
+        let theta_times =
+            |v: &[f64]| -> Vec<f64> { v.iter().zip(theta).map(|(v, t)| v * t).collect() };
+        let mut p = self.a_times(&theta_times(&h));
+        p.iter_mut().zip(&res.primal).for_each(|(p, r)| *p -= eta * r);
+        chol.solve(&mut p);
+        let mut q = self.a_times(&theta_times(&g));
+        q.iter_mut().zip(&self.b).for_each(|(q, b)| *q = b - *q);
+        chol.solve(&mut q);
+
+        // Directions as `d0 + d1 dτ`.
+        let atp = self.a_transpose_times(&p);
+        let atq = self.a_transpose_times(&q);
+        let dx0: Vec<f64> = (0..n).map(|j| theta[j] * (atp[j] - h[j])).collect();
+        let dx1: Vec<f64> = (0..n).map(|j| theta[j] * (atq[j] + g[j])).collect();
+        let mut dw0 = vec![0.0; n];
+        let mut dw1 = vec![0.0; n];
+        let mut dv0 = vec![0.0; n];
+        let mut dv1 = vec![0.0; n];
+        for j in (0..n).filter(|&j| bounded(j)) {
+            dw0[j] = -eta * res.upper[j] - dx0[j];
+            dw1[j] = self.upper[j] - dx1[j];
+            dv0[j] = (rwv[j] - self.v[j] * dw0[j]) / self.w[j];
+            dv1[j] = -self.v[j] * dw1[j] / self.w[j];
+        }
+
+        let u_dot = |d: &[f64]| -> f64 {
+            (0..n).filter(|&j| bounded(j)).map(|j| self.upper[j] * d[j]).sum()
+        };
+        let num = -eta * res.gap - dot(&self.b, &p)
+            + u_dot(&dv0)
+            + dot(&self.c, &dx0)
+            + rtk / self.tau;
+        let den =
+            dot(&self.b, &q) - u_dot(&dv1) - dot(&self.c, &dx1) + self.kappa / self.tau;
+        let dtau = num / den;
+
+        let combine = |d0: &[f64], d1: &[f64]| -> Vec<f64> {
+            d0.iter().zip(d1).map(|(a, b)| a + b * dtau).collect()
+        };
+        let dx = combine(&dx0, &dx1);
+        let dw = combine(&dw0, &dw1);
+        let dv = combine(&dv0, &dv1);
+        let dy = combine(&p, &q);
+        let dz = (0..n).map(|j| (rxz[j] - self.z[j] * dx[j]) / self.x[j]).collect();
+        let dkappa = (rtk - self.kappa * dtau) / self.tau;
+
+        Direction { dx, dw, dy, dz, dv, dtau, dkappa }
+    }
+
+    /// Largest step along `dir` that keeps every iterate non-negative.
+    fn max_step(&self, dir: &Direction) -> f64 {
+        let ratio = |value: f64, d: f64| if d < 0.0 { -value / d } else { f64::INFINITY };
+        let mut alpha = ratio(self.tau, dir.dtau).min(ratio(self.kappa, dir.dkappa));
+        for j in 0..self.x.len() {
+            alpha = alpha
+                .min(ratio(self.x[j], dir.dx[j]))
+                .min(ratio(self.z[j], dir.dz[j]));
+            if self.upper[j].is_finite() {
+                alpha = alpha
+                    .min(ratio(self.w[j], dir.dw[j]))
+                    .min(ratio(self.v[j], dir.dv[j]));
+            }
+        }
+        alpha
+    }
+
+    /// Move every iterate by `alpha` along `dir`.
+    fn step(&mut self, dir: &Direction, alpha: f64) {
+        let update = |v: &mut [f64], d: &[f64]| {
+            v.iter_mut().zip(d).for_each(|(v, d)| *v += alpha * d);
+        };
+        update(&mut self.x, &dir.dx);
+        update(&mut self.y, &dir.dy);
+        update(&mut self.z, &dir.dz);
+        for j in 0..self.x.len() {
+            if self.upper[j].is_finite() {
+                self.w[j] += alpha * dir.dw[j];
+                self.v[j] += alpha * dir.dv[j];
+            }
+        }
+        self.tau += alpha * dir.dtau;
+        self.kappa += alpha * dir.dkappa;
+    }
+}
+
+/// Incremental Gaussian elimination, used to pick linearly independent rows
+/// and columns.
+#[derive(Default)]
+struct Elimination {
+    /// Accepted vectors after elimination, with the index of their pivot.
+    pivots: Vec<(Vec<f64>, usize)>,
+    /// Remainder of the most recently rejected vector.
+    last: Vec<f64>,
+}
+
+impl Elimination {
+    /// Eliminate the accepted vectors from `v`, and accept it if the remainder
+    /// of its first `len` entries is significant. Entries from `len` on (e.g. a
+    /// right-hand side) are eliminated alongside but never pivoted on.
+    ///
+    /// Returns whether `v` was accepted. A rejected `v` leaves its remainder in
+    /// [`last`](Self::last).
+    fn insert(&mut self, mut v: Vec<f64>, len: usize) -> bool {
+        let scale = v[..len].iter().fold(0.0_f64, |s, a| s.max(a.abs()));
+        for (u, r) in &self.pivots {
+            let factor = v[*r] / u[*r];
+            if factor != 0.0 {
+                v.iter_mut().zip(u).for_each(|(v, u)| *v -= factor * u);
+            }
+        }
+
+        let (r, pivot) = v[..len]
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.abs().total_cmp(&b.1.abs()))
+            .map_or((0, 0.0), |(r, p)| (r, p.abs()));
+        let accepted = scale > 0.0 && pivot > DEPENDENCE_TOL * scale;
+        if accepted {
+            self.pivots.push((v, r));
+        } else {
+            self.last = v;
+        }
+        accepted
+    }
+}
+
+/// Linearly independent rows of `[A | b]` given by its columns `cols`, and
+/// whether any dependent row has an inconsistent right-hand side (in which case
+/// `Ax = b` has no solution).
+fn independent_rows(cols: &[Vec<(usize, f64)>], b: &[f64]) -> (Vec<usize>, bool) {
+    let n = cols.len();
+    let mut dense = vec![vec![0.0; n + 1]; b.len()];
+    for (j, col) in cols.iter().enumerate() {
+        for &(i, a) in col {
+            dense[i][j] += a;
+        }
+    }
+
+    let mut elimination = Elimination::default();
+    let mut rows = Vec::with_capacity(b.len());
+    let mut inconsistent = false;
+    for (i, mut row) in dense.into_iter().enumerate() {
+        row[n] = b[i];
+        if elimination.insert(row, n) {
+            rows.push(i);
+        } else {
+            inconsistent |=
+                elimination.last[n].abs() > DEPENDENCE_TOL * (1.0 + b[i].abs());
+        }
+    }
+    (rows, inconsistent)
+}
+
+/// Dot product of two vectors.
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
+}
+
+/// Euclidean norm of a vector.
+fn norm(a: &[f64]) -> f64 {
+    dot(a, a).sqrt()
+}
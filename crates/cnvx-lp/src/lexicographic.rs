@@ -0,0 +1,139 @@
+//! Lexicographic optimization of models with several prioritised objectives.
+//!
+//! [`solve_lexicographic`] optimizes the objectives one level at a time, highest
+//! [`priority`](crate::Objective::priority) first. Once a level is optimal, a
+//! constraint holds its objective at the optimal value, or within its
+//! [`Degradation`](crate::Degradation) of it, while the lower levels are
+//! optimized. [`LpSolver`](crate::LpSolver) solves every model with several
+//! objectives this way.
+
+use cnvx_core::{Sense, SolveError, SolveStatus};
+
+use crate::{LpModel, LpSolution, Objective, Solver};
+
+/// Each level is held within this fraction of `1 + |optimum|` of its optimum
+/// on top of its degradation, so that roundoff in the optimum cannot make the
+/// next level infeasible.
+const LEVEL_SLACK: f64 = 1e-9;
+
+/// Optimize the objectives of `model` lexicographically with `solver`.
+///
+/// The first level is solved with [`Solver::solve`] and the others with
+/// [`Solver::resolve`], so solvers with warm starts continue from the basis of
+/// the previous level. A model with a single objective is solved as usual.
+///
+/// The solution reports the value of every objective in
+/// [`objective_values`](LpSolution::objective_values), and that of the highest
+/// priority in [`objective_value`](LpSolution::objective_value). If the first
+/// level is not optimal, its solution is returned as is. If a later level is
+/// unbounded, the solution has no certificate: its ray improves that level,
+/// not the highest priority. Shadow prices and reduced costs belong to the
+/// problem of the last level, so they are only reported for a single level.
+///
+/// # Examples
+///
+/// ```rust
+/// # use cnvx_lp::*;
+/// let mut model = LpModel::new();
+/// let x = model.add_var().name("x").finish();
+/// let y = model.add_var().name("y").finish();
+/// model += (x + y).leq(10.0).named("capacity");
+/// model.add_objective(Objective::maximize(x + y).priority(2).name("output"));
+/// model.add_objective(Objective::minimize(2.0 * x + y).priority(1).name("waste"));
+///
+/// let mut solver = PrimalSimplexSolver::new();
+/// let solution = solve_lexicographic(&mut solver, &model).unwrap();
+/// assert!((solution.objective_values[0] - 10.0).abs() < 1e-6);
+/// assert!((solution.objective_values[1] - 10.0).abs() < 1e-6);
+///
+/// // Giving up two units of output saves two units of waste.
+/// model.add_objective(
+///     Objective::maximize(x + y).priority(2).abs_tol(2.0).name("output"),
+/// );
+/// let solution = solve_lexicographic(&mut solver, &model).unwrap();
+/// assert!((solution.objective_values[0] - 8.0).abs() < 1e-6);
+/// assert!((solution.objective_values[1] - 8.0).abs() < 1e-6);
+/// ```
+///
+/// # Errors
+///
+/// Returns [`SolveError::NoObjective`] if `model` has no objective,
+/// [`SolveError::Unsupported`] if it has several and one of them is quadratic,
+/// [`SolveError::NumericalFailure`] if holding a level at its optimum makes
+/// the next level infeasible, and any error of `solver`.
+pub fn solve_lexicographic(
+    solver: &mut dyn Solver,
+    model: &LpModel,
+) -> Result<LpSolution, SolveError> {
+    let objectives = model.objectives();
+    if objectives.is_empty() {
+        return Err(SolveError::NoObjective);
+    }
+    if objectives.len() > 1 && objectives.iter().any(|o| o.is_quadratic()) {
+        return Err(SolveError::Unsupported(
+            "lexicographic optimization of quadratic objectives is not supported".into(),
+        ));
+    }
+
+    let mut level = model.clone();
+    let mut solution = None;
+    for (k, objective) in objectives.iter().enumerate() {
+        level.objectives = vec![objective.clone()];
+        let mut current =
+            if k == 0 { solver.solve(&level)? } else { solver.resolve(&level)? };
+
+        match current.status {
+            SolveStatus::Optimal => {}
+            SolveStatus::Infeasible if k > 0 => {
+                return Err(SolveError::NumericalFailure(format!(
+                    "{} became infeasible with the objectives above it held",
+                    label(objective, k)
+                )));
+            }
+            _ => {
+                if k > 0 {
+                    current.certificate = None;
+                }
+                return Ok(finish(model, current, k + 1));
+            }
+        }
+
+        let optimum = objective.expr.evaluate(&current.values);
+        let allowance = objective.degradation.map_or(0.0, |d| d.allowance(optimum));
+        let allowance = allowance.max(0.0) + LEVEL_SLACK * (1.0 + optimum.abs());
+        let hold = match objective.sense {
+            Sense::Minimize => objective.expr.clone().leq(optimum + allowance),
+            Sense::Maximize => objective.expr.clone().geq(optimum - allowance),
+        };
+        level.constraints.push(hold.named(&label(objective, k)));
+        solution = Some(current);
+    }
+
+    let solution = solution.expect("the model has an objective");
+    Ok(finish(model, solution, objectives.len()))
+}
+
+/// Map the solution of the last of `levels` solved levels back to `model`.
+fn finish(model: &LpModel, mut solution: LpSolution, levels: usize) -> LpSolution {
+    if levels > 1 {
+        solution.duals.clear();
+        solution.reduced_costs.clear();
+    }
+    if solution.status == SolveStatus::Optimal {
+        solution.objective_values = model
+            .objectives()
+            .iter()
+            .map(|o| o.evaluate(&solution.values))
+            .collect();
+        solution.objective_value = solution.objective_values.first().copied();
+    }
+    solution.with_activities(model)
+}
+
+/// Name of the objective of level `k` for constraints and messages.
+fn label(objective: &Objective, k: usize) -> String {
+    match &objective.name {
+        Some(name) => format!("objective {name}"),
+        None => format!("objective {k}"),
+    }
+}
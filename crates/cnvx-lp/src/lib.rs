@@ -7,23 +7,62 @@
 //! # Features
 //!
 //! - [`LpSolver`]: A high-level solver that automatically selects the appropriate LP algorithm based on the problem characteristics.
-//! - [`DualSimplexSolver`]: Solver implementing the dual simplex algorithm for LP problems. (TODO)
+//! - [`DualSimplexSolver`]: Solver implementing the dual simplex algorithm for LP problems.
 //! - [`PrimalSimplexSolver`]: Solver implementing the 2-phase primal simplex algorithm for LP problems.
+//! - [`InteriorPointSolver`]: Homogeneous self-dual interior-point solver with optional crossover.
+//! - [`AdmmSolver`]: First-order ADMM solver for large, moderately accurate QPs and LPs.
+//! - [`ConicSolver`]: Primal-dual interior-point solver for second-order cone programs.
+//! - [`QpSolver`]: Primal-dual interior-point solver for convex quadratic programs.
+//! - [`MipSolver`]: Branch-and-bound solver for models with integer variables.
 //!
 //! # Modules
 //!
+//! - [`admm`]: Contains the [`AdmmSolver`] struct, an OSQP-style operator-splitting solver.
+//! - [`conic`]: Contains the [`ConicSolver`] struct for second-order cone constraints.
 //! - [`lp_solver`]: Contains the [`LpSolver`] struct, which automatically selects the appropriate LP solver based on the problem characteristics.
-//! - [`dual_simplex`]: Contains the [`DualSimplexSolver`] struct and dual
+//! - [`dual_simplex`]: Contains the [`DualSimplexSolver`] struct and dual simplex-specific solver logic.
 //! - [`primal_simplex`]: Contains the [`PrimalSimplexSolver`] struct and primal simplex-specific solver logic.
+//! - [`iis`]: Irreducible infeasible subsystems of infeasible models, see [`compute_iis`].
+//! - [`lexicographic`]: Lexicographic optimization of models with several prioritised objectives.
+//! - [`interior_point`]: Contains the [`InteriorPointSolver`] struct and interior-point solver logic.
+//! - [`mip`]: Contains the [`MipSolver`] struct and other mixed-integer solver logic.
+//! - [`pareto`]: Pareto frontiers of models with several objectives, see [`pareto_frontier`].
+//! - [`qp`]: Contains the [`QpSolver`] struct for convex quadratic objectives.
+//! - [`presolve`]: Presolve reductions of an [`LpModel`] and postsolve of their solutions.
+//! - [`pricing`]: Pricing rules choosing the entering column of the primal simplex.
+//! - [`scaling`]: Row and column scaling of the standard form used by the simplex solvers.
+//! - [`simplex`]: Standard-form conversion and basis helpers shared by the simplex solvers.
 
+pub mod admm;
+pub mod conic;
 pub mod dual_simplex;
+pub mod iis;
+pub mod interior_point;
+pub mod lexicographic;
 pub mod lp_solver;
+pub mod mip;
+pub mod pareto;
+pub mod presolve;
+pub mod pricing;
 pub mod primal_simplex;
+pub mod qp;
+pub mod scaling;
+pub mod simplex;
 pub mod validate;
 
+pub use admm::*;
+pub use conic::*;
 pub use dual_simplex::*;
+pub use iis::*;
+pub use interior_point::*;
+pub use lexicographic::*;
 pub use lp_solver::*;
+pub use mip::*;
+pub use pareto::*;
+pub use pricing::*;
 pub use primal_simplex::*;
+pub use qp::*;
+pub use scaling::*;
 
 pub mod core;
 pub use core::*;
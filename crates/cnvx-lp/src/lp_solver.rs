@@ -10,24 +10,108 @@
 //! let mut solver = LpSolver::new();
 //! let solution = solver.solve(&model).unwrap();
 //! ```
+//!
+//! # Solver selection
+//!
+//! Only solvers whose [`capabilities`](Solver::capabilities) cover the model
+//! are considered. Among those, the first rule that applies picks the solver:
+//!
+//! 1. Models with integer variables go to a solver that enforces integrality
+//!    (`branch-and-bound`).
+//! 2. Models with a quadratic objective go to a solver that accepts one
+//!    (`qp-interior-point`).
+//! 3. Models with second-order cone constraints go to a solver that accepts
+//!    them (`conic-interior-point`).
+//! 4. On [`resolve`](Solver::resolve), the solver of the previous solve keeps
+//!    the model if it holds a warm start (e.g. an optimal basis).
+//! 5. Very large models, whose dense simplex tableau would not fit in memory,
+//!    go to `admm`, which only factors a sparse KKT matrix.
+//! 6. Large models, by number of rows and columns or of nonzero coefficients,
+//!    go to `interior-point`, whose iteration count barely grows with size.
+//! 7. Models whose all-slack starting basis is dual feasible but not primal
+//!    feasible go to `dual-simplex`, which needs no phase 1 for them.
+//! 8. Everything else goes to `primal-simplex`.
+//!
+//! If the preferred solver is not registered, the highest-ranked solver that
+//! supports the model is used. [`LpSolver::selection_for`] reports the choice
+//! and the rule behind it.
+//!
+//! Models with several objectives are solved with
+//! [`solve_lexicographic`], which
+//! selects a solver for each level by the same rules.
+
+use std::fmt::{Display, Formatter};
+
+use cnvx_core::{Sense, SolveError};
 
-use cnvx_core::SolveError;
+use crate::{
+    AdmmSolver, Capabilities, Cmp, ConicSolver, DualSimplexSolver, InteriorPointSolver,
+    LpModel, LpSolution, MipSolver, PrimalSimplexSolver, QpSolver, Scaling, Sensitivity,
+    Solver, solve_lexicographic,
+};
 
-use crate::{DualSimplexSolver, LpModel, LpSolution, PrimalSimplexSolver, Solver};
+/// Models whose dense simplex tableau, with a column for each variable and
+/// slack, would hold at least this many entries go to the ADMM solver.
+const ADMM_MIN_TABLEAU: usize = 100_000_000;
+
+/// Models with at least this many rows plus columns go to the interior-point
+/// solver.
+const INTERIOR_POINT_MIN_SIZE: usize = 150;
+
+/// Models with at least this many nonzero constraint coefficients go to the
+/// interior-point solver.
+const INTERIOR_POINT_MIN_NONZEROS: usize = 1000;
+
+/// The solver [`LpSolver`] picked for a model, and why.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Selection {
+    /// Name of the selected solver (see [`Solver::name`]).
+    pub solver: String,
+    /// Human-readable reason for the choice.
+    pub reason: String,
+}
+
+impl Display for Selection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.solver, self.reason)
+    }
+}
 
 /// The recommended entry point for solving LP problems with `cnvx-lp`.
 ///
-/// Internally holds a ranked list of LP solvers and delegates to the first one
-/// that supports the given problem. The list is constructed once at
+/// Internally holds a ranked list of LP solvers and delegates each model to the
+/// best one that supports it. The list is constructed once at
 /// [`LpSolver::new()`].
 ///
-/// See the [module-level documentation](self) for the current solver ranking.
+/// See the [module-level documentation](self) for how the solver is selected.
+///
+/// # Examples
+///
+/// ```rust
+/// # use cnvx_lp::*;
+/// let mut model = LpModel::new();
+/// let x = model.add_var().integer().finish();
+/// let y = model.add_var().finish();
+/// model += (2.0 * x + y).leq(5.0);
+/// model.add_objective(Objective::maximize(x + y).name("Z"));
+///
+/// let solver = LpSolver::new();
+/// let selection = solver.selection_for(&model).unwrap();
+/// assert_eq!(selection.solver, "branch-and-bound");
+/// assert_eq!(selection.reason, "model has integer variables");
+/// ```
 pub struct LpSolver {
-    /// Ranked list of candidate solvers, tried in order.
+    /// Ranked list of candidate solvers.
     ///
-    /// The first solver for which `supports(problem)` returns `true` is used.
-    /// If none match, `solve` returns [`SolveError::Unsupported`].
+    /// Only solvers for which `supports(problem)` returns `true` are
+    /// considered, and the ranking breaks ties between them. If none match,
+    /// `solve` returns [`SolveError::Unsupported`].
     solvers: Vec<Box<dyn Solver>>,
+
+    /// Index of the solver that handled the most recent solve.
+    last_used: Option<usize>,
+    /// Selection made for the most recent solve.
+    last_selection: Option<Selection>,
 }
 
 impl LpSolver {
@@ -37,20 +121,93 @@ impl LpSolver {
     /// [`push_solver`](Self::push_solver) or by building the solver list
     /// manually with [`from_solvers`](Self::from_solvers).
     pub fn new() -> Self {
+        Self::with_primal_simplex(PrimalSimplexSolver::new())
+    }
+
+    /// Creates an `LpSolver` with the default solver ranking, using `primal`
+    /// as its primal simplex solver.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use cnvx_lp::{LpSolver, PrimalSimplexSolver, Scaling};
+    ///
+    /// let mut primal = PrimalSimplexSolver::new();
+    /// primal.scaling = Scaling::Equilibration;
+    /// let solver = LpSolver::with_primal_simplex(primal);
+    /// ```
+    pub fn with_primal_simplex(primal: PrimalSimplexSolver) -> Self {
+        Self::with_lp_solvers(primal, DualSimplexSolver::new(), MipSolver::new())
+    }
+
+    /// Creates an `LpSolver` with the default solver ranking, whose simplex and
+    /// branch-and-bound solvers scale the constraint matrix with `scaling`.
+    ///
+    /// The interior-point, QP, conic and ADMM solvers do not use the setting:
+    /// check [`Capabilities::scaling`] of the
+    /// [selected solver](Self::get_selected_solver) to see whether it applies.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use cnvx_lp::*;
+    /// let mut model = LpModel::new();
+    /// let x = model.add_var().finish();
+    /// model += (1000.0 * x).leq(3000.0);
+    /// model.add_objective(Objective::maximize(x * 2.0).name("Z"));
+    ///
+    /// let mut solver = LpSolver::with_scaling(Scaling::Equilibration);
+    /// assert!(solver.get_selected_solver(&model).unwrap().capabilities().scaling);
+    /// let solution = solver.solve(&model).unwrap();
+    /// assert!((solution.objective_value.unwrap() - 6.0).abs() < 1e-9);
+    /// ```
+    pub fn with_scaling(scaling: Scaling) -> Self {
+        let mut primal = PrimalSimplexSolver::new();
+        primal.scaling = scaling;
+        let mut dual = DualSimplexSolver::new();
+        dual.scaling = scaling;
+        let mut mip = MipSolver::new();
+        mip.scaling = scaling;
+        Self::with_lp_solvers(primal, dual, mip)
+    }
+
+    /// The default solver ranking, with the given simplex and branch-and-bound
+    /// solvers.
+    fn with_lp_solvers(
+        primal: PrimalSimplexSolver,
+        dual: DualSimplexSolver,
+        mip: MipSolver,
+    ) -> Self {
+        // Crossover makes interior-point solutions basic, like those of the
+        // simplex solvers, so sensitivity analysis works whichever is chosen.
+        let mut interior_point = InteriorPointSolver::new();
+        interior_point.crossover = true;
+
         Self {
             solvers: vec![
                 // Primal simplex first: fully implemented.
-                Box::new(PrimalSimplexSolver::new()),
-                // Dual simplex second: will take precedence for warm-started
-                // re-optimisation once implemented.
-                Box::new(DualSimplexSolver::new()),
+                Box::new(primal),
+                // Dual simplex second: suited to warm-started re-optimisation.
+                Box::new(dual),
+                // Branch-and-bound for models with integer variables.
+                Box::new(mip),
+                // Interior point for large LPs.
+                Box::new(interior_point),
+                // Interior point for convex quadratic objectives.
+                Box::new(QpSolver::new()),
+                // Interior point for second-order cone constraints.
+                Box::new(ConicSolver::new()),
+                // First-order method for models too large for a dense tableau.
+                Box::new(AdmmSolver::new()),
             ],
+            last_used: None,
+            last_selection: None,
         }
     }
 
     /// Creates an `LpSolver` from a custom ordered list of solvers.
     ///
-    /// Solvers are tried in the order they appear in `solvers`.  This is the
+    /// Solvers are ranked in the order they appear in `solvers`.  This is the
     /// escape hatch for users who want precise control over the fallback chain.
     ///
     /// # Examples
@@ -63,7 +220,7 @@ impl LpSolver {
     /// ]);
     /// ```
     pub fn from_solvers(solvers: Vec<Box<dyn Solver>>) -> Self {
-        Self { solvers }
+        Self { solvers, last_used: None, last_selection: None }
     }
 
     /// Appends a solver to the end of the candidate list (lowest priority).
@@ -82,15 +239,183 @@ impl LpSolver {
         self.get_selected_solver(model).map(|s| s.name())
     }
 
+    /// Returns the solver that would be selected for `model`, or `None` if no
+    /// registered solver supports it.
     pub fn get_selected_solver(
         &mut self,
         model: &LpModel,
     ) -> Option<&mut Box<dyn Solver>> {
-        // TODO: implement this method properly once multiple solvers are implemented.
-        // Returns the Primal Simplex as this is all that is implemented for now
-        _ = model; // Silence unused parameter warning until this method is implemented
-        self.solvers.iter_mut().find(|s| s.name() == "primal-simplex")
+        let (i, _) = self.select(model, false)?;
+        self.solvers.get_mut(i)
+    }
+
+    /// Returns the solver that [`solve`](Solver::solve) would use for `model`
+    /// and the reason for the choice, or `None` if no registered solver
+    /// supports it.
+    pub fn selection_for(&self, model: &LpModel) -> Option<Selection> {
+        self.select(model, false).map(|(_, selection)| selection)
+    }
+
+    /// Returns the selection made for the most recent solve.
+    pub fn last_selection(&self) -> Option<&Selection> {
+        self.last_selection.as_ref()
+    }
+
+    /// Index of the solver to use for `model`, and the reason for the choice.
+    ///
+    /// `warm` prefers a solver holding a warm start, for re-solves.
+    fn select(&self, model: &LpModel, warm: bool) -> Option<(usize, Selection)> {
+        let candidates: Vec<usize> = (0..self.solvers.len())
+            .filter(|&i| self.solvers[i].supports(model))
+            .collect();
+        let first = *candidates.first()?;
+        let pick = |i: usize, reason: String| {
+            Some((i, Selection { solver: self.solvers[i].name().to_string(), reason }))
+        };
+        let named = |name: &str| {
+            candidates.iter().copied().find(|&i| self.solvers[i].name() == name)
+        };
+
+        let (rows, cols) = model.shape();
+        if model.vars().iter().any(|v| v.is_integer)
+            && let Some(i) = candidates
+                .iter()
+                .copied()
+                .find(|&i| self.solvers[i].capabilities().integer_vars)
+        {
+            return pick(i, "model has integer variables".to_string());
+        }
+
+        if model.objective().is_some_and(|o| o.is_quadratic())
+            && let Some(i) = candidates
+                .iter()
+                .copied()
+                .find(|&i| self.solvers[i].capabilities().quadratic_objective)
+        {
+            return pick(i, "model has a quadratic objective".to_string());
+        }
+
+        if !model.cones().is_empty()
+            && let Some(i) = candidates
+                .iter()
+                .copied()
+                .find(|&i| self.solvers[i].capabilities().conic_constraints)
+        {
+            return pick(i, "model has second-order cone constraints".to_string());
+        }
+
+        if warm
+            && let Some(i) = self.last_used
+            && candidates.contains(&i)
+            && self.solvers[i].has_warm_start()
+        {
+            return pick(i, "warm start from the previous solve".to_string());
+        }
+
+        let tableau = rows.saturating_mul(rows + cols);
+        if tableau >= ADMM_MIN_TABLEAU
+            && let Some(i) = named("admm")
+        {
+            return pick(
+                i,
+                format!("very large model: dense tableau of {tableau} entries"),
+            );
+        }
+
+        let nonzeros: usize =
+            model.constraints().iter().map(|c| c.expr.terms.len()).sum();
+        if (rows + cols >= INTERIOR_POINT_MIN_SIZE
+            || nonzeros >= INTERIOR_POINT_MIN_NONZEROS)
+            && let Some(i) = named("interior-point")
+        {
+            let density = 100.0 * nonzeros as f64 / (rows * cols).max(1) as f64;
+            return pick(
+                i,
+                format!(
+                    "large model: {rows} rows, {cols} columns, {density:.1}% nonzero"
+                ),
+            );
+        }
+
+        if !slack_basis_primal_feasible(model)
+            && slack_basis_dual_feasible(model)
+            && let Some(i) = named("dual-simplex")
+        {
+            return pick(
+                i,
+                "slack basis is dual feasible but not primal feasible".to_string(),
+            );
+        }
+
+        match named("primal-simplex") {
+            Some(i) => pick(i, "default for small and medium LPs".to_string()),
+            None => pick(first, "highest-ranked solver supporting the model".to_string()),
+        }
     }
+
+    /// Select the solver for `model` and remember it as the last one used.
+    fn select_for_solve(
+        &mut self,
+        model: &LpModel,
+        warm: bool,
+    ) -> Result<usize, SolveError> {
+        let (i, selection) = self.select(model, warm).ok_or_else(|| {
+            SolveError::Unsupported(
+                "No registered solver supports this problem".to_string(),
+            )
+        })?;
+        self.last_used = Some(i);
+        self.last_selection = Some(selection);
+        Ok(i)
+    }
+}
+
+/// Value each variable takes when non-basic in the all-slack basis: its lower
+/// bound, else its upper bound, else zero.
+fn resting_value(model: &LpModel, var: usize) -> f64 {
+    let var = &model.vars()[var];
+    var.lb.or(var.ub).unwrap_or(0.0)
+}
+
+/// Whether the all-slack basis satisfies every constraint, i.e. the simplex
+/// can start without a phase 1.
+fn slack_basis_primal_feasible(model: &LpModel) -> bool {
+    model.constraints().iter().all(|cons| {
+        let activity: f64 = cons.expr.constant
+            + cons
+                .expr
+                .terms
+                .iter()
+                .map(|t| t.coeff * resting_value(model, t.var.0))
+                .sum::<f64>();
+        match cons.cmp {
+            Cmp::LEQ => activity <= cons.rhs,
+            Cmp::GEQ => activity >= cons.rhs,
+            Cmp::EQ => activity == cons.rhs,
+        }
+    })
+}
+
+/// Whether the all-slack basis is dual feasible: no variable can improve the
+/// objective by leaving the bound it rests at.
+fn slack_basis_dual_feasible(model: &LpModel) -> bool {
+    let Some(objective) = model.objective() else {
+        return false;
+    };
+    let mut costs = vec![0.0; model.vars().len()];
+    for term in &objective.expr.terms {
+        costs[term.var.0] += term.coeff;
+    }
+    let sign = if objective.sense == Sense::Minimize { -1.0 } else { 1.0 };
+    model.vars().iter().zip(costs).all(|(var, cost)| {
+        // Improving direction in maximisation form.
+        let cost = sign * cost;
+        match (var.lb, var.ub) {
+            (Some(_), _) => cost <= 0.0,
+            (None, Some(_)) => cost >= 0.0,
+            (None, None) => cost == 0.0,
+        }
+    })
 }
 
 impl Default for LpSolver {
@@ -111,12 +436,37 @@ impl Solver for LpSolver {
     /// Returns [`SolveError::Unsupported`] if no registered solver supports
     /// the problem.  All other errors are propagated from the chosen solver.
     fn solve(&mut self, model: &LpModel) -> Result<LpSolution, SolveError> {
-        let solver = self.get_selected_solver(model).ok_or_else(|| {
-            SolveError::Unsupported(
-                "No registered solver supports this problem".to_string(),
-            )
-        })?;
-        solver.solve(model)
+        if model.objectives().len() > 1 {
+            return solve_lexicographic(self, model);
+        }
+        let i = self.select_for_solve(model, false)?;
+        self.solvers[i].solve(model)
+    }
+
+    /// Delegates a warm-started re-solve to the selected solver.
+    ///
+    /// See [`Solver::resolve`].
+    fn resolve(&mut self, model: &LpModel) -> Result<LpSolution, SolveError> {
+        if model.objectives().len() > 1 {
+            return solve_lexicographic(self, model);
+        }
+        let i = self.select_for_solve(model, true)?;
+        self.solvers[i].resolve(model)
+    }
+
+    /// Delegates to the solver that handled the most recent solve.
+    ///
+    /// See [`Solver::sensitivity`].
+    fn sensitivity(&self, model: &LpModel) -> Result<Sensitivity, SolveError> {
+        if model.objectives().len() > 1 {
+            return Err(SolveError::Unsupported(
+                "sensitivity analysis of several objectives is not supported".into(),
+            ));
+        }
+        let i = self
+            .last_used
+            .ok_or_else(|| SolveError::Other("no previous solve to analyse".into()))?;
+        self.solvers[i].sensitivity(model)
     }
 
     fn objective_value(&self) -> Option<f64> {
@@ -125,6 +475,33 @@ impl Solver for LpSolver {
         self.solvers.iter().find_map(|s| s.objective_value())
     }
 
+    /// The combined capabilities of the registered solvers.
+    fn capabilities(&self) -> Capabilities {
+        let all: Vec<Capabilities> =
+            self.solvers.iter().map(|s| s.capabilities()).collect();
+        let max = |limit: fn(&Capabilities) -> Option<usize>| {
+            all.iter().map(limit).try_fold(0, |m, l| l.map(|l| m.max(l)))
+        };
+        Capabilities {
+            integer_vars: all.iter().any(|c| c.integer_vars),
+            free_vars: all.iter().any(|c| c.free_vars),
+            max_vars: max(|c| c.max_vars),
+            max_constraints: max(|c| c.max_constraints),
+            warm_start: all.iter().any(|c| c.warm_start),
+            quadratic_objective: all.iter().any(|c| c.quadratic_objective),
+            conic_constraints: all.iter().any(|c| c.conic_constraints),
+            scaling: all.iter().any(|c| c.scaling),
+        }
+    }
+
+    fn supports(&self, model: &LpModel) -> bool {
+        self.solvers.iter().any(|s| s.supports(model))
+    }
+
+    fn has_warm_start(&self) -> bool {
+        self.last_used.is_some_and(|i| self.solvers[i].has_warm_start())
+    }
+
     fn solution_vector(&self) -> Vec<f64> {
         self.solvers
             .iter()
@@ -0,0 +1,714 @@
+use cnvx_core::{Sense, SolveError, SolveStatus};
+use cnvx_math::{DenseMatrix, Matrix, SparseMatrix};
+
+use crate::mip::cuts::{self, CutFamily, CutPool, CutStats};
+use crate::mip::heuristics::{self, Heuristic};
+use crate::simplex;
+use crate::{
+    Capabilities, DualSimplexSolver, DualSimplexState, LpModel, LpSolution,
+    PrimalSimplexState, Scaling, Solver,
+};
+
+/// Pooled cuts are only added to the LP if they cut off the relaxation
+/// solution by at least this distance.
+const MIN_EFFICACY: f64 = 1e-4;
+
+/// Cut rounds stop once a round moves the root bound by less than this
+/// fraction of its magnitude.
+const MIN_BOUND_PROGRESS: f64 = 1e-3;
+
+/// At most this many cuts per original row or column are added at the root,
+/// so the node LPs stay comparable in size to the model.
+const MAX_CUT_GROWTH: usize = 2;
+
+/// Cuts with more slack than this at the root optimum are dropped before
+/// branching.
+const CUT_SLACK_TOL: f64 = 1e-6;
+
+/// Solutions proposed by heuristics must satisfy the model within this
+/// tolerance.
+const HEURISTIC_FEAS_TOL: f64 = 1e-6;
+
+/// Strategy for choosing the next open node of the search tree.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum NodeSelection {
+    /// Explore the node with the best relaxation bound first. Closes the gap
+    /// quickly but keeps many nodes open.
+    #[default]
+    BestBound,
+    /// Explore the most recently created node first. Finds incumbents early and
+    /// keeps the open list small.
+    DepthFirst,
+}
+
+/// Rule for choosing the fractional variable to branch on.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Branching {
+    /// Branch on the variable whose value is furthest from an integer.
+    MostFractional,
+    /// Branch on the variable with the largest estimated objective degradation,
+    /// learned from previous branchings on it. Falls back to the average over
+    /// all variables for variables that have not been branched on yet.
+    #[default]
+    PseudoCost,
+}
+
+/// Statistics of the most recent [`MipSolver`] solve.
+#[derive(Clone, Debug, Default)]
+pub struct MipStats {
+    /// Number of nodes whose LP relaxation was solved.
+    pub nodes: usize,
+    /// Number of improving integer solutions found.
+    pub incumbents: usize,
+    /// Number of the improving solutions found by primal heuristics.
+    pub heuristic_incumbents: usize,
+    /// Best bound on the optimal objective value over the unexplored part of
+    /// the tree, in the sense of the original objective.
+    pub best_bound: Option<f64>,
+    /// Relative gap `|bound - incumbent| / max(1, |incumbent|)` at termination,
+    /// or `None` without an incumbent.
+    pub gap: Option<f64>,
+    /// Cuts added to the root relaxation, per family.
+    pub cuts: CutStats,
+}
+
+/// An improving integer solution found during a [`MipSolver`] solve, as passed
+/// to the [`on_incumbent`](MipSolver::on_incumbent) callback.
+#[derive(Clone, Debug)]
+pub struct Incumbent {
+    /// Variable values, indexed by variable ID.
+    pub values: Vec<f64>,
+    /// Objective value, in the sense of the model.
+    pub objective_value: f64,
+    /// Name of the heuristic that found the solution, or `"branch-and-bound"`
+    /// for an integral node relaxation.
+    pub source: String,
+    /// Number of nodes solved when the solution was found.
+    pub node: usize,
+}
+
+/// Callback receiving the improving solutions of a [`MipSolver`] solve.
+type IncumbentCallback = Box<dyn FnMut(&Incumbent) + Send>;
+
+/// A branch-and-bound solver for mixed-integer linear programs.
+///
+/// Solves the LP relaxation at each node with the [`DualSimplexSolver`],
+/// warm-started from the previously solved node, and branches on integer
+/// variables with fractional values. The search stops once the gap between the
+/// incumbent and the best bound is within [`relative_gap`](Self::relative_gap)
+/// or [`absolute_gap`](Self::absolute_gap), or after
+/// [`node_limit`](Self::node_limit) nodes.
+///
+/// Before branching, up to [`cut_rounds`](Self::cut_rounds) rounds of cutting
+/// planes from [`cut_families`](Self::cut_families) tighten the root
+/// relaxation (see [`cuts`]). Each round re-optimises from the
+/// previous basis with the dual simplex, and the cuts stay in the relaxation of
+/// every node.
+///
+/// The [`heuristics`](Self::heuristics) are run on the node relaxations they
+/// select and supply incumbents before the tree search finds them (see
+/// the [`heuristics` module](crate::mip::heuristics)). Every improving solution is passed
+/// to the callback registered with [`on_incumbent`](Self::on_incumbent).
+///
+/// The solution status is [`Optimal`](SolveStatus::Optimal) when the gap is
+/// closed, [`Infeasible`](SolveStatus::Infeasible) if no integer solution
+/// exists, [`Unbounded`](SolveStatus::Unbounded) if the root relaxation is
+/// unbounded, and [`Other`](SolveStatus::Other) if the node limit was reached
+/// (reporting the incumbent, if any).
+///
+/// # Examples
+///
+/// ```rust
+/// # use cnvx_lp::*;
+/// let mut model = LpModel::new();
+/// let x: Vec<_> = (0..4).map(|_| model.add_var().binary().finish()).collect();
+/// model += (5.0 * x[0] + 7.0 * x[1] + 4.0 * x[2] + 3.0 * x[3]).leq(14.0);
+/// model.add_objective(
+///     Objective::maximize(8.0 * x[0] + 11.0 * x[1] + 6.0 * x[2] + 4.0 * x[3])
+///         .name("value"),
+/// );
+///
+/// let mut solver = MipSolver::new();
+/// let solution = solver.solve(&model).unwrap();
+/// assert!((solution.objective_value.unwrap() - 21.0).abs() < 1e-9);
+/// assert_eq!(solution.value(x[0]), 0.0);
+/// ```
+pub struct MipSolver {
+    /// Solver for the LP relaxations, retained between nodes for warm-starting.
+    lp: DualSimplexSolver,
+    /// Strategy for choosing the next open node.
+    pub node_selection: NodeSelection,
+    /// Rule for choosing the branching variable.
+    pub branching: Branching,
+    /// Stop once the relative gap between the incumbent and the best bound is
+    /// at most this value.
+    pub relative_gap: f64,
+    /// Stop once the absolute gap between the incumbent and the best bound is
+    /// at most this value.
+    pub absolute_gap: f64,
+    /// Maximum number of nodes to solve before terminating.
+    pub node_limit: usize,
+    /// Values within this distance of an integer are considered integral.
+    pub integrality_tolerance: f64,
+    /// The numerical tolerance used by the LP relaxations.
+    pub tolerance: f64,
+    /// The maximum number of simplex iterations per LP relaxation.
+    pub max_iter: usize,
+    /// How to scale the constraint matrix of LP relaxations solved from
+    /// scratch.
+    pub scaling: Scaling,
+    /// Maximum number of cut separation rounds at the root; `0` disables cuts.
+    pub cut_rounds: usize,
+    /// Maximum number of cuts added to the LP per round.
+    pub max_cuts_per_round: usize,
+    /// Cut families separated at the root.
+    pub cut_families: Vec<CutFamily>,
+    /// Primal heuristics, run in order on the nodes they select.
+    pub heuristics: Vec<Box<dyn Heuristic>>,
+    /// Whether to log search progress.
+    pub logging: bool,
+    /// Interval, in nodes, at which to log progress if logging is enabled.
+    log_interval: usize,
+
+    /// Callback invoked with every improving solution.
+    incumbent_callback: Option<IncumbentCallback>,
+
+    /// Statistics of the most recent solve.
+    stats: MipStats,
+    /// Pseudo-costs per variable: summed per-unit degradation and observation
+    /// count for the down (`0`) and up (`1`) branches.
+    pseudo_costs: Vec<[(f64, usize); 2]>,
+
+    /// Cached objective value from the most recent solve.
+    last_objective: Option<f64>,
+    /// Cached solution vector from the most recent solve.
+    last_solution: Vec<f64>,
+}
+
+/// An open node of the search tree.
+struct Node {
+    /// Bounds `(var, lb, ub)` tightened on the path from the root, applied in order.
+    bounds: Vec<(usize, Option<f64>, Option<f64>)>,
+    /// Relaxation value of the parent (maximisation form), a bound on the node.
+    bound: f64,
+    /// The branching that created the node: variable, direction (`0` down,
+    /// `1` up) and distance the variable was moved by.
+    branch: Option<(usize, usize, f64)>,
+}
+
+impl MipSolver {
+    /// Creates a new branch-and-bound solver with default settings.
+    pub fn new() -> Self {
+        Self {
+            lp: DualSimplexSolver::new(),
+            node_selection: NodeSelection::default(),
+            branching: Branching::default(),
+            relative_gap: 1e-4,
+            absolute_gap: 1e-6,
+            node_limit: 100_000,
+            integrality_tolerance: 1e-6,
+            tolerance: 1e-8,
+            max_iter: 1000,
+            scaling: Scaling::default(),
+            cut_rounds: 10,
+            max_cuts_per_round: 50,
+            cut_families: vec![
+                CutFamily::Gomory,
+                CutFamily::Mir,
+                CutFamily::KnapsackCover,
+            ],
+            heuristics: heuristics::default_heuristics(),
+            incumbent_callback: None,
+            logging: false,
+            log_interval: 100,
+            stats: MipStats::default(),
+            pseudo_costs: Vec::new(),
+            last_objective: None,
+            last_solution: Vec::new(),
+        }
+    }
+
+    /// Returns the statistics of the most recent solve.
+    pub fn stats(&self) -> &MipStats {
+        &self.stats
+    }
+
+    /// Registers a callback invoked with every improving solution found during
+    /// subsequent solves, replacing any previous callback.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use cnvx_lp::*;
+    /// # use std::sync::mpsc;
+    /// let mut model = LpModel::new();
+    /// let x: Vec<_> = (0..4).map(|_| model.add_var().binary().finish()).collect();
+    /// model += (5.0 * x[0] + 7.0 * x[1] + 4.0 * x[2] + 3.0 * x[3]).leq(14.0);
+    /// model.add_objective(
+    ///     Objective::maximize(8.0 * x[0] + 11.0 * x[1] + 6.0 * x[2] + 4.0 * x[3])
+    ///         .name("value"),
+    /// );
+    ///
+    /// let (tx, rx) = mpsc::channel();
+    /// let mut solver = MipSolver::new();
+    /// solver.on_incumbent(move |incumbent| {
+    ///     tx.send(incumbent.objective_value).unwrap();
+    /// });
+    /// solver.solve(&model).unwrap();
+    ///
+    /// let found: Vec<f64> = rx.try_iter().collect();
+    /// assert!(found.windows(2).all(|w| w[0] < w[1]));
+    /// assert_eq!(found.last(), Some(&21.0));
+    /// ```
+    pub fn on_incumbent(&mut self, callback: impl FnMut(&Incumbent) + Send + 'static) {
+        self.incumbent_callback = Some(Box::new(callback));
+    }
+
+    /// Whether the gap between `bound` and `incumbent` (both in maximisation
+    /// form) is within the configured tolerances.
+    fn gap_closed(&self, bound: f64, incumbent: f64) -> bool {
+        let gap = bound - incumbent;
+        gap <= self.absolute_gap || gap <= self.relative_gap * incumbent.abs().max(1.0)
+    }
+
+    /// Tighten the LP relaxation of `model` with rounds of cutting planes,
+    /// solving the relaxation with the constraint matrix stored as `A`.
+    ///
+    /// Returns `model` with the added cuts appended to its constraints. Stops
+    /// early once a round finds no sufficiently violated cut, or the relaxation
+    /// stops being optimal (e.g. the cuts prove it infeasible, which the tree
+    /// search then reports).
+    fn add_root_cuts<A: Matrix>(
+        &mut self,
+        model: &LpModel,
+    ) -> Result<LpModel, SolveError> {
+        let mut cut_model = model.clone();
+        if self.cut_rounds == 0 || !model.vars().iter().any(|v| v.is_integer) {
+            return Ok(cut_model);
+        }
+
+        let n = model.vars().len();
+        let mut budget = MAX_CUT_GROWTH * (n + model.constraints().len());
+        let mut state = PrimalSimplexState::<A>::new(&cut_model);
+        state.solve_lp(self.max_iter, self.tolerance)?;
+        let mut pool = CutPool::default();
+
+        for _ in 0..self.cut_rounds {
+            if state.status != SolveStatus::Optimal {
+                break;
+            }
+            let (mut x, _) = state.solution();
+            x.truncate(n);
+
+            for family in &self.cut_families {
+                let found = match family {
+                    CutFamily::Gomory => cuts::gomory_cuts(&state, &cut_model)?,
+                    CutFamily::Mir => cuts::mir_cuts(&cut_model, &x),
+                    CutFamily::KnapsackCover => cuts::cover_cuts(&cut_model, &x),
+                };
+                for cut in found {
+                    pool.add(cut);
+                }
+            }
+
+            let limit = self.max_cuts_per_round.min(budget);
+            let selected = pool.select(&x, limit, MIN_EFFICACY);
+            if selected.is_empty() {
+                break;
+            }
+            budget -= selected.len();
+            for cut in &selected {
+                self.stats.cuts.record(cut.family);
+                cut_model.constraints.push(cut.to_constraint());
+            }
+            self.stats.cuts.rounds += 1;
+
+            // The previous optimum violates the new rows, so its basis is dual
+            // feasible but primal infeasible: re-optimise with the dual simplex.
+            let Some(warm) = state.warm_start() else { break };
+            let Some(mut dual) = DualSimplexState::<A>::with_basis(&cut_model, &warm)
+            else {
+                break;
+            };
+            dual.solve_lp(self.max_iter, self.tolerance)?;
+            let Some(next) = dual
+                .warm_start()
+                .and_then(|w| PrimalSimplexState::with_basis(&cut_model, &w))
+            else {
+                break;
+            };
+            let previous = state.objective;
+            state = next;
+            state.status = dual.status;
+            let progress = (state.objective - previous).abs();
+            if progress < MIN_BOUND_PROGRESS * previous.abs().max(1.0) {
+                break;
+            }
+        }
+
+        // Cuts that are slack at the final root optimum only enlarge the node
+        // LPs; keep the binding ones.
+        if state.status == SolveStatus::Optimal {
+            let (x, _) = state.solution();
+            let m = model.constraints().len();
+            let mut index = 0;
+            cut_model.constraints.retain(|row| {
+                index += 1;
+                index <= m || row.rhs - row.expr.evaluate(&x) <= CUT_SLACK_TOL
+            });
+        }
+
+        if self.logging {
+            println!(
+                "Added {} cuts in {} rounds ({} Gomory, {} MIR, {} cover)",
+                self.stats.cuts.total(),
+                self.stats.cuts.rounds,
+                self.stats.cuts.gomory,
+                self.stats.cuts.mir,
+                self.stats.cuts.knapsack_cover
+            );
+        }
+
+        Ok(cut_model)
+    }
+
+    /// Replace the incumbent with `values` if its objective value `value`
+    /// (maximisation form) improves on it, notifying the incumbent callback.
+    fn improve(
+        &mut self,
+        incumbent: &mut Option<(Vec<f64>, f64)>,
+        values: Vec<f64>,
+        value: f64,
+        source: &str,
+        sign: f64,
+    ) {
+        if incumbent.as_ref().is_some_and(|(_, best)| value <= *best) {
+            return;
+        }
+
+        self.stats.incumbents += 1;
+        if self.logging {
+            println!("New incumbent {:>12.6} from {source}", sign * value);
+        }
+        if let Some(callback) = &mut self.incumbent_callback {
+            callback(&Incumbent {
+                values: values.clone(),
+                objective_value: sign * value,
+                source: source.to_string(),
+                node: self.stats.nodes,
+            });
+        }
+        *incumbent = Some((values, value));
+    }
+
+    /// Run the heuristics selected for the current node on its relaxation
+    /// `solution`, offering every feasible solution they find as an incumbent.
+    fn run_heuristics(
+        &mut self,
+        model: &LpModel,
+        solution: &LpSolution,
+        incumbent: &mut Option<(Vec<f64>, f64)>,
+        sign: f64,
+    ) -> Result<(), SolveError> {
+        let node = self.stats.nodes - 1;
+        let mut heuristics = std::mem::take(&mut self.heuristics);
+        let mut result = Ok(());
+
+        for heuristic in heuristics.iter_mut().filter(|h| h.should_run(node)) {
+            let found = heuristic.find(
+                model,
+                solution,
+                incumbent.as_ref().map(|(values, _)| values.as_slice()),
+            );
+            let values = match found {
+                Ok(Some(values)) => values,
+                Ok(None) => continue,
+                Err(e) => {
+                    result = Err(e);
+                    break;
+                }
+            };
+            if !heuristics::is_feasible(model, &values, HEURISTIC_FEAS_TOL) {
+                continue;
+            }
+
+            let value = sign
+                * model
+                    .objective()
+                    .map_or(0.0, |o| o.expr.evaluate(&values) - o.expr.constant);
+            let before = self.stats.incumbents;
+            self.improve(incumbent, values, value, heuristic.name(), sign);
+            self.stats.heuristic_incumbents += self.stats.incumbents - before;
+        }
+
+        self.heuristics = heuristics;
+        result
+    }
+
+    /// Remove and return the next node to explore.
+    fn next_node(&self, open: &mut Vec<Node>) -> Option<Node> {
+        match self.node_selection {
+            NodeSelection::DepthFirst => open.pop(),
+            NodeSelection::BestBound => {
+                let best = (0..open.len()).max_by(|&a, &b| {
+                    open[a].bound.partial_cmp(&open[b].bound).unwrap()
+                })?;
+                Some(open.swap_remove(best))
+            }
+        }
+    }
+
+    /// Choose the integer variable to branch on, or `None` if `values` is
+    /// integral.
+    fn choose_branch(&self, values: &[f64], integers: &[usize]) -> Option<usize> {
+        let fractional = integers.iter().filter_map(|&j| {
+            let f = values[j] - values[j].floor();
+            (f.min(1.0 - f) > self.integrality_tolerance).then_some((j, f))
+        });
+
+        let score = |j: usize, f: f64| match self.branching {
+            Branching::MostFractional => f.min(1.0 - f),
+            Branching::PseudoCost => {
+                let down = self.pseudo_cost(j, 0) * f;
+                let up = self.pseudo_cost(j, 1) * (1.0 - f);
+                down.max(1e-6) * up.max(1e-6)
+            }
+        };
+
+        fractional
+            .map(|(j, f)| (j, score(j, f)))
+            .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
+            .map(|(j, _)| j)
+    }
+
+    /// Average per-unit degradation of branching variable `j` in direction
+    /// `dir`, or the average over all variables if `j` has no observations.
+    fn pseudo_cost(&self, j: usize, dir: usize) -> f64 {
+        let (sum, count) = self.pseudo_costs[j][dir];
+        if count > 0 {
+            return sum / count as f64;
+        }
+        let (sum, count) = self
+            .pseudo_costs
+            .iter()
+            .map(|pc| pc[dir])
+            .filter(|&(_, n)| n > 0)
+            .fold((0.0, 0), |(s, n), (ps, pn)| (s + ps / pn as f64, n + 1));
+        if count > 0 { sum / count as f64 } else { 1.0 }
+    }
+
+    /// Record the outcome of a solve and return its [`LpSolution`].
+    fn finish(&mut self, solution: LpSolution) -> LpSolution {
+        if self.logging {
+            println!(
+                "Branch-and-bound finished with status {:?} after {} nodes. Objective value: {}",
+                solution.status,
+                self.stats.nodes,
+                solution.objective_value.unwrap_or_default()
+            );
+        }
+
+        self.last_objective = solution.objective_value;
+        self.last_solution = solution.values.clone();
+        solution
+    }
+}
+
+impl Default for MipSolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Solver for MipSolver {
+    fn name(&self) -> &str {
+        "branch-and-bound"
+    }
+
+    fn solve(&mut self, model: &LpModel) -> Result<LpSolution, SolveError> {
+        crate::validate::check_lp(model)?;
+
+        let sign = match model.objective().map(|o| o.sense) {
+            Some(Sense::Minimize) => -1.0,
+            _ => 1.0,
+        };
+        let n = model.vars().len();
+        let integers: Vec<usize> =
+            model.vars().iter().filter(|v| v.is_integer).map(|v| v.id.0).collect();
+
+        self.lp.tolerance = self.tolerance;
+        self.lp.max_iter = self.max_iter;
+        self.lp.scaling = self.scaling;
+        self.stats = MipStats::default();
+        self.pseudo_costs = vec![[(0.0, 0); 2]; n];
+
+        let mut open = vec![Node {
+            bounds: Vec::new(),
+            bound: f64::INFINITY,
+            branch: None,
+        }];
+        // Incumbent values and objective value (maximisation form).
+        let mut incumbent: Option<(Vec<f64>, f64)> = None;
+        let mut relaxed = if simplex::is_sparse(model) {
+            self.add_root_cuts::<SparseMatrix>(model)?
+        } else {
+            self.add_root_cuts::<DenseMatrix>(model)?
+        };
+        let mut limit_reached = false;
+
+        while let Some(node) = self.next_node(&mut open) {
+            if let Some((_, best)) = &incumbent
+                && self.gap_closed(node.bound, *best)
+            {
+                continue;
+            }
+            if self.stats.nodes >= self.node_limit {
+                open.push(node);
+                limit_reached = true;
+                break;
+            }
+
+            for var in &mut relaxed.vars {
+                let orig = &model.vars()[var.id.0];
+                (var.lb, var.ub) = (orig.lb, orig.ub);
+            }
+            for &(j, lb, ub) in &node.bounds {
+                (relaxed.vars[j].lb, relaxed.vars[j].ub) = (lb, ub);
+            }
+
+            let solution = if self.stats.nodes == 0 {
+                self.lp.solve(&relaxed)?
+            } else {
+                self.lp.resolve(&relaxed)?
+            };
+            self.stats.nodes += 1;
+
+            match solution.status {
+                SolveStatus::Optimal => {}
+                SolveStatus::Infeasible => continue,
+                SolveStatus::Unbounded if node.branch.is_none() => {
+                    self.stats.best_bound = Some(sign * f64::INFINITY);
+                    let solution = LpSolution::new(Vec::new(), None, solution.status);
+                    return Ok(self.finish(solution));
+                }
+                status => {
+                    return Err(SolveError::Other(format!(
+                        "LP relaxation ended with status {status}"
+                    )));
+                }
+            }
+
+            let value = sign * solution.objective_value.unwrap_or_default();
+            if let Some((j, dir, dist)) = node.branch
+                && node.bound.is_finite()
+            {
+                let pc = &mut self.pseudo_costs[j][dir];
+                pc.0 += (node.bound - value).max(0.0) / dist;
+                pc.1 += 1;
+            }
+
+            if let Some((_, best)) = &incumbent
+                && self.gap_closed(value, *best)
+            {
+                continue;
+            }
+
+            match self.choose_branch(&solution.values, &integers) {
+                None => {
+                    let mut values = solution.values;
+                    values.truncate(n);
+                    for &j in &integers {
+                        values[j] = values[j].round();
+                    }
+                    self.improve(&mut incumbent, values, value, "branch-and-bound", sign);
+                }
+                Some(j) => {
+                    self.run_heuristics(model, &solution, &mut incumbent, sign)?;
+                    if let Some((_, best)) = &incumbent
+                        && self.gap_closed(value, *best)
+                    {
+                        continue;
+                    }
+
+                    let x = solution.values[j];
+                    let var = &relaxed.vars[j];
+                    let down = (j, var.lb, Some(x.floor()));
+                    let up = (j, Some(x.ceil()), var.ub);
+                    let child = |b, dir, dist| {
+                        let mut bounds = node.bounds.clone();
+                        bounds.push(b);
+                        Node { bounds, bound: value, branch: Some((j, dir, dist)) }
+                    };
+                    let (down, up) =
+                        (child(down, 0, x - x.floor()), child(up, 1, x.ceil() - x));
+                    // Depth-first search explores the last node pushed first:
+                    // prefer the direction the relaxation leans towards.
+                    if x - x.floor() >= 0.5 {
+                        open.extend([down, up]);
+                    } else {
+                        open.extend([up, down]);
+                    }
+                }
+            }
+
+            if self.logging && self.stats.nodes.is_multiple_of(self.log_interval) {
+                println!(
+                    "Node {:>6}: open = {:>6}, incumbent = {:>12.6}",
+                    self.stats.nodes,
+                    open.len(),
+                    incumbent.as_ref().map_or(f64::NAN, |(_, v)| sign * v)
+                );
+            }
+
+            if let Some((_, best)) = &incumbent {
+                let bound =
+                    open.iter().map(|n| n.bound).fold(f64::NEG_INFINITY, f64::max);
+                if self.gap_closed(bound, *best) {
+                    break;
+                }
+            }
+        }
+
+        let bound = open.iter().map(|n| n.bound).fold(f64::NEG_INFINITY, f64::max);
+        let solution = match incumbent {
+            Some((values, value)) => {
+                let bound = bound.max(value);
+                self.stats.best_bound = Some(sign * bound);
+                self.stats.gap = Some((bound - value) / value.abs().max(1.0));
+                let status = if limit_reached {
+                    SolveStatus::Other("node limit reached".into())
+                } else {
+                    SolveStatus::Optimal
+                };
+                LpSolution::new(values, Some(sign * value), status).with_activities(model)
+            }
+            None if limit_reached => {
+                self.stats.best_bound = Some(sign * bound);
+                LpSolution::new(
+                    Vec::new(),
+                    None,
+                    SolveStatus::Other("node limit reached".into()),
+                )
+            }
+            None => LpSolution::new(Vec::new(), None, SolveStatus::Infeasible),
+        };
+
+        Ok(self.finish(solution))
+    }
+
+    fn objective_value(&self) -> Option<f64> {
+        self.last_objective
+    }
+
+    fn solution_vector(&self) -> Vec<f64> {
+        self.last_solution.clone()
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            integer_vars: true,
+            scaling: true,
+            ..Capabilities::default()
+        }
+    }
+}
@@ -0,0 +1,540 @@
+//! Cutting planes for the mixed-integer solver.
+//!
+//! Cuts are valid inequalities for the integer hull that are violated by the
+//! current LP relaxation. [`MipSolver`](crate::MipSolver) separates them in
+//! rounds at the root node and keeps them in the model for the tree search:
+//!
+//! - [`gomory_cuts`]: Gomory mixed-integer cuts read from the optimal simplex
+//!   tableau.
+//! - [`mir_cuts`]: Mixed-integer rounding cuts from the model rows.
+//! - [`cover_cuts`]: Knapsack cover cuts from rows over binary variables.
+
+use cnvx_core::SolveError;
+use cnvx_math::Matrix;
+
+use crate::{
+    Cmp, LinExpr, LinTerm, LinearConstraint, LpModel, PrimalSimplexState, VarId,
+};
+
+/// Fractional parts closer than this to `0` or `1` are treated as integral.
+const FRAC_TOL: f64 = 1e-4;
+
+/// Coefficients smaller than this are dropped from a cut.
+const COEFF_TOL: f64 = 1e-9;
+
+/// Cuts whose coefficient magnitudes span more than this ratio are rejected as
+/// numerically unsafe.
+const MAX_DYNAMISM: f64 = 1e6;
+
+/// Family of a cutting plane.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CutFamily {
+    /// Gomory mixed-integer cut from a row of the simplex tableau.
+    Gomory,
+    /// Mixed-integer rounding cut from a model row.
+    Mir,
+    /// Cover cut from a knapsack row over binary variables.
+    KnapsackCover,
+}
+
+/// Number of cuts added to the root relaxation, per family.
+#[derive(Clone, Debug, Default)]
+pub struct CutStats {
+    /// Number of separation rounds that added at least one cut.
+    pub rounds: usize,
+    /// Number of Gomory mixed-integer cuts added.
+    pub gomory: usize,
+    /// Number of mixed-integer rounding cuts added.
+    pub mir: usize,
+    /// Number of knapsack cover cuts added.
+    pub knapsack_cover: usize,
+}
+
+impl CutStats {
+    /// Total number of cuts added.
+    pub fn total(&self) -> usize {
+        self.gomory + self.mir + self.knapsack_cover
+    }
+
+    /// Count one added cut of `family`.
+    pub fn record(&mut self, family: CutFamily) {
+        match family {
+            CutFamily::Gomory => self.gomory += 1,
+            CutFamily::Mir => self.mir += 1,
+            CutFamily::KnapsackCover => self.knapsack_cover += 1,
+        }
+    }
+}
+
+/// A valid inequality `Σ coeffs_j x_j <= rhs` over the model variables.
+#[derive(Clone, Debug)]
+pub struct Cut {
+    /// Family the cut was derived by.
+    pub family: CutFamily,
+    /// Coefficient of each variable, indexed by variable ID.
+    pub coeffs: Vec<f64>,
+    /// Right-hand side.
+    pub rhs: f64,
+}
+
+impl Cut {
+    /// Builds a cut, dropping tiny coefficients. Returns `None` if the cut is
+    /// empty or numerically unsafe.
+    fn new(family: CutFamily, mut coeffs: Vec<f64>, rhs: f64) -> Option<Self> {
+        let max = coeffs.iter().fold(0.0_f64, |m, c| m.max(c.abs()));
+        for c in &mut coeffs {
+            if c.abs() <= COEFF_TOL * max.max(1.0) {
+                *c = 0.0;
+            }
+        }
+        let min = coeffs
+            .iter()
+            .filter(|c| **c != 0.0)
+            .fold(f64::INFINITY, |m, c| m.min(c.abs()));
+        (max > COEFF_TOL && max / min <= MAX_DYNAMISM && rhs.is_finite())
+            .then_some(Self { family, coeffs, rhs })
+    }
+
+    /// Amount by which `x` violates the cut (positive if violated).
+    pub fn violation(&self, x: &[f64]) -> f64 {
+        self.coeffs.iter().zip(x).map(|(c, x)| c * x).sum::<f64>() - self.rhs
+    }
+
+    /// Euclidean distance from `x` to the cut hyperplane, or a negative value
+    /// if `x` satisfies the cut.
+    pub fn efficacy(&self, x: &[f64]) -> f64 {
+        self.violation(x) / self.coeffs.iter().map(|c| c * c).sum::<f64>().sqrt()
+    }
+
+    /// Converts the cut to a model constraint.
+    pub fn to_constraint(&self) -> LinearConstraint {
+        let terms = (0..self.coeffs.len())
+            .filter(|&j| self.coeffs[j] != 0.0)
+            .map(|j| LinTerm { var: VarId(j), coeff: self.coeffs[j] })
+            .collect();
+        LinExpr { terms, constant: 0.0 }.leq(self.rhs)
+    }
+
+    /// Whether `other` describes the same half-space, up to scaling.
+    fn is_parallel_to(&self, other: &Cut) -> bool {
+        let scale = |c: &Cut| c.coeffs.iter().fold(0.0_f64, |m, v| m.max(v.abs()));
+        let (sa, sb) = (scale(self), scale(other));
+        self.coeffs
+            .iter()
+            .zip(&other.coeffs)
+            .all(|(a, b)| (a / sa - b / sb).abs() < 1e-9)
+            && (self.rhs / sa - other.rhs / sb).abs() < 1e-9
+    }
+}
+
+/// Every cut generated during a solve, with a flag for those already in the LP.
+///
+/// New cuts that duplicate a pooled one are discarded, so a cut violated again
+/// in a later round is only added once.
+#[derive(Clone, Debug, Default)]
+pub struct CutPool {
+    /// Generated cuts and whether each one has been added to the LP.
+    cuts: Vec<(Cut, bool)>,
+}
+
+impl CutPool {
+    /// Number of cuts in the pool.
+    pub fn len(&self) -> usize {
+        self.cuts.len()
+    }
+
+    /// Whether the pool is empty.
+    pub fn is_empty(&self) -> bool {
+        self.cuts.is_empty()
+    }
+
+    /// All cuts in the pool.
+    pub fn cuts(&self) -> impl Iterator<Item = &Cut> {
+        self.cuts.iter().map(|(c, _)| c)
+    }
+
+    /// Adds `cut` to the pool unless an equivalent cut is already pooled.
+    /// Returns whether it was added.
+    pub fn add(&mut self, cut: Cut) -> bool {
+        if self.cuts.iter().any(|(c, _)| c.is_parallel_to(&cut)) {
+            return false;
+        }
+        self.cuts.push((cut, false));
+        true
+    }
+
+    /// Selects up to `limit` pooled cuts not yet in the LP whose efficacy at `x`
+    /// is at least `min_efficacy`, most efficacious first, and marks them as
+    /// added.
+    pub fn select(&mut self, x: &[f64], limit: usize, min_efficacy: f64) -> Vec<Cut> {
+        let mut candidates: Vec<(usize, f64)> = self
+            .cuts
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, added))| !added)
+            .map(|(i, (c, _))| (i, c.efficacy(x)))
+            .filter(|&(_, e)| e >= min_efficacy)
+            .collect();
+        candidates.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+        candidates.truncate(limit);
+
+        candidates
+            .into_iter()
+            .map(|(i, _)| {
+                self.cuts[i].1 = true;
+                self.cuts[i].0.clone()
+            })
+            .collect()
+    }
+}
+
+/// A model row in the form `Σ coeffs_j x_j <= rhs`.
+struct Row {
+    coeffs: Vec<f64>,
+    rhs: f64,
+}
+
+/// Dense coefficients of a constraint's left-hand side and its right-hand side
+/// with the constant moved across.
+fn dense_row(model: &LpModel, cons: &LinearConstraint) -> (Vec<f64>, f64) {
+    let mut coeffs = vec![0.0; model.vars().len()];
+    for term in &cons.expr.terms {
+        coeffs[term.var.0] += term.coeff;
+    }
+    (coeffs, cons.rhs - cons.expr.constant)
+}
+
+/// The model constraints as `<=` rows; equality rows give one row each way.
+fn leq_rows(model: &LpModel) -> Vec<Row> {
+    let mut rows = Vec::new();
+    for cons in model.constraints() {
+        let (coeffs, rhs) = dense_row(model, cons);
+        let negated = || Row {
+            coeffs: coeffs.iter().map(|c| -c).collect(),
+            rhs: -rhs,
+        };
+        match cons.cmp {
+            Cmp::LEQ => rows.push(Row { coeffs, rhs }),
+            Cmp::GEQ => rows.push(negated()),
+            Cmp::EQ => {
+                rows.push(negated());
+                rows.push(Row { coeffs, rhs });
+            }
+        }
+    }
+    rows
+}
+
+/// Bounds of variable `j`, rounded inwards for integer variables.
+fn var_bounds(model: &LpModel, j: usize) -> (f64, f64) {
+    let var = &model.vars()[j];
+    let lb = var.lb.unwrap_or(f64::NEG_INFINITY);
+    let ub = var.ub.unwrap_or(f64::INFINITY);
+    if var.is_integer { (lb.ceil(), ub.floor()) } else { (lb, ub) }
+}
+
+/// Fractional part of `v`, in `[0, 1)`.
+fn frac(v: f64) -> f64 {
+    v - v.floor()
+}
+
+/// Whether `v` is within [`FRAC_TOL`] of an integer.
+fn is_integral(v: f64) -> bool {
+    let f = frac(v);
+    !(FRAC_TOL..=1.0 - FRAC_TOL).contains(&f)
+}
+
+/// Derive Gomory mixed-integer cuts from the optimal tableau of `state`.
+///
+/// Each basic integer variable with a fractional value gives the tableau row
+/// `x_k + Σ a_j y_j = x̄_k`, where every non-basic column is written as its
+/// distance `y_j >= 0` from the bound it rests at. The cut
+/// `Σ g_j y_j >= 1` with
+///
+/// - `g_j = f_j / f_0` or `(1 - f_j) / (1 - f_0)` for integer `y_j`,
+///   depending on whether `f_j = frac(a_j)` exceeds `f_0 = frac(x̄_k)`,
+/// - `g_j = a_j / f_0` or `-a_j / (1 - f_0)` for continuous `y_j`, depending
+///   on the sign of `a_j`,
+///
+/// is then expressed in the model variables by substituting each slack with
+/// its row. `model` must be the model `state` was solved from.
+pub fn gomory_cuts<A: Matrix>(
+    state: &PrimalSimplexState<A>,
+    model: &LpModel,
+) -> Result<Vec<Cut>, SolveError> {
+    let layout = state.layout();
+    let n = layout.n_vars;
+    let rows: Vec<(Vec<f64>, f64)> =
+        model.constraints().iter().map(|c| dense_row(model, c)).collect();
+
+    // Whether the distance of column `j` from an integral bound is integer: an
+    // integer variable, or the slack of a row over integer variables with
+    // integer data.
+    let integral_row = |(coeffs, rhs): &(Vec<f64>, f64)| {
+        is_integral(*rhs)
+            && (0..n).all(|j| {
+                coeffs[j] == 0.0 || (model.vars()[j].is_integer && is_integral(coeffs[j]))
+            })
+    };
+    let slack_row: Vec<Option<(usize, f64)>> = (0..layout.n_cols)
+        .map(|j| {
+            layout
+                .slacks
+                .iter()
+                .position(|s| s.is_some_and(|(col, _)| col == j))
+                .map(|r| {
+                    let (_, sign) = layout.slacks[r].unwrap();
+                    (r, sign)
+                })
+        })
+        .collect();
+
+    let mut cuts = Vec::new();
+    for (r, &k) in state.basis.iter().enumerate() {
+        if k >= n || !model.vars()[k].is_integer {
+            continue;
+        }
+        let f0 = frac(state.x_b[r]);
+        if !(FRAC_TOL..=1.0 - FRAC_TOL).contains(&f0) {
+            continue;
+        }
+
+        let alpha = state.tableau_row(r)?;
+        let mut coeffs = vec![0.0; n];
+        let mut constant = 0.0;
+        let mut usable = true;
+
+        for &j in &state.non_basis {
+            let (lower, upper) = (state.lower[j], state.upper[j]);
+            if j >= layout.n_cols || lower == upper || alpha[j].abs() < COEFF_TOL {
+                continue;
+            }
+            // `y_j = x_j - l_j` at the lower bound, `y_j = u_j - x_j` at the upper.
+            let (a, bound, dir) = if state.x_n[j] == lower {
+                (alpha[j], lower, 1.0)
+            } else if state.x_n[j] == upper {
+                (-alpha[j], upper, -1.0)
+            } else {
+                usable = false;
+                break;
+            };
+
+            let integer = match slack_row[j] {
+                Some((row, _)) => integral_row(&rows[row]),
+                None => model.vars()[j].is_integer && is_integral(bound),
+            };
+            let g = if integer {
+                let fj = frac(a);
+                if fj <= f0 { fj / f0 } else { (1.0 - fj) / (1.0 - f0) }
+            } else if a > 0.0 {
+                a / f0
+            } else {
+                -a / (1.0 - f0)
+            };
+
+            // Add `g · y_j` to the left-hand side in terms of the model variables.
+            match slack_row[j] {
+                Some((row, sign)) => {
+                    // `y_j = s = sign · (b_i - a_iᵀ x)`, with `s` at its lower bound 0.
+                    let (ai, bi) = &rows[row];
+                    for (c, a) in coeffs.iter_mut().zip(ai) {
+                        *c -= g * sign * a;
+                    }
+                    constant += g * sign * bi;
+                }
+                None => {
+                    coeffs[j] += g * dir;
+                    constant -= g * dir * bound;
+                }
+            }
+        }
+
+        // `Σ coeffs x + constant >= 1`, i.e. `-Σ coeffs x <= constant - 1`.
+        if usable
+            && let Some(cut) = Cut::new(
+                CutFamily::Gomory,
+                coeffs.iter().map(|c| -c).collect(),
+                constant - 1.0,
+            )
+        {
+            cuts.push(cut);
+        }
+    }
+
+    Ok(cuts)
+}
+
+/// Derive mixed-integer rounding cuts from the model rows, violated by `x`.
+///
+/// Each `<=` row is rewritten over `y_j >= 0` by complementing every variable
+/// with the bound closest to `x`. Continuous terms with a positive coefficient
+/// are dropped and the negative ones collected into `s >= 0`, giving
+/// `Σ a_j y_j - s <= β`. After dividing by `δ`, the MIR inequality
+/// `Σ (⌊a_j⌋ + max(0, f_j - f) / (1 - f)) y_j - s / (1 - f) <= ⌊β⌋` (with
+/// `f = frac(β)`) is valid. The divisors tried are `1` and the coefficients of
+/// integer variables strictly between their bounds; the most efficacious cut
+/// per row is kept.
+pub fn mir_cuts(model: &LpModel, x: &[f64]) -> Vec<Cut> {
+    let n = model.vars().len();
+    let mut cuts = Vec::new();
+
+    'rows: for row in leq_rows(model) {
+        if !(0..n).any(|j| row.coeffs[j] != 0.0 && model.vars()[j].is_integer) {
+            continue;
+        }
+
+        // Complemented form: `a_j y_j` with `y_j = dir · (x_j - bound)`.
+        let mut beta = row.rhs;
+        let mut terms = Vec::new();
+        for j in (0..n).filter(|&j| row.coeffs[j] != 0.0) {
+            let a = row.coeffs[j];
+            let (lb, ub) = var_bounds(model, j);
+            let integer = model.vars()[j].is_integer;
+            let use_lower = if integer {
+                lb.is_finite() && (!ub.is_finite() || x[j] - lb <= ub - x[j])
+            } else {
+                // Prefer the bound that lets the term be dropped.
+                if a > 0.0 { lb.is_finite() } else { !ub.is_finite() }
+            };
+            let (bound, dir) = if use_lower { (lb, 1.0) } else { (ub, -1.0) };
+            if !bound.is_finite() {
+                continue 'rows;
+            }
+            beta -= a * bound;
+            terms.push((j, a * dir, bound, dir, integer));
+        }
+
+        let deltas = std::iter::once(1.0).chain(
+            terms
+                .iter()
+                .filter(|&&(j, a, _, _, integer)| {
+                    let (lb, ub) = var_bounds(model, j);
+                    integer
+                        && a.abs() > COEFF_TOL
+                        && x[j] > lb + FRAC_TOL
+                        && x[j] < ub - FRAC_TOL
+                })
+                .map(|&(_, a, _, _, _)| a.abs()),
+        );
+
+        let best = deltas
+            .filter_map(|delta| {
+                let b = beta / delta;
+                let f = frac(b);
+                if !(FRAC_TOL..=1.0 - FRAC_TOL).contains(&f) {
+                    return None;
+                }
+                let mut coeffs = vec![0.0; n];
+                let mut rhs = b.floor();
+                for &(j, a, bound, dir, integer) in &terms {
+                    let a = a / delta;
+                    let g = if integer {
+                        a.floor() + (frac(a) - f).max(0.0) / (1.0 - f)
+                    } else if a < 0.0 {
+                        a / (1.0 - f)
+                    } else {
+                        continue;
+                    };
+                    // `g · y_j = g · dir · (x_j - bound)`.
+                    coeffs[j] += g * dir;
+                    rhs += g * dir * bound;
+                }
+                Cut::new(CutFamily::Mir, coeffs, rhs)
+            })
+            .max_by(|a, b| a.efficacy(x).partial_cmp(&b.efficacy(x)).unwrap());
+
+        if let Some(cut) = best
+            && cut.violation(x) > FRAC_TOL
+        {
+            cuts.push(cut);
+        }
+    }
+
+    cuts
+}
+
+/// Derive knapsack cover cuts from rows over binary variables, violated by `x`.
+///
+/// Each `<=` row whose variables are all binary is complemented to have
+/// positive weights `w_j` and capacity `β`. A cover `C` with `Σ_C w_j > β` is
+/// built greedily from the items with the largest LP values, then shrunk to a
+/// minimal cover, giving `Σ_C z_j <= |C| - 1` over the (complemented)
+/// variables `z_j`.
+pub fn cover_cuts(model: &LpModel, x: &[f64]) -> Vec<Cut> {
+    let n = model.vars().len();
+    let is_binary =
+        |j: usize| model.vars()[j].is_integer && var_bounds(model, j) == (0.0, 1.0);
+    let mut cuts = Vec::new();
+
+    for row in leq_rows(model) {
+        let items: Vec<usize> = (0..n).filter(|&j| row.coeffs[j] != 0.0).collect();
+        if items.len() < 2 || !items.iter().all(|&j| is_binary(j)) {
+            continue;
+        }
+
+        // `z_j = x_j` for positive weights, `z_j = 1 - x_j` for negative ones.
+        let mut capacity = row.rhs;
+        let mut weighted: Vec<(usize, f64, f64)> = items
+            .iter()
+            .map(|&j| {
+                let a = row.coeffs[j];
+                if a > 0.0 {
+                    (j, a, x[j])
+                } else {
+                    capacity -= a;
+                    (j, -a, 1.0 - x[j])
+                }
+            })
+            .collect();
+        if capacity < 0.0 {
+            continue;
+        }
+
+        weighted.sort_by(|a, b| {
+            ((1.0 - a.2) / a.1).partial_cmp(&((1.0 - b.2) / b.1)).unwrap()
+        });
+        let mut weight = 0.0;
+        let mut cover = Vec::new();
+        for &item in &weighted {
+            if weight > capacity + COEFF_TOL {
+                break;
+            }
+            weight += item.1;
+            cover.push(item);
+        }
+        if weight <= capacity + COEFF_TOL {
+            continue;
+        }
+
+        // Drop the items with the smallest LP values while it stays a cover.
+        cover.sort_by(|a, b| a.2.partial_cmp(&b.2).unwrap());
+        let mut k = 0;
+        while k < cover.len() {
+            if weight - cover[k].1 > capacity + COEFF_TOL {
+                weight -= cover[k].1;
+                cover.remove(k);
+            } else {
+                k += 1;
+            }
+        }
+
+        let mut coeffs = vec![0.0; n];
+        let mut rhs = cover.len() as f64 - 1.0;
+        for &(j, _, _) in &cover {
+            if row.coeffs[j] > 0.0 {
+                coeffs[j] = 1.0;
+            } else {
+                coeffs[j] = -1.0;
+                rhs -= 1.0;
+            }
+        }
+
+        if let Some(cut) = Cut::new(CutFamily::KnapsackCover, coeffs, rhs)
+            && cut.violation(x) > FRAC_TOL
+        {
+            cuts.push(cut);
+        }
+    }
+
+    cuts
+}
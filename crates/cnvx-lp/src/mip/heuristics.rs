@@ -0,0 +1,672 @@
+//! Primal heuristics for the mixed-integer solver.
+//!
+//! Heuristics look for integer-feasible solutions of a model starting from the
+//! solution of an LP relaxation. They give [`MipSolver`] good
+//! incumbents early, which both answers the user sooner and lets the tree search
+//! prune more nodes:
+//!
+//! - [`Rounding`]: Rounds the relaxation in the direction that keeps the rows
+//!   satisfied.
+//! - [`Diving`]: Repeatedly bounds a fractional variable and re-solves the LP.
+//! - [`FeasibilityPump`]: Alternates between roundings and the closest LP
+//!   solutions to them.
+//! - [`Rins`]: Solves a sub-MIP fixing the variables on which the incumbent and
+//!   the relaxation agree.
+//!
+//! Every heuristic implements [`Heuristic`] and can also be run on its own:
+//!
+//! ```rust
+//! # use cnvx_lp::*;
+//! let mut model = LpModel::new();
+//! let x = model.add_var().integer().finish();
+//! let y = model.add_var().integer().finish();
+//! model += (2.0 * x + 2.0 * y).leq(7.0);
+//! model.add_objective(Objective::maximize(x + y).name("z"));
+//!
+//! let relaxation = DualSimplexSolver::new().solve(&model).unwrap();
+//! let values = Rounding::new().find(&model, &relaxation, None).unwrap().unwrap();
+//! assert!(is_feasible(&model, &values, 1e-9));
+//! ```
+
+use cnvx_core::{Sense, SolveError, SolveStatus};
+
+use crate::{
+    Cmp, DualSimplexSolver, LinExpr, LinTerm, LpModel, LpSolution, MipSolver, Objective,
+    Solver, VarId,
+};
+
+/// Values within this distance of an integer are considered integral.
+const INT_TOL: f64 = 1e-6;
+
+/// Tolerance for the bound and row checks of candidate solutions.
+const FEAS_TOL: f64 = 1e-6;
+
+/// A primal heuristic searching for integer-feasible solutions.
+///
+/// [`MipSolver`] calls [`find`](Self::find) on the nodes selected by
+/// [`should_run`](Self::should_run) and checks every returned solution with
+/// [`is_feasible`] before accepting it as an incumbent.
+///
+/// # Examples
+///
+/// ```rust
+/// use cnvx_core::SolveError;
+/// use cnvx_lp::{Heuristic, LpModel, LpSolution};
+///
+/// /// Proposes the all-zero solution.
+/// struct Zero;
+///
+/// impl Heuristic for Zero {
+///     fn name(&self) -> &str { "zero" }
+///
+///     fn find(
+///         &mut self,
+///         model: &LpModel,
+///         _relaxation: &LpSolution,
+///         _incumbent: Option<&[f64]>,
+///     ) -> Result<Option<Vec<f64>>, SolveError> {
+///         Ok(Some(vec![0.0; model.vars().len()]))
+///     }
+/// }
+/// ```
+pub trait Heuristic: Send {
+    /// Returns a human-readable name for this heuristic, reported as the
+    /// [`source`](crate::Incumbent::source) of the incumbents it finds.
+    fn name(&self) -> &str;
+
+    /// Whether to run at the `node`-th node of the search, counting the root as
+    /// `0`. The default runs at the root only.
+    fn should_run(&self, node: usize) -> bool {
+        node == 0
+    }
+
+    /// Search for an integer-feasible solution of `model`.
+    ///
+    /// `relaxation` is an optimal solution of an LP relaxation of `model` (e.g.
+    /// of a node of the search tree), and `incumbent` the best known solution,
+    /// if any. Returns the values of the model variables, indexed by variable
+    /// ID, or `None` if no solution was found.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an LP solved by the heuristic fails.
+    fn find(
+        &mut self,
+        model: &LpModel,
+        relaxation: &LpSolution,
+        incumbent: Option<&[f64]>,
+    ) -> Result<Option<Vec<f64>>, SolveError>;
+}
+
+/// Returns the default set of heuristics used by [`MipSolver`].
+pub fn default_heuristics() -> Vec<Box<dyn Heuristic>> {
+    vec![
+        Box::new(Rounding::new()),
+        Box::new(Diving::new()),
+        Box::new(FeasibilityPump::new()),
+        Box::new(Rins::new()),
+    ]
+}
+
+/// Whether `values` satisfies the variable bounds, the integrality
+/// restrictions and the constraints of `model` within `tol`.
+///
+/// Row violations are measured relative to `max(1, |rhs|)`.
+pub fn is_feasible(model: &LpModel, values: &[f64], tol: f64) -> bool {
+    if values.len() < model.vars().len() {
+        return false;
+    }
+
+    let bounds_ok = model.vars().iter().all(|v| {
+        let x = values[v.id.0];
+        x.is_finite()
+            && v.lb.is_none_or(|lb| x >= lb - tol)
+            && v.ub.is_none_or(|ub| x <= ub + tol)
+            && (!v.is_integer || (x - x.round()).abs() <= tol)
+    });
+
+    bounds_ok
+        && model.constraints().iter().all(|c| {
+            let slack = c.rhs - c.expr.evaluate(values);
+            let tol = tol * c.rhs.abs().max(1.0);
+            match c.cmp {
+                Cmp::LEQ => slack >= -tol,
+                Cmp::GEQ => slack <= tol,
+                Cmp::EQ => slack.abs() <= tol,
+            }
+        })
+}
+
+/// IDs of the integer variables of `model`.
+fn integer_vars(model: &LpModel) -> Vec<usize> {
+    model.vars().iter().filter(|v| v.is_integer).map(|v| v.id.0).collect()
+}
+
+/// Whether `x` is within [`INT_TOL`] of an integer.
+fn is_integral(x: f64) -> bool {
+    (x - x.round()).abs() <= INT_TOL
+}
+
+/// Round `x` to an integer inside the bounds of integer variable `j`.
+fn round_within(model: &LpModel, j: usize, x: f64) -> f64 {
+    let var = &model.vars()[j];
+    let lb = var.lb.map_or(f64::NEG_INFINITY, f64::ceil);
+    let ub = var.ub.map_or(f64::INFINITY, f64::floor);
+    x.round().clamp(lb, ub.max(lb))
+}
+
+/// Objective value of `values` for `model`, in the sense of the model.
+fn objective_value(model: &LpModel, values: &[f64]) -> f64 {
+    model
+        .objective()
+        .map_or(0.0, |o| o.expr.evaluate(values) - o.expr.constant)
+}
+
+/// Complete the integer values in `values` to a feasible solution of `model`.
+///
+/// Fixes every integer variable to its (rounded) value and solves the LP over
+/// the continuous variables with `lp`. Returns `None` if that LP is infeasible.
+fn complete(
+    lp: &mut DualSimplexSolver,
+    model: &LpModel,
+    values: &[f64],
+) -> Result<Option<Vec<f64>>, SolveError> {
+    let integers = integer_vars(model);
+    let mut fixed = values[..model.vars().len()].to_vec();
+    for &j in &integers {
+        fixed[j] = round_within(model, j, fixed[j]);
+    }
+    if is_feasible(model, &fixed, FEAS_TOL) {
+        return Ok(Some(fixed));
+    }
+    if integers.len() == model.vars().len() {
+        return Ok(None);
+    }
+
+    let mut sub = model.clone();
+    for &j in &integers {
+        (sub.vars[j].lb, sub.vars[j].ub) = (Some(fixed[j]), Some(fixed[j]));
+    }
+    let solution = lp.solve(&sub)?;
+    if solution.status != SolveStatus::Optimal {
+        return Ok(None);
+    }
+
+    let mut values = solution.values;
+    values.truncate(model.vars().len());
+    for &j in &integers {
+        values[j] = fixed[j];
+    }
+    Ok(is_feasible(model, &values, FEAS_TOL).then_some(values))
+}
+
+/// Simple rounding of the LP relaxation.
+///
+/// Each fractional integer variable is rounded in a direction in which no row
+/// can become violated (its down- or up-locks), falling back to the nearest
+/// integer when both directions are locked. Continuous variables are then
+/// re-optimised with the integer variables fixed.
+///
+/// Cheap enough to run at every node by default.
+pub struct Rounding {
+    /// Run at every `frequency`-th node; `0` runs at the root only.
+    pub frequency: usize,
+    /// LP solver for the continuous completion.
+    lp: DualSimplexSolver,
+}
+
+impl Rounding {
+    /// Creates a rounding heuristic running at every node.
+    pub fn new() -> Self {
+        Self { frequency: 1, lp: DualSimplexSolver::new() }
+    }
+}
+
+impl Default for Rounding {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Heuristic for Rounding {
+    fn name(&self) -> &str {
+        "rounding"
+    }
+
+    fn should_run(&self, node: usize) -> bool {
+        node == 0 || (self.frequency > 0 && node.is_multiple_of(self.frequency))
+    }
+
+    fn find(
+        &mut self,
+        model: &LpModel,
+        relaxation: &LpSolution,
+        _incumbent: Option<&[f64]>,
+    ) -> Result<Option<Vec<f64>>, SolveError> {
+        let n = model.vars().len();
+        if relaxation.values.len() < n {
+            return Ok(None);
+        }
+
+        // Number of rows that may become violated when a variable decreases
+        // (`0`) or increases (`1`).
+        let mut locks = vec![[0usize; 2]; n];
+        for c in model.constraints() {
+            for t in &c.expr.terms {
+                let lock = &mut locks[t.var.0];
+                match c.cmp {
+                    Cmp::LEQ => lock[usize::from(t.coeff > 0.0)] += 1,
+                    Cmp::GEQ => lock[usize::from(t.coeff < 0.0)] += 1,
+                    Cmp::EQ => *lock = [lock[0] + 1, lock[1] + 1],
+                }
+            }
+        }
+
+        let mut values = relaxation.values[..n].to_vec();
+        for j in integer_vars(model) {
+            let x = values[j];
+            if is_integral(x) {
+                continue;
+            }
+            values[j] = match locks[j] {
+                [0, _] => x.floor(),
+                [_, 0] => x.ceil(),
+                _ => x.round(),
+            };
+        }
+
+        complete(&mut self.lp, model, &values)
+    }
+}
+
+/// Fractional diving.
+///
+/// Starting from the relaxation, repeatedly bounds the least fractional
+/// integer variable towards its nearest integer and re-solves the LP with the
+/// warm-started dual simplex. If the LP becomes infeasible the last bound is
+/// flipped once; a second failure abandons the dive.
+pub struct Diving {
+    /// Run at every `frequency`-th node; `0` runs at the root only.
+    pub frequency: usize,
+    /// Maximum number of bound changes per dive.
+    pub max_depth: usize,
+    /// LP solver for the dive, warm-started between bound changes.
+    lp: DualSimplexSolver,
+}
+
+impl Diving {
+    /// Creates a diving heuristic running at every tenth node.
+    pub fn new() -> Self {
+        Self {
+            frequency: 10,
+            max_depth: 200,
+            lp: DualSimplexSolver::new(),
+        }
+    }
+}
+
+impl Default for Diving {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Heuristic for Diving {
+    fn name(&self) -> &str {
+        "diving"
+    }
+
+    fn should_run(&self, node: usize) -> bool {
+        node == 0 || (self.frequency > 0 && node.is_multiple_of(self.frequency))
+    }
+
+    fn find(
+        &mut self,
+        model: &LpModel,
+        relaxation: &LpSolution,
+        _incumbent: Option<&[f64]>,
+    ) -> Result<Option<Vec<f64>>, SolveError> {
+        let n = model.vars().len();
+        if relaxation.values.len() < n {
+            return Ok(None);
+        }
+        let integers = integer_vars(model);
+        let mut dive = model.clone();
+        let mut values = relaxation.values[..n].to_vec();
+        let mut solved = false;
+
+        for _ in 0..self.max_depth {
+            let Some(j) =
+                integers.iter().copied().filter(|&j| !is_integral(values[j])).min_by(
+                    |&a, &b| {
+                        let dist = |j: usize| (values[j] - values[j].round()).abs();
+                        dist(a).partial_cmp(&dist(b)).unwrap()
+                    },
+                )
+            else {
+                return complete(&mut self.lp, model, &values);
+            };
+
+            let x = values[j];
+            let (lb, ub) = (dive.vars[j].lb, dive.vars[j].ub);
+            let down = (lb, Some(x.floor()));
+            let up = (Some(x.ceil()), ub);
+            let (first, second) =
+                if x - x.floor() < 0.5 { (down, up) } else { (up, down) };
+
+            let mut next = None;
+            for (lb, ub) in [first, second] {
+                (dive.vars[j].lb, dive.vars[j].ub) = (lb, ub);
+                let solution = if solved {
+                    self.lp.resolve(&dive)?
+                } else {
+                    self.lp.solve(&dive)?
+                };
+                solved = true;
+                if solution.status == SolveStatus::Optimal {
+                    next = Some(solution);
+                    break;
+                }
+            }
+            let Some(solution) = next else {
+                return Ok(None);
+            };
+            values = solution.values;
+            values.truncate(n);
+        }
+
+        Ok(None)
+    }
+}
+
+/// The feasibility pump of Fischetti, Glover and Lodi.
+///
+/// Alternates between rounding the integer variables of an LP solution and
+/// solving the LP that minimises the L1 distance of the integer variables to
+/// that rounding. When the rounding stops changing, the variables furthest
+/// from their rounded values are flipped; when it cycles, the rounding is
+/// perturbed at random.
+///
+/// The distance LP keeps its shape between iterations (only the objective
+/// and the right-hand sides of the distance rows change), so each iteration is
+/// warm-started from the previous basis.
+pub struct FeasibilityPump {
+    /// Maximum number of pumping iterations.
+    pub max_iterations: usize,
+    /// Number of variables flipped when the rounding stalls.
+    pub flips: usize,
+    /// Seed of the random perturbation applied when the rounding cycles.
+    pub seed: u64,
+    /// LP solver for the distance problems.
+    lp: DualSimplexSolver,
+}
+
+impl FeasibilityPump {
+    /// Creates a feasibility pump running at the root node.
+    pub fn new() -> Self {
+        Self {
+            max_iterations: 100,
+            flips: 10,
+            seed: 1,
+            lp: DualSimplexSolver::new(),
+        }
+    }
+
+    /// Build the distance LP of `model` to `target`.
+    ///
+    /// Integer variables with both bounds one apart are measured directly by
+    /// their distance to the bound they are rounded to. Every other integer
+    /// variable `j` gets an auxiliary variable `d_j >= |x_j - target_j|` and
+    /// two rows, at positions `rows[j]`, whose right-hand sides carry the target.
+    fn distance_lp(
+        model: &LpModel,
+        integers: &[usize],
+        target: &[f64],
+        rows: &mut [Option<usize>],
+        aux: &mut [Option<VarId>],
+    ) -> LpModel {
+        let mut lp = model.clone();
+        let mut expr = LinExpr::constant(0.0);
+        for &j in integers {
+            let var = &model.vars()[j];
+            let binary =
+                matches!((var.lb, var.ub), (Some(lb), Some(ub)) if ub - lb == 1.0);
+            if binary {
+                let at_lower = Some(target[j]) == var.lb;
+                expr += LinExpr::new(VarId(j), if at_lower { 1.0 } else { -1.0 });
+                continue;
+            }
+
+            let d = lp.add_var().finish();
+            aux[j] = Some(d);
+            rows[j] = Some(lp.constraints().len());
+            lp += (VarId(j) + -1.0 * d).leq(target[j]);
+            lp += (VarId(j) + d).geq(target[j]);
+            expr += d;
+        }
+        lp.add_objective(Objective::minimize(expr).name("distance"));
+        lp
+    }
+
+    /// Move the distance LP to a new `target` without changing its shape.
+    fn retarget(
+        lp: &mut LpModel,
+        integers: &[usize],
+        target: &[f64],
+        rows: &[Option<usize>],
+        aux: &[Option<VarId>],
+    ) {
+        let mut terms = Vec::new();
+        for &j in integers {
+            match (rows[j], aux[j]) {
+                (Some(r), Some(d)) => {
+                    lp.constraints[r].rhs = target[j];
+                    lp.constraints[r + 1].rhs = target[j];
+                    terms.push(LinTerm { var: d, coeff: 1.0 });
+                }
+                _ => {
+                    let at_lower = Some(target[j]) == lp.vars[j].lb;
+                    let coeff = if at_lower { 1.0 } else { -1.0 };
+                    terms.push(LinTerm { var: VarId(j), coeff });
+                }
+            }
+        }
+        if let Some(objective) = lp.objectives.first_mut() {
+            objective.expr = LinExpr { terms, constant: 0.0 };
+        }
+    }
+
+    /// Next value of the xorshift generator used for perturbations, in `[0, 1)`.
+    fn next_random(state: &mut u64) -> f64 {
+        *state ^= *state << 13;
+        *state ^= *state >> 7;
+        *state ^= *state << 17;
+        (*state >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+impl Default for FeasibilityPump {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Heuristic for FeasibilityPump {
+    fn name(&self) -> &str {
+        "feasibility-pump"
+    }
+
+    fn find(
+        &mut self,
+        model: &LpModel,
+        relaxation: &LpSolution,
+        _incumbent: Option<&[f64]>,
+    ) -> Result<Option<Vec<f64>>, SolveError> {
+        let n = model.vars().len();
+        if relaxation.values.len() < n {
+            return Ok(None);
+        }
+        let integers = integer_vars(model);
+        let mut x = relaxation.values[..n].to_vec();
+        let mut target = x.clone();
+        for &j in &integers {
+            target[j] = round_within(model, j, x[j]);
+        }
+
+        let mut rows = vec![None; n];
+        let mut aux = vec![None; n];
+        let mut lp = Self::distance_lp(model, &integers, &target, &mut rows, &mut aux);
+        let mut history: Vec<Vec<f64>> = Vec::new();
+        let mut rng = self.seed.max(1);
+
+        for iteration in 0..self.max_iterations {
+            // The rounding may already be feasible with the current continuous
+            // values, or the LP solution may be integral.
+            let mut candidate = x.clone();
+            for &j in &integers {
+                candidate[j] = target[j];
+            }
+            if is_feasible(model, &candidate, FEAS_TOL) {
+                return Ok(Some(candidate));
+            }
+            if integers.iter().all(|&j| is_integral(x[j]))
+                && let Some(values) = complete(&mut self.lp, model, &x)?
+            {
+                return Ok(Some(values));
+            }
+
+            if iteration > 0 {
+                Self::retarget(&mut lp, &integers, &target, &rows, &aux);
+            }
+            let solution = if iteration == 0 {
+                self.lp.solve(&lp)?
+            } else {
+                self.lp.resolve(&lp)?
+            };
+            if solution.status != SolveStatus::Optimal {
+                return Ok(None);
+            }
+            x = solution.values[..n].to_vec();
+
+            history.push(target.clone());
+            let mut next = target.clone();
+            for &j in &integers {
+                next[j] = round_within(model, j, x[j]);
+            }
+
+            if next == target {
+                // Stalled: flip the variables furthest from their rounding.
+                let mut order: Vec<usize> =
+                    integers.iter().copied().filter(|&j| !is_integral(x[j])).collect();
+                order.sort_by(|&a, &b| {
+                    let dist = |j: usize| (x[j] - target[j]).abs();
+                    dist(b).partial_cmp(&dist(a)).unwrap()
+                });
+                for &j in order.iter().take(self.flips) {
+                    let step = if x[j] > target[j] { 1.0 } else { -1.0 };
+                    next[j] = round_within(model, j, target[j] + step);
+                }
+            }
+            if history.contains(&next) {
+                // Cycling: perturb the rounding at random.
+                for &j in &integers {
+                    let r = Self::next_random(&mut rng) - 0.3;
+                    if (x[j] - next[j]).abs() + r.max(0.0) > 0.5 {
+                        let step = if x[j] > next[j] { 1.0 } else { -1.0 };
+                        next[j] = round_within(model, j, next[j] + step);
+                    }
+                }
+            }
+            target = next;
+        }
+
+        Ok(None)
+    }
+}
+
+/// Relaxation induced neighbourhood search (RINS).
+///
+/// Fixes every integer variable on which the incumbent and the LP relaxation
+/// agree to its incumbent value, and searches the remaining variables with a
+/// small branch-and-bound restricted to solutions that improve on the
+/// incumbent. Requires an incumbent.
+pub struct Rins {
+    /// Run at every `frequency`-th node; `0` runs at the root only.
+    pub frequency: usize,
+    /// Minimum fraction of the integer variables that must be fixed for the
+    /// sub-MIP to be worth solving.
+    pub min_fixed: f64,
+    /// Node limit of the sub-MIP.
+    pub node_limit: usize,
+}
+
+impl Rins {
+    /// Creates a RINS heuristic running at every fiftieth node.
+    pub fn new() -> Self {
+        Self { frequency: 50, min_fixed: 0.3, node_limit: 500 }
+    }
+}
+
+impl Default for Rins {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Heuristic for Rins {
+    fn name(&self) -> &str {
+        "rins"
+    }
+
+    fn should_run(&self, node: usize) -> bool {
+        node == 0 || (self.frequency > 0 && node.is_multiple_of(self.frequency))
+    }
+
+    fn find(
+        &mut self,
+        model: &LpModel,
+        relaxation: &LpSolution,
+        incumbent: Option<&[f64]>,
+    ) -> Result<Option<Vec<f64>>, SolveError> {
+        let n = model.vars().len();
+        let (Some(incumbent), Some(objective)) = (incumbent, model.objective()) else {
+            return Ok(None);
+        };
+        if relaxation.values.len() < n || incumbent.len() < n {
+            return Ok(None);
+        }
+
+        let integers = integer_vars(model);
+        let mut sub = model.clone();
+        let mut fixed = 0;
+        for &j in &integers {
+            if (incumbent[j] - relaxation.values[j]).abs() <= INT_TOL {
+                let v = incumbent[j].round();
+                (sub.vars[j].lb, sub.vars[j].ub) = (Some(v), Some(v));
+                fixed += 1;
+            }
+        }
+        if fixed == integers.len()
+            || (fixed as f64) < self.min_fixed * integers.len() as f64
+        {
+            return Ok(None);
+        }
+
+        // Only solutions improving on the incumbent are of interest.
+        let value = objective_value(model, incumbent);
+        let step = 1e-6 * value.abs().max(1.0);
+        let expr = LinExpr { constant: 0.0, ..objective.expr.clone() };
+        sub += match objective.sense {
+            Sense::Maximize => expr.geq(value + step),
+            Sense::Minimize => expr.leq(value - step),
+        };
+
+        let mut mip = MipSolver::new();
+        mip.node_limit = self.node_limit;
+        mip.cut_rounds = 0;
+        mip.heuristics = vec![Box::new(Rounding::new())];
+        let solution = mip.solve(&sub)?;
+
+        Ok((!solution.values.is_empty()).then_some(solution.values))
+    }
+}
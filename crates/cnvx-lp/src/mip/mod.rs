@@ -0,0 +1,18 @@
+//! Mixed-integer programming on top of the LP solvers.
+//!
+//! Variables marked with [`VarBuilder::integer`](crate::VarBuilder::integer) or
+//! [`VarBuilder::binary`](crate::VarBuilder::binary) are only honoured by the
+//! solvers in this module; the simplex solvers solve the LP relaxation.
+//!
+//! - [`branch_and_bound`]: Contains the [`MipSolver`] struct, a branch-and-bound
+//!   search over LP relaxations solved with the dual simplex.
+//! - [`cuts`]: Cutting planes tightening the root relaxation.
+//! - [`heuristics`]: Primal heuristics finding incumbents early.
+
+pub mod branch_and_bound;
+pub mod cuts;
+pub mod heuristics;
+
+pub use branch_and_bound::*;
+pub use cuts::{Cut, CutFamily, CutPool, CutStats};
+pub use heuristics::{Diving, FeasibilityPump, Heuristic, Rins, Rounding, is_feasible};
@@ -0,0 +1,413 @@
+//! Pareto frontiers of models with several objectives.
+//!
+//! Where [`solve_lexicographic`] ranks the objectives of a model,
+//! [`pareto_frontier`] traces the trade-off between them: it solves a sequence
+//! of single-objective problems built from the objectives and returns the
+//! Pareto-optimal solutions found, those that cannot improve in one objective
+//! without getting worse in another.
+//!
+//! Every method starts from the payoff table: each objective is optimized
+//! lexicographically ahead of the others, which gives the extreme points of the
+//! frontier, its ideal point (the best value of each objective) and an estimate
+//! of its nadir point (the worst value of each objective on the frontier). The
+//! objectives are normalised by the ranges between the two before they are
+//! combined.
+
+use std::collections::VecDeque;
+
+use cnvx_core::{Sense, SolveError, SolveStatus};
+
+use crate::{LinExpr, LpModel, LpSolution, Objective, Solver, solve_lexicographic};
+
+/// Frontier points whose normalised objective values all differ by at most
+/// this much are the same point.
+const SAME_POINT_TOL: f64 = 1e-6;
+
+/// Epsilon-constraint bounds are loosened by this fraction of `1 + |bound|`,
+/// so that roundoff in the ideal point cannot make them infeasible.
+const BOUND_SLACK: f64 = 1e-7;
+
+/// Objectives whose range on the frontier is at most this fraction of
+/// `1 + |ideal|` are treated as constant.
+const FLAT_RANGE_TOL: f64 = 1e-9;
+
+/// How [`pareto_frontier`] chooses the problems it solves.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ParetoMethod {
+    /// Optimize weighted sums of the normalised objectives, with every
+    /// combination of positive weights that are multiples of `1 / steps` and
+    /// sum to one.
+    ///
+    /// Every point found is a vertex of the frontier, so several weights may
+    /// give the same point.
+    WeightedSum {
+        /// Number of divisions of the weights.
+        steps: usize,
+    },
+
+    /// Optimize the first objective with each of the others held at least as
+    /// good as a level, for every combination of levels that divide its range
+    /// into `steps` equal parts.
+    ///
+    /// Ties in the first objective are broken by the sum of the others, so
+    /// every point found is Pareto-optimal. Unlike
+    /// [`WeightedSum`](Self::WeightedSum), the points can lie inside faces of
+    /// the frontier.
+    EpsilonConstraint {
+        /// Number of divisions of the range of each constrained objective.
+        steps: usize,
+    },
+
+    /// Two objectives only: repeatedly split the segment between two adjacent
+    /// points of the frontier by optimizing the weighted sum whose weights are
+    /// normal to it, until no point lies beyond any segment by more than
+    /// `tolerance` or the frontier has `max_points` points.
+    ///
+    /// With a tolerance of zero this finds every vertex of the frontier of an
+    /// LP, with one solve per vertex and segment.
+    Bisection {
+        /// Largest number of points on the frontier.
+        max_points: usize,
+        /// Smallest improvement over a segment, in normalised objective units,
+        /// that adds a point.
+        tolerance: f64,
+    },
+}
+
+/// The Pareto-optimal solutions found by [`pareto_frontier`].
+#[derive(Debug)]
+pub struct ParetoFrontier {
+    /// Pareto-optimal solutions, from best to worst value of the first
+    /// objective.
+    ///
+    /// Each reports the value of every objective in
+    /// [`objective_values`](LpSolution::objective_values), and that of the first
+    /// in [`objective_value`](LpSolution::objective_value).
+    pub points: Vec<LpSolution>,
+
+    /// Number of objectives.
+    pub num_objectives: usize,
+
+    /// [`SolveStatus::Optimal`] if the frontier was traced, or the status of
+    /// the objective that prevented it, e.g. [`SolveStatus::Unbounded`]. The
+    /// frontier has no points unless it is optimal.
+    pub status: SolveStatus,
+}
+
+impl ParetoFrontier {
+    /// All points of the frontier.
+    pub fn points(&self) -> &[LpSolution] {
+        &self.points
+    }
+
+    /// Whether the frontier has no points.
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+}
+
+/// Trace the Pareto frontier of the objectives of `model` with `solver`.
+///
+/// The objectives are taken in the order of [`LpModel::objectives`]; their
+/// priorities and degradations play no other part. Integrality is left to
+/// `solver`, but the methods are designed for LPs: on a model with integer
+/// variables, weighted sums and bisection only find the supported points of
+/// the frontier.
+///
+/// # Examples
+///
+/// ```rust
+/// # use cnvx_core::SolveStatus;
+/// # use cnvx_lp::*;
+/// let mut model = LpModel::new();
+/// let x = model.add_var().name("x").finish();
+/// let y = model.add_var().name("y").finish();
+/// model += (x + y).leq(10.0).named("capacity");
+/// model += (x + 3.0 * y).leq(24.0).named("labour");
+/// model.add_objective(Objective::maximize(1.0 * x).priority(2).name("profit"));
+/// model.add_objective(Objective::maximize(1.0 * y).priority(1).name("quality"));
+///
+/// let method = ParetoMethod::Bisection { max_points: 10, tolerance: 1e-9 };
+/// let frontier = pareto_frontier(&mut LpSolver::new(), &model, method).unwrap();
+/// assert_eq!(frontier.status, SolveStatus::Optimal);
+///
+/// // The vertices (10, 0), (3, 7) and (0, 8), from best to worst profit.
+/// let values: Vec<_> = frontier.points().iter().map(|p| &p.objective_values).collect();
+/// assert_eq!(values.len(), 3);
+/// for (value, expected) in values.iter().zip([[10.0, 0.0], [3.0, 7.0], [0.0, 8.0]]) {
+///     assert!((value[0] - expected[0]).abs() < 1e-6);
+///     assert!((value[1] - expected[1]).abs() < 1e-6);
+/// }
+///
+/// // Five quality levels, from 8 down to 0, with the epsilon-constraint method.
+/// let method = ParetoMethod::EpsilonConstraint { steps: 4 };
+/// let frontier = pareto_frontier(&mut LpSolver::new(), &model, method).unwrap();
+/// assert_eq!(frontier.points().len(), 5);
+/// ```
+///
+/// # Errors
+///
+/// Returns [`SolveError::InvalidModel`] if `model` has fewer than two
+/// objectives, [`SolveError::Unsupported`] for
+/// [`Bisection`](ParetoMethod::Bisection) of more than two objectives or for
+/// quadratic objectives, and any error of `solver`.
+pub fn pareto_frontier(
+    solver: &mut dyn Solver,
+    model: &LpModel,
+    method: ParetoMethod,
+) -> Result<ParetoFrontier, SolveError> {
+    let n = model.objectives().len();
+    if n < 2 {
+        return Err(SolveError::InvalidModel(
+            "a Pareto frontier needs at least two objectives".into(),
+        ));
+    }
+    if matches!(method, ParetoMethod::Bisection { .. }) && n != 2 {
+        return Err(SolveError::Unsupported(format!(
+            "bisection traces the frontier of two objectives, not {n}"
+        )));
+    }
+    if model.objectives().iter().any(|o| o.is_quadratic()) {
+        return Err(SolveError::Unsupported(
+            "Pareto frontiers of quadratic objectives are not supported".into(),
+        ));
+    }
+
+    let mut frontier = Frontier::new(model);
+
+    // Payoff table.
+    let mut payoff = Vec::with_capacity(n);
+    for i in 0..n {
+        let mut lex = model.clone();
+        lex.objectives = std::iter::once(i)
+            .chain((0..n).filter(|&j| j != i))
+            .map(|j| Objective { degradation: None, ..model.objectives()[j].clone() })
+            .collect();
+        let solution = solve_lexicographic(solver, &lex)?;
+        if solution.status != SolveStatus::Optimal {
+            return Ok(ParetoFrontier {
+                points: Vec::new(),
+                num_objectives: n,
+                status: solution.status,
+            });
+        }
+        payoff.push(frontier.costs(&solution.values));
+        frontier.add(solution);
+    }
+    frontier.normalise(&payoff);
+
+    match method {
+        ParetoMethod::WeightedSum { steps } => {
+            for parts in compositions(steps, n) {
+                let weights = parts.iter().map(|&p| p as f64 / steps as f64);
+                let objective = frontier.weighted(&weights.collect::<Vec<_>>());
+                let solution = frontier.solve(solver, objective)?;
+                if solution.status == SolveStatus::Optimal {
+                    frontier.add(solution);
+                }
+            }
+        }
+        ParetoMethod::EpsilonConstraint { steps } => {
+            let steps = steps.max(1);
+            let mut tie_break = vec![0.0; n];
+            tie_break[1..].fill(1.0);
+            for mut k in 0..(steps + 1).pow(n as u32 - 1) {
+                let mut eps = model.clone();
+                for i in 1..n {
+                    let t = (k % (steps + 1)) as f64 / steps as f64;
+                    k /= steps + 1;
+                    let bound = frontier.ideal[i] + t * frontier.range[i];
+                    let slack = BOUND_SLACK * (1.0 + bound.abs());
+                    eps += frontier.minimised(i).leq(bound + slack);
+                }
+                eps.objectives = vec![
+                    Objective::minimize(frontier.minimised(0)).name("first"),
+                    Objective::minimize(frontier.weighted(&tie_break)).name("others"),
+                ];
+                let solution = solve_lexicographic(solver, &eps)?;
+                if solution.status == SolveStatus::Optimal {
+                    frontier.add(solution);
+                }
+            }
+        }
+        ParetoMethod::Bisection { max_points, tolerance } => {
+            // The extremes coincide if the objectives do not conflict.
+            let mut segments = VecDeque::new();
+            if frontier.points.len() == 2 {
+                segments.push_back((0, 1));
+            }
+            while let Some((a, b)) = segments.pop_front() {
+                if frontier.points.len() >= max_points {
+                    break;
+                }
+                let ua = frontier.normalised(&frontier.points[a].1);
+                let ub = frontier.normalised(&frontier.points[b].1);
+                let weights = [(ua[1] - ub[1]).abs(), (ub[0] - ua[0]).abs()];
+                let norm = weights[0].hypot(weights[1]);
+                if norm <= SAME_POINT_TOL {
+                    continue;
+                }
+                let solution = frontier.solve(solver, frontier.weighted(&weights))?;
+                if solution.status != SolveStatus::Optimal {
+                    continue;
+                }
+                let uc = frontier.normalised(&frontier.costs(&solution.values));
+                let gain = weights[0] * (ua[0] - uc[0]) + weights[1] * (ua[1] - uc[1]);
+                if gain > tolerance.max(SAME_POINT_TOL) * norm
+                    && let Some(c) = frontier.add(solution)
+                {
+                    segments.push_back((a, c));
+                    segments.push_back((c, b));
+                }
+            }
+        }
+    }
+
+    Ok(frontier.finish())
+}
+
+/// The points of a frontier being traced, and the objectives in minimisation
+/// form.
+struct Frontier<'a> {
+    model: &'a LpModel,
+    /// `1` for minimised objectives, `-1` for maximised ones.
+    sign: Vec<f64>,
+    /// Best value of each minimisation-form objective on the frontier.
+    ideal: Vec<f64>,
+    /// Range of each minimisation-form objective on the frontier.
+    range: Vec<f64>,
+    /// Points found, with their minimisation-form objective values.
+    points: Vec<(LpSolution, Vec<f64>)>,
+}
+
+impl<'a> Frontier<'a> {
+    fn new(model: &'a LpModel) -> Self {
+        let n = model.objectives().len();
+        let sign = model
+            .objectives()
+            .iter()
+            .map(|o| if o.sense == Sense::Minimize { 1.0 } else { -1.0 })
+            .collect();
+        Self {
+            model,
+            sign,
+            ideal: vec![0.0; n],
+            range: vec![1.0; n],
+            points: Vec::new(),
+        }
+    }
+
+    /// Objective `i` in minimisation form.
+    fn minimised(&self, i: usize) -> LinExpr {
+        self.model.objectives()[i].expr.clone() * self.sign[i]
+    }
+
+    /// The sum of the normalised minimisation-form objectives with `weights`.
+    fn weighted(&self, weights: &[f64]) -> LinExpr {
+        let mut expr = LinExpr::constant(0.0);
+        for (i, &w) in weights.iter().enumerate() {
+            if w != 0.0 {
+                expr += self.minimised(i) * (w / self.range[i]);
+            }
+        }
+        expr
+    }
+
+    /// Minimisation-form objective values at `values`.
+    fn costs(&self, values: &[f64]) -> Vec<f64> {
+        (0..self.sign.len())
+            .map(|i| self.minimised(i).evaluate(values))
+            .collect()
+    }
+
+    /// Normalised values of minimisation-form objective values `costs`.
+    fn normalised(&self, costs: &[f64]) -> Vec<f64> {
+        (0..costs.len())
+            .map(|i| (costs[i] - self.ideal[i]) / self.range[i])
+            .collect()
+    }
+
+    /// Set the ideal point and ranges from the `payoff` table.
+    fn normalise(&mut self, payoff: &[Vec<f64>]) {
+        for i in 0..self.sign.len() {
+            let ideal = payoff[i][i];
+            let nadir = payoff.iter().fold(ideal, |m, p| m.max(p[i]));
+            self.ideal[i] = ideal;
+            self.range[i] = if nadir - ideal > FLAT_RANGE_TOL * (1.0 + ideal.abs()) {
+                nadir - ideal
+            } else {
+                1.0
+            };
+        }
+    }
+
+    /// Minimize `objective` over the constraints of the model.
+    ///
+    /// Only the objective differs from the problems solved before, so `solver`
+    /// re-solves from the state they left.
+    fn solve(
+        &self,
+        solver: &mut dyn Solver,
+        objective: LinExpr,
+    ) -> Result<LpSolution, SolveError> {
+        let mut scalarised = self.model.clone();
+        scalarised.objectives = vec![Objective::minimize(objective).name("pareto")];
+        solver.resolve(&scalarised)
+    }
+
+    /// Add `solution` to the frontier, unless it is already there, and return
+    /// its index.
+    fn add(&mut self, solution: LpSolution) -> Option<usize> {
+        let costs = self.costs(&solution.values);
+        let same = |other: &Vec<f64>| {
+            (0..costs.len())
+                .all(|i| (costs[i] - other[i]).abs() <= SAME_POINT_TOL * self.range[i])
+        };
+        if self.points.iter().any(|(_, other)| same(other)) {
+            return None;
+        }
+        self.points.push((solution, costs));
+        Some(self.points.len() - 1)
+    }
+
+    /// The traced frontier, with the solutions mapped back to the model.
+    fn finish(mut self) -> ParetoFrontier {
+        self.points.sort_by(|a, b| a.1[0].total_cmp(&b.1[0]));
+        let model = self.model;
+        let points = self
+            .points
+            .into_iter()
+            .map(|(mut solution, _)| {
+                solution.objective_values = model
+                    .objectives()
+                    .iter()
+                    .map(|o| o.expr.evaluate(&solution.values))
+                    .collect();
+                solution.objective_value = solution.objective_values.first().copied();
+                solution.duals.clear();
+                solution.reduced_costs.clear();
+                solution.with_activities(model)
+            })
+            .collect();
+        ParetoFrontier {
+            points,
+            num_objectives: model.objectives().len(),
+            status: SolveStatus::Optimal,
+        }
+    }
+}
+
+/// All ways of writing `total` as an ordered sum of `parts` positive integers.
+fn compositions(total: usize, parts: usize) -> Vec<Vec<usize>> {
+    if parts == 1 {
+        return if total >= 1 { vec![vec![total]] } else { Vec::new() };
+    }
+    let mut all = Vec::new();
+    for first in 1..total {
+        for mut rest in compositions(total - first, parts - 1) {
+            rest.insert(0, first);
+            all.push(rest);
+        }
+    }
+    all
+}
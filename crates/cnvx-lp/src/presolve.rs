@@ -0,0 +1,962 @@
+//! Presolve and postsolve for linear programs.
+//!
+//! [`presolve`] removes rows and columns from an [`LpModel`] that can be
+//! decided without solving it: empty and singleton rows and columns, fixed
+//! variables, forcing, redundant and parallel rows, dominated and duplicate
+//! columns. It also tightens variable bounds using the row activities. Each
+//! reduction is recorded, so that [`Presolved::postsolve`] can map a solution
+//! of the reduced model, including its duals and reduced costs, back onto the
+//! variables and constraints of the original model.
+//!
+//! Internally every objective is treated as a maximisation, and every
+//! constraint as a range `lo <= a x <= hi`.
+
+use std::collections::HashMap;
+
+use cnvx_core::{Sense, SolveError, SolveStatus};
+
+use crate::{
+    Cmp, LinExpr, LinTerm, LinearConstraint, LpModel, LpSolution, Objective, Var, VarId,
+};
+
+/// Tolerance on the constraint violation of a reduction.
+const FEAS_TOL: f64 = 1e-9;
+
+/// Tolerance on reduced costs and on comparing coefficients.
+const DUAL_TOL: f64 = 1e-9;
+
+/// Bounds derived from the row activities are only applied if they improve
+/// the current bound by at least this relative amount.
+const MIN_IMPROVEMENT: f64 = 1e-6;
+
+/// Derived bounds of at least this magnitude are not applied.
+const MAX_BOUND: f64 = 1e10;
+
+/// A column singleton is only substituted out if its coefficient is at least
+/// this fraction of the largest coefficient in its row.
+const MIN_PIVOT_RATIO: f64 = 1e-2;
+
+/// Maximum number of passes over the model.
+const MAX_PASSES: usize = 20;
+
+/// Counts of the reductions made by [`presolve`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PresolveStats {
+    /// Number of constraints removed from the model.
+    pub removed_rows: usize,
+    /// Number of variables removed from the model.
+    pub removed_cols: usize,
+    /// Number of variables removed by fixing them at a value.
+    pub fixed_cols: usize,
+    /// Number of variable bounds tightened using the constraints.
+    pub tightened_bounds: usize,
+    /// Number of passes made over the model.
+    pub passes: usize,
+}
+
+/// The result of [`presolve`]: a reduced model and the reductions that lead to
+/// it.
+///
+/// # Examples
+///
+/// ```rust
+/// # use cnvx_core::SolveStatus;
+/// # use cnvx_lp::*;
+/// # use cnvx_lp::presolve::presolve;
+/// let mut model = LpModel::new();
+/// let x = model.add_var().finish();
+/// let y = model.add_var().finish();
+/// model += (x + y).leq(4.0);
+/// model += x.leq(3.0);
+/// model += (2.0 * x + 2.0 * y).leq(10.0); // implied by the first row
+/// model.add_objective(Objective::maximize(3.0 * x + 2.0 * y).name("profit"));
+///
+/// let presolved = presolve(&model).unwrap();
+/// assert_eq!(presolved.status, SolveStatus::NotSolved);
+/// assert!(presolved.model.constraints.len() < model.constraints.len());
+///
+/// let reduced = PrimalSimplexSolver::new().solve(&presolved.model).unwrap();
+/// let solution = presolved.postsolve(&reduced);
+/// assert!((solution.objective_value.unwrap() - 11.0).abs() < 1e-9);
+/// assert!((solution.value(x) - 3.0).abs() < 1e-9);
+/// assert!((solution.dual(0).unwrap() - 2.0).abs() < 1e-9);
+/// assert!((solution.dual(1).unwrap() - 1.0).abs() < 1e-9);
+/// assert_eq!(solution.dual(2), Some(0.0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Presolved {
+    /// The reduced model, with its own [`VarId`]s.
+    pub model: LpModel,
+
+    /// [`SolveStatus::Infeasible`] if presolve proved the model infeasible,
+    /// [`SolveStatus::Unbounded`] if it proved the model unbounded provided it
+    /// is feasible, and [`SolveStatus::NotSolved`] otherwise.
+    pub status: SolveStatus,
+
+    /// Counts of the reductions made.
+    pub stats: PresolveStats,
+
+    /// The original model.
+    original: LpModel,
+    /// Original variable index of each variable of the reduced model.
+    col_map: Vec<usize>,
+    /// Original constraint index of each constraint of the reduced model.
+    row_map: Vec<usize>,
+    /// Coefficients `(row, a)` of each original column.
+    cols: Vec<Vec<(usize, f64)>>,
+    /// `1.0` for a maximisation and `-1.0` for a minimisation.
+    sign: f64,
+    /// Reductions in the order they were made.
+    stack: Vec<Reduction>,
+}
+
+/// A reduction, with the information needed to undo it.
+///
+/// Costs are stored as they were when the reduction was made, in maximisation
+/// form, so that the reduced cost of the column at that point can be recomputed.
+#[derive(Debug, Clone)]
+enum Reduction {
+    /// `col` was fixed at `value` and removed.
+    Fix { col: usize, value: f64 },
+
+    /// The bounds of `col` were tightened to `lower` and/or `upper` using its
+    /// coefficient `a` in `row`.
+    Bound {
+        row: usize,
+        col: usize,
+        a: f64,
+        lower: Option<f64>,
+        upper: Option<f64>,
+        cost: f64,
+    },
+
+    /// `row` forced each of `cols` to the bound minimising (`upper`) or
+    /// maximising its activity.
+    Forcing { row: usize, upper: bool, cols: Vec<(usize, f64, f64)> },
+
+    /// The (implied) free column singleton `col` was removed with `row`, which
+    /// keeps `lo <= a x_col + Σ others <= hi` satisfied.
+    FreeColumn {
+        row: usize,
+        col: usize,
+        a: f64,
+        lo: f64,
+        hi: f64,
+        others: Vec<(usize, f64)>,
+        cost: f64,
+    },
+
+    /// `removed` was `ratio` times `kept` and was merged into it. `lower` and
+    /// `upper` tell which sides of the range of `kept` came from `removed`.
+    ParallelRow { kept: usize, removed: usize, ratio: f64, lower: bool, upper: bool },
+
+    /// Column `removed` was `ratio` times column `kept`, which now stands for
+    /// `x_kept + ratio x_removed`. `bounds` are the bounds of both before.
+    DuplicateColumn { kept: usize, removed: usize, ratio: f64, bounds: [(f64, f64); 2] },
+}
+
+/// Presolve `model`.
+///
+/// The reduced model is returned in [`Presolved::model`] and can be solved by
+/// any solver; [`Presolved::postsolve`] maps its solution back. If presolve
+/// decides the model on its own, [`Presolved::status`] says so.
+///
+/// # Errors
+///
+/// Returns the errors of [`check_lp`](crate::validate::check_lp).
+pub fn presolve(model: &LpModel) -> Result<Presolved, SolveError> {
+    crate::validate::check_lp(model)?;
+
+    let mut presolver = Presolver::new(model);
+    presolver.run();
+    Ok(presolver.finish(model))
+}
+
+impl Presolved {
+    /// Map a solution of the reduced [`model`](Self::model) back onto the
+    /// original model.
+    ///
+    /// Values are restored for every original variable. If `reduced` is optimal
+    /// and carries duals, the shadow price of every original constraint and the
+    /// reduced cost of every original variable are restored too. The objective
+    /// value is recomputed on the original model, without the constant of its
+    /// objective, and [`basis_status`](LpSolution::basis_status) is left empty.
+    pub fn postsolve(&self, reduced: &LpSolution) -> LpSolution {
+        let status = match (&self.status, &reduced.status) {
+            (SolveStatus::Infeasible, _) | (_, SolveStatus::Infeasible) => {
+                SolveStatus::Infeasible
+            }
+            (SolveStatus::Unbounded, _) => SolveStatus::Unbounded,
+            (_, status) => status.clone(),
+        };
+
+        let mut x = vec![0.0; self.original.vars.len()];
+        for (k, &j) in self.col_map.iter().enumerate() {
+            x[j] = reduced.values.get(k).copied().unwrap_or(0.0);
+        }
+
+        let with_duals =
+            status == SolveStatus::Optimal && reduced.duals.len() == self.row_map.len();
+        let mut y = vec![0.0; self.original.constraints.len()];
+        if with_duals {
+            for (r, &i) in self.row_map.iter().enumerate() {
+                y[i] += self.sign * reduced.duals[r];
+            }
+        }
+
+        for reduction in self.stack.iter().rev() {
+            self.undo(reduction, &mut x, &mut y);
+        }
+
+        let objective = self.original.objective().expect("checked by presolve");
+        let objective_value = reduced
+            .objective_value
+            .map(|_| objective.expr.evaluate(&x) - objective.expr.constant);
+
+        let mut solution = LpSolution::new(x, objective_value, status);
+        if with_duals {
+            let cost = costs(&self.original);
+            solution.reduced_costs = (0..cost.len())
+                .map(|j| self.sign * self.reduced_cost(&y, j, self.sign * cost[j]))
+                .collect();
+            solution.duals = y.iter().map(|&yi| self.sign * yi).collect();
+        }
+        solution.with_activities(&self.original)
+    }
+
+    /// Undo `reduction`, restoring the values `x` and the maximisation-form
+    /// duals `y` it removed.
+    fn undo(&self, reduction: &Reduction, x: &mut [f64], y: &mut [f64]) {
+        match *reduction {
+            Reduction::Fix { col, value } => x[col] = value,
+            Reduction::Bound { row, col, a, lower, upper, cost } => {
+                let rc = self.reduced_cost(y, col, cost);
+                let at = |bound: Option<f64>| {
+                    bound
+                        .is_some_and(|b| (x[col] - b).abs() <= FEAS_TOL * (1.0 + b.abs()))
+                };
+                if (rc < -DUAL_TOL && at(lower)) || (rc > DUAL_TOL && at(upper)) {
+                    y[row] += rc / a;
+                }
+            }
+            Reduction::Forcing { row, upper, ref cols } => {
+                let ratios = cols.iter().map(|&(j, a, c)| self.reduced_cost(y, j, c) / a);
+                y[row] = if upper {
+                    ratios.fold(0.0, f64::max)
+                } else {
+                    ratios.fold(0.0, f64::min)
+                };
+            }
+            Reduction::FreeColumn { row, col, a, lo, hi, ref others, cost } => {
+                let rest: f64 = others.iter().map(|&(k, ak)| ak * x[k]).sum();
+                x[col] = (rest.max(lo).min(hi) - rest) / a;
+                y[row] = self.reduced_cost(y, col, cost) / a;
+            }
+            Reduction::ParallelRow { kept, removed, ratio, lower, upper } => {
+                if (y[kept] > 0.0 && upper) || (y[kept] < 0.0 && lower) {
+                    y[removed] = y[kept] / ratio;
+                    y[kept] = 0.0;
+                }
+            }
+            Reduction::DuplicateColumn { kept, removed, ratio, bounds } => {
+                let [(lb, ub), (lb_r, ub_r)] = bounds;
+                let z = x[kept];
+                // Choose x_removed as close to zero as the bounds of both allow.
+                let (lo, hi) = if ratio > 0.0 {
+                    ((z - ub) / ratio, (z - lb) / ratio)
+                } else {
+                    ((z - lb) / ratio, (z - ub) / ratio)
+                };
+                let value = 0.0f64.max(lo.max(lb_r)).min(hi.min(ub_r));
+                x[removed] = value;
+                x[kept] = z - ratio * value;
+            }
+        }
+    }
+
+    /// Reduced cost `cost - Σ_i y_i a_ij` of original column `col`.
+    fn reduced_cost(&self, y: &[f64], col: usize, cost: f64) -> f64 {
+        cost - self.cols[col].iter().map(|&(i, a)| y[i] * a).sum::<f64>()
+    }
+}
+
+/// Objective coefficient of every variable of `model`.
+fn costs(model: &LpModel) -> Vec<f64> {
+    let mut cost = vec![0.0; model.vars.len()];
+    if let Some(objective) = model.objective() {
+        for term in &objective.expr.terms {
+            cost[term.var.0] += term.coeff;
+        }
+    }
+    cost
+}
+
+/// Lower and upper bound of the activity of a row, each as a finite part and
+/// a count of infinite contributions.
+#[derive(Debug, Clone, Copy, Default)]
+struct Activity {
+    min: f64,
+    min_inf: usize,
+    max: f64,
+    max_inf: usize,
+}
+
+impl Activity {
+    /// The lower bound, or `-∞`.
+    fn min(&self) -> f64 {
+        if self.min_inf == 0 { self.min } else { f64::NEG_INFINITY }
+    }
+
+    /// The upper bound, or `+∞`.
+    fn max(&self) -> f64 {
+        if self.max_inf == 0 { self.max } else { f64::INFINITY }
+    }
+
+    /// The lower bound without the contribution `contrib` of one column.
+    fn min_without(&self, contrib: f64) -> f64 {
+        match (self.min_inf, contrib.is_finite()) {
+            (0, _) => self.min - contrib,
+            (1, false) => self.min,
+            _ => f64::NEG_INFINITY,
+        }
+    }
+
+    /// The upper bound without the contribution `contrib` of one column.
+    fn max_without(&self, contrib: f64) -> f64 {
+        match (self.max_inf, contrib.is_finite()) {
+            (0, _) => self.max - contrib,
+            (1, false) => self.max,
+            _ => f64::INFINITY,
+        }
+    }
+}
+
+/// Whether `a` and `b` agree up to a relative tolerance.
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() <= DUAL_TOL * (1.0 + a.abs().max(b.abs()))
+}
+
+/// The working form of a model during presolve.
+struct Presolver {
+    /// Coefficients `(col, a)` of each row, in column order.
+    rows: Vec<Vec<(usize, f64)>>,
+    /// Coefficients `(row, a)` of each column.
+    cols: Vec<Vec<(usize, f64)>>,
+    lo: Vec<f64>,
+    hi: Vec<f64>,
+    lb: Vec<f64>,
+    ub: Vec<f64>,
+    /// Maximisation-form cost of each column.
+    cost: Vec<f64>,
+    integer: Vec<bool>,
+    row_alive: Vec<bool>,
+    col_alive: Vec<bool>,
+    /// Columns of the original model, kept for postsolve.
+    original_cols: Vec<Vec<(usize, f64)>>,
+    sign: f64,
+    stack: Vec<Reduction>,
+    stats: PresolveStats,
+    /// Number of changes made to the model, to detect a fixpoint.
+    changes: usize,
+    infeasible: bool,
+    unbounded: bool,
+}
+
+impl Presolver {
+    fn new(model: &LpModel) -> Self {
+        let (m, n) = model.shape();
+        let objective = model.objective().expect("checked by check_lp");
+        let sign = match objective.sense {
+            Sense::Maximize => 1.0,
+            Sense::Minimize => -1.0,
+        };
+
+        let mut rows = Vec::with_capacity(m);
+        let mut cols = vec![Vec::new(); n];
+        let (mut lo, mut hi) = (Vec::with_capacity(m), Vec::with_capacity(m));
+        for (i, cons) in model.constraints().iter().enumerate() {
+            let mut row: Vec<(usize, f64)> =
+                cons.expr.terms.iter().map(|t| (t.var.0, t.coeff)).collect();
+            row.sort_unstable_by_key(|&(j, _)| j);
+            row.dedup_by(|next, kept| {
+                let same = next.0 == kept.0;
+                if same {
+                    kept.1 += next.1;
+                }
+                same
+            });
+            row.retain(|&(_, a)| a != 0.0);
+            for &(j, a) in &row {
+                cols[j].push((i, a));
+            }
+            rows.push(row);
+
+            let rhs = cons.rhs - cons.expr.constant;
+            let (l, h) = match cons.cmp {
+                Cmp::EQ => (rhs, rhs),
+                Cmp::LEQ => (f64::NEG_INFINITY, rhs),
+                Cmp::GEQ => (rhs, f64::INFINITY),
+            };
+            lo.push(l);
+            hi.push(h);
+        }
+
+        let vars = model.vars();
+        Self {
+            original_cols: cols.clone(),
+            rows,
+            cols,
+            lo,
+            hi,
+            lb: vars.iter().map(|v| v.lb.unwrap_or(f64::NEG_INFINITY)).collect(),
+            ub: vars.iter().map(|v| v.ub.unwrap_or(f64::INFINITY)).collect(),
+            cost: costs(model).into_iter().map(|c| sign * c).collect(),
+            integer: vars.iter().map(|v| v.is_integer).collect(),
+            row_alive: vec![true; m],
+            col_alive: vec![true; n],
+            sign,
+            stack: Vec::new(),
+            stats: PresolveStats::default(),
+            changes: 0,
+            infeasible: false,
+            unbounded: false,
+        }
+    }
+
+    /// Apply the reductions until none applies, the model is found infeasible,
+    /// or [`MAX_PASSES`] is reached.
+    fn run(&mut self) {
+        while self.stats.passes < MAX_PASSES && !self.infeasible {
+            self.stats.passes += 1;
+            let changes = self.changes;
+            self.reduce_rows();
+            self.reduce_cols();
+            self.tighten_bounds();
+            self.parallel_rows();
+            self.duplicate_cols();
+            if self.changes == changes {
+                break;
+            }
+        }
+    }
+
+    /// Build the reduced model and the postsolve information.
+    fn finish(self, model: &LpModel) -> Presolved {
+        let status = if self.infeasible {
+            SolveStatus::Infeasible
+        } else if self.unbounded {
+            SolveStatus::Unbounded
+        } else {
+            SolveStatus::NotSolved
+        };
+
+        let col_map: Vec<usize> =
+            (0..self.cols.len()).filter(|&j| self.col_alive[j]).collect();
+        let mut new_id = vec![usize::MAX; self.cols.len()];
+        let mut reduced = LpModel::new();
+        for (k, &j) in col_map.iter().enumerate() {
+            new_id[j] = k;
+            let var = &model.vars[j];
+            reduced.vars.push(Var {
+                id: VarId(k),
+                name: var.name.clone(),
+                lb: self.lb[j].is_finite().then_some(self.lb[j]),
+                ub: self.ub[j].is_finite().then_some(self.ub[j]),
+                is_integer: var.is_integer,
+                is_artificial: var.is_artificial,
+            });
+        }
+
+        let mut row_map = Vec::new();
+        for (i, row) in self.rows.iter().enumerate().filter(|&(i, _)| self.row_alive[i]) {
+            let expr = LinExpr {
+                terms: row
+                    .iter()
+                    .map(|&(j, a)| LinTerm { var: VarId(new_id[j]), coeff: a })
+                    .collect(),
+                constant: 0.0,
+            };
+            let name = model.constraints[i].name.clone();
+            let (lo, hi) = (self.lo[i], self.hi[i]);
+            let sides = if lo == hi {
+                vec![(Cmp::EQ, lo)]
+            } else {
+                let lower = lo.is_finite().then_some((Cmp::GEQ, lo));
+                let upper = hi.is_finite().then_some((Cmp::LEQ, hi));
+                lower.into_iter().chain(upper).collect()
+            };
+            for (cmp, rhs) in sides {
+                reduced.constraints.push(LinearConstraint {
+                    expr: expr.clone(),
+                    rhs,
+                    cmp,
+                    name: name.clone(),
+                });
+                row_map.push(i);
+            }
+        }
+
+        let objective = model.objective().expect("checked by check_lp");
+        reduced.add_objective(Objective {
+            sense: objective.sense,
+            expr: LinExpr {
+                terms: col_map
+                    .iter()
+                    .enumerate()
+                    .filter(|&(_, &j)| self.cost[j] != 0.0)
+                    .map(|(k, &j)| LinTerm {
+                        var: VarId(k),
+                        coeff: self.sign * self.cost[j],
+                    })
+                    .collect(),
+                constant: 0.0,
+            },
+            quad: Vec::new(),
+            name: objective.name.clone(),
+            priority: objective.priority,
+            degradation: objective.degradation,
+        });
+
+        Presolved {
+            model: reduced,
+            status,
+            stats: self.stats,
+            original: model.clone(),
+            col_map,
+            row_map,
+            cols: self.original_cols,
+            sign: self.sign,
+            stack: self.stack,
+        }
+    }
+
+    /// Remove `row` from the model.
+    fn remove_row(&mut self, row: usize) {
+        self.row_alive[row] = false;
+        for (j, _) in std::mem::take(&mut self.rows[row]) {
+            self.cols[j].retain(|&(i, _)| i != row);
+        }
+        self.stats.removed_rows += 1;
+        self.changes += 1;
+    }
+
+    /// Remove `col` from the model.
+    fn remove_col(&mut self, col: usize) {
+        self.col_alive[col] = false;
+        for (i, _) in std::mem::take(&mut self.cols[col]) {
+            self.rows[i].retain(|&(j, _)| j != col);
+        }
+        self.stats.removed_cols += 1;
+        self.changes += 1;
+    }
+
+    /// Fix `col` at `value`, moving its contribution into the row ranges.
+    fn fix_col(&mut self, col: usize, value: f64) {
+        for &(i, a) in &self.cols[col] {
+            self.lo[i] -= a * value;
+            self.hi[i] -= a * value;
+        }
+        self.stack.push(Reduction::Fix { col, value });
+        self.stats.fixed_cols += 1;
+        self.remove_col(col);
+    }
+
+    /// Bounds on the activity of `row`.
+    fn activity(&self, row: usize) -> Activity {
+        let mut act = Activity::default();
+        for &(j, a) in &self.rows[row] {
+            let (low, high) = self.contribution(j, a);
+            if low.is_finite() {
+                act.min += low;
+            } else {
+                act.min_inf += 1;
+            }
+            if high.is_finite() {
+                act.max += high;
+            } else {
+                act.max_inf += 1;
+            }
+        }
+        act
+    }
+
+    /// Smallest and largest value of `a x_col` within the bounds of `col`.
+    fn contribution(&self, col: usize, a: f64) -> (f64, f64) {
+        if a > 0.0 {
+            (a * self.lb[col], a * self.ub[col])
+        } else {
+            (a * self.ub[col], a * self.lb[col])
+        }
+    }
+
+    /// Bounds on `x_col` implied by its coefficient `a` in `row` and the
+    /// activity bounds `act` of the row.
+    fn implied_bounds(
+        &self,
+        row: usize,
+        col: usize,
+        a: f64,
+        act: &Activity,
+    ) -> (f64, f64) {
+        let (low, high) = self.contribution(col, a);
+        let upper = (self.hi[row] - act.min_without(low)) / a;
+        let lower = (self.lo[row] - act.max_without(high)) / a;
+        let (lower, upper) = if a > 0.0 { (lower, upper) } else { (upper, lower) };
+        // `∞ - ∞` only arises for a side that gives no bound.
+        (
+            if lower.is_nan() { f64::NEG_INFINITY } else { lower },
+            if upper.is_nan() { f64::INFINITY } else { upper },
+        )
+    }
+
+    /// Set the bounds of `col` to `[lower, upper]`, checking that they do not
+    /// cross.
+    fn set_bounds(&mut self, col: usize, lower: f64, upper: f64) {
+        if lower > upper + FEAS_TOL * (1.0 + lower.abs()) {
+            self.infeasible = true;
+        }
+        self.lb[col] = lower;
+        self.ub[col] = upper.max(lower);
+        self.changes += 1;
+    }
+
+    /// Remove empty, singleton, forcing and redundant rows.
+    fn reduce_rows(&mut self) {
+        for i in 0..self.rows.len() {
+            if !self.row_alive[i] || self.infeasible {
+                continue;
+            }
+            let (lo, hi) = (self.lo[i], self.hi[i]);
+            let tol = FEAS_TOL * (1.0 + lo.abs().min(hi.abs()));
+
+            match self.rows[i][..] {
+                [] => {
+                    if lo > tol || hi < -tol {
+                        self.infeasible = true;
+                    }
+                    self.remove_row(i);
+                }
+                [(j, a)] => {
+                    let (l, h) =
+                        if a > 0.0 { (lo / a, hi / a) } else { (hi / a, lo / a) };
+                    let lower = (l > self.lb[j]).then_some(l);
+                    let upper = (h < self.ub[j]).then_some(h);
+                    if lower.is_some() || upper.is_some() {
+                        self.set_bounds(
+                            j,
+                            lower.unwrap_or(self.lb[j]),
+                            upper.unwrap_or(self.ub[j]),
+                        );
+                        self.stack.push(Reduction::Bound {
+                            row: i,
+                            col: j,
+                            a,
+                            lower: lower.map(|_| self.lb[j]),
+                            upper: upper.map(|_| self.ub[j]),
+                            cost: self.cost[j],
+                        });
+                        self.stats.tightened_bounds += 1;
+                    }
+                    self.remove_row(i);
+                }
+                _ => self.reduce_row(i),
+            }
+        }
+    }
+
+    /// Remove row `i` if it is forcing or redundant, or drop a redundant side.
+    fn reduce_row(&mut self, i: usize) {
+        let act = self.activity(i);
+        let (lo, hi) = (self.lo[i], self.hi[i]);
+        let (min, max) = (act.min(), act.max());
+        if min > hi + FEAS_TOL * (1.0 + hi.abs())
+            || max < lo - FEAS_TOL * (1.0 + lo.abs())
+        {
+            self.infeasible = true;
+            return;
+        }
+
+        let upper = hi.is_finite() && min >= hi - FEAS_TOL * (1.0 + hi.abs());
+        let lower = lo.is_finite() && max <= lo + FEAS_TOL * (1.0 + lo.abs());
+        if upper || lower {
+            // Every column is forced to the bound at which the row is active.
+            let entries = self.rows[i].clone();
+            let mut cols = Vec::new();
+            for &(j, a) in &entries {
+                if self.lb[j] < self.ub[j] {
+                    cols.push((j, a, self.cost[j]));
+                }
+            }
+            for (j, a) in entries {
+                let value = if (a > 0.0) == upper { self.lb[j] } else { self.ub[j] };
+                self.fix_col(j, value);
+            }
+            self.stack.push(Reduction::Forcing { row: i, upper, cols });
+            self.remove_row(i);
+            return;
+        }
+
+        if lo.is_finite() && min >= lo - FEAS_TOL * (1.0 + lo.abs()) {
+            self.lo[i] = f64::NEG_INFINITY;
+            self.changes += 1;
+        }
+        if hi.is_finite() && max <= hi + FEAS_TOL * (1.0 + hi.abs()) {
+            self.hi[i] = f64::INFINITY;
+            self.changes += 1;
+        }
+        if self.lo[i] == f64::NEG_INFINITY && self.hi[i] == f64::INFINITY {
+            self.remove_row(i);
+        }
+    }
+
+    /// Remove empty, fixed, free singleton and dominated columns.
+    fn reduce_cols(&mut self) {
+        for j in 0..self.cols.len() {
+            if !self.col_alive[j] || self.infeasible {
+                continue;
+            }
+            let (lb, ub, c) = (self.lb[j], self.ub[j], self.cost[j]);
+
+            if lb == ub {
+                self.fix_col(j, lb);
+            } else if self.cols[j].is_empty() {
+                let best = if c > 0.0 {
+                    ub
+                } else if c < 0.0 {
+                    lb
+                } else {
+                    0.0f64.clamp(lb, ub)
+                };
+                if best.is_finite() {
+                    self.fix_col(j, best);
+                } else {
+                    self.unbounded = true;
+                    self.fix_col(j, 0.0f64.clamp(lb, ub));
+                }
+            } else if self.cols[j].len() == 1 && !self.integer[j] {
+                self.free_column_singleton(j);
+            } else {
+                self.dominated_col(j);
+            }
+        }
+    }
+
+    /// Substitute out column `j`, which appears in one row only, if its bounds
+    /// are implied by that row.
+    fn free_column_singleton(&mut self, j: usize) {
+        let (i, a) = self.cols[j][0];
+        let largest = self.rows[i].iter().fold(0.0f64, |m, &(_, a)| m.max(a.abs()));
+        if a.abs() < MIN_PIVOT_RATIO * largest {
+            return;
+        }
+        let act = self.activity(i);
+        let (lower, upper) = self.implied_bounds(i, j, a, &act);
+        let (lb, ub) = (self.lb[j], self.ub[j]);
+        if lower < lb - FEAS_TOL * (1.0 + lb.abs())
+            || upper > ub + FEAS_TOL * (1.0 + ub.abs())
+        {
+            return;
+        }
+
+        // With a nonzero cost the row is active on the side the column pushes it
+        // to; if that side is open the model is unbounded provided it is
+        // feasible, and the row is free.
+        let c = self.cost[j];
+        if self.lo[i] < self.hi[i] && c != 0.0 {
+            let side = if c / a > 0.0 { self.hi[i] } else { self.lo[i] };
+            if side.is_finite() {
+                self.lo[i] = side;
+                self.hi[i] = side;
+            } else {
+                self.unbounded = true;
+            }
+        }
+
+        let others: Vec<(usize, f64)> =
+            self.rows[i].iter().copied().filter(|&(k, _)| k != j).collect();
+        if self.lo[i] == self.hi[i] {
+            for &(k, ak) in &others {
+                self.cost[k] -= c / a * ak;
+            }
+        }
+        self.stack.push(Reduction::FreeColumn {
+            row: i,
+            col: j,
+            a,
+            lo: self.lo[i],
+            hi: self.hi[i],
+            others,
+            cost: c,
+        });
+        self.remove_row(i);
+        self.remove_col(j);
+    }
+
+    /// Fix column `j` at a bound if moving it towards that bound improves the
+    /// objective and never violates a row.
+    fn dominated_col(&mut self, j: usize) {
+        let (mut up, mut down) = (true, true);
+        for &(i, a) in &self.cols[j] {
+            let (lo_open, hi_open) =
+                (self.lo[i] == f64::NEG_INFINITY, self.hi[i] == f64::INFINITY);
+            up &= if a > 0.0 { hi_open } else { lo_open };
+            down &= if a > 0.0 { lo_open } else { hi_open };
+        }
+
+        let c = self.cost[j];
+        let (lb, ub) = (self.lb[j], self.ub[j]);
+        if up && c >= 0.0 && ub.is_finite() {
+            self.fix_col(j, ub);
+        } else if down && c <= 0.0 && lb.is_finite() {
+            self.fix_col(j, lb);
+        }
+    }
+
+    /// Tighten column bounds using the activity bounds of the rows.
+    fn tighten_bounds(&mut self) {
+        for i in 0..self.rows.len() {
+            if !self.row_alive[i] || self.infeasible {
+                continue;
+            }
+            let mut act = self.activity(i);
+            for (j, a) in self.rows[i].clone() {
+                if self.integer[j] {
+                    continue;
+                }
+                let (lower, upper) = self.implied_bounds(i, j, a, &act);
+                let (lb, ub) = (self.lb[j], self.ub[j]);
+                let tighter = |new: f64, old: f64| {
+                    new.abs() < MAX_BOUND
+                        && (new - old).abs() > MIN_IMPROVEMENT * (1.0 + new.abs())
+                };
+                let lower = (lower > lb && tighter(lower, lb)).then_some(lower);
+                let upper = (upper < ub && tighter(upper, ub)).then_some(upper);
+                if lower.is_none() && upper.is_none() {
+                    continue;
+                }
+
+                self.set_bounds(j, lower.unwrap_or(lb), upper.unwrap_or(ub));
+                self.stack.push(Reduction::Bound {
+                    row: i,
+                    col: j,
+                    a,
+                    lower: lower.map(|_| self.lb[j]),
+                    upper: upper.map(|_| self.ub[j]),
+                    cost: self.cost[j],
+                });
+                self.stats.tightened_bounds += 1;
+                act = self.activity(i);
+            }
+        }
+    }
+
+    /// Merge rows that are multiples of each other.
+    fn parallel_rows(&mut self) {
+        let mut buckets: HashMap<Vec<usize>, Vec<usize>> = HashMap::new();
+        for i in (0..self.rows.len()).filter(|&i| self.row_alive[i]) {
+            if self.rows[i].len() >= 2 {
+                let support = self.rows[i].iter().map(|&(j, _)| j).collect();
+                buckets.entry(support).or_default().push(i);
+            }
+        }
+
+        let mut buckets: Vec<Vec<usize>> =
+            buckets.into_values().filter(|b| b.len() > 1).collect();
+        buckets.sort_unstable();
+        for bucket in buckets {
+            let mut kept: Vec<usize> = Vec::new();
+            'rows: for k in bucket {
+                for &i in &kept {
+                    let Some(ratio) = ratio(&self.rows[i], &self.rows[k]) else {
+                        continue;
+                    };
+                    self.merge_rows(i, k, ratio);
+                    if self.infeasible {
+                        return;
+                    }
+                    continue 'rows;
+                }
+                kept.push(k);
+            }
+        }
+    }
+
+    /// Merge row `removed`, which is `ratio` times row `kept`, into `kept`.
+    fn merge_rows(&mut self, kept: usize, removed: usize, ratio: f64) {
+        let (l, h) = (self.lo[removed] / ratio, self.hi[removed] / ratio);
+        let (l, h) = if ratio > 0.0 { (l, h) } else { (h, l) };
+        let lower = l > self.lo[kept];
+        let upper = h < self.hi[kept];
+        let lo = self.lo[kept].max(l);
+        let hi = self.hi[kept].min(h);
+        if lo > hi + FEAS_TOL * (1.0 + lo.abs()) {
+            self.infeasible = true;
+        }
+        self.lo[kept] = lo;
+        self.hi[kept] = hi.max(lo);
+
+        self.stack
+            .push(Reduction::ParallelRow { kept, removed, ratio, lower, upper });
+        self.remove_row(removed);
+    }
+
+    /// Merge columns that are multiples of each other, costs included.
+    fn duplicate_cols(&mut self) {
+        let mut buckets: HashMap<Vec<usize>, Vec<usize>> = HashMap::new();
+        for j in (0..self.cols.len()).filter(|&j| self.col_alive[j] && !self.integer[j]) {
+            if !self.cols[j].is_empty() {
+                self.cols[j].sort_unstable_by_key(|&(i, _)| i);
+                let support = self.cols[j].iter().map(|&(i, _)| i).collect();
+                buckets.entry(support).or_default().push(j);
+            }
+        }
+
+        let mut buckets: Vec<Vec<usize>> =
+            buckets.into_values().filter(|b| b.len() > 1).collect();
+        buckets.sort_unstable();
+        for bucket in buckets {
+            let mut kept: Vec<usize> = Vec::new();
+            'cols: for k in bucket {
+                for &j in &kept {
+                    let Some(ratio) = ratio(&self.cols[j], &self.cols[k]) else {
+                        continue;
+                    };
+                    if !close(self.cost[k], ratio * self.cost[j]) {
+                        continue;
+                    }
+                    self.merge_cols(j, k, ratio);
+                    continue 'cols;
+                }
+                kept.push(k);
+            }
+        }
+    }
+
+    /// Merge column `removed`, which is `ratio` times column `kept`, into
+    /// `kept`.
+    fn merge_cols(&mut self, kept: usize, removed: usize, ratio: f64) {
+        let bounds =
+            [(self.lb[kept], self.ub[kept]), (self.lb[removed], self.ub[removed])];
+        let (low, high) = if ratio > 0.0 {
+            (ratio * self.lb[removed], ratio * self.ub[removed])
+        } else {
+            (ratio * self.ub[removed], ratio * self.lb[removed])
+        };
+        self.lb[kept] += low;
+        self.ub[kept] += high;
+
+        self.stack
+            .push(Reduction::DuplicateColumn { kept, removed, ratio, bounds });
+        self.remove_col(removed);
+    }
+}
+
+/// The ratio `λ` with `b = λ a`, if the two coefficient lists, which cover the
+/// same indices in the same order, are parallel.
+fn ratio(a: &[(usize, f64)], b: &[(usize, f64)]) -> Option<f64> {
+    let ratio = b[0].1 / a[0].1;
+    a.iter()
+        .zip(b)
+        .all(|(&(_, x), &(_, y))| close(y, ratio * x))
+        .then_some(ratio)
+}
@@ -0,0 +1,152 @@
+//! Pricing rules choosing the entering column of the primal simplex.
+//!
+//! Dantzig's rule picks the column with the largest reduced cost `|d_j|`, which
+//! favours columns that happen to be measured in small units. Devex and
+//! steepest-edge pricing instead pick the largest `d_j² / w_j`, where the weight
+//! `w_j` approximates (Devex) or equals (steepest edge) the squared norm
+//! `1 + ‖B⁻¹ A_j‖²` of the edge that column `j` moves the solution along. Both
+//! weights are updated after every pivot from the pivot row `ρᵀ A`.
+
+use cnvx_math::Matrix;
+
+use crate::simplex::{self, BasisFactor};
+
+/// Devex weights are reset to one (a new reference framework) once the weight
+/// of an entering column exceeds this value.
+const DEVEX_RESET: f64 = 1e6;
+
+/// How the primal simplex chooses its entering column.
+///
+/// # Examples
+///
+/// ```rust
+/// # use cnvx_lp::*;
+/// // A 3 × 3 assignment problem, whose vertices are highly degenerate.
+/// let cost = [[4.0, 1.0, 3.0], [2.0, 0.0, 5.0], [3.0, 2.0, 2.0]];
+/// let mut model = LpModel::new();
+/// let x = [[(); 3]; 3].map(|row| row.map(|_| model.add_var().finish()));
+/// for i in 0..3 {
+///     model += (x[i][0] + x[i][1] + x[i][2]).eq(1.0);
+///     model += (x[0][i] + x[1][i] + x[2][i]).eq(1.0);
+///     for j in 0..3 {
+///         model += x[i][j].geq(0.0);
+///     }
+/// }
+/// let mut objective = LinExpr::constant(0.0);
+/// for i in 0..3 {
+///     for j in 0..3 {
+///         objective += x[i][j] * cost[i][j];
+///     }
+/// }
+/// model.add_objective(Objective::minimize(objective).name("cost"));
+///
+/// for rule in [PricingRule::Dantzig, PricingRule::Devex, PricingRule::SteepestEdge] {
+///     let mut solver = PrimalSimplexSolver::new();
+///     solver.pricing = rule;
+///     let solution = solver.solve(&model).unwrap();
+///     assert!((solution.objective_value.unwrap() - 5.0).abs() < 1e-9);
+/// }
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum PricingRule {
+    /// The column with the largest reduced cost.
+    #[default]
+    Dantzig,
+
+    /// The column with the largest reduced cost relative to an approximate edge
+    /// norm, measured against a reference framework of non-basic columns.
+    Devex,
+
+    /// The column with the largest reduced cost relative to the exact edge
+    /// norm, which is computed once and then updated after every pivot.
+    SteepestEdge,
+}
+
+/// Pricing weights of the columns for a [`PricingRule`].
+pub(crate) struct PricingWeights {
+    rule: PricingRule,
+    weights: Vec<f64>,
+}
+
+impl PricingWeights {
+    /// Weights for the basis factored by `factor`, with non-basic columns
+    /// `non_basis`.
+    pub(crate) fn new<A: Matrix>(
+        rule: PricingRule,
+        a: &A,
+        factor: &BasisFactor<A>,
+        non_basis: &[usize],
+    ) -> Self {
+        let mut weights = vec![1.0; a.cols()];
+        if rule == PricingRule::SteepestEdge {
+            for &j in non_basis {
+                let d = simplex::compute_direction(a, factor, j);
+                weights[j] = 1.0 + dot(&d, &d);
+            }
+        }
+        Self { rule, weights }
+    }
+
+    /// Score of column `j` with reduced cost `rc`; the best column has the
+    /// largest score.
+    pub(crate) fn score(&self, j: usize, rc: f64) -> f64 {
+        match self.rule {
+            PricingRule::Dantzig => rc.abs(),
+            PricingRule::Devex | PricingRule::SteepestEdge => rc * rc / self.weights[j],
+        }
+    }
+
+    /// Update the weights for a pivot in which column `entering`, with
+    /// direction `d = B⁻¹ A_entering`, replaces column `leaving` in row `row`.
+    ///
+    /// `factor` and `non_basis` must still describe the basis before the pivot.
+    pub(crate) fn update<A: Matrix>(
+        &mut self,
+        a: &A,
+        factor: &BasisFactor<A>,
+        non_basis: &[usize],
+        d: &[f64],
+        (row, entering, leaving): (usize, usize, usize),
+    ) {
+        let steepest = match self.rule {
+            PricingRule::Dantzig => return,
+            PricingRule::Devex => false,
+            PricingRule::SteepestEdge => true,
+        };
+
+        let pivot = d[row];
+        let rho = simplex::compute_row(factor, row);
+        let (w_q, tau) = if steepest {
+            let mut tau = d.to_vec();
+            factor.solve_transpose(&mut tau);
+            (1.0 + dot(d, d), tau)
+        } else {
+            (self.weights[entering], Vec::new())
+        };
+
+        if !steepest && w_q > DEVEX_RESET {
+            self.weights.fill(1.0);
+            return;
+        }
+
+        for &j in non_basis.iter().filter(|&&j| j != entering) {
+            let alpha = simplex::column_dot(a, j, &rho) / pivot;
+            if alpha == 0.0 {
+                continue;
+            }
+            self.weights[j] = if steepest {
+                let cross = simplex::column_dot(a, j, &tau);
+                (self.weights[j] - 2.0 * alpha * cross + alpha * alpha * w_q)
+                    .max(1.0 + alpha * alpha)
+            } else {
+                self.weights[j].max(alpha * alpha * w_q)
+            };
+        }
+        self.weights[leaving] = (w_q / (pivot * pivot)).max(1.0);
+    }
+}
+
+/// Dot product of two vectors.
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
+}
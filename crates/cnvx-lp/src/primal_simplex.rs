@@ -1,13 +1,31 @@
-use std::ops::Neg;
-
 // FIXME: Replace with better solving techniques.
 use cnvx_core::*;
-use cnvx_math::{DenseMatrix, Matrix, matrix::SparseMatrix};
+use cnvx_math::{DenseMatrix, Matrix, SparseMatrix};
+
+use crate::pricing::{PricingRule, PricingWeights};
+use crate::scaling::{ScaleFactors, Scaling};
+use crate::simplex::{
+    self, BasisFactor, BasisView, ColumnKey, Layout, Reoptimized, StandardForm,
+    WarmStart, is_sparse,
+};
+use crate::{Capabilities, Certificate, LpModel, LpSolution, Sensitivity, Solver};
+
+/// Number of consecutive degenerate pivots after which the bounds of the basic
+/// columns are perturbed.
+const PERTURB_AFTER: usize = 20;
 
-use crate::{Cmp, LinExpr, LinearConstraint, LpModel, LpSolution, Solver};
+/// Number of consecutive degenerate pivots after which Bland's rule replaces
+/// the pricing rule, until the next pivot that makes progress.
+const BLAND_AFTER: usize = 50;
+
+/// Relative size of the bound perturbation.
+const PERTURBATION: f64 = 1e-6;
 
 /// A simplex solver for linear programs (LPs).
 ///
+/// The constraint matrix is stored as a [`SparseMatrix`] when the model is
+/// sparse, and as a [`DenseMatrix`] otherwise.
+///
 /// # Examples
 ///
 /// ```rust
@@ -31,6 +49,22 @@ pub struct PrimalSimplexSolver {
     pub max_iter: usize,
     /// Whether to log iteration details during the simplex algorithm.
     pub logging: bool,
+    /// Whether to [`presolve`](crate::presolve) the model before solving it.
+    ///
+    /// A presolved solve leaves no basis behind, so the next
+    /// [`resolve`](Solver::resolve) starts from scratch and
+    /// [`sensitivity`](Solver::sensitivity) is unavailable.
+    pub presolve: bool,
+    /// How to scale the constraint matrix before solving from scratch.
+    ///
+    /// Values, duals and reduced costs are always reported for the original,
+    /// unscaled model.
+    pub scaling: Scaling,
+    /// How to choose the entering column.
+    ///
+    /// Whatever the rule, a run of degenerate pivots first perturbs the bounds
+    /// and then falls back to Bland's rule, so the simplex cannot cycle.
+    pub pricing: PricingRule,
 
     /// Cached objective value from the most recent solve.
     last_objective: Option<f64>,
@@ -45,12 +79,120 @@ impl PrimalSimplexSolver {
             tolerance: 1e-8,
             max_iter: 1000,
             logging: false,
+            presolve: false,
+            scaling: Scaling::default(),
+            pricing: PricingRule::default(),
             last_objective: None,
             last_solution: Vec::new(),
         }
     }
 }
 
+impl PrimalSimplexSolver {
+    /// Record the outcome of a solve and return its [`LpSolution`].
+    fn finish(&mut self, state: Option<State>, solution: LpSolution) -> LpSolution {
+        if self.logging
+            && let Some(state) = &state
+        {
+            println!(
+                "Simplex finished with status {:?} in {} iterations. Objective value: {}",
+                solution.status,
+                state.iteration(),
+                solution.objective_value.unwrap_or_default()
+            );
+        }
+
+        self.last_objective = solution.objective_value;
+        self.last_solution = solution.values.clone();
+        self.state = state;
+
+        solution
+    }
+
+    /// Solve `model` from scratch with the constraint matrix stored as `A`.
+    fn solve_with<A: Matrix>(
+        &self,
+        model: &LpModel,
+    ) -> Result<(LpSolution, PrimalSimplexState<A>), SolveError> {
+        let mut state: PrimalSimplexState<A> =
+            PrimalSimplexState::with_scaling(model, self.scaling);
+        state.pricing = self.pricing;
+        state.logging = self.logging;
+
+        let (values, obj) = state.solve_lp(self.max_iter, self.tolerance)?;
+
+        let objective_value = (state.status == SolveStatus::Optimal).then_some(obj);
+        let mut solution = LpSolution::new(values, objective_value, state.status.clone())
+            .with_activities(model);
+        state.attach_duals(&mut solution)?;
+        solution.certificate = state.certificate().cloned();
+        Ok((solution, state))
+    }
+
+    /// Presolve `model`, solve the reduced model and postsolve its solution.
+    fn solve_presolved(&self, model: &LpModel) -> Result<LpSolution, SolveError> {
+        let presolved = crate::presolve::presolve(model)?;
+        if self.logging {
+            println!("Presolve: {:?}", presolved.stats);
+        }
+
+        let reduced = if presolved.status == SolveStatus::Infeasible {
+            LpSolution::new(
+                vec![0.0; presolved.model.vars.len()],
+                None,
+                SolveStatus::Infeasible,
+            )
+        } else if presolved.model.vars.is_empty() {
+            LpSolution::new(Vec::new(), Some(0.0), SolveStatus::Optimal)
+        } else if is_sparse(&presolved.model) {
+            self.solve_with::<SparseMatrix>(&presolved.model)?.0
+        } else {
+            self.solve_with::<DenseMatrix>(&presolved.model)?.0
+        };
+        Ok(presolved.postsolve(&reduced))
+    }
+
+    /// Re-optimise `model` from `warm` with the constraint matrix stored as `A`.
+    ///
+    /// Returns `None` if the basis no longer fits the model.
+    fn resolve_with<A: Matrix>(
+        &self,
+        model: &LpModel,
+        warm: &WarmStart,
+    ) -> Result<Option<Resolved<A>>, SolveError> {
+        let Some(state) =
+            simplex::reoptimize::<A>(model, warm, self.max_iter, self.tolerance)?
+        else {
+            return Ok(None);
+        };
+
+        let (values, obj) = state.solution();
+        let status = state.status();
+        let objective_value = (status == SolveStatus::Optimal).then_some(obj);
+        let mut solution =
+            LpSolution::new(values, objective_value, status).with_activities(model);
+        state.attach_duals(&mut solution)?;
+        solution.certificate = state.certificate();
+
+        let state = match state {
+            Reoptimized::Primal(s) => Some(s),
+            Reoptimized::Dual(s) => s
+                .warm_start()
+                .and_then(|w| PrimalSimplexState::with_basis(model, &w))
+                .map(|mut p| {
+                    p.status = s.status.clone();
+                    p
+                }),
+        };
+
+        Ok(Some((solution, state)))
+    }
+}
+
+/// A re-optimised solution, with the primal simplex state it ended in if that
+/// could be rebuilt.
+type Resolved<A> = (LpSolution, Option<PrimalSimplexState<A>>);
+
 impl Default for PrimalSimplexSolver {
     fn default() -> Self {
         Self::new()
@@ -65,28 +207,53 @@ impl Solver for PrimalSimplexSolver {
     fn solve(&mut self, model: &LpModel) -> Result<LpSolution, SolveError> {
         crate::validate::check_lp(model)?;
 
-        let mut state: PrimalSimplexState<DenseMatrix> = PrimalSimplexState::new(model);
-
-        let (values, obj) = state.solve_lp(self.max_iter, self.tolerance)?;
-
-        if self.logging {
-            println!(
-                "Simplex finished with status {:?} in {} iterations. Objective value: {}",
-                state.status, state.iteration, obj
-            );
+        if self.presolve {
+            let solution = self.solve_presolved(model)?;
+            return Ok(self.finish(None, solution));
         }
 
-        self.last_objective = Some(obj);
-        self.last_solution = values.clone();
-        self.state = Some(State::Dense(state));
+        let (solution, state) = if is_sparse(model) {
+            let (solution, state) = self.solve_with::<SparseMatrix>(model)?;
+            (solution, State::Sparse(state))
+        } else {
+            let (solution, state) = self.solve_with::<DenseMatrix>(model)?;
+            (solution, State::Dense(state))
+        };
+        Ok(self.finish(Some(state), solution))
+    }
+
+    /// Re-optimises `model` from the basis of the previous solve.
+    ///
+    /// New primal infeasibilities (added constraints, tightened bounds, changed
+    /// right-hand sides) are removed with the dual simplex; new dual
+    /// infeasibilities (changed objective coefficients) with the primal simplex.
+    /// Falls back to [`solve`](Solver::solve) when there is no previous optimal
+    /// basis or it no longer fits the model.
+    fn resolve(&mut self, model: &LpModel) -> Result<LpSolution, SolveError> {
+        crate::validate::check_lp(model)?;
 
-        let status = match &self.state {
-            Some(State::Dense(s)) => s.status.clone(),
-            Some(State::Sparse(s)) => s.status.clone(),
-            None => unreachable!(),
+        let Some(warm) = self.state.as_ref().and_then(State::warm_start) else {
+            return self.solve(model);
+        };
+        let resolved = if is_sparse(model) {
+            self.resolve_with::<SparseMatrix>(model, &warm)?
+                .map(|(solution, state)| (solution, state.map(State::Sparse)))
+        } else {
+            self.resolve_with::<DenseMatrix>(model, &warm)?
+                .map(|(solution, state)| (solution, state.map(State::Dense)))
         };
+        match resolved {
+            Some((solution, state)) => Ok(self.finish(state, solution)),
+            None => self.solve(model),
+        }
+    }
 
-        Ok(LpSolution { values, objective_value: Some(obj), status })
+    fn sensitivity(&self, model: &LpModel) -> Result<Sensitivity, SolveError> {
+        match &self.state {
+            Some(State::Dense(s)) => s.sensitivity(model),
+            Some(State::Sparse(s)) => s.sensitivity(model),
+            None => Err(SolveError::Other("no previous solve to analyse".into())),
+        }
     }
 
     fn objective_value(&self) -> Option<f64> {
@@ -96,18 +263,49 @@ impl Solver for PrimalSimplexSolver {
     fn solution_vector(&self) -> Vec<f64> {
         self.last_solution.clone()
     }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            warm_start: true,
+            scaling: true,
+            ..Capabilities::default()
+        }
+    }
+
+    fn has_warm_start(&self) -> bool {
+        self.state.as_ref().and_then(State::warm_start).is_some()
+    }
 }
 
-#[allow(dead_code)]
+/// The state of the last solve, with the constraint matrix in the format
+/// chosen for the model.
 enum State {
     Dense(PrimalSimplexState<DenseMatrix>),
     Sparse(PrimalSimplexState<SparseMatrix>),
 }
 
+impl State {
+    fn iteration(&self) -> usize {
+        match self {
+            State::Dense(s) => s.iteration,
+            State::Sparse(s) => s.iteration,
+        }
+    }
+
+    fn warm_start(&self) -> Option<WarmStart> {
+        match self {
+            State::Dense(s) => s.warm_start(),
+            State::Sparse(s) => s.warm_start(),
+        }
+    }
+}
+
 /// Internal state for the simplex algorithm.
 ///
-/// Tracks the current basis, non-basis variables, solution vector, objective value,
-/// and the LP tableau.
+/// Tracks the current basis, the values of the non-basic columns, the basic
+/// solution, the objective value, and the LP tableau. Non-basic columns rest at
+/// one of their bounds (or at zero if free) and may flip between them without a
+/// basis change.
 #[derive(Clone)]
 pub struct PrimalSimplexState<A: Matrix> {
     /// Current iteration count of the simplex algorithm.
@@ -119,6 +317,9 @@ pub struct PrimalSimplexState<A: Matrix> {
     pub non_basis: Vec<usize>,
     /// Values of the basic variables.
     pub x_b: Vec<f64>,
+    /// Values of the non-basic columns, indexed by column (entries of basic
+    /// columns are unused).
+    pub x_n: Vec<f64>,
 
     /// Constraint matrix `A`.
     pub a: A,
@@ -126,11 +327,42 @@ pub struct PrimalSimplexState<A: Matrix> {
     pub b: Vec<f64>,
     /// Objective coefficients vector `c`.
     pub c: Vec<f64>,
+    /// Lower bound of each column.
+    pub lower: Vec<f64>,
+    /// Upper bound of each column.
+    pub upper: Vec<f64>,
 
     /// Current objective value.
     pub objective: f64,
     /// Solution status after solving (Optimal, Infeasible, Unbounded, etc.).
     pub status: SolveStatus,
+    /// How to choose the entering column.
+    pub pricing: PricingRule,
+    /// How ratio-test ties are broken during a run of degenerate pivots; other
+    /// pivots take the largest pivot element.
+    pub tie_break: TieBreak,
+    /// Number of consecutive degenerate pivots after which the bounds of the
+    /// basic columns are perturbed (default 20).
+    pub perturb_after: usize,
+    /// Number of consecutive degenerate pivots after which Bland's rule takes
+    /// over (default 50).
+    pub bland_after: usize,
+
+    /// Origin of the standard-form rows and columns.
+    layout: Layout,
+
+    /// Factors the standard form was scaled by.
+    scale: ScaleFactors,
+
+    /// Rows owning the artificial columns, in column order starting at `layout.n_cols`.
+    art_rows: Vec<usize>,
+
+    /// Columns whose bounds were perturbed to escape a run of degenerate
+    /// pivots, with their original lower and upper bounds.
+    perturbed: Vec<(usize, f64, f64)>,
+
+    /// Proof of infeasibility or unboundedness found by the last solve.
+    certificate: Option<Certificate>,
 
     /// Whether the LP is a minimization problem.
     minimise: bool,
@@ -142,98 +374,132 @@ pub struct PrimalSimplexState<A: Matrix> {
     log_interval: usize,
 }
 
+/// Outcome of the primal ratio test.
+enum Step {
+    /// The entering column reaches its opposite bound before any basic
+    /// variable is blocked, so it moves there without a basis change.
+    Flip,
+    /// The basic variable in `row` is blocked at `bound` and leaves the basis.
+    Pivot { row: usize, bound: f64 },
+}
+
+/// How the primal ratio test chooses between basic variables blocked at the
+/// same step length.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum TieBreak {
+    /// The largest pivot element, for numerical stability.
+    Pivot,
+    /// The lexicographically smallest ratio of the rows of `B⁻¹`, as if the
+    /// right-hand side were perturbed by `(ε, ε², …)`.
+    #[default]
+    Lexicographic,
+    /// The smallest column index (Bland's rule).
+    Bland,
+}
+
 impl<A: Matrix> PrimalSimplexState<A> {
     /// Initialize a new simplex state from a given `Model`.
     ///
-    /// Constructs the tableau, sets up artificial variables for inequalities, and
-    /// computes the objective coefficients based on the problem's sense (min/max).
+    /// Every non-basic column starts at one of its bounds. A row whose slack can
+    /// absorb the remaining right-hand side starts with its slack in the basis;
+    /// all other rows (including equality rows) receive an artificial column,
+    /// which phase 1 drives to zero.
     pub fn new(model: &LpModel) -> Self {
-        // Clone the model so we can inject bound constraints without mutating the original
-        let mut model = model.clone();
-
-        // Inject variable bounds as constraints
-        // Collect bound constraints separately to avoid borrow checker issues
-        let vars: Vec<_> = model.vars().to_vec();
-        let mut bound_constraints = Vec::new();
-        for var in vars.iter() {
-            if let Some(lb) = var.lb {
-                bound_constraints.push(
-                    LinearConstraint::geq(LinExpr::from(var.id), lb).named(&format!(
-                        "{}_lower_bound",
-                        var.name.as_deref().unwrap_or(&format!("var{}", var.id.0))
-                    )),
-                );
-            }
-            if let Some(ub) = var.ub {
-                bound_constraints.push(
-                    LinearConstraint::leq(LinExpr::from(var.id), ub).named(&format!(
-                        "{}_upper_bound",
-                        var.name.as_deref().unwrap_or(&format!("var{}", var.id.0))
-                    )),
-                );
+        Self::with_scaling(model, Scaling::None)
+    }
+
+    /// Initialize a new simplex state from a given `Model`, with its standard
+    /// form scaled by `scaling`.
+    ///
+    /// The fields of the state, such as [`x_b`](Self::x_b) and
+    /// [`tableau_row`](Self::tableau_row), refer to the scaled problem;
+    /// [`solution`](Self::solution), [`attach_duals`](Self::attach_duals) and
+    /// [`sensitivity`](Self::sensitivity) report values of the original model.
+    pub fn with_scaling(model: &LpModel, scaling: Scaling) -> Self {
+        let mut form = StandardForm::<A>::new(model);
+        let scale = form.scale(scaling);
+        let x_n = form.initial_values();
+        let all = (0..form.layout.n_cols).collect::<Vec<_>>();
+        let r = simplex::nonbasic_rhs(&form.a, &form.b, &all, &x_n);
+
+        let mut basis = Vec::new();
+        let mut artificials = Vec::new();
+        for (i, slack) in form.layout.slacks.iter().enumerate() {
+            match *slack {
+                Some((j, coeff)) if coeff * r[i] >= 0.0 => basis.push(j),
+                _ => artificials.push((i, if r[i] < 0.0 { -1.0 } else { 1.0 })),
             }
         }
-        model.constraints.extend(bound_constraints);
-
-        let n_vars = model.vars().len();
-        let n_cons = model.constraints().len();
 
-        let mut b = vec![0.0; n_cons];
+        Self::from_form(form, scale, basis, &artificials, x_n)
+    }
 
-        let mut n_total = n_vars;
-        for cons in model.constraints().iter() {
-            match cons.cmp {
-                Cmp::LEQ | Cmp::GEQ => n_total += 1,
-                Cmp::EQ => {}
-            }
+    /// Initialise a simplex state for `model` from a previous optimal basis.
+    ///
+    /// Returns `None` if the basis cannot be mapped onto the model without
+    /// artificial columns, or is singular.
+    pub fn with_basis(model: &LpModel, warm: &WarmStart) -> Option<Self> {
+        let form = StandardForm::<A>::new(model);
+        let (basis, art_rows) = warm.map_onto(&form.layout)?;
+        if !art_rows.is_empty() {
+            return None;
         }
 
-        let mut a = A::new(n_cons, n_total);
-        let mut c = vec![0.0; n_total];
+        let mut x_n = form.initial_values();
+        warm.restore_upper(&form.layout, &form.upper, &mut x_n);
 
-        let minimise =
-            model.objective().map(|o| o.sense == Sense::Minimize).unwrap_or(false);
-
-        if let Some(obj) = model.objective() {
-            for term in &obj.expr.terms {
-                c[term.var.0] = match obj.sense {
-                    Sense::Maximize => term.coeff,
-                    Sense::Minimize => -term.coeff,
-                };
-            }
-        }
+        let scale = ScaleFactors::identity(form.a.rows(), form.a.cols());
+        let mut state = Self::from_form(form, scale, basis, &[], x_n);
+        let factor = state.factor_or_repair().ok()?;
+        state.x_b = state.compute_basic_solution(&factor);
+        state.update_objective();
+        Some(state)
+    }
 
-        let mut extra_idx = n_vars;
-        for (i, cons) in model.constraints().iter().enumerate() {
-            b[i] = cons.rhs;
-            for term in &cons.expr.terms {
-                a.set(i, term.var.0, term.coeff);
-            }
-            match cons.cmp {
-                Cmp::LEQ => {
-                    a.set(i, extra_idx, 1.0);
-                    extra_idx += 1;
-                }
-                Cmp::GEQ => {
-                    a.set(i, extra_idx, -1.0);
-                    extra_idx += 1;
-                }
-                Cmp::EQ => {}
-            }
+    /// Build the state from a standard form scaled by `scale`, a set of basic
+    /// columns, the artificial columns `(row, sign)` completing the basis, and
+    /// the starting values of the non-basic columns.
+    fn from_form(
+        mut form: StandardForm<A>,
+        scale: ScaleFactors,
+        mut basis: Vec<usize>,
+        artificials: &[(usize, f64)],
+        mut x_n: Vec<f64>,
+    ) -> Self {
+        let n = form.a.cols();
+        form.add_artificials(artificials);
+        basis.extend(n..n + artificials.len());
+        x_n.resize(n + artificials.len(), 0.0);
+
+        let mut in_basis = vec![false; n];
+        for &j in basis.iter().filter(|&&j| j < n) {
+            in_basis[j] = true;
         }
 
         Self {
             iteration: 0,
-            basis: Vec::new(),
-            non_basis: (0..n_vars).collect(),
-            x_b: vec![0.0; n_cons],
-            a,
-            b,
-            c,
+            basis,
+            non_basis: (0..n).filter(|&j| !in_basis[j]).collect(),
+            x_b: vec![0.0; form.a.rows()],
+            x_n,
+            a: form.a,
+            b: form.b,
+            c: form.c,
+            lower: form.lower,
+            upper: form.upper,
             objective: 0.0,
             status: SolveStatus::NotSolved,
-            minimise,
-            logging: true,
+            pricing: PricingRule::default(),
+            tie_break: TieBreak::default(),
+            perturb_after: PERTURB_AFTER,
+            bland_after: BLAND_AFTER,
+            layout: form.layout,
+            scale,
+            art_rows: artificials.iter().map(|&(r, _)| r).collect(),
+            perturbed: Vec::new(),
+            certificate: None,
+            minimise: form.minimise,
+            logging: false,
             log_interval: 100,
         }
     }
@@ -248,171 +514,299 @@ impl<A: Matrix> PrimalSimplexState<A> {
         max_iter: usize,
         tol: f64,
     ) -> Result<(Vec<f64>, f64), SolveError> {
-        self.init_basis();
-        let orig_n = self.a.cols();
-
-        if self.try_phase2(max_iter, tol)? {
-            return Ok(self.extract_solution(orig_n));
-        }
-
-        self.phase1(orig_n, max_iter, tol)?;
-        self.phase2(max_iter, tol)?;
-
-        Ok(self.extract_solution(orig_n))
-    }
-
-    /// Attempt to directly run phase 2 if the initial basis is feasible.
-    fn try_phase2(&mut self, max_iter: usize, tol: f64) -> Result<bool, SolveError> {
-        let mut bmat = self.build_bmat();
-        match self.compute_basic_solution(&mut bmat) {
-            Ok(xb) if xb.iter().all(|&v| v >= -tol) => {
-                self.x_b = xb;
-                self.remove_artificial_from_basis(&mut bmat, self.a.cols())
-                    .map_err(SolveError::InvalidModel)?;
-                self.run_simplex(&mut bmat, max_iter, tol)?;
-                Ok(true)
+        self.certificate = None;
+        if !self.art_rows.is_empty() {
+            self.phase1(max_iter, tol)?;
+            if self.status == SolveStatus::Infeasible {
+                return Ok(self.solution());
             }
-            _ => Ok(false),
         }
+
+        self.run_simplex(max_iter, tol)?;
+        Ok(self.solution())
     }
 
-    /// Phase 1 of the two-phase simplex method to remove artificial variables.
-    fn phase1(
+    /// Continue phase 2 from the current (primal feasible) basis.
+    ///
+    /// Used after [`with_basis`](Self::with_basis) to re-optimise without
+    /// repeating phase 1.
+    pub fn resolve_lp(
         &mut self,
-        orig_n: usize,
         max_iter: usize,
         tol: f64,
-    ) -> Result<(), SolveError> {
-        let (orig_a, orig_c, mut bmat) = self.setup_phase1(orig_n);
-        self.run_simplex(&mut bmat, max_iter, tol)?;
+    ) -> Result<(Vec<f64>, f64), SolveError> {
+        self.certificate = None;
+        self.run_simplex(max_iter, tol)?;
+        Ok(self.solution())
+    }
+
+    /// Returns the solution vector (structural and slack columns) and the
+    /// objective value of the current basis.
+    pub fn solution(&self) -> (Vec<f64>, f64) {
+        let (mut x, obj) = simplex::extract_solution(
+            &self.c,
+            &self.basis,
+            &self.x_b,
+            &self.x_n,
+            self.layout.n_cols,
+            self.minimise,
+        );
+        for (j, x) in x.iter_mut().enumerate() {
+            *x = self.scale.value(j, *x);
+        }
+        (x, self.scale.objective_value(obj))
+    }
 
-        let sum_art: f64 = self
+    /// Returns the optimal basis for warm-starting a later re-solve, or `None`
+    /// if the last solve did not reach an optimum.
+    pub fn warm_start(&self) -> Option<WarmStart> {
+        if self.status != SolveStatus::Optimal {
+            return None;
+        }
+        let basis = self
             .basis
             .iter()
-            .enumerate()
-            .map(|(i, &v)| self.c[v] * self.x_b[i])
-            .sum::<f64>()
-            .neg();
+            .map(|&j| match self.artificial_row(j) {
+                Some(row) => Some(ColumnKey::Artificial(self.layout.rows[row])),
+                None => self.layout.column_key(j),
+            })
+            .collect::<Option<Vec<_>>>()?;
+        let at_upper = simplex::upper_keys(
+            &self.layout,
+            &self.non_basis,
+            &self.x_n,
+            &self.lower,
+            &self.upper,
+        );
+        Some(WarmStart { rows: self.layout.rows.clone(), basis, at_upper })
+    }
 
-        if sum_art > tol {
-            self.status = SolveStatus::Infeasible;
-            return Ok(());
+    /// Origin of the standard-form rows and columns.
+    pub fn layout(&self) -> &Layout {
+        &self.layout
+    }
+
+    /// Proof that the model is infeasible or unbounded, in terms of the
+    /// original model, if the last solve ended with either status.
+    pub fn certificate(&self) -> Option<&Certificate> {
+        self.certificate.as_ref()
+    }
+
+    /// Row `r` of the tableau `B⁻¹A` of the current basis, over every column.
+    ///
+    /// Together with [`x_b`](Self::x_b) this is the row `x_B[r] + Σ α_j x_j = β`
+    /// used to derive Gomory cuts.
+    pub fn tableau_row(&self, r: usize) -> Result<Vec<f64>, SolveError> {
+        Ok(simplex::tableau_row(&self.a, &self.factor_basis()?, r))
+    }
+
+    /// Fill in the shadow prices, reduced costs and basis status of `solution`
+    /// from the final basis. Does nothing unless the last solve was optimal.
+    pub fn attach_duals(&self, solution: &mut LpSolution) -> Result<(), SolveError> {
+        match self.final_basis() {
+            Some(view) => view.attach_duals(solution),
+            None => Ok(()),
         }
+    }
 
-        self.remove_artificial_from_basis(&mut bmat, orig_n)
-            .map_err(SolveError::InvalidModel)?;
+    /// Compute objective and right-hand side ranging of the optimal basis.
+    ///
+    /// `model` must be the model this state was solved from.
+    pub fn sensitivity(&self, model: &LpModel) -> Result<Sensitivity, SolveError> {
+        self.final_basis()
+            .ok_or_else(|| {
+                SolveError::Other("sensitivity analysis requires an optimal basis".into())
+            })?
+            .sensitivity(model)
+    }
 
-        self.a = orig_a;
-        self.c = orig_c;
-        let mut used = vec![false; orig_n];
-        for &b in &self.basis {
-            if b < orig_n {
-                used[b] = true;
-            }
+    /// Returns a view of the basis if the last solve ended on an optimal basis.
+    fn final_basis(&self) -> Option<BasisView<'_, A>> {
+        if self.status != SolveStatus::Optimal {
+            return None;
         }
-        self.non_basis = (0..orig_n).filter(|&j| !used[j]).collect();
-        Ok(())
+        Some(BasisView {
+            layout: &self.layout,
+            a: &self.a,
+            b: &self.b,
+            c: &self.c,
+            lower: &self.lower,
+            upper: &self.upper,
+            x_n: &self.x_n,
+            basis: &self.basis,
+            non_basis: &self.non_basis,
+            minimise: self.minimise,
+            scale: Some(&self.scale),
+        })
     }
 
-    /// Phase 2 of the simplex method to optimize the LP.
-    fn phase2(&mut self, max_iter: usize, tol: f64) -> Result<(), SolveError> {
-        let mut bmat = self.build_bmat();
-        self.run_simplex(&mut bmat, max_iter, tol)
-    }
-
-    /// Initialize the basis using slack, surplus, and identity columns.
-    pub fn init_basis(&mut self) {
-        let m = self.a.rows();
-        let n = self.a.cols();
-
-        let mut basis = vec![None; m];
-        let mut used = vec![false; n];
-
-        for (j, used_j) in used.iter_mut().enumerate().take(n) {
-            let mut one_row = None;
-            let mut ok = true;
-            for i in 0..m {
-                let v = self.a.get(i, j);
-                if v.abs() > 1e-12 {
-                    if (v - 1.0).abs() < 1e-12 {
-                        if one_row.is_some() {
-                            ok = false;
-                            break;
-                        }
-                        one_row = Some(i);
-                    } else {
-                        ok = false;
-                        break;
-                    }
-                }
-            }
-            if ok && one_row.is_some_and(|r| basis[r].is_none()) {
-                let r = one_row.unwrap();
-                basis[r] = Some(j);
-                *used_j = true;
-            }
+    /// Phase 1 of the two-phase simplex method to remove artificial variables.
+    ///
+    /// Maximises the negated sum of the artificial columns. If it cannot be
+    /// brought to zero the LP is infeasible, and the phase 1 duals give its
+    /// Farkas certificate; otherwise the artificial columns are fixed at zero
+    /// and those that left the basis are dropped.
+    fn phase1(&mut self, max_iter: usize, tol: f64) -> Result<(), SolveError> {
+        let n = self.layout.n_cols;
+        let mut c = vec![0.0; self.c.len()];
+        c[n..].fill(-1.0);
+        let orig_c = std::mem::replace(&mut self.c, c);
+        self.upper[n..].fill(f64::INFINITY);
+
+        self.run_simplex(max_iter, tol)?;
+
+        let sum_art = simplex::column_values(&self.basis, &self.x_b, &self.x_n)[n..]
+            .iter()
+            .sum::<f64>();
+
+        let infeasible = sum_art > tol;
+        if infeasible {
+            let pi = self.compute_duals(&self.factor_basis()?);
+            self.certificate = Some(self.farkas_certificate(&pi));
         }
 
-        if basis.iter().all(|b| b.is_some()) {
-            self.basis = basis.into_iter().map(|b| b.unwrap()).collect();
-            self.non_basis = (0..n).filter(|j| !used[*j]).collect();
-        } else {
-            self.basis = (0..m).collect();
-            self.non_basis = (m..n).collect();
+        self.c = orig_c;
+        self.upper[n..].fill(0.0);
+        if infeasible {
+            self.status = SolveStatus::Infeasible;
+            return Ok(());
         }
+
+        self.non_basis.retain(|&j| j < n);
+        Ok(())
+    }
+
+    /// Factor the current basis matrix `B` from scratch.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SolveError::NumericalFailure`] if `B` is singular.
+    pub fn factor_basis(&self) -> Result<BasisFactor<A>, SolveError> {
+        BasisFactor::new(&self.a, &self.basis).map_err(SolveError::NumericalFailure)
     }
 
-    /// Build the current basis matrix `B` from the full tableau `A`.
-    pub fn build_bmat(&self) -> A {
-        let m = self.a.rows();
-        let mut bmat = A::new(m, m);
-        for i in 0..m {
-            for j in 0..m {
-                bmat.set(i, j, self.a.get(i, self.basis[j]));
+    /// Factor the current basis matrix, first repairing it with
+    /// [`simplex::repair_basis`] if it is singular.
+    ///
+    /// Columns swapped out of the basis become non-basic at their current
+    /// value in `x_n`; artificial columns are dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SolveError::NumericalFailure`] if the basis cannot be repaired.
+    pub fn factor_or_repair(&mut self) -> Result<BasisFactor<A>, SolveError> {
+        if let Ok(factor) = self.factor_basis() {
+            return Ok(factor);
+        }
+
+        let swaps = simplex::repair_basis(&self.a, &self.layout, &mut self.basis)
+            .map_err(SolveError::NumericalFailure)?;
+        for (slack, leaving) in swaps {
+            let pos = self.non_basis.iter().position(|&j| j == slack).unwrap();
+            if self.artificial_row(leaving).is_some() {
+                self.non_basis.remove(pos);
+            } else {
+                self.non_basis[pos] = leaving;
             }
         }
-        bmat
+        self.factor_basis()
     }
 
-    /// Compute the values of the basic variables by solving `B x_B = b`.
-    pub fn compute_basic_solution(&self, bmat: &mut A) -> Result<Vec<f64>, String> {
-        let mut xb = self.b.clone();
-        bmat.mldivide(&mut xb).map_err(|e| format!("gauss failed: {e}"))?;
-        Ok(xb)
+    /// Compute the values of the basic variables by solving `B x_B = b - N x_N`.
+    pub fn compute_basic_solution(&self, factor: &BasisFactor<A>) -> Vec<f64> {
+        let rhs = simplex::nonbasic_rhs(&self.a, &self.b, &self.non_basis, &self.x_n);
+        simplex::basic_solution(factor, &rhs)
     }
 
     /// Run the main simplex iteration loop.
-    fn run_simplex(
-        &mut self,
-        bmat: &mut A,
-        max_iter: usize,
-        tol: f64,
-    ) -> Result<(), SolveError> {
+    ///
+    /// If the bounds were perturbed along the way, they are restored once the
+    /// perturbed problem is solved, and the simplex continues from that basis.
+    fn run_simplex(&mut self, max_iter: usize, tol: f64) -> Result<(), SolveError> {
+        loop {
+            self.run_primal(max_iter, tol)?;
+            if !self.remove_perturbation(max_iter, tol)? {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Iterate until the current, possibly perturbed, problem is solved.
+    ///
+    /// Pivots that do not move the solution are counted. While a run of them
+    /// lasts, ratio-test ties are broken by [`tie_break`](Self::tie_break); after
+    /// [`perturb_after`](Self::perturb_after) of them the bounds are perturbed,
+    /// and after [`bland_after`](Self::bland_after) Bland's rule takes over
+    /// until a pivot makes progress.
+    fn run_primal(&mut self, max_iter: usize, tol: f64) -> Result<(), SolveError> {
+        let mut factor = self.factor_basis()?;
+        let mut weights =
+            PricingWeights::new(self.pricing, &self.a, &factor, &self.non_basis);
+        let mut degenerate = 0;
+
         let current_iter = self.iteration;
         for iter in current_iter..max_iter {
             self.iteration = iter;
 
-            let pi = self.compute_duals(bmat)?;
-            let Some((nb_pos, entering)) = self.choose_entering(&pi, tol) else {
+            self.x_b = self.compute_basic_solution(&factor);
+            self.update_objective();
+
+            let bland = degenerate >= self.bland_after;
+            let pi = self.compute_duals(&factor);
+            let Some((nb_pos, entering, dir)) =
+                self.choose_entering(&pi, &weights, bland, tol)
+            else {
                 self.status = SolveStatus::Optimal;
                 return Ok(());
             };
 
-            let d = self.compute_direction(bmat, entering)?;
-            let Some((leave_row, theta)) = self.choose_leaving(&d, tol) else {
+            let tie_break = if bland {
+                TieBreak::Bland
+            } else if degenerate > 0 {
+                self.tie_break
+            } else {
+                TieBreak::Pivot
+            };
+            let d = self.compute_direction(&factor, entering);
+            let Some((step, theta)) =
+                self.choose_leaving(&factor, &d, entering, dir, tie_break, tol)
+            else {
                 self.status = SolveStatus::Unbounded;
+                self.certificate = Some(self.unbounded_certificate(&d, entering, dir));
                 return Ok(());
             };
 
-            self.update_primal(&d, leave_row, theta);
-            self.pivot(bmat, nb_pos, leave_row, entering);
-            self.update_objective();
+            self.x_n[entering] += dir * theta;
+            match step {
+                Step::Flip => {
+                    self.x_n[entering] = if dir > 0.0 {
+                        self.upper[entering]
+                    } else {
+                        self.lower[entering]
+                    };
+                    degenerate = 0;
+                }
+                Step::Pivot { row, bound } => {
+                    degenerate = if theta > tol { 0 } else { degenerate + 1 };
+                    let pivot = (row, entering, self.basis[row]);
+                    weights.update(&self.a, &factor, &self.non_basis, &d, pivot);
+                    self.pivot(&mut factor, &d, nb_pos, row, entering, bound)?;
+                }
+            }
+
+            if degenerate == self.perturb_after && self.perturbed.is_empty() {
+                self.perturb_bounds();
+            }
 
             if self.logging && (iter + 1) % self.log_interval == 0 {
                 println!(
                     "Iteration {:>4}: Objective = {:>12.6}",
                     iter + 1,
-                    if self.minimise { -self.objective } else { self.objective }
+                    self.scale.objective_value(if self.minimise {
+                        -self.objective
+                    } else {
+                        self.objective
+                    })
                 );
             }
         }
@@ -420,216 +814,349 @@ impl<A: Matrix> PrimalSimplexState<A> {
         Err(SolveError::Other("max iterations reached".into()))
     }
 
-    /// Compute dual variables for the current basis.
-    fn compute_duals(&self, bmat: &A) -> Result<Vec<f64>, SolveError> {
-        let m = bmat.rows();
-        let mut pi = (0..m).map(|i| self.c[self.basis[i]]).collect::<Vec<_>>();
-
-        let mut bt = A::new(m, m);
-        for i in 0..m {
-            for j in 0..m {
-                bt.set(i, j, bmat.get(j, i));
+    /// The Farkas certificate given by the duals `pi` of an optimal phase 1
+    /// basis with a positive sum of artificial columns.
+    ///
+    /// Every structural and slack column `j` then has `πᵀA_j >= 0` if it rests
+    /// at its lower bound and `<= 0` at its upper bound, so `πᵀAx` over the
+    /// bounds is smallest at the phase 1 solution, where it exceeds `πᵀb` by the
+    /// sum of the artificial columns. The slack bounds make `π` non-negative on
+    /// `<=` rows and non-positive on `>=` rows.
+    fn farkas_certificate(&self, pi: &[f64]) -> Certificate {
+        let farkas = pi.iter().zip(&self.scale.rows).map(|(p, r)| p * r).collect();
+        Certificate::infeasible(farkas)
+    }
+
+    /// The direction of unboundedness along which column `entering` moves in
+    /// direction `dir`, changing the basic variables by `-dir · d`.
+    fn unbounded_certificate(&self, d: &[f64], entering: usize, dir: f64) -> Certificate {
+        let mut ray = vec![0.0; self.c.len()];
+        ray[entering] = dir;
+        for (&j, &d) in self.basis.iter().zip(d) {
+            ray[j] = -dir * d;
+        }
+        ray.truncate(self.layout.n_vars);
+        for (j, r) in ray.iter_mut().enumerate() {
+            *r = self.scale.value(j, *r);
+        }
+        Certificate::unbounded(ray)
+    }
+
+    /// Relax the finite bounds of the basic structural and slack columns by
+    /// small pseudo-random amounts, recording the originals in `perturbed`.
+    ///
+    /// Degenerate basic variables then lie strictly inside their bounds, so the
+    /// ratio test can take positive steps again.
+    fn perturb_bounds(&mut self) {
+        let basis = self.basis.clone();
+        for j in basis.into_iter().filter(|&j| j < self.layout.n_cols) {
+            let (lower, upper) = (self.lower[j], self.upper[j]);
+            self.perturbed.push((j, lower, upper));
+            let shift =
+                |bound: f64| PERTURBATION * (1.0 + bound.abs()) * (1.0 + jitter(j));
+            if lower.is_finite() {
+                self.lower[j] -= shift(lower);
             }
+            if upper.is_finite() {
+                self.upper[j] += shift(upper);
+            }
+        }
+    }
+
+    /// Restore the bounds changed by [`perturb_bounds`](Self::perturb_bounds).
+    ///
+    /// Non-basic columns resting at a perturbed bound move back to the original
+    /// one. An optimal basis of the perturbed problem stays dual feasible, but
+    /// its basic variables may now violate their bounds by about the size of
+    /// the perturbation; the dual simplex brings them back within them. Returns
+    /// whether the primal simplex needs to run again.
+    fn remove_perturbation(
+        &mut self,
+        max_iter: usize,
+        tol: f64,
+    ) -> Result<bool, SolveError> {
+        if self.perturbed.is_empty() {
+            return Ok(false);
         }
 
-        bt.mldivide(&mut pi)
-            .map_err(|e| SolveError::Other(format!("dual solve failed: {e}")))?;
+        for (j, lower, upper) in std::mem::take(&mut self.perturbed) {
+            self.lower[j] = lower;
+            self.upper[j] = upper;
+            self.x_n[j] = self.x_n[j].clamp(lower, upper);
+        }
+        if self.status != SolveStatus::Optimal {
+            return Ok(false);
+        }
 
-        Ok(pi)
+        self.restore_feasibility(max_iter, tol)?;
+        Ok(self.status == SolveStatus::Optimal)
     }
 
-    /// Choose entering variable using reduced costs.
-    fn choose_entering(&self, pi: &[f64], tol: f64) -> Option<(usize, usize)> {
-        self.non_basis
+    /// Make a dual feasible basis primal feasible with the dual simplex.
+    ///
+    /// Sets the status to [`Infeasible`](SolveStatus::Infeasible) if a basic
+    /// variable cannot be brought within its bounds.
+    fn restore_feasibility(
+        &mut self,
+        max_iter: usize,
+        tol: f64,
+    ) -> Result<(), SolveError> {
+        let mut factor = self.factor_basis()?;
+
+        let current_iter = self.iteration;
+        for iter in current_iter..max_iter {
+            self.iteration = iter;
+
+            self.x_b = self.compute_basic_solution(&factor);
+            self.update_objective();
+
+            let Some((row, bound)) = self.choose_infeasible(tol) else {
+                return Ok(());
+            };
+
+            let pi = self.compute_duals(&factor);
+            let rho = simplex::compute_row(&factor, row);
+            let increase = bound > self.x_b[row];
+            let Some((nb_pos, entering)) =
+                self.choose_dual_entering(&pi, &rho, increase, tol)
+            else {
+                self.status = SolveStatus::Infeasible;
+                return Ok(());
+            };
+
+            let d = self.compute_direction(&factor, entering);
+            self.pivot(&mut factor, &d, nb_pos, row, entering, bound)?;
+        }
+
+        Err(SolveError::Other("max iterations reached".into()))
+    }
+
+    /// Choose the basic variable furthest outside its bounds, together with the
+    /// bound it is moved to, as the dual simplex does.
+    fn choose_infeasible(&self, tol: f64) -> Option<(usize, f64)> {
+        self.x_b
             .iter()
             .enumerate()
-            .filter_map(|(pos, &j)| {
-                let rc = self.c[j]
-                    - (0..pi.len()).map(|i| pi[i] * self.a.get(i, j)).sum::<f64>();
-                (rc > tol).then_some((pos, j, rc))
+            .filter_map(|(i, &v)| {
+                let j = self.basis[i];
+                if self.lower[j] - v > tol {
+                    Some((i, self.lower[j], self.lower[j] - v))
+                } else if v - self.upper[j] > tol {
+                    Some((i, self.upper[j], v - self.upper[j]))
+                } else {
+                    None
+                }
             })
             .max_by(|a, b| a.2.partial_cmp(&b.2).unwrap())
-            .map(|(pos, j, _)| (pos, j))
+            .map(|(i, bound, _)| (i, bound))
     }
 
-    /// Compute the simplex direction `d = B^{-1} A_j`.
-    fn compute_direction(
+    /// Choose the entering column with the dual ratio test on row `ρᵀ A`, as
+    /// the dual simplex does.
+    ///
+    /// The leaving variable changes by `-α_j` per unit increase of non-basic
+    /// column `j`, so raising it needs a column with `α_j < 0` that can increase
+    /// or one with `α_j > 0` that can decrease (and conversely for lowering it).
+    fn choose_dual_entering(
         &self,
-        bmat: &mut A,
-        entering: usize,
-    ) -> Result<Vec<f64>, SolveError> {
-        let mut d = (0..bmat.rows()).map(|i| self.a.get(i, entering)).collect::<Vec<_>>();
+        pi: &[f64],
+        rho: &[f64],
+        increase: bool,
+        tol: f64,
+    ) -> Option<(usize, usize)> {
+        let sign = if increase { -1.0 } else { 1.0 };
+        self.non_basis
+            .iter()
+            .enumerate()
+            .filter_map(|(pos, &j)| {
+                let alpha = simplex::column_dot(&self.a, j, rho);
+                let movable = if sign * alpha > tol {
+                    self.x_n[j] < self.upper[j]
+                } else if sign * alpha < -tol {
+                    self.x_n[j] > self.lower[j]
+                } else {
+                    false
+                };
+                movable.then(|| {
+                    let rc = simplex::reduced_cost(&self.a, &self.c, pi, j);
+                    (pos, j, rc.abs() / alpha.abs(), alpha.abs())
+                })
+            })
+            .min_by(|a, b| {
+                a.2.partial_cmp(&b.2).unwrap().then(b.3.partial_cmp(&a.3).unwrap())
+            })
+            .map(|(pos, j, _, _)| (pos, j))
+    }
 
-        bmat.mldivide(&mut d)
-            .map_err(|e| SolveError::Other(format!("direction solve failed: {e}")))?;
+    /// Compute dual variables for the current basis.
+    fn compute_duals(&self, factor: &BasisFactor<A>) -> Vec<f64> {
+        simplex::compute_duals(factor, &self.c, &self.basis)
+    }
+
+    /// Choose entering variable using reduced costs.
+    ///
+    /// A column with a positive reduced cost improves the objective by
+    /// increasing, one with a negative reduced cost by decreasing; it is only
+    /// eligible if its bounds allow that move. The eligible column with the
+    /// best score under `weights` enters, or under Bland's rule the one with
+    /// the smallest index. Returns its position in the non-basis, its column
+    /// and the direction (`1.0` or `-1.0`) it moves in.
+    fn choose_entering(
+        &self,
+        pi: &[f64],
+        weights: &PricingWeights,
+        bland: bool,
+        tol: f64,
+    ) -> Option<(usize, usize, f64)> {
+        let eligible = self.non_basis.iter().enumerate().filter_map(|(pos, &j)| {
+            let rc = simplex::reduced_cost(&self.a, &self.c, pi, j);
+            let dir = if rc > tol && self.x_n[j] < self.upper[j] {
+                1.0
+            } else if rc < -tol && self.x_n[j] > self.lower[j] {
+                -1.0
+            } else {
+                return None;
+            };
+            Some((pos, j, dir, weights.score(j, rc)))
+        });
 
-        Ok(d)
+        let best = if bland {
+            eligible.min_by_key(|&(_, j, _, _)| j)
+        } else {
+            eligible.max_by(|a, b| a.3.partial_cmp(&b.3).unwrap())
+        };
+        best.map(|(pos, j, dir, _)| (pos, j, dir))
     }
 
-    /// Choose leaving variable using minimum ratio test.
-    fn choose_leaving(&self, d: &[f64], tol: f64) -> Option<(usize, f64)> {
-        (0..d.len())
-            .filter(|&i| d[i] > tol)
-            .map(|i| (i, self.x_b[i] / d[i]))
-            .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
+    /// Compute the simplex direction `d = B^{-1} A_j`.
+    fn compute_direction(&self, factor: &BasisFactor<A>, entering: usize) -> Vec<f64> {
+        simplex::compute_direction(&self.a, factor, entering)
     }
 
-    /// Update the primal solution vector `x_B` after a pivot.
-    fn update_primal(&mut self, d: &[f64], leave: usize, theta: f64) {
-        for (xi, di) in self.x_b.iter_mut().zip(d.iter()) {
-            *xi -= theta * di;
-            if (*xi).abs() < 1e-12 {
-                *xi = 0.0;
+    /// Choose the leaving variable using the bounded minimum ratio test.
+    ///
+    /// Moving the entering column by `θ` in direction `dir` changes the basic
+    /// variables by `-θ · dir · d`, so each one is blocked by the bound it moves
+    /// towards. The entering column is itself blocked by its opposite bound.
+    /// Ties are broken according to `tie_break`. Returns `None` if nothing
+    /// blocks the move.
+    fn choose_leaving(
+        &self,
+        factor: &BasisFactor<A>,
+        d: &[f64],
+        entering: usize,
+        dir: f64,
+        tie_break: TieBreak,
+        tol: f64,
+    ) -> Option<(Step, f64)> {
+        let blocking = (0..d.len()).filter_map(|i| {
+            let rate = -dir * d[i];
+            let j = self.basis[i];
+            let bound = if rate > tol {
+                self.upper[j]
+            } else if rate < -tol {
+                self.lower[j]
+            } else {
+                return None;
+            };
+            bound
+                .is_finite()
+                .then(|| (i, bound, ((bound - self.x_b[i]) / rate).max(0.0), rate))
+        });
+
+        let blocking = if tie_break == TieBreak::Pivot {
+            blocking.min_by(|a, b| {
+                a.2.partial_cmp(&b.2)
+                    .unwrap()
+                    .then(b.3.abs().partial_cmp(&a.3.abs()).unwrap())
+            })
+        } else {
+            let blocking = blocking.collect::<Vec<_>>();
+            let min = blocking.iter().fold(f64::INFINITY, |m, b| m.min(b.2));
+            let ties = blocking.into_iter().filter(|b| b.2 <= min + tol);
+            if tie_break == TieBreak::Bland {
+                ties.min_by_key(|b| self.basis[b.0])
+            } else {
+                self.lexicographic_min(factor, ties.collect())
             }
+        };
+
+        let range = self.upper[entering] - self.lower[entering];
+        match blocking {
+            Some((_, _, theta, _)) if range <= theta => Some((Step::Flip, range)),
+            Some((row, bound, theta, _)) => Some((Step::Pivot { row, bound }, theta)),
+            None if range.is_finite() => Some((Step::Flip, range)),
+            None => None,
+        }
+    }
+
+    /// Break a tie between blocking rows `(row, bound, θ, rate)` with the
+    /// lexicographic ratio test.
+    ///
+    /// Perturbing the right-hand side by `(ε, ε², …)` moves basic variable `i`
+    /// by `ρ_i · (ε, ε², …)`, where `ρ_i` is row `i` of `B⁻¹`, so its ratio shrinks
+    /// by `ρ_i / rate_i`. The rows of `B⁻¹` are linearly independent, so the
+    /// row with the lexicographically largest `ρ_i / rate_i` is unique.
+    fn lexicographic_min(
+        &self,
+        factor: &BasisFactor<A>,
+        ties: Vec<(usize, f64, f64, f64)>,
+    ) -> Option<(usize, f64, f64, f64)> {
+        if ties.len() <= 1 {
+            return ties.into_iter().next();
         }
-        self.x_b[leave] = theta;
+        ties.into_iter()
+            .map(|t| {
+                let rho = simplex::compute_row(factor, t.0);
+                (t, rho.iter().map(|r| r / t.3).collect::<Vec<_>>())
+            })
+            .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
+            .map(|(t, _)| t)
     }
 
     /// Perform pivot operations on the basis and non-basis sets.
+    ///
+    /// The leaving column becomes non-basic at `bound`. Artificial columns are
+    /// dropped once they leave the basis.
     fn pivot(
         &mut self,
-        bmat: &mut A,
+        factor: &mut BasisFactor<A>,
+        d: &[f64],
         enter_pos: usize,
         leave_row: usize,
         entering: usize,
-    ) {
+        bound: f64,
+    ) -> Result<(), SolveError> {
         let leaving = self.basis[leave_row];
         self.basis[leave_row] = entering;
-        self.non_basis[enter_pos] = leaving;
-
-        for i in 0..bmat.rows() {
-            bmat.set(i, leave_row, self.a.get(i, entering));
+        self.x_n[leaving] = bound;
+        if self.artificial_row(leaving).is_some() {
+            self.non_basis.remove(enter_pos);
+        } else {
+            self.non_basis[enter_pos] = leaving;
         }
-    }
 
-    /// Update the current objective value.
-    fn update_objective(&mut self) {
-        self.objective = self
-            .basis
-            .iter()
-            .enumerate()
-            .map(|(i, &v)| self.c[v] * self.x_b[i])
-            .sum();
+        factor
+            .replace_column(&self.a, leave_row, entering, d)
+            .map_err(SolveError::NumericalFailure)
     }
 
-    /// Prepare the LP for phase 1 of two-phase simplex by adding artificial variables.
-    pub fn setup_phase1(&mut self, orig_n: usize) -> (A, Vec<f64>, A) {
-        let m = self.a.rows();
-        let n = self.a.cols();
-
-        let mut a_aug = A::new(m, n + m);
-        let mut b_aug = self.b.clone();
-
-        for (i, bval) in b_aug.iter_mut().enumerate().take(m) {
-            if *bval < 0.0 {
-                *bval = -*bval;
-                for j in 0..n {
-                    a_aug.set(i, j, -self.a.get(i, j));
-                }
-            } else {
-                for j in 0..n {
-                    a_aug.set(i, j, self.a.get(i, j));
-                }
-            }
-
-            for j in 0..m {
-                a_aug.set(i, n + j, if i == j { 1.0 } else { 0.0 });
-            }
-        }
-
-        let mut c_aug = vec![0.0; n + m];
-        for j in 0..m {
-            c_aug[n + j] = -1.0;
-        }
-
-        let orig_a = self.a.clone();
-        let orig_c = self.c.clone();
-
-        self.a = a_aug;
-        self.c = c_aug;
-        self.basis = (orig_n..orig_n + m).collect();
-        self.non_basis = (0..orig_n).collect();
-        self.x_b = b_aug;
-
-        let mut bmat = A::new(m, m);
-        for i in 0..m {
-            for j in 0..m {
-                bmat.set(i, j, self.a.get(i, self.basis[j]));
-            }
-        }
-
-        (orig_a, orig_c, bmat)
+    /// Returns the row owning column `j` if it is an artificial column.
+    fn artificial_row(&self, j: usize) -> Option<usize> {
+        j.checked_sub(self.layout.n_cols)
+            .and_then(|k| self.art_rows.get(k).copied())
     }
 
-    /// Remove artificial variables from the basis once feasibility is established.
-    pub fn remove_artificial_from_basis(
-        &mut self,
-        bmat: &mut A,
-        orig_n: usize,
-    ) -> Result<(), String> {
-        let m = bmat.rows();
-        for row in 0..m {
-            if self.basis[row] >= orig_n {
-                let mut pivot = None;
-                for (nb_pos, &j) in self.non_basis.iter().enumerate() {
-                    if j < orig_n && self.a.get(row, j).abs() > 1e-12 {
-                        pivot = Some((nb_pos, j));
-                        break;
-                    }
-                }
-
-                if let Some((nb_pos, j)) = pivot {
-                    let leaving = self.basis[row];
-                    self.basis[row] = j;
-                    self.non_basis[nb_pos] = leaving;
-                    for i in 0..m {
-                        bmat.set(i, row, self.a.get(i, j));
-                    }
-                } else if self.x_b[row].abs() > 1e-12 {
-                    return Err(
-                        "artificial variable left in basis with non-zero value".into()
-                    );
-                } else {
-                    for (nb_pos, &j) in self.non_basis.iter().enumerate() {
-                        if j < orig_n && self.a.get(row, j).abs() < 1e-12 {
-                            let leaving = self.basis[row];
-                            self.basis[row] = j;
-                            self.non_basis[nb_pos] = leaving;
-                            for i in 0..m {
-                                bmat.set(i, row, self.a.get(i, j));
-                            }
-                            break;
-                        }
-                    }
-                }
-            }
-        }
-        Ok(())
+    /// Update the current objective value.
+    fn update_objective(&mut self) {
+        self.objective =
+            simplex::basis_objective(&self.c, &self.basis, &self.x_b, &self.x_n);
     }
+}
 
-    /// Extract the final solution and objective value.
-    pub fn extract_solution(&self, orig_n: usize) -> (Vec<f64>, f64) {
-        let m = self.a.rows();
-        let mut sol = vec![0.0; orig_n];
-
-        for i in 0..m {
-            if self.basis[i] < orig_n {
-                sol[self.basis[i]] = self.x_b[i];
-            }
-        }
-
-        let mut obj = self
-            .basis
-            .iter()
-            .enumerate()
-            .filter(|(_, v)| **v < orig_n)
-            .map(|(i, v)| self.c[*v] * self.x_b[i])
-            .sum::<f64>();
-
-        if self.minimise {
-            obj = -obj;
-        }
-
-        (sol, obj)
-    }
+/// A pseudo-random number in `[0, 1)` derived from column `j`, so that bound
+/// perturbations are reproducible.
+fn jitter(j: usize) -> f64 {
+    let hash = (j as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    (hash >> 11) as f64 / (1u64 << 53) as f64
 }
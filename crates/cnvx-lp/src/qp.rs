@@ -0,0 +1,920 @@
+//! Convex quadratic programs, solved by a primal-dual interior-point method.
+//!
+//! The [`QpSolver`] handles models whose objective has quadratic terms, built
+//! with [`QuadExpr`](crate::QuadExpr), such as mean-variance portfolios or
+//! least-squares fits with linear constraints. Its solutions carry shadow
+//! prices and reduced costs like those of the LP solvers.
+
+use std::collections::BTreeMap;
+
+use cnvx_core::{Sense, SolveError, SolveStatus};
+use cnvx_math::{
+    Factorisation, Matrix, Regularisation, SparseCholesky, SparseMatrix, SymbolicCholesky,
+};
+
+use crate::simplex::Layout;
+use crate::{
+    Capabilities, Certificate, LinExpr, LinTerm, LinearConstraint, LpModel, LpSolution,
+    Objective, PrimalSimplexSolver, Solver, Var,
+};
+
+/// Fraction of the distance to the boundary taken by each step.
+const STEP_FRACTION: f64 = 0.99;
+
+/// The iterations stop once the step length falls below this value.
+const MIN_STEP: f64 = 1e-12;
+
+/// The iterations stop once an iterate exceeds this magnitude, which happens
+/// when the problem is infeasible or unbounded.
+const DIVERGENCE: f64 = 1e12;
+
+/// Static regularisation `δ` of the KKT matrix `[Q + D + δI, Aᵀ; A, -δI]`.
+/// Iterative refinement against the unregularised matrix removes its effect.
+const KKT_DELTA: f64 = 1e-9;
+
+/// Largest regularisation `δ` tried before giving up. When the factorisation
+/// fails or the step length vanishes, typically because small pivots of free
+/// columns cancel, `δ` grows a hundredfold and the iteration is retried.
+const MAX_KKT_DELTA: f64 = 1e-5;
+
+/// Rounds of iterative refinement of each KKT solve.
+const REFINEMENT_STEPS: usize = 3;
+
+/// The quadratic form is convex if it stays positive definite after shifting
+/// its diagonal by this fraction of its largest entry.
+const CONVEXITY_TOL: f64 = 1e-9;
+
+/// Tolerance of the LPs that decide whether a problem the iterations could not
+/// solve is infeasible or unbounded.
+const CLASSIFY_TOL: f64 = 1e-7;
+
+/// Primal-dual interior-point solver for convex quadratic programs.
+///
+/// Minimizes `½ xᵀQx + cᵀx`, or maximizes its negation, subject to the
+/// linear constraints and variable bounds of the model. The quadratic part of
+/// a minimized objective must be convex, and that of a maximized objective
+/// concave; this is checked before solving with a [`SparseCholesky`]
+/// factorisation of `Q`.
+///
+/// Each iteration takes a Mehrotra predictor-corrector step from the
+/// quasi-definite KKT system `[Q + D, Aᵀ; A, 0]`, where `D` holds the ratios
+/// of the bound duals to the bound slacks. The KKT matrix keeps the same
+/// pattern in every iteration, so its symbolic analysis is done once, and the
+/// factor is reused for the predictor and corrector directions. When a
+/// factorisation breaks down, the regularisation of the KKT matrix grows and
+/// the iteration is repeated.
+///
+/// If the iterations stall or diverge, phase-1 and recession-direction LPs
+/// solved with the [`PrimalSimplexSolver`] decide whether the model is
+/// infeasible or unbounded, and the solution carries the matching
+/// [`Certificate`].
+///
+/// Models without quadratic terms are solved as LPs by the same method.
+///
+/// # Examples
+///
+/// A mean-variance portfolio: minimize the variance of the return, subject to
+/// a minimum expected return.
+///
+/// ```rust
+/// # use cnvx_lp::*;
+/// let mut model = LpModel::new();
+/// let stocks = model.add_var().name("stocks").finish();
+/// let bonds = model.add_var().name("bonds").finish();
+/// model += (stocks + bonds).eq(1.0).named("budget");
+/// model += (0.10 * stocks + 0.04 * bonds).geq(0.07).named("return");
+///
+/// let variance = 0.04 * (stocks * stocks) + 0.01 * (bonds * bonds)
+///     + 0.004 * (stocks * bonds);
+/// model.add_objective(Objective::minimize(variance).name("risk"));
+///
+/// let solution = QpSolver::new().solve(&model).unwrap();
+/// assert!((solution.value(stocks) - 0.5).abs() < 1e-6);
+/// assert!((solution.value(bonds) - 0.5).abs() < 1e-6);
+/// assert!((solution.objective_value.unwrap() - 0.0135).abs() < 1e-8);
+///
+/// // Each extra unit of required return costs this much variance.
+/// assert!((solution.dual("return").unwrap() - 0.5).abs() < 1e-6);
+/// ```
+///
+/// A least-squares fit of the line `a t + b` to three points, with the slope
+/// limited to one:
+///
+/// ```rust
+/// # use cnvx_lp::*;
+/// let mut model = LpModel::new();
+/// let a = model.add_var().free().name("a").finish();
+/// let b = model.add_var().free().name("b").finish();
+/// model += a.leq(1.0).named("max_slope");
+///
+/// let mut loss = QuadExpr::default();
+/// for (t, y) in [(0.0, 1.0), (1.0, 3.0), (2.0, 5.0)] {
+///     let residual = t * a + b + -y;
+///     loss += residual.clone() * residual;
+/// }
+/// model.add_objective(Objective::minimize(loss).name("loss"));
+///
+/// let solution = QpSolver::new().solve(&model).unwrap();
+/// assert!((solution.value(a) - 1.0).abs() < 1e-6);
+/// assert!((solution.value(b) - 2.0).abs() < 1e-6);
+/// ```
+pub struct QpSolver {
+    /// Relative tolerance on the primal and dual residuals and the
+    /// complementarity gap.
+    pub tolerance: f64,
+    /// The maximum number of interior-point iterations before terminating.
+    pub max_iter: usize,
+    /// Whether to log iteration details during solving.
+    pub logging: bool,
+
+    /// Cached objective value from the most recent solve.
+    last_objective: Option<f64>,
+    /// Cached solution vector from the most recent solve.
+    last_solution: Vec<f64>,
+}
+
+impl QpSolver {
+    /// Creates a new QP solver with default settings.
+    pub fn new() -> Self {
+        Self {
+            tolerance: 1e-8,
+            max_iter: 100,
+            logging: false,
+            last_objective: None,
+            last_solution: Vec::new(),
+        }
+    }
+
+    /// Record the outcome of a solve and return its [`LpSolution`].
+    fn finish(&mut self, iterations: usize, solution: LpSolution) -> LpSolution {
+        if self.logging {
+            println!(
+                "QP interior point finished with status {:?} in {} iterations. Objective value: {}",
+                solution.status,
+                iterations,
+                solution.objective_value.unwrap_or_default()
+            );
+        }
+
+        self.last_objective = solution.objective_value;
+        self.last_solution = solution.values.clone();
+        solution
+    }
+}
+
+impl Default for QpSolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Solver for QpSolver {
+    fn name(&self) -> &str {
+        "qp-interior-point"
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            quadratic_objective: true,
+            ..Capabilities::default()
+        }
+    }
+
+    /// Solve the convex QP `model`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`check_qp`](crate::validate::check_qp),
+    /// [`SolveError::InvalidModel`] if the objective is not convex (or, when
+    /// maximized, concave), and [`SolveError::NumericalFailure`] or
+    /// [`SolveError::Other`] if the iterations fail on a model that is neither
+    /// infeasible nor unbounded.
+    fn solve(&mut self, model: &LpModel) -> Result<LpSolution, SolveError> {
+        crate::validate::check_qp(model)?;
+
+        let mut state = QpState::new(model)?;
+        state.logging = self.logging;
+        if let Err(err) = state.solve(self.max_iter, self.tolerance) {
+            let solution = classify(model)?.ok_or(err)?;
+            return Ok(self.finish(state.iteration, solution));
+        }
+
+        let values = state.x[..model.vars().len()].to_vec();
+        let objective = model.objective().expect("checked by check_qp");
+        let value = objective.evaluate(&values) - objective.expr.constant;
+        let mut solution = LpSolution::new(values, Some(value), SolveStatus::Optimal)
+            .with_activities(model);
+        state.attach_duals(model, &mut solution);
+        Ok(self.finish(state.iteration, solution))
+    }
+
+    fn objective_value(&self) -> Option<f64> {
+        self.last_objective
+    }
+
+    fn solution_vector(&self) -> Vec<f64> {
+        self.last_solution.clone()
+    }
+}
+
+/// The KKT matrix `[Q + D + δI, Aᵀ; A, -δI]`, whose off-diagonal entries are
+/// fixed, factored with one symbolic analysis for all iterations.
+struct Kkt {
+    /// Lower triangle of the matrix.
+    matrix: SparseMatrix,
+    /// Storage position in `matrix` of each diagonal entry.
+    diag: Vec<usize>,
+    /// The symbolic analysis of the pattern.
+    symbolic: SymbolicCholesky,
+    /// The factorisation, once the matrix has been factored.
+    chol: Option<SparseCholesky>,
+}
+
+impl Kkt {
+    /// Analyse the KKT pattern for the columns `cols` of an `A` with `m` rows
+    /// and the strictly lower triangle `q_lower` of `Q`.
+    fn new(
+        cols: &[Vec<(usize, f64)>],
+        m: usize,
+        q_lower: &[(usize, usize, f64)],
+    ) -> Result<Self, SolveError> {
+        let n = cols.len();
+        // Unit diagonal entries keep the whole diagonal in the pattern.
+        let mut triplets: Vec<(usize, usize, f64)> =
+            (0..n + m).map(|k| (k, k, 1.0)).collect();
+        triplets.extend_from_slice(q_lower);
+        for (j, col) in cols.iter().enumerate() {
+            triplets.extend(col.iter().map(|&(i, a)| (n + i, j, a)));
+        }
+        let matrix = SparseMatrix::from_triplets(n + m, n + m, &triplets);
+
+        // The diagonal entry leads each column of the lower triangle.
+        let mut diag = Vec::with_capacity(n + m);
+        let mut pos = 0;
+        for k in 0..n + m {
+            diag.push(pos);
+            pos += matrix.col(k).count();
+        }
+
+        let symbolic =
+            SymbolicCholesky::analyse(&matrix).map_err(SolveError::NumericalFailure)?;
+        Ok(Self { matrix, diag, symbolic, chol: None })
+    }
+
+    /// Set the diagonal of the `Q + D` block to `h`, regularise it by `delta`
+    /// and factor the matrix.
+    fn factor(&mut self, h: &[f64], delta: f64) -> Result<&SparseCholesky, SolveError> {
+        let n = h.len();
+        let values = self.matrix.values_mut();
+        for (k, &p) in self.diag.iter().enumerate() {
+            values[p] = if k < n { h[k] + delta } else { -delta };
+        }
+
+        let chol = match self.chol.take() {
+            Some(mut chol) => {
+                chol.refactor(&self.matrix).map_err(SolveError::NumericalFailure)?;
+                chol
+            }
+            None => {
+                let reg = Regularisation::quasi_definite(n, self.diag.len() - n);
+                SparseCholesky::factor_regularised(&self.symbolic, &self.matrix, reg)
+                    .map_err(SolveError::NumericalFailure)?
+            }
+        };
+        Ok(self.chol.insert(chol))
+    }
+}
+
+/// A search direction for all iterates.
+struct Direction {
+    dx: Vec<f64>,
+    dy: Vec<f64>,
+    dzl: Vec<f64>,
+    dzu: Vec<f64>,
+}
+
+impl Direction {
+    /// Whether every entry of the direction is finite.
+    fn is_finite(&self) -> bool {
+        [&self.dx, &self.dy, &self.dzl, &self.dzu]
+            .iter()
+            .all(|v| v.iter().all(|d| d.is_finite()))
+    }
+}
+
+/// Internal state of the QP interior-point method.
+///
+/// The model is converted to `min ½ xᵀQx + cᵀx` subject to `Ax = b` and
+/// `l <= x <= u`, with a slack column for each inequality row. A fixed
+/// variable becomes a free column held by an extra equality row, so that the
+/// iterates can stay strictly inside every bound. The dual is
+/// `Qx + c - Aᵀy - z_l + z_u = 0` with `z_l, z_u >= 0`.
+struct QpState {
+    /// Current iteration count.
+    iteration: usize,
+
+    /// Columns `x`, strictly inside their bounds.
+    x: Vec<f64>,
+    /// Row duals `y`.
+    y: Vec<f64>,
+    /// Duals of the lower bounds (zero for columns without one).
+    zl: Vec<f64>,
+    /// Duals of the upper bounds (zero for columns without one).
+    zu: Vec<f64>,
+
+    /// Sparse columns of the constraint matrix, as `(row, coefficient)` pairs.
+    cols: Vec<Vec<(usize, f64)>>,
+    /// Right-hand side vector `b`.
+    b: Vec<f64>,
+    /// Linear objective coefficients `c` (minimisation form).
+    c: Vec<f64>,
+    /// Diagonal of `Q` (minimisation form).
+    q_diag: Vec<f64>,
+    /// Strictly lower triangle of `Q` as `(row, column, value)` triplets.
+    q_lower: Vec<(usize, usize, f64)>,
+    /// Lower bound of each column (`-inf` if absent).
+    lower: Vec<f64>,
+    /// Upper bound of each column (`inf` if absent).
+    upper: Vec<f64>,
+    /// Whether the QP is a minimization problem.
+    minimise: bool,
+    /// Whether to log iteration details.
+    logging: bool,
+}
+
+impl QpState {
+    /// Initialize the state from `model`, starting at the middle of the
+    /// bounds, or one unit inside a single bound, with `y = 0` and unit bound
+    /// duals.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SolveError::InvalidModel`] if the objective is not convex in
+    /// its sense.
+    fn new(model: &LpModel) -> Result<Self, SolveError> {
+        let layout = Layout::new(model);
+        let n = layout.n_cols;
+        let objective = model.objective().expect("checked by check_qp");
+        let minimise = objective.sense == Sense::Minimize;
+        let sign = if minimise { 1.0 } else { -1.0 };
+
+        let mut cols: Vec<Vec<(usize, f64)>> = vec![Vec::new(); n];
+        let mut b = Vec::with_capacity(model.constraints().len());
+        for (i, cons) in model.constraints().iter().enumerate() {
+            b.push(cons.rhs - cons.expr.constant);
+            for term in &cons.expr.terms {
+                let col = &mut cols[term.var.0];
+                match col.last_mut() {
+                    Some((row, coeff)) if *row == i => *coeff += term.coeff,
+                    _ => col.push((i, term.coeff)),
+                }
+            }
+            if let Some((j, coeff)) = layout.slacks[i] {
+                cols[j].push((i, coeff));
+            }
+        }
+
+        let mut lower = vec![0.0; n];
+        let mut upper = vec![f64::INFINITY; n];
+        for var in model.vars() {
+            let j = var.id.0;
+            let (lb, ub) =
+                (var.lb.unwrap_or(f64::NEG_INFINITY), var.ub.unwrap_or(f64::INFINITY));
+            if lb == ub {
+                cols[j].push((b.len(), 1.0));
+                b.push(lb);
+                (lower[j], upper[j]) = (f64::NEG_INFINITY, f64::INFINITY);
+            } else {
+                (lower[j], upper[j]) = (lb, ub);
+            }
+        }
+
+        let mut c = vec![0.0; n];
+        for term in &objective.expr.terms {
+            c[term.var.0] += sign * term.coeff;
+        }
+
+        // `coeff * x_i * x_j` contributes `coeff` to both `Q_ij` and `Q_ji`,
+        // and `2 * coeff` to `Q_ii`.
+        let mut q_diag = vec![0.0; n];
+        let mut off: BTreeMap<(usize, usize), f64> = BTreeMap::new();
+        for term in &objective.quad {
+            let (i, j) = (term.var1.0, term.var2.0);
+            if i == j {
+                q_diag[i] += sign * 2.0 * term.coeff;
+            } else {
+                *off.entry((i.max(j), i.min(j))).or_default() += sign * term.coeff;
+            }
+        }
+        let q_lower: Vec<_> = off
+            .into_iter()
+            .filter(|&(_, v)| v != 0.0)
+            .map(|((i, j), v)| (i, j, v))
+            .collect();
+        check_convex(&q_diag, &q_lower, minimise)?;
+
+        let m = b.len();
+        let x = (0..n)
+            .map(|j| match (lower[j].is_finite(), upper[j].is_finite()) {
+                (true, true) => 0.5 * (lower[j] + upper[j]),
+                (true, false) => lower[j] + 1.0,
+                (false, true) => upper[j] - 1.0,
+                (false, false) => 0.0,
+            })
+            .collect();
+        let zl = lower.iter().map(|l| if l.is_finite() { 1.0 } else { 0.0 }).collect();
+        let zu = upper.iter().map(|u| if u.is_finite() { 1.0 } else { 0.0 }).collect();
+
+        Ok(Self {
+            iteration: 0,
+            x,
+            y: vec![0.0; m],
+            zl,
+            zu,
+            cols,
+            b,
+            c,
+            q_diag,
+            q_lower,
+            lower,
+            upper,
+            minimise,
+            logging: false,
+        })
+    }
+
+    /// Run the predictor-corrector iterations until the residuals and the
+    /// complementarity gap fall below `tol`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SolveError::NumericalFailure`] if the steps stall or the
+    /// iterates diverge, and [`SolveError::Other`] if `max_iter` iterations
+    /// are reached.
+    fn solve(&mut self, max_iter: usize, tol: f64) -> Result<(), SolveError> {
+        let n = self.x.len();
+        let n_pairs =
+            self.lower.iter().chain(&self.upper).filter(|b| b.is_finite()).count();
+        let mut kkt = Kkt::new(&self.cols, self.b.len(), &self.q_lower)?;
+        let b_norm = 1.0 + norm(&self.b);
+        let c_norm = 1.0 + norm(&self.c);
+        let mut delta = KKT_DELTA;
+
+        for iter in 0..max_iter {
+            self.iteration = iter;
+
+            let (sl, su) = self.bound_slacks();
+            let qx = self.q_mul(&self.x);
+            let rp = self.primal_residual();
+            let rd = self.dual_residual(&qx);
+            let gap = self.complementarity(&sl, &su);
+            let mu = if n_pairs > 0 { gap / n_pairs as f64 } else { 0.0 };
+
+            let objective: f64 =
+                (0..n).map(|j| (0.5 * qx[j] + self.c[j]) * self.x[j]).sum();
+            if norm(&rp) <= tol * b_norm
+                && norm(&rd) <= tol * c_norm
+                && gap <= tol * (1.0 + objective.abs())
+            {
+                return Ok(());
+            }
+
+            let largest = norm(&self.x)
+                .max(norm(&self.y))
+                .max(norm(&self.zl))
+                .max(norm(&self.zu));
+            if !largest.is_finite() || largest > DIVERGENCE {
+                return Err(SolveError::NumericalFailure(
+                    "QP interior-point iterates diverged".into(),
+                ));
+            }
+
+            // Diagonal of `Q + D`, with `D = Z_l / S_l + Z_u / S_u`.
+            let h: Vec<f64> = (0..n)
+                .map(|j| {
+                    let mut d = self.q_diag[j];
+                    if self.lower[j].is_finite() {
+                        d += self.zl[j] / sl[j];
+                    }
+                    if self.upper[j].is_finite() {
+                        d += self.zu[j] / su[j];
+                    }
+                    d
+                })
+                .collect();
+            let chol = match kkt.factor(&h, delta) {
+                Ok(chol) => chol,
+                Err(_) if delta < MAX_KKT_DELTA => {
+                    delta *= 100.0;
+                    continue;
+                }
+                Err(err) => return Err(err),
+            };
+
+            // Predictor (affine-scaling) direction.
+            let rl: Vec<f64> = (0..n).map(|j| -sl[j] * self.zl[j]).collect();
+            let ru: Vec<f64> = (0..n).map(|j| -su[j] * self.zu[j]).collect();
+            let aff = self.direction(chol, &h, &sl, &su, &rp, &rd, &rl, &ru);
+            let alpha = self.max_step(&sl, &su, &aff).min(1.0);
+
+            // Centring parameter from the predicted reduction of complementarity.
+            let mut gap_aff = 0.0;
+            for j in 0..n {
+                if self.lower[j].is_finite() {
+                    gap_aff +=
+                        (sl[j] + alpha * aff.dx[j]) * (self.zl[j] + alpha * aff.dzl[j]);
+                }
+                if self.upper[j].is_finite() {
+                    gap_aff +=
+                        (su[j] - alpha * aff.dx[j]) * (self.zu[j] + alpha * aff.dzu[j]);
+                }
+            }
+            let sigma =
+                if gap > 0.0 { (gap_aff / gap).clamp(0.0, 1.0).powi(3) } else { 0.0 };
+
+            // Corrector direction, with Mehrotra's second-order term.
+            let target = sigma * mu;
+            let rl: Vec<f64> = (0..n)
+                .map(|j| {
+                    if self.lower[j].is_finite() {
+                        target - sl[j] * self.zl[j] - aff.dx[j] * aff.dzl[j]
+                    } else {
+                        0.0
+                    }
+                })
+                .collect();
+            let ru: Vec<f64> = (0..n)
+                .map(|j| {
+                    if self.upper[j].is_finite() {
+                        target - su[j] * self.zu[j] + aff.dx[j] * aff.dzu[j]
+                    } else {
+                        0.0
+                    }
+                })
+                .collect();
+            let dir = self.direction(chol, &h, &sl, &su, &rp, &rd, &rl, &ru);
+
+            let alpha = (STEP_FRACTION * self.max_step(&sl, &su, &dir)).min(1.0);
+            let failed = alpha < MIN_STEP || !aff.is_finite() || !dir.is_finite();
+            if failed && delta < MAX_KKT_DELTA {
+                delta *= 100.0;
+                continue;
+            }
+            if failed {
+                return Err(SolveError::NumericalFailure(
+                    "QP interior-point step length vanished".into(),
+                ));
+            }
+            self.step(&dir, alpha);
+
+            if self.logging {
+                println!(
+                    "Iteration {:>3}: mu = {:>10.3e}, primal = {:>10.3e}, dual = {:>10.3e}, step = {:>6.4}",
+                    iter + 1,
+                    mu,
+                    norm(&rp),
+                    norm(&rd),
+                    alpha
+                );
+            }
+        }
+
+        Err(SolveError::Other("max iterations reached".into()))
+    }
+
+    /// Fill in the shadow prices and reduced costs of `solution`, in the sense
+    /// of the original objective. The reduced cost of a variable is the
+    /// gradient of the objective at the solution, minus the shadow prices
+    /// times its constraint coefficients.
+    fn attach_duals(&self, model: &LpModel, solution: &mut LpSolution) {
+        let sign = if self.minimise { 1.0 } else { -1.0 };
+        solution.duals =
+            self.y[..model.constraints().len()].iter().map(|y| sign * y).collect();
+
+        let mut reduced_costs = vec![0.0; model.vars().len()];
+        if let Some(obj) = model.objective() {
+            for term in &obj.expr.terms {
+                reduced_costs[term.var.0] += term.coeff;
+            }
+            for term in &obj.quad {
+                reduced_costs[term.var1.0] += term.coeff * self.x[term.var2.0];
+                reduced_costs[term.var2.0] += term.coeff * self.x[term.var1.0];
+            }
+        }
+        for (cons, dual) in model.constraints().iter().zip(&solution.duals) {
+            for term in &cons.expr.terms {
+                reduced_costs[term.var.0] -= dual * term.coeff;
+            }
+        }
+        solution.reduced_costs = reduced_costs;
+    }
+
+    /// Distances `x - l` and `u - x` of the columns to their bounds (`inf`
+    /// where a bound is absent).
+    fn bound_slacks(&self) -> (Vec<f64>, Vec<f64>) {
+        let sl = self.x.iter().zip(&self.lower).map(|(x, l)| x - l).collect();
+        let su = self.upper.iter().zip(&self.x).map(|(u, x)| u - x).collect();
+        (sl, su)
+    }
+
+    /// The product `Q v`.
+    fn q_mul(&self, v: &[f64]) -> Vec<f64> {
+        let mut out: Vec<f64> = self.q_diag.iter().zip(v).map(|(q, v)| q * v).collect();
+        for &(i, j, q) in &self.q_lower {
+            out[i] += q * v[j];
+            out[j] += q * v[i];
+        }
+        out
+    }
+
+    /// `b - A x`.
+    fn primal_residual(&self) -> Vec<f64> {
+        let mut r = self.b.clone();
+        for (col, x) in self.cols.iter().zip(&self.x) {
+            for &(i, a) in col {
+                r[i] -= a * x;
+            }
+        }
+        r
+    }
+
+    /// `Q x + c - Aᵀ y - z_l + z_u`, given `qx = Q x`.
+    fn dual_residual(&self, qx: &[f64]) -> Vec<f64> {
+        (0..self.x.len())
+            .map(|j| {
+                let aty: f64 = self.cols[j].iter().map(|&(i, a)| a * self.y[i]).sum();
+                qx[j] + self.c[j] - aty - self.zl[j] + self.zu[j]
+            })
+            .collect()
+    }
+
+    /// Total complementarity `Σ s_l z_l + Σ s_u z_u` over the finite bounds.
+    fn complementarity(&self, sl: &[f64], su: &[f64]) -> f64 {
+        (0..self.x.len())
+            .map(|j| {
+                let mut gap = 0.0;
+                if self.lower[j].is_finite() {
+                    gap += sl[j] * self.zl[j];
+                }
+                if self.upper[j].is_finite() {
+                    gap += su[j] * self.zu[j];
+                }
+                gap
+            })
+            .sum()
+    }
+
+    /// Solve the Newton system for the residuals `rp = b - Ax`, `rd` of the
+    /// dual, and the complementarity targets `rl` and `ru` of
+    /// `S_l z_l` and `S_u z_u`.
+    ///
+    /// Eliminating the bound duals leaves the KKT system
+    /// `(Q + D) dx - Aᵀ dy = -rd + rl / s_l - ru / s_u`, `A dx = rp`.
+    #[allow(clippy::too_many_arguments)]
+    fn direction(
+        &self,
+        chol: &SparseCholesky,
+        h: &[f64],
+        sl: &[f64],
+        su: &[f64],
+        rp: &[f64],
+        rd: &[f64],
+        rl: &[f64],
+        ru: &[f64],
+    ) -> Direction {
+        let n = self.x.len();
+        let mut rhs: Vec<f64> = (0..n)
+            .map(|j| {
+                let mut r = -rd[j];
+                if self.lower[j].is_finite() {
+                    r += rl[j] / sl[j];
+                }
+                if self.upper[j].is_finite() {
+                    r -= ru[j] / su[j];
+                }
+                r
+            })
+            .collect();
+        rhs.extend_from_slice(rp);
+
+        // The unknowns are `dx` and `-dy`.
+        let sol = self.solve_kkt(chol, h, &rhs);
+        let dx = sol[..n].to_vec();
+        let dy = sol[n..].iter().map(|v| -v).collect();
+        let dzl = (0..n)
+            .map(|j| {
+                if self.lower[j].is_finite() {
+                    (rl[j] - self.zl[j] * dx[j]) / sl[j]
+                } else {
+                    0.0
+                }
+            })
+            .collect();
+        let dzu = (0..n)
+            .map(|j| {
+                if self.upper[j].is_finite() {
+                    (ru[j] + self.zu[j] * dx[j]) / su[j]
+                } else {
+                    0.0
+                }
+            })
+            .collect();
+        Direction { dx, dy, dzl, dzu }
+    }
+
+    /// Solve `[Q + D, Aᵀ; A, 0] v = rhs`, where `h` is the diagonal of
+    /// `Q + D`, with the regularised factor `chol` and iterative refinement.
+    fn solve_kkt(&self, chol: &SparseCholesky, h: &[f64], rhs: &[f64]) -> Vec<f64> {
+        let n = self.x.len();
+        let mut sol = rhs.to_vec();
+        chol.solve(&mut sol);
+        for _ in 0..REFINEMENT_STEPS {
+            // Residual `rhs - K v` of the unregularised system.
+            let mut r = rhs.to_vec();
+            for j in 0..n {
+                r[j] -= h[j] * sol[j];
+                for &(i, a) in &self.cols[j] {
+                    r[j] -= a * sol[n + i];
+                    r[n + i] -= a * sol[j];
+                }
+            }
+            for &(i, j, q) in &self.q_lower {
+                r[i] -= q * sol[j];
+                r[j] -= q * sol[i];
+            }
+            if norm(&r) <= f64::EPSILON * norm(rhs) {
+                break;
+            }
+            chol.solve(&mut r);
+            sol.iter_mut().zip(&r).for_each(|(v, d)| *v += d);
+        }
+        sol
+    }
+
+    /// Longest step along `dir` that keeps the bound slacks and duals
+    /// non-negative.
+    fn max_step(&self, sl: &[f64], su: &[f64], dir: &Direction) -> f64 {
+        let ratio = |value: f64, d: f64| if d < 0.0 { -value / d } else { f64::INFINITY };
+        let mut alpha = f64::INFINITY;
+        for j in 0..self.x.len() {
+            if self.lower[j].is_finite() {
+                alpha =
+                    alpha.min(ratio(sl[j], dir.dx[j])).min(ratio(self.zl[j], dir.dzl[j]));
+            }
+            if self.upper[j].is_finite() {
+                alpha = alpha
+                    .min(ratio(su[j], -dir.dx[j]))
+                    .min(ratio(self.zu[j], dir.dzu[j]));
+            }
+        }
+        alpha
+    }
+
+    /// Move every iterate by `alpha` along `dir`.
+    fn step(&mut self, dir: &Direction, alpha: f64) {
+        let update = |v: &mut [f64], d: &[f64]| {
+            v.iter_mut().zip(d).for_each(|(v, d)| *v += alpha * d);
+        };
+        update(&mut self.x, &dir.dx);
+        update(&mut self.y, &dir.dy);
+        update(&mut self.zl, &dir.dzl);
+        update(&mut self.zu, &dir.dzu);
+    }
+}
+
+/// Check that the quadratic form with diagonal `q_diag` and strictly lower
+/// triangle `q_lower` is positive semidefinite, by factoring it with its
+/// diagonal shifted as described by [`CONVEXITY_TOL`].
+pub(crate) fn check_convex(
+    q_diag: &[f64],
+    q_lower: &[(usize, usize, f64)],
+    minimise: bool,
+) -> Result<(), SolveError> {
+    // Only the variables with quadratic terms take part.
+    let mut index = vec![None; q_diag.len()];
+    let mut k = 0;
+    for j in 0..q_diag.len() {
+        if q_diag[j] != 0.0 || q_lower.iter().any(|&(r, c, _)| r == j || c == j) {
+            index[j] = Some(k);
+            k += 1;
+        }
+    }
+    if k == 0 {
+        return Ok(());
+    }
+
+    let largest = q_diag
+        .iter()
+        .chain(q_lower.iter().map(|(_, _, q)| q))
+        .fold(1.0f64, |m, q| m.max(q.abs()));
+    let shift = CONVEXITY_TOL * largest;
+    let mut triplets: Vec<(usize, usize, f64)> = Vec::new();
+    for (j, &q) in q_diag.iter().enumerate() {
+        if let Some(k) = index[j] {
+            triplets.push((k, k, q + shift));
+        }
+    }
+    for &(i, j, q) in q_lower {
+        triplets.push((index[i].unwrap(), index[j].unwrap(), q));
+    }
+
+    let q = SparseMatrix::from_triplets(k, k, &triplets);
+    let convex = SymbolicCholesky::analyse(&q)
+        .and_then(|symbolic| SparseCholesky::factor(&symbolic, &q))
+        .is_ok();
+    if convex {
+        Ok(())
+    } else if minimise {
+        Err(SolveError::InvalidModel("the quadratic objective is not convex".into()))
+    } else {
+        Err(SolveError::InvalidModel("the quadratic objective is not concave".into()))
+    }
+}
+
+/// Decide whether `model`, on which the iterations failed, is infeasible or
+/// unbounded, and return the solution reporting it with its certificate.
+///
+/// Returns `None` if the model is feasible and its objective bounded.
+pub(crate) fn classify(model: &LpModel) -> Result<Option<LpSolution>, SolveError> {
+    let mut simplex = PrimalSimplexSolver::new();
+    simplex.tolerance = CLASSIFY_TOL;
+
+    // Phase 1: is there a feasible point at all?
+    let mut feasibility = model.clone();
+    feasibility.objectives =
+        vec![Objective::minimize(LinExpr::constant(0.0)).name("feasibility")];
+    let phase1 = simplex.solve(&feasibility)?;
+    match phase1.status {
+        SolveStatus::Infeasible => {
+            let mut solution = LpSolution::new(Vec::new(), None, SolveStatus::Infeasible);
+            solution.certificate = phase1.certificate;
+            return Ok(Some(solution));
+        }
+        SolveStatus::Optimal => {}
+        _ => return Ok(None),
+    }
+
+    // A recession direction `d` with `Qd = 0` along which the linear part of
+    // the objective improves.
+    let objective = model.objective().expect("checked by check_qp");
+    let mut recession = LpModel::new();
+    recession.vars = model
+        .vars()
+        .iter()
+        .map(|v| Var {
+            lb: Some(if v.lb.is_some() { 0.0 } else { -1.0 }),
+            ub: Some(if v.ub.is_some() { 0.0 } else { 1.0 }),
+            is_integer: false,
+            ..v.clone()
+        })
+        .collect();
+    recession.constraints = model
+        .constraints()
+        .iter()
+        .map(|cons| LinearConstraint {
+            expr: LinExpr { terms: cons.expr.terms.clone(), constant: 0.0 },
+            rhs: 0.0,
+            ..cons.clone()
+        })
+        .collect();
+    let mut rows: BTreeMap<usize, LinExpr> = BTreeMap::new();
+    for term in &objective.quad {
+        rows.entry(term.var1.0)
+            .or_default()
+            .terms
+            .push(LinTerm { var: term.var2, coeff: term.coeff });
+        rows.entry(term.var2.0)
+            .or_default()
+            .terms
+            .push(LinTerm { var: term.var1, coeff: term.coeff });
+    }
+    recession
+        .constraints
+        .extend(rows.into_values().map(|row| row.eq(0.0)));
+    recession.add_objective(Objective {
+        expr: LinExpr { terms: objective.expr.terms.clone(), constant: 0.0 },
+        quad: Vec::new(),
+        degradation: None,
+        ..objective.clone()
+    });
+
+    let ray = simplex.solve(&recession)?;
+    let slope = ray.objective_value.unwrap_or_default();
+    let improving = match objective.sense {
+        Sense::Minimize => slope < -CLASSIFY_TOL,
+        Sense::Maximize => slope > CLASSIFY_TOL,
+    };
+    if ray.status != SolveStatus::Optimal || !improving {
+        return Ok(None);
+    }
+    let mut values = ray.values;
+    values.truncate(model.vars().len());
+    let mut solution = LpSolution::new(Vec::new(), None, SolveStatus::Unbounded);
+    solution.certificate = Some(Certificate::unbounded(values));
+    Ok(Some(solution))
+}
+
+/// Largest magnitude of the entries of `v`.
+fn norm(v: &[f64]) -> f64 {
+    v.iter().fold(0.0f64, |m, x| m.max(x.abs()))
+}
@@ -0,0 +1,183 @@
+//! Row and column scaling of the simplex standard form.
+//!
+//! The simplex compares reduced costs, pivots and infeasibilities against one
+//! absolute tolerance, which only makes sense if the coefficients of the model
+//! are of similar magnitude. Scaling replaces `A` by `R A C`, `b` by `R b` and
+//! `c` by `κ C c`, for diagonal `R` and `C` and a scalar `κ`, so that the scaled
+//! coefficients are close to one. Every factor is a power of two, so scaling
+//! introduces no rounding error.
+//!
+//! A scaled solution `x̃`, with duals `π̃` and reduced costs `d̃`, is mapped back
+//! by `x = C x̃`, `π = R π̃ / κ` and `d = C⁻¹ d̃ / κ`; see [`ScaleFactors`].
+
+/// Maximum number of geometric-mean passes.
+const MAX_PASSES: usize = 20;
+
+/// Geometric-mean passes stop once a pass reduces the ratio between the largest
+/// and smallest coefficient by less than this factor.
+const MIN_PROGRESS: f64 = 0.9;
+
+/// How the constraint matrix is scaled before solving.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Scaling {
+    /// Solve the model as given.
+    None,
+
+    /// Repeatedly divide each row and then each column by the geometric mean
+    /// of its largest and smallest coefficient, until the spread of the
+    /// coefficients stops shrinking.
+    #[default]
+    GeometricMean,
+
+    /// Divide each row and then each column by its largest coefficient, so
+    /// that every row and column has a largest coefficient of one.
+    Equilibration,
+}
+
+/// The factors of a scaled standard form.
+///
+/// Column `j` of the scaled problem stands for `cols[j]` units of the original
+/// column, row `i` is `rows[i]` times the original row, and the objective is
+/// `objective` times the original objective.
+///
+/// # Examples
+///
+/// ```rust
+/// # use cnvx_lp::scaling::{ScaleFactors, Scaling};
+/// // Row 0: 1000 x0 + 2000 x1; row 1: 0.001 x0 + 0.004 x1.
+/// let entries = [(0, 0, 1000.0), (0, 1, 2000.0), (1, 0, 0.001), (1, 1, 0.004)];
+/// let scale = ScaleFactors::new(Scaling::Equilibration, 2, 2, &entries, &[1.0, 1.0]);
+///
+/// for &(i, j, a) in &entries {
+///     let scaled = scale.rows[i] * a * scale.cols[j];
+///     assert!((0.25..=2.0).contains(&scaled));
+/// }
+/// assert_eq!(scale.value(1, 1.0), scale.cols[1]);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScaleFactors {
+    /// Factor of each row.
+    pub rows: Vec<f64>,
+    /// Factor of each column.
+    pub cols: Vec<f64>,
+    /// Factor of the objective.
+    pub objective: f64,
+}
+
+impl ScaleFactors {
+    /// Factors that leave an `m × n` problem unchanged.
+    pub fn identity(m: usize, n: usize) -> Self {
+        Self {
+            rows: vec![1.0; m],
+            cols: vec![1.0; n],
+            objective: 1.0,
+        }
+    }
+
+    /// Compute the factors of an `m × n` matrix, given by its nonzero
+    /// `(row, col, value)` entries, with objective coefficients `cost`.
+    pub fn new(
+        scaling: Scaling,
+        m: usize,
+        n: usize,
+        entries: &[(usize, usize, f64)],
+        cost: &[f64],
+    ) -> Self {
+        let mut scale = Self::identity(m, n);
+        match scaling {
+            Scaling::None => return scale,
+            Scaling::GeometricMean => {
+                let mut spread = scale.spread(entries);
+                for _ in 0..MAX_PASSES {
+                    let previous = scale.clone();
+                    scale.pass(entries, true, |(min, max)| (min * max).sqrt());
+                    scale.pass(entries, false, |(min, max)| (min * max).sqrt());
+                    let next = scale.spread(entries);
+                    if next > MIN_PROGRESS * spread {
+                        if next > spread {
+                            scale = previous;
+                        }
+                        break;
+                    }
+                    spread = next;
+                }
+            }
+            Scaling::Equilibration => {
+                scale.pass(entries, true, |(_, max)| max);
+                scale.pass(entries, false, |(_, max)| max);
+            }
+        }
+
+        let largest = cost
+            .iter()
+            .zip(&scale.cols)
+            .fold(0.0f64, |m, (c, s)| m.max((c * s).abs()));
+        if largest > 0.0 {
+            scale.objective = power_of_two(1.0 / largest);
+        }
+        scale
+    }
+
+    /// Divide each row (if `rows`) or each column by `size` of the smallest and
+    /// largest magnitude of its scaled entries, rounded to a power of two.
+    fn pass(
+        &mut self,
+        entries: &[(usize, usize, f64)],
+        rows: bool,
+        size: impl Fn((f64, f64)) -> f64,
+    ) {
+        let len = if rows { self.rows.len() } else { self.cols.len() };
+        let mut range = vec![(f64::INFINITY, 0.0f64); len];
+        for &(i, j, a) in entries {
+            let v = (self.rows[i] * a * self.cols[j]).abs();
+            let k = if rows { i } else { j };
+            range[k] = (range[k].0.min(v), range[k].1.max(v));
+        }
+
+        let factors = if rows { &mut self.rows } else { &mut self.cols };
+        for (factor, &r) in factors.iter_mut().zip(&range) {
+            if r.1 > 0.0 {
+                *factor *= power_of_two(1.0 / size(r));
+            }
+        }
+    }
+
+    /// Ratio between the largest and smallest magnitude of the scaled entries.
+    fn spread(&self, entries: &[(usize, usize, f64)]) -> f64 {
+        let (min, max) = entries.iter().fold((f64::INFINITY, 0.0f64), |(lo, hi), e| {
+            let v = (self.rows[e.0] * e.2 * self.cols[e.1]).abs();
+            (lo.min(v), hi.max(v))
+        });
+        if max > 0.0 { max / min } else { 1.0 }
+    }
+
+    /// Original value of column `j` from its scaled value.
+    pub fn value(&self, j: usize, scaled: f64) -> f64 {
+        scaled * self.cols[j]
+    }
+
+    /// Original cost (or reduced cost) of column `j` from its scaled value.
+    pub fn cost(&self, j: usize, scaled: f64) -> f64 {
+        scaled / (self.cols[j] * self.objective)
+    }
+
+    /// Original dual of row `i` from its scaled value.
+    pub fn dual(&self, i: usize, scaled: f64) -> f64 {
+        scaled * self.rows[i] / self.objective
+    }
+
+    /// Original right-hand side change of row `i` from a scaled change.
+    pub fn rhs(&self, i: usize, scaled: f64) -> f64 {
+        scaled / self.rows[i]
+    }
+
+    /// Original objective value from the scaled objective value.
+    pub fn objective_value(&self, scaled: f64) -> f64 {
+        scaled / self.objective
+    }
+}
+
+/// The power of two nearest to `x` (on a log scale).
+fn power_of_two(x: f64) -> f64 {
+    2f64.powi(x.log2().round() as i32)
+}
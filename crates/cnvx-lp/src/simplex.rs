@@ -0,0 +1,868 @@
+//! Building blocks shared by the primal and dual simplex solvers.
+//!
+//! Both [`PrimalSimplexState`] and [`DualSimplexState`] work on the same bounded
+//! standard form (`max cᵀx` subject to `Ax = b`, `l <= x <= u`) and the same basis
+//! representation, so the conversion from an [`LpModel`] and the linear-algebra
+//! helpers on the basis live here.
+//!
+//! Variable bounds are handled natively: every non-basic column rests at one of
+//! its bounds (or at zero if it is free), and the basic variables are
+//! `x_B = B⁻¹ (b - N x_N)`.
+//!
+//! Systems with the basis matrix are solved with a [`BasisFactor`], an LU
+//! factorisation of `B` that is updated after each pivot and refactored every
+//! [`REFACTOR_INTERVAL`] pivots.
+
+use std::collections::HashSet;
+
+use cnvx_core::{Sense, SolveError, SolveStatus};
+use cnvx_math::{Factorisation, Matrix, SparseLu};
+
+use crate::scaling::{ScaleFactors, Scaling};
+use crate::{
+    BasisStatus, Certificate, Cmp, DualSimplexState, LpModel, LpSolution,
+    PrimalSimplexState, Sensitivity, SensitivityRange, VarId,
+};
+
+/// Origin of a row of a [`StandardForm`] in the model it was built from.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RowKey {
+    /// The `i`-th constraint of the model.
+    Constraint(usize),
+}
+
+/// Origin of a column of a [`StandardForm`] in the model it was built from.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ColumnKey {
+    /// A structural (model) variable.
+    Var(VarId),
+    /// The slack or surplus column of a row.
+    Slack(RowKey),
+    /// An artificial column of a row, fixed at zero once a basis is feasible.
+    Artificial(RowKey),
+}
+
+/// Describes how the rows and columns of a [`StandardForm`] map back to the model.
+#[derive(Clone, Debug, Default)]
+pub struct Layout {
+    /// Number of structural (model) variables, i.e. the first `n_vars` columns.
+    pub n_vars: usize,
+    /// Number of structural plus slack columns.
+    pub n_cols: usize,
+    /// Origin of each row.
+    pub rows: Vec<RowKey>,
+    /// Slack column of each row and its coefficient (`1.0` for `<=`, `-1.0` for
+    /// `>=`), or `None` for equality rows.
+    pub slacks: Vec<Option<(usize, f64)>>,
+}
+
+impl Layout {
+    /// Builds the layout of the standard form of `model`: the structural
+    /// columns, followed by a slack column for each `<=`/`>=` row in order.
+    pub fn new(model: &LpModel) -> Self {
+        let n_vars = model.vars().len();
+        let mut n_cols = n_vars;
+        let slacks = model
+            .constraints()
+            .iter()
+            .map(|cons| {
+                let coeff = match cons.cmp {
+                    Cmp::LEQ => 1.0,
+                    Cmp::GEQ => -1.0,
+                    Cmp::EQ => return None,
+                };
+                n_cols += 1;
+                Some((n_cols - 1, coeff))
+            })
+            .collect();
+        let rows = (0..model.constraints().len()).map(RowKey::Constraint).collect();
+        Self { n_vars, n_cols, rows, slacks }
+    }
+
+    /// Returns the origin of structural or slack column `j`.
+    pub fn column_key(&self, j: usize) -> Option<ColumnKey> {
+        if j < self.n_vars {
+            return Some(ColumnKey::Var(VarId(j)));
+        }
+        self.slacks
+            .iter()
+            .position(|s| s.is_some_and(|(col, _)| col == j))
+            .map(|r| ColumnKey::Slack(self.rows[r]))
+    }
+
+    /// Returns the index of the structural or slack column identified by `key`.
+    pub fn column_index(&self, key: ColumnKey) -> Option<usize> {
+        match key {
+            ColumnKey::Var(v) => (v.0 < self.n_vars).then_some(v.0),
+            ColumnKey::Slack(row) => {
+                let r = self.rows.iter().position(|&k| k == row)?;
+                self.slacks[r].map(|(col, _)| col)
+            }
+            ColumnKey::Artificial(_) => None,
+        }
+    }
+}
+
+/// An optimal basis expressed in terms of model rows and columns.
+///
+/// Because it does not refer to tableau indices directly, a `WarmStart` taken
+/// from one solve can be mapped onto the standard form of an edited model
+/// (added constraints, changed bounds, right-hand sides or objective).
+#[derive(Clone, Debug)]
+pub struct WarmStart {
+    /// Rows of the model the basis was taken from.
+    pub rows: Vec<RowKey>,
+    /// Basic columns.
+    pub basis: Vec<ColumnKey>,
+    /// Non-basic columns resting at their upper bound.
+    pub at_upper: Vec<ColumnKey>,
+}
+
+impl WarmStart {
+    /// Maps the basis onto `layout`.
+    ///
+    /// Rows that did not exist when the basis was taken start with their slack
+    /// (or an artificial column for equality rows) in the basis. Returns the
+    /// basic columns together with the rows that need an artificial column, or
+    /// `None` if the basis no longer fits the layout (e.g. rows were removed).
+    pub fn map_onto(&self, layout: &Layout) -> Option<(Vec<usize>, Vec<usize>)> {
+        let old_rows: HashSet<RowKey> = self.rows.iter().copied().collect();
+        let mut used = vec![false; layout.n_cols];
+        let mut basis = Vec::with_capacity(layout.rows.len());
+        let mut art_rows = Vec::new();
+
+        for &key in &self.basis {
+            match key {
+                ColumnKey::Artificial(row) => {
+                    art_rows.push(layout.rows.iter().position(|&k| k == row)?);
+                }
+                _ => {
+                    if let Some(j) = layout.column_index(key)
+                        && !used[j]
+                    {
+                        used[j] = true;
+                        basis.push(j);
+                    }
+                }
+            }
+        }
+
+        for (r, row) in layout.rows.iter().enumerate() {
+            if old_rows.contains(row) {
+                continue;
+            }
+            match layout.slacks[r] {
+                Some((j, _)) if !used[j] => {
+                    used[j] = true;
+                    basis.push(j);
+                }
+                _ => art_rows.push(r),
+            }
+        }
+
+        (basis.len() + art_rows.len() == layout.rows.len()).then_some((basis, art_rows))
+    }
+
+    /// Places the non-basic columns that rested at their upper bound back
+    /// there in `x_n`, provided that bound is still finite.
+    pub fn restore_upper(&self, layout: &Layout, upper: &[f64], x_n: &mut [f64]) {
+        for &key in &self.at_upper {
+            if let Some(j) = layout.column_index(key)
+                && upper[j].is_finite()
+            {
+                x_n[j] = upper[j];
+            }
+        }
+    }
+}
+
+/// Keys of the non-basic structural and slack columns resting at their upper
+/// bound, as recorded in [`WarmStart::at_upper`].
+pub fn upper_keys(
+    layout: &Layout,
+    non_basis: &[usize],
+    x_n: &[f64],
+    lower: &[f64],
+    upper: &[f64],
+) -> Vec<ColumnKey> {
+    non_basis
+        .iter()
+        .filter(|&&j| x_n[j] == upper[j] && lower[j] < upper[j])
+        .filter_map(|&j| layout.column_key(j))
+        .collect()
+}
+
+/// An [`LpModel`] converted to the standard form used by the simplex solvers.
+///
+/// Each `<=`/`>=` row receives a slack/surplus column after the structural
+/// columns, and every column carries its bounds (`-inf`/`inf` when absent).
+/// Minimisation problems are negated so that the tableau is always a
+/// maximisation.
+#[derive(Clone)]
+pub struct StandardForm<A: Matrix> {
+    /// Constraint matrix `A`.
+    pub a: A,
+    /// Right-hand side vector `b`.
+    pub b: Vec<f64>,
+    /// Objective coefficients vector `c` (maximisation form).
+    pub c: Vec<f64>,
+    /// Lower bound of each column.
+    pub lower: Vec<f64>,
+    /// Upper bound of each column.
+    pub upper: Vec<f64>,
+    /// Origin of each row and column.
+    pub layout: Layout,
+    /// Whether the original objective is a minimisation.
+    pub minimise: bool,
+}
+
+impl<A: Matrix> StandardForm<A> {
+    /// Builds the standard form of `model`.
+    pub fn new(model: &LpModel) -> Self {
+        let layout = Layout::new(model);
+        let n_total = layout.n_cols;
+
+        let mut triplets = Vec::new();
+        let mut b = vec![0.0; layout.rows.len()];
+        let mut c = vec![0.0; n_total];
+        let mut lower = vec![0.0; n_total];
+        let mut upper = vec![f64::INFINITY; n_total];
+
+        for var in model.vars() {
+            lower[var.id.0] = var.lb.unwrap_or(f64::NEG_INFINITY);
+            upper[var.id.0] = var.ub.unwrap_or(f64::INFINITY);
+        }
+
+        let minimise =
+            model.objective().map(|o| o.sense == Sense::Minimize).unwrap_or(false);
+
+        if let Some(obj) = model.objective() {
+            for term in &obj.expr.terms {
+                c[term.var.0] += match obj.sense {
+                    Sense::Maximize => term.coeff,
+                    Sense::Minimize => -term.coeff,
+                };
+            }
+        }
+
+        for (i, cons) in model.constraints().iter().enumerate() {
+            // Constant terms on the left-hand side move to the right-hand side.
+            b[i] = cons.rhs - cons.expr.constant;
+            triplets.extend(cons.expr.terms.iter().map(|t| (i, t.var.0, t.coeff)));
+            if let Some((j, coeff)) = layout.slacks[i] {
+                triplets.push((i, j, coeff));
+            }
+        }
+        let a = A::from_triplets(layout.rows.len(), n_total, &triplets);
+
+        Self { a, b, c, lower, upper, layout, minimise }
+    }
+
+    /// Append an artificial column `sign · e_r` for each `(r, sign)` in `rows`.
+    ///
+    /// Artificial columns have no cost and are fixed at zero.
+    pub fn add_artificials(&mut self, rows: &[(usize, f64)]) {
+        let m = self.a.rows();
+        let n = self.a.cols();
+        let mut triplets = (0..n)
+            .flat_map(|j| self.a.col(j).map(move |(i, v)| (i, j, v)))
+            .collect::<Vec<_>>();
+        triplets
+            .extend(rows.iter().enumerate().map(|(k, &(row, sign))| (row, n + k, sign)));
+        self.a = A::from_triplets(m, n + rows.len(), &triplets);
+        self.c.resize(n + rows.len(), 0.0);
+        self.lower.resize(n + rows.len(), 0.0);
+        self.upper.resize(n + rows.len(), 0.0);
+    }
+
+    /// Scale the rows and structural columns with `scaling`, see
+    /// [`ScaleFactors`], and return the factors used.
+    ///
+    /// Each slack column is scaled by the inverse of its row factor, so that it
+    /// keeps its unit coefficient and its bounds.
+    pub fn scale(&mut self, scaling: Scaling) -> ScaleFactors {
+        let (m, n) = (self.a.rows(), self.layout.n_vars);
+        let entries = (0..n)
+            .flat_map(|j| self.a.col(j).map(move |(i, v)| (i, j, v)))
+            .collect::<Vec<_>>();
+        let mut scale = ScaleFactors::new(scaling, m, n, &entries, &self.c[..n]);
+        scale.cols.resize(self.a.cols(), 1.0);
+        if scaling == Scaling::None {
+            return scale;
+        }
+
+        for (i, slack) in self.layout.slacks.iter().enumerate() {
+            if let Some((j, _)) = *slack {
+                scale.cols[j] = 1.0 / scale.rows[i];
+            }
+        }
+
+        let triplets = (0..self.a.cols())
+            .flat_map(|j| {
+                let scale = &scale;
+                self.a
+                    .col(j)
+                    .map(move |(i, v)| (i, j, scale.rows[i] * v * scale.cols[j]))
+            })
+            .collect::<Vec<_>>();
+        self.a = A::from_triplets(m, self.a.cols(), &triplets);
+        for (b, r) in self.b.iter_mut().zip(&scale.rows) {
+            *b *= r;
+        }
+        for (j, &s) in scale.cols.iter().enumerate() {
+            self.c[j] *= s * scale.objective;
+            self.lower[j] /= s;
+            self.upper[j] /= s;
+        }
+        scale
+    }
+
+    /// Starting value of each non-basic column, see [`initial_value`].
+    pub fn initial_values(&self) -> Vec<f64> {
+        (0..self.lower.len())
+            .map(|j| initial_value(self.lower[j], self.upper[j]))
+            .collect()
+    }
+}
+
+/// The value a non-basic column with bounds `[lower, upper]` rests at by
+/// default: its lower bound if finite, otherwise its upper bound if finite,
+/// otherwise zero.
+pub fn initial_value(lower: f64, upper: f64) -> f64 {
+    if lower.is_finite() {
+        lower
+    } else if upper.is_finite() {
+        upper
+    } else {
+        0.0
+    }
+}
+
+/// Models whose constraint matrix has at most this fraction of nonzero
+/// coefficients are solved with a [`SparseMatrix`](cnvx_math::SparseMatrix).
+pub const SPARSE_MAX_DENSITY: f64 = 0.1;
+
+/// Whether at most [`SPARSE_MAX_DENSITY`] of the constraint coefficients of
+/// `model` are nonzero.
+pub fn is_sparse(model: &LpModel) -> bool {
+    let (rows, cols) = model.shape();
+    let nonzeros: usize = model.constraints().iter().map(|c| c.expr.terms.len()).sum();
+    (nonzeros as f64) <= SPARSE_MAX_DENSITY * (rows * cols) as f64
+}
+
+/// Number of pivots after which a [`BasisFactor`] is refactored from scratch,
+/// bounding the cost of its eta file and the error it accumulates.
+pub const REFACTOR_INTERVAL: usize = 64;
+
+/// The basis matrix `B` of a simplex state together with its sparse LU
+/// factorisation.
+///
+/// A pivot replaces a column of `B` and records the change as an eta matrix
+/// instead of factoring `B` again, so solves cost no more than the non-zeros of
+/// the factors and etas. Every [`REFACTOR_INTERVAL`] pivots the factorisation is
+/// rebuilt from `B`.
+#[derive(Clone)]
+pub struct BasisFactor<A: Matrix> {
+    /// The basis matrix `B`.
+    pub bmat: A,
+    /// Factorisation of `bmat`.
+    lu: SparseLu,
+}
+
+impl<A: Matrix> BasisFactor<A> {
+    /// Factor the basis matrix formed by the columns of `a` listed in `basis`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err(String)` if the basis matrix is singular.
+    pub fn new(a: &A, basis: &[usize]) -> Result<Self, String> {
+        let bmat = build_bmat(a, basis);
+        let lu = SparseLu::factor(&bmat)?;
+        Ok(Self { bmat, lu })
+    }
+
+    /// Solve `B x = rhs`, overwriting `rhs` with `x`.
+    pub fn solve(&self, rhs: &mut [f64]) {
+        self.lu.solve(rhs);
+    }
+
+    /// Solve the transposed system `Bᵀ y = rhs`, overwriting `rhs` with `y`.
+    pub fn solve_transpose(&self, rhs: &mut [f64]) {
+        self.lu.solve_transpose(rhs);
+    }
+
+    /// Replace column `row` of `B` with column `entering` of `a` after a pivot,
+    /// given the direction `d = B⁻¹ A_entering` of the old basis.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err(String)` if the new basis matrix is singular.
+    pub fn replace_column(
+        &mut self,
+        a: &A,
+        row: usize,
+        entering: usize,
+        d: &[f64],
+    ) -> Result<(), String> {
+        replace_column(&mut self.bmat, a, row, entering);
+        if self.lu.updates() + 1 >= REFACTOR_INTERVAL {
+            self.lu = SparseLu::factor(&self.bmat)?;
+            Ok(())
+        } else {
+            self.lu.replace_column(row, d)
+        }
+    }
+}
+
+/// Repair a singular basis by swapping each basic column found dependent by a
+/// rank-revealing factorisation of `B` for the slack column of the row left
+/// without a pivot.
+///
+/// Returns the swaps as `(entering slack, leaving column)` pairs.
+///
+/// # Errors
+///
+/// Returns an `Err(String)` if such a row is an equality row, which has no
+/// slack column.
+pub fn repair_basis<A: Matrix>(
+    a: &A,
+    layout: &Layout,
+    basis: &mut [usize],
+) -> Result<Vec<(usize, usize)>, String> {
+    let lu = SparseLu::factor_rank_revealing(&build_bmat(a, basis))?;
+    lu.dependent_columns()
+        .iter()
+        .map(|&(pos, row)| {
+            let (slack, _) = layout.slacks[row]
+                .ok_or_else(|| format!("row {row} has no slack to repair the basis"))?;
+            Ok((slack, std::mem::replace(&mut basis[pos], slack)))
+        })
+        .collect()
+}
+
+/// Build the basis matrix `B` from the columns of `a` listed in `basis`.
+pub fn build_bmat<A: Matrix>(a: &A, basis: &[usize]) -> A {
+    let triplets = basis
+        .iter()
+        .enumerate()
+        .flat_map(|(k, &j)| a.col(j).map(move |(i, v)| (i, k, v)))
+        .collect::<Vec<_>>();
+    A::from_triplets(a.rows(), basis.len(), &triplets)
+}
+
+/// Replace column `row` of `bmat` with column `entering` of `a` after a pivot.
+pub fn replace_column<A: Matrix>(bmat: &mut A, a: &A, row: usize, entering: usize) {
+    let old = bmat.col(row).map(|(i, _)| i).collect::<Vec<_>>();
+    for i in old {
+        bmat.set(i, row, 0.0);
+    }
+    for (i, v) in a.col(entering) {
+        bmat.set(i, row, v);
+    }
+}
+
+/// Right-hand side `b - N x_N` left for the basic variables once the non-basic
+/// columns are fixed at their values in `x_n`.
+pub fn nonbasic_rhs<A: Matrix>(
+    a: &A,
+    b: &[f64],
+    non_basis: &[usize],
+    x_n: &[f64],
+) -> Vec<f64> {
+    let mut rhs = b.to_vec();
+    for &j in non_basis.iter().filter(|&&j| x_n[j] != 0.0) {
+        for (i, v) in a.col(j) {
+            rhs[i] -= v * x_n[j];
+        }
+    }
+    rhs
+}
+
+/// Solve `B x_B = rhs` for the values of the basic variables.
+pub fn basic_solution<A: Matrix>(factor: &BasisFactor<A>, rhs: &[f64]) -> Vec<f64> {
+    let mut xb = rhs.to_vec();
+    factor.solve(&mut xb);
+    xb
+}
+
+/// Compute the simplex multipliers `π` by solving `Bᵀ π = c_B`.
+pub fn compute_duals<A: Matrix>(
+    factor: &BasisFactor<A>,
+    c: &[f64],
+    basis: &[usize],
+) -> Vec<f64> {
+    let mut pi = basis.iter().map(|&j| c[j]).collect::<Vec<_>>();
+    factor.solve_transpose(&mut pi);
+    pi
+}
+
+/// Inner product `yᵀ A_j` of `y` with column `j` of `a`.
+pub fn column_dot<A: Matrix>(a: &A, j: usize, y: &[f64]) -> f64 {
+    a.col(j).map(|(i, v)| v * y[i]).sum()
+}
+
+/// Reduced cost `c_j - πᵀ A_j` of column `j`.
+pub fn reduced_cost<A: Matrix>(a: &A, c: &[f64], pi: &[f64], j: usize) -> f64 {
+    c[j] - column_dot(a, j, pi)
+}
+
+/// Compute the simplex direction `d = B⁻¹ A_j`.
+pub fn compute_direction<A: Matrix>(
+    a: &A,
+    factor: &BasisFactor<A>,
+    entering: usize,
+) -> Vec<f64> {
+    let mut d = vec![0.0; a.rows()];
+    for (i, v) in a.col(entering) {
+        d[i] = v;
+    }
+    factor.solve(&mut d);
+    d
+}
+
+/// Compute row `r` of `B⁻¹`, i.e. `ρ` solving `Bᵀ ρ = e_r`.
+pub fn compute_row<A: Matrix>(factor: &BasisFactor<A>, r: usize) -> Vec<f64> {
+    let mut rho = vec![0.0; factor.bmat.rows()];
+    rho[r] = 1.0;
+    factor.solve_transpose(&mut rho);
+    rho
+}
+
+/// Row `r` of the simplex tableau `B⁻¹A`, over every column of `a`.
+pub fn tableau_row<A: Matrix>(a: &A, factor: &BasisFactor<A>, r: usize) -> Vec<f64> {
+    a.tr_mul_vec(&compute_row(factor, r))
+}
+
+/// Value of every column: `x_n` with the basic entries replaced by `x_b`.
+pub fn column_values(basis: &[usize], x_b: &[f64], x_n: &[f64]) -> Vec<f64> {
+    let mut x = x_n.to_vec();
+    for (i, &j) in basis.iter().enumerate() {
+        x[j] = x_b[i];
+    }
+    x
+}
+
+/// Objective value `cᵀx` of the current basis and non-basic values.
+pub fn basis_objective(c: &[f64], basis: &[usize], x_b: &[f64], x_n: &[f64]) -> f64 {
+    column_values(basis, x_b, x_n).iter().zip(c).map(|(x, c)| x * c).sum()
+}
+
+/// Extract the structural solution and objective value from a basis.
+///
+/// Only the first `orig_n` columns are reported; the objective is returned in
+/// the sense of the original model.
+pub fn extract_solution(
+    c: &[f64],
+    basis: &[usize],
+    x_b: &[f64],
+    x_n: &[f64],
+    orig_n: usize,
+    minimise: bool,
+) -> (Vec<f64>, f64) {
+    let mut sol = column_values(basis, x_b, x_n);
+    sol.truncate(orig_n);
+
+    let mut obj = sol.iter().zip(c).map(|(x, c)| x * c).sum::<f64>();
+
+    if minimise {
+        obj = -obj;
+    }
+
+    (sol, obj)
+}
+
+/// Pivots smaller than this are treated as zero in the ranging ratio tests.
+const RANGING_TOL: f64 = 1e-9;
+
+/// A read-only view of a final simplex basis, used to report dual information
+/// and sensitivity ranges in terms of the original model.
+pub struct BasisView<'a, A: Matrix> {
+    /// Origin of the standard-form rows and columns.
+    pub layout: &'a Layout,
+    /// Constraint matrix `A`.
+    pub a: &'a A,
+    /// Right-hand side vector `b`.
+    pub b: &'a [f64],
+    /// Objective coefficients vector `c` (maximisation form).
+    pub c: &'a [f64],
+    /// Lower bound of each column.
+    pub lower: &'a [f64],
+    /// Upper bound of each column.
+    pub upper: &'a [f64],
+    /// Values of the non-basic columns.
+    pub x_n: &'a [f64],
+    /// Basic columns.
+    pub basis: &'a [usize],
+    /// Non-basic columns that may enter the basis.
+    pub non_basis: &'a [usize],
+    /// Whether the original objective is a minimisation.
+    pub minimise: bool,
+    /// Factors the standard form was scaled by, if any.
+    pub scale: Option<&'a ScaleFactors>,
+}
+
+impl<A: Matrix> BasisView<'_, A> {
+    /// Sign converting maximisation-form quantities to the original sense.
+    fn sign(&self) -> f64 {
+        if self.minimise { -1.0 } else { 1.0 }
+    }
+
+    /// Original value of column `j` from its (scaled) standard-form value.
+    fn value(&self, j: usize, x: f64) -> f64 {
+        self.scale.map_or(x, |s| s.value(j, x))
+    }
+
+    /// Original cost of column `j` from its (scaled) standard-form cost.
+    fn cost(&self, j: usize, c: f64) -> f64 {
+        self.scale.map_or(c, |s| s.cost(j, c))
+    }
+
+    /// Original dual of row `i` from its (scaled) standard-form dual.
+    fn dual(&self, i: usize, pi: f64) -> f64 {
+        self.scale.map_or(pi, |s| s.dual(i, pi))
+    }
+
+    /// Original right-hand side change of row `i` from a (scaled) change.
+    fn rhs(&self, i: usize, delta: f64) -> f64 {
+        self.scale.map_or(delta, |s| s.rhs(i, delta))
+    }
+
+    /// Status of non-basic column `j`.
+    fn nonbasic_status(&self, j: usize) -> BasisStatus {
+        if self.x_n[j] == self.lower[j] {
+            BasisStatus::AtLower
+        } else if self.x_n[j] == self.upper[j] {
+            BasisStatus::AtUpper
+        } else {
+            BasisStatus::Superbasic
+        }
+    }
+
+    /// Fill in the dual information of `solution`.
+    ///
+    /// The simplex multipliers of the rows become the shadow prices, and the
+    /// reduced cost `c_j - πᵀA_j` of a variable resting at one of its bounds is
+    /// the shadow price of that bound. Both are reported in the sense of the
+    /// original objective.
+    pub fn attach_duals(&self, solution: &mut LpSolution) -> Result<(), SolveError> {
+        let factor =
+            BasisFactor::new(self.a, self.basis).map_err(SolveError::NumericalFailure)?;
+        let pi = compute_duals(&factor, self.c, self.basis);
+        let sign = self.sign();
+
+        let mut in_basis = vec![false; self.a.cols()];
+        for &j in self.basis {
+            in_basis[j] = true;
+        }
+
+        let vars = 0..self.layout.n_vars;
+        solution.duals =
+            pi.iter().enumerate().map(|(i, &p)| sign * self.dual(i, p)).collect();
+        solution.reduced_costs = vars
+            .clone()
+            .map(|j| sign * self.cost(j, reduced_cost(self.a, self.c, &pi, j)))
+            .collect();
+        solution.basis_status =
+            vars.map(|j| {
+                if in_basis[j] { BasisStatus::Basic } else { self.nonbasic_status(j) }
+            })
+            .collect();
+        Ok(())
+    }
+
+    /// Compute objective and right-hand side ranging for `model`.
+    ///
+    /// For a non-basic variable the objective coefficient may move until its
+    /// reduced cost changes sign. For a basic variable in row `r`, the reduced
+    /// costs of the non-basic columns move by `δ α_rk`, where `α_r` is row `r` of
+    /// the tableau `B⁻¹A`. A right-hand side change `δ` shifts the basic solution
+    /// by `δ B⁻¹ e_r`, and the basis stays optimal while it remains within bounds.
+    ///
+    /// Returns [`SolveError::InvalidModel`] if variables or constraints were
+    /// added to `model` after the solve.
+    pub fn sensitivity(&self, model: &LpModel) -> Result<Sensitivity, SolveError> {
+        if model.vars().len() != self.layout.n_vars
+            || model.constraints().len() != self.layout.rows.len()
+        {
+            return Err(SolveError::InvalidModel(
+                "the model has changed since it was solved".into(),
+            ));
+        }
+        let a = self.a;
+        let factor =
+            BasisFactor::new(a, self.basis).map_err(SolveError::NumericalFailure)?;
+        let pi = compute_duals(&factor, self.c, self.basis);
+        let rhs = nonbasic_rhs(a, self.b, self.non_basis, self.x_n);
+        let x_b = basic_solution(&factor, &rhs);
+        let sign = self.sign();
+
+        // Non-basic columns that can move, with their reduced cost and status.
+        let movable: Vec<(usize, f64, BasisStatus)> = self
+            .non_basis
+            .iter()
+            .filter(|&&j| self.lower[j] < self.upper[j])
+            .map(|&j| (j, reduced_cost(a, self.c, &pi, j), self.nonbasic_status(j)))
+            .collect();
+
+        let mut objective = Vec::with_capacity(self.layout.n_vars);
+        for j in 0..self.layout.n_vars {
+            let (mut lo, mut hi, x) = match self.basis.iter().position(|&k| k == j) {
+                Some(r) => {
+                    let rho = compute_row(&factor, r);
+                    let (mut lo, mut hi) = (f64::NEG_INFINITY, f64::INFINITY);
+                    for &(k, rc, status) in &movable {
+                        let alpha = column_dot(a, k, &rho);
+                        if alpha.abs() <= RANGING_TOL {
+                            continue;
+                        }
+                        // The reduced cost becomes `rc - δ α` and must keep its sign.
+                        let limit = rc / alpha;
+                        match status {
+                            BasisStatus::AtLower if alpha > 0.0 => lo = lo.max(limit),
+                            BasisStatus::AtLower => hi = hi.min(limit),
+                            BasisStatus::AtUpper if alpha > 0.0 => hi = hi.min(limit),
+                            BasisStatus::AtUpper => lo = lo.max(limit),
+                            _ => (lo, hi) = (0.0, 0.0),
+                        }
+                    }
+                    (lo, hi, x_b[r])
+                }
+                None => {
+                    let rc = reduced_cost(a, self.c, &pi, j);
+                    let (lo, hi) = if self.lower[j] == self.upper[j] {
+                        (f64::NEG_INFINITY, f64::INFINITY)
+                    } else {
+                        match self.nonbasic_status(j) {
+                            BasisStatus::AtLower => (f64::NEG_INFINITY, -rc),
+                            BasisStatus::AtUpper => (-rc, f64::INFINITY),
+                            _ => (0.0, 0.0),
+                        }
+                    };
+                    (lo, hi, self.x_n[j])
+                }
+            };
+            lo = self.cost(j, lo.min(0.0));
+            hi = self.cost(j, hi.max(0.0));
+            if self.minimise {
+                (lo, hi) = (-hi, -lo);
+            }
+            let value = sign * self.cost(j, self.c[j]);
+            objective.push(SensitivityRange {
+                value,
+                lower: value + lo,
+                upper: value + hi,
+                rate: self.value(j, x),
+            });
+        }
+
+        let mut rhs = Vec::with_capacity(self.layout.rows.len());
+        for (r, cons) in model.constraints.iter().enumerate() {
+            let mut e = vec![0.0; a.rows()];
+            e[r] = 1.0;
+            let d = basic_solution(&factor, &e);
+
+            let (mut lo, mut hi) = (f64::NEG_INFINITY, f64::INFINITY);
+            for (p, &j) in self.basis.iter().enumerate() {
+                if d[p].abs() <= RANGING_TOL {
+                    continue;
+                }
+                // `x_B[p] + δ d[p]` must stay within the bounds of column `j`.
+                let to_lower = (self.lower[j] - x_b[p]) / d[p];
+                let to_upper = (self.upper[j] - x_b[p]) / d[p];
+                let (below, above) =
+                    if d[p] > 0.0 { (to_lower, to_upper) } else { (to_upper, to_lower) };
+                lo = lo.max(below);
+                hi = hi.min(above);
+            }
+
+            rhs.push(SensitivityRange {
+                value: cons.rhs,
+                lower: cons.rhs + self.rhs(r, lo.min(0.0)),
+                upper: cons.rhs + self.rhs(r, hi.max(0.0)),
+                rate: sign * self.dual(r, pi[r]),
+            });
+        }
+
+        Ok(Sensitivity {
+            objective,
+            rhs,
+            constraint_names: model.constraints.iter().map(|c| c.name.clone()).collect(),
+        })
+    }
+}
+
+/// Result of [`reoptimize`]: the state of whichever algorithm finished the solve.
+pub enum Reoptimized<A: Matrix> {
+    /// The warm basis was primal feasible, so the primal simplex finished it.
+    Primal(PrimalSimplexState<A>),
+    /// The warm basis had primal infeasibilities, so the dual simplex finished it.
+    Dual(DualSimplexState<A>),
+}
+
+impl<A: Matrix> Reoptimized<A> {
+    /// Returns the solution vector and the objective value of the final basis.
+    pub fn solution(&self) -> (Vec<f64>, f64) {
+        match self {
+            Reoptimized::Primal(s) => s.solution(),
+            Reoptimized::Dual(s) => s.solution(),
+        }
+    }
+
+    /// Returns the status of the finished solve.
+    pub fn status(&self) -> SolveStatus {
+        match self {
+            Reoptimized::Primal(s) => s.status.clone(),
+            Reoptimized::Dual(s) => s.status.clone(),
+        }
+    }
+
+    /// Fill in the dual information of `solution` from the final basis.
+    pub fn attach_duals(&self, solution: &mut LpSolution) -> Result<(), SolveError> {
+        match self {
+            Reoptimized::Primal(s) => s.attach_duals(solution),
+            Reoptimized::Dual(s) => s.attach_duals(solution),
+        }
+    }
+
+    /// Returns the proof of infeasibility or unboundedness found by the primal
+    /// simplex, if any.
+    pub fn certificate(&self) -> Option<Certificate> {
+        match self {
+            Reoptimized::Primal(s) => s.certificate().cloned(),
+            Reoptimized::Dual(_) => None,
+        }
+    }
+}
+
+/// Re-optimise `model` starting from a previous optimal basis.
+///
+/// The basis is mapped onto the edited model with [`WarmStart::map_onto`]. If it
+/// is still primal feasible but has lost dual feasibility (e.g. objective
+/// coefficients changed), the primal simplex continues from it. Otherwise
+/// (added constraints, tightened bounds, changed right-hand sides) the dual
+/// simplex removes the new primal infeasibilities.
+///
+/// Returns `None` if the basis cannot be reused, in which case the caller
+/// should fall back to a cold solve.
+pub fn reoptimize<A: Matrix>(
+    model: &LpModel,
+    warm: &WarmStart,
+    max_iter: usize,
+    tol: f64,
+) -> Result<Option<Reoptimized<A>>, SolveError> {
+    let Some(mut dual) = DualSimplexState::<A>::with_basis(model, warm) else {
+        return Ok(None);
+    };
+
+    let (primal_feasible, dual_feasible) = dual.feasibility(tol)?;
+    if primal_feasible
+        && !dual_feasible
+        && let Some(mut primal) = PrimalSimplexState::<A>::with_basis(model, warm)
+    {
+        primal.resolve_lp(max_iter, tol)?;
+        return Ok(Some(Reoptimized::Primal(primal)));
+    }
+
+    dual.solve_lp(max_iter, tol)?;
+    Ok(Some(Reoptimized::Dual(dual)))
+}
@@ -4,15 +4,94 @@ use crate::LpModel;
 
 /// Validates a linear programming model before solving.
 ///
-/// Checks that the model has a defined objective function.
+/// Runs the checks of [`check_qp`] and checks that the objective is linear.
 /// You can add more LP-specific checks here in the future.
 ///
 /// # Errors
 ///
-/// Returns [`SolveError::NoObjective`] if the model does not have an objective.
+/// Returns the errors of [`check_qp`], and [`SolveError::Unsupported`] if the
+/// objective is quadratic.
 pub fn check_lp(model: &LpModel) -> Result<(), SolveError> {
-    if model.objective().is_none() {
-        return Err(SolveError::NoObjective);
+    check_qp(model)?;
+    if model.objective().is_some_and(|o| o.is_quadratic()) {
+        return Err(SolveError::Unsupported(
+            "model has a quadratic objective; solve it with `QpSolver` or `LpSolver`"
+                .into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Validates a model with a linear or quadratic objective before solving.
+///
+/// Runs the checks of [`check_model`] and checks that the model has no cone
+/// constraints.
+///
+/// # Errors
+///
+/// Returns the errors of [`check_model`], and [`SolveError::Unsupported`] if
+/// the model has second-order cone constraints.
+pub fn check_qp(model: &LpModel) -> Result<(), SolveError> {
+    check_model(model)?;
+    if !model.cones().is_empty() {
+        return Err(SolveError::Unsupported(
+            "model has second-order cone constraints; solve it with `ConicSolver` or \
+             `LpSolver`"
+                .into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Validates a second-order cone program before solving.
+///
+/// Runs the checks of [`check_model`] and checks that the objective is linear.
+///
+/// # Errors
+///
+/// Returns the errors of [`check_model`], and [`SolveError::Unsupported`] if
+/// the objective is quadratic.
+pub fn check_socp(model: &LpModel) -> Result<(), SolveError> {
+    check_model(model)?;
+    if model.objective().is_some_and(|o| o.is_quadratic()) {
+        return Err(SolveError::Unsupported(
+            "quadratic objectives with second-order cone constraints are not supported"
+                .into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Validates the parts of a model every solver relies on.
+///
+/// Checks that the model has a single objective function and that no variable
+/// has a lower bound above its upper bound.
+///
+/// # Errors
+///
+/// Returns [`SolveError::NoObjective`] if the model does not have an objective,
+/// [`SolveError::Unsupported`] if it has several, and
+/// [`SolveError::InvalidModel`] if a variable has inconsistent bounds.
+pub fn check_model(model: &LpModel) -> Result<(), SolveError> {
+    match model.objectives().len() {
+        0 => return Err(SolveError::NoObjective),
+        1 => {}
+        n => {
+            return Err(SolveError::Unsupported(format!(
+                "model has {n} objectives; solve it lexicographically with \
+                 `solve_lexicographic` or `LpSolver`"
+            )));
+        }
+    }
+    for var in model.vars() {
+        if let (Some(lb), Some(ub)) = (var.lb, var.ub)
+            && lb > ub
+        {
+            return Err(SolveError::InvalidModel(format!(
+                "variable {} has lower bound {lb} above upper bound {ub}",
+                var.name.clone().unwrap_or_else(|| format!("x{}", var.id.0))
+            )));
+        }
     }
     Ok(())
 }
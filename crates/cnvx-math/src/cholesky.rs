@@ -0,0 +1,172 @@
+//! Cholesky factorisation of symmetric positive definite matrices.
+//!
+//! Interior-point methods solve a sequence of normal-equations systems
+//! `A Θ Aᵀ dy = r`, whose matrix is symmetric positive definite but becomes
+//! increasingly ill-conditioned near the optimum (and singular if `A` has
+//! dependent rows). [`Cholesky::factor_regularised`] handles this the usual
+//! way: pivots that collapse to (almost) zero are dropped, which sets the
+//! corresponding solution component to zero instead of failing.
+
+use crate::matrix::MatrixWrapper;
+
+/// A Cholesky factorisation `A = L Lᵀ` of a symmetric positive definite matrix.
+///
+/// Only the lower triangle of the factored matrix is read.
+///
+/// # Examples
+///
+/// ```
+/// # use cnvx_math::{Cholesky, DenseMatrix, Matrix};
+/// let mut a = DenseMatrix::new(2, 2);
+/// a.set(0, 0, 4.0);
+/// a.set(1, 0, 2.0);
+/// a.set(0, 1, 2.0);
+/// a.set(1, 1, 3.0);
+///
+/// let chol = Cholesky::factor(&a).unwrap();
+/// let mut rhs = vec![2.0, 5.0];
+/// chol.solve(&mut rhs);
+/// assert!((rhs[0] + 0.5).abs() < 1e-12);
+/// assert!((rhs[1] - 2.0).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Cholesky {
+    /// Dimension of the factored matrix.
+    n: usize,
+    /// The factor `L`, stored row by row (`l[i * n + j]` for `j <= i`).
+    l: Vec<f64>,
+    /// Whether the pivot of each row was dropped as (numerically) zero.
+    dropped: Vec<bool>,
+}
+
+impl Cholesky {
+    /// Factor the symmetric positive definite matrix `a`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err(String)` if `a` is not square or not positive definite.
+    pub fn factor<M: MatrixWrapper>(a: &M) -> Result<Self, String> {
+        Self::factorise(a, None)
+    }
+
+    /// Factor the symmetric positive semidefinite matrix `a`, dropping pivots
+    /// that fall below `pivot_tol` times the corresponding diagonal entry of
+    /// `a`.
+    ///
+    /// A dropped pivot sets the corresponding component of every solution to
+    /// zero, so systems with a singular matrix (e.g. normal equations of a matrix
+    /// with dependent rows) still yield a solution when they are consistent.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err(String)` if `a` is not square.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cnvx_math::{Cholesky, DenseMatrix, Matrix};
+    /// // A singular matrix with two identical rows.
+    /// let mut a = DenseMatrix::new(2, 2);
+    /// for i in 0..2 {
+    ///     for j in 0..2 {
+    ///         a.set(i, j, 1.0);
+    ///     }
+    /// }
+    ///
+    /// let chol = Cholesky::factor_regularised(&a, 1e-12).unwrap();
+    /// assert_eq!(chol.dropped_pivots(), 1);
+    ///
+    /// let mut rhs = vec![2.0, 2.0];
+    /// chol.solve(&mut rhs);
+    /// assert!((rhs[0] + rhs[1] - 2.0).abs() < 1e-12);
+    /// ```
+    pub fn factor_regularised<M: MatrixWrapper>(
+        a: &M,
+        pivot_tol: f64,
+    ) -> Result<Self, String> {
+        Self::factorise(a, Some(pivot_tol))
+    }
+
+    /// Dimension of the factored matrix.
+    pub fn dim(&self) -> usize {
+        self.n
+    }
+
+    /// Number of pivots dropped by [`factor_regularised`](Self::factor_regularised).
+    pub fn dropped_pivots(&self) -> usize {
+        self.dropped.iter().filter(|&&d| d).count()
+    }
+
+    /// Solve `A x = rhs` with the factorisation.
+    ///
+    /// On return, `rhs` is overwritten with the solution vector `x`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs.len()` differs from the dimension of the factorisation.
+    pub fn solve(&self, rhs: &mut [f64]) {
+        let n = self.n;
+        assert_eq!(rhs.len(), n, "right-hand side has the wrong dimension");
+
+        // Forward substitution with L.
+        for i in 0..n {
+            if self.dropped[i] {
+                rhs[i] = 0.0;
+                continue;
+            }
+            let row = &self.l[i * n..i * n + i];
+            let s = rhs[i] - row.iter().zip(&rhs[..i]).map(|(l, y)| l * y).sum::<f64>();
+            rhs[i] = s / self.l[i * n + i];
+        }
+
+        // Backward substitution with Lᵀ.
+        for i in (0..n).rev() {
+            let x = rhs[i] / self.l[i * n + i];
+            rhs[i] = x;
+            let row = &self.l[i * n..i * n + i];
+            rhs[..i].iter_mut().zip(row).for_each(|(r, l)| *r -= l * x);
+        }
+    }
+
+    /// Row-wise (Cholesky–Banachiewicz) factorisation, dropping small pivots
+    /// if `pivot_tol` is given and failing on them otherwise.
+    fn factorise<M: MatrixWrapper>(
+        a: &M,
+        pivot_tol: Option<f64>,
+    ) -> Result<Self, String> {
+        let n = a.rows();
+        if a.cols() != n {
+            return Err("Matrix is not square".to_string());
+        }
+
+        let mut l = vec![0.0; n * n];
+        let mut dropped = vec![false; n];
+        for i in 0..n {
+            for j in 0..=i {
+                let s = a.get(i, j)
+                    - (0..j).map(|k| l[i * n + k] * l[j * n + k]).sum::<f64>();
+                if j < i {
+                    l[i * n + j] = if dropped[j] { 0.0 } else { s / l[j * n + j] };
+                    continue;
+                }
+
+                let diag = a.get(i, i);
+                match pivot_tol {
+                    Some(tol) if s <= tol * diag.abs() || s <= f64::MIN_POSITIVE => {
+                        // Drop the row: it is (numerically) a combination of
+                        // the previous ones.
+                        dropped[i] = true;
+                        l[i * n..i * n + i].fill(0.0);
+                        l[i * n + i] = 1.0;
+                    }
+                    None if s <= 0.0 || !s.is_finite() => {
+                        return Err("Matrix is not positive definite".to_string());
+                    }
+                    _ => l[i * n + i] = s.sqrt(),
+                }
+            }
+        }
+
+        Ok(Self { n, l, dropped })
+    }
+}
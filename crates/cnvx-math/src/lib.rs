@@ -6,8 +6,22 @@
 //!
 //! # Modules
 //!
-//! - [`matrix`]: Defines [`DenseMatrix`] and the [`Matrix`] trait for linear algebra operations.
+//! - [`matrix`]: Defines [`DenseMatrix`], [`SparseMatrix`] and [`CsrMatrix`], and the [`Matrix`] and [`Factorisation`] traits for linear algebra operations.
+//! - [`cholesky`]: Defines [`Cholesky`], a factorisation for symmetric positive definite systems.
+//! - [`lu`]: Defines [`LuFactor`], an LU factorisation with column replacement updates.
+//! - [`sparse_lu`]: Defines [`SparseLu`], a sparse LU factorisation with Markowitz pivoting.
+//! - [`sparse_cholesky`]: Defines [`SymbolicCholesky`] and [`SparseCholesky`], a supernodal `LDLᵀ` factorisation for positive definite and quasi-definite systems.
 
+pub mod cholesky;
+pub mod lu;
 pub mod matrix;
+pub mod sparse_cholesky;
+pub mod sparse_lu;
 
-pub use matrix::{DenseMatrix, MatrixWrapper as Matrix, SparseMatrix};
+pub use cholesky::Cholesky;
+pub use lu::LuFactor;
+pub use matrix::{
+    CsrMatrix, DenseMatrix, Factorisation, MatrixWrapper as Matrix, SparseMatrix,
+};
+pub use sparse_cholesky::{Regularisation, SparseCholesky, SymbolicCholesky};
+pub use sparse_lu::SparseLu;
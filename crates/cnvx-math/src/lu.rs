@@ -0,0 +1,264 @@
+//! LU factorisation of square matrices, with product-form updates.
+//!
+//! The revised simplex method solves two systems with the basis matrix `B` in
+//! every iteration (`B d = a_q` and `Bᵀ π = c_B`), and then replaces a single
+//! column of `B`. [`LuFactor`] factors `B` once and records each column
+//! replacement as an eta matrix, so an iteration costs `O(m²)` instead of the
+//! `O(m³)` of a fresh factorisation. The eta file grows with every update, so
+//! callers refactorise periodically (see [`LuFactor::updates`]).
+
+use crate::matrix::{Factorisation, MatrixWrapper};
+
+/// An LU factorisation `P A = L U` with partial pivoting, followed by a product
+/// of eta matrices for the columns replaced since.
+///
+/// # Examples
+///
+/// ```
+/// # use cnvx_math::{DenseMatrix, Factorisation, LuFactor, Matrix};
+/// let mut a = DenseMatrix::new(2, 2);
+/// a.set(0, 0, 2.0);
+/// a.set(0, 1, 1.0);
+/// a.set(1, 0, 1.0);
+/// a.set(1, 1, 3.0);
+///
+/// let mut lu = LuFactor::factor(&a).unwrap();
+/// let mut rhs = vec![3.0, 7.0];
+/// lu.solve(&mut rhs);
+/// assert!((rhs[0] - 0.4).abs() < 1e-12);
+/// assert!((rhs[1] - 2.2).abs() < 1e-12);
+///
+/// // Replace column 1 with (1, 1): pass A⁻¹ (1, 1) = (0.4, 0.2).
+/// lu.replace_column(1, &[0.4, 0.2]).unwrap();
+/// let mut rhs = vec![3.0, 2.0];
+/// lu.solve(&mut rhs);
+/// assert!((rhs[0] - 1.0).abs() < 1e-12);
+/// assert!((rhs[1] - 1.0).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone)]
+pub struct LuFactor {
+    /// Dimension of the factored matrix.
+    n: usize,
+    /// `L` (strictly below the diagonal, unit diagonal implied) and `U` (on and
+    /// above the diagonal), stored row by row.
+    lu: Vec<f64>,
+    /// Row permutation: row `i` of `P A` is row `perm[i]` of `A`.
+    perm: Vec<usize>,
+    /// Eta matrices of the column replacements.
+    etas: EtaFile,
+}
+
+/// The eta matrices recording column replacements of a factored matrix,
+/// oldest first.
+///
+/// Replacing column `col` of `A` by `a` gives `A' = A E⁻¹`, where `E` is the
+/// identity with column `col` replaced by an eta column derived from
+/// `d = A⁻¹ a`; solves with `A'` apply the eta matrices after those with `A`.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct EtaFile {
+    etas: Vec<Eta>,
+}
+
+/// An eta matrix: the identity with column `col` replaced by `values`.
+#[derive(Debug, Clone)]
+struct Eta {
+    /// The replaced column.
+    col: usize,
+    /// Diagonal entry of the replaced column.
+    pivot: f64,
+    /// Off-diagonal non-zeros of the replaced column.
+    values: Vec<(usize, f64)>,
+}
+
+impl EtaFile {
+    /// Number of recorded replacements.
+    pub(crate) fn len(&self) -> usize {
+        self.etas.len()
+    }
+
+    /// Record the replacement of column `col` given `d = A⁻¹ a`.
+    pub(crate) fn push(&mut self, col: usize, d: &[f64]) -> Result<(), String> {
+        let pivot = d[col];
+        if pivot == 0.0 || !pivot.is_finite() {
+            return Err("Column replacement makes the matrix singular".to_string());
+        }
+
+        let values = d
+            .iter()
+            .enumerate()
+            .filter(|&(i, &v)| i != col && v != 0.0)
+            .map(|(i, &v)| (i, -v / pivot))
+            .collect();
+        self.etas.push(Eta { col, pivot: 1.0 / pivot, values });
+        Ok(())
+    }
+
+    /// Apply the eta matrices to a solution of `A x = b`, oldest first.
+    pub(crate) fn solve(&self, x: &mut [f64]) {
+        for eta in &self.etas {
+            let t = x[eta.col];
+            if t != 0.0 {
+                x[eta.col] = eta.pivot * t;
+                for &(i, v) in &eta.values {
+                    x[i] += v * t;
+                }
+            }
+        }
+    }
+
+    /// Apply the transposed eta matrices to a right-hand side of `Aᵀ x = b`,
+    /// newest first.
+    pub(crate) fn solve_transpose(&self, b: &mut [f64]) {
+        for eta in self.etas.iter().rev() {
+            b[eta.col] = eta.pivot * b[eta.col]
+                + eta.values.iter().map(|&(i, v)| v * b[i]).sum::<f64>();
+        }
+    }
+}
+
+impl LuFactor {
+    /// Factor the square matrix `a`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err(String)` if `a` is not square or is singular.
+    pub fn factor<M: MatrixWrapper>(a: &M) -> Result<Self, String> {
+        let n = a.rows();
+        if a.cols() != n {
+            return Err("Matrix is not square".to_string());
+        }
+
+        let mut lu = vec![0.0; n * n];
+        for j in 0..n {
+            for (i, v) in a.col(j) {
+                lu[i * n + j] = v;
+            }
+        }
+        let mut perm: Vec<usize> = (0..n).collect();
+
+        for k in 0..n {
+            let p = (k..n)
+                .max_by(|&x, &y| lu[x * n + k].abs().total_cmp(&lu[y * n + k].abs()))
+                .unwrap_or(k);
+            let pivot = lu[p * n + k];
+            if pivot == 0.0 || !pivot.is_finite() {
+                return Err("Matrix is singular".to_string());
+            }
+            if p != k {
+                for j in 0..n {
+                    lu.swap(k * n + j, p * n + j);
+                }
+                perm.swap(k, p);
+            }
+
+            for i in k + 1..n {
+                let factor = lu[i * n + k] / pivot;
+                lu[i * n + k] = factor;
+                if factor != 0.0 {
+                    for j in k + 1..n {
+                        lu[i * n + j] -= factor * lu[k * n + j];
+                    }
+                }
+            }
+        }
+
+        Ok(Self { n, lu, perm, etas: EtaFile::default() })
+    }
+
+    /// Dimension of the factored matrix.
+    pub fn dim(&self) -> usize {
+        self.n
+    }
+
+    /// Number of column replacements since the matrix was factored.
+    pub fn updates(&self) -> usize {
+        self.etas.len()
+    }
+
+    /// Replace column `col` of the factored matrix `A` with a new column `a`,
+    /// given `d = A⁻¹ a` (as computed by [`solve`](Self::solve) before the
+    /// replacement).
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err(String)` if `d[col]` is zero, i.e. the new matrix would
+    /// be singular.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `d.len()` differs from the dimension of the factorisation.
+    pub fn replace_column(&mut self, col: usize, d: &[f64]) -> Result<(), String> {
+        assert_eq!(d.len(), self.n, "column has the wrong dimension");
+        self.etas.push(col, d)
+    }
+}
+
+impl Factorisation for LuFactor {
+    /// Solve `A x = rhs` for the current matrix.
+    ///
+    /// On return, `rhs` is overwritten with the solution vector `x`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs.len()` differs from the dimension of the factorisation.
+    fn solve(&self, rhs: &mut [f64]) {
+        let n = self.n;
+        assert_eq!(rhs.len(), n, "right-hand side has the wrong dimension");
+
+        // Forward substitution with L on the permuted right-hand side.
+        let mut x: Vec<f64> = self.perm.iter().map(|&p| rhs[p]).collect();
+        for i in 0..n {
+            let row = &self.lu[i * n..i * n + i];
+            x[i] -= row.iter().zip(&x[..i]).map(|(l, x)| l * x).sum::<f64>();
+        }
+
+        // Backward substitution with U.
+        for i in (0..n).rev() {
+            let row = &self.lu[i * n + i + 1..(i + 1) * n];
+            let s = x[i] - row.iter().zip(&x[i + 1..]).map(|(u, x)| u * x).sum::<f64>();
+            x[i] = s / self.lu[i * n + i];
+        }
+
+        self.etas.solve(&mut x);
+        rhs.copy_from_slice(&x);
+    }
+
+    /// Solve the transposed system `Aᵀ x = rhs` for the current matrix.
+    ///
+    /// On return, `rhs` is overwritten with the solution vector `x`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs.len()` differs from the dimension of the factorisation.
+    fn solve_transpose(&self, rhs: &mut [f64]) {
+        let n = self.n;
+        assert_eq!(rhs.len(), n, "right-hand side has the wrong dimension");
+
+        self.etas.solve_transpose(rhs);
+
+        // Forward substitution with Uᵀ.
+        for i in 0..n {
+            rhs[i] /= self.lu[i * n + i];
+            let x = rhs[i];
+            if x != 0.0 {
+                let row = &self.lu[i * n + i + 1..(i + 1) * n];
+                rhs[i + 1..].iter_mut().zip(row).for_each(|(r, u)| *r -= u * x);
+            }
+        }
+
+        // Backward substitution with Lᵀ.
+        for i in (0..n).rev() {
+            let x = rhs[i];
+            if x != 0.0 {
+                let row = &self.lu[i * n..i * n + i];
+                rhs[..i].iter_mut().zip(row).for_each(|(r, l)| *r -= l * x);
+            }
+        }
+
+        // Undo the row permutation.
+        let w = rhs.to_vec();
+        for (i, &p) in self.perm.iter().enumerate() {
+            rhs[p] = w[i];
+        }
+    }
+}
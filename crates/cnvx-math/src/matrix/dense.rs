@@ -1,5 +1,6 @@
-use nalgebra::DMatrix;
+use nalgebra::{DMatrix, DVector};
 
+use crate::lu::LuFactor;
 use crate::matrix::MatrixWrapper;
 
 #[derive(Debug, Clone)]
@@ -8,6 +9,8 @@ pub struct ExposedDenseMatrix {
 }
 
 impl MatrixWrapper for ExposedDenseMatrix {
+    type Factor = LuFactor;
+
     fn new(rows: usize, cols: usize) -> Self {
         Self { inner: DMatrix::zeros(rows, cols) }
     }
@@ -28,8 +31,40 @@ impl MatrixWrapper for ExposedDenseMatrix {
         self.inner[(row, col)] = value;
     }
 
+    fn col(&self, j: usize) -> impl Iterator<Item = (usize, f64)> + '_ {
+        // nalgebra stores matrices column by column.
+        let m = self.inner.nrows();
+        self.inner.as_slice()[j * m..(j + 1) * m]
+            .iter()
+            .copied()
+            .enumerate()
+            .filter(|&(_, v)| v != 0.0)
+    }
+
+    fn row(&self, i: usize) -> impl Iterator<Item = (usize, f64)> + '_ {
+        (0..self.inner.ncols())
+            .map(move |j| (j, self.inner[(i, j)]))
+            .filter(|&(_, v)| v != 0.0)
+    }
+
+    fn mul_vec(&self, x: &[f64]) -> Vec<f64> {
+        (&self.inner * DVector::from_column_slice(x)).data.into()
+    }
+
+    fn tr_mul_vec(&self, y: &[f64]) -> Vec<f64> {
+        self.inner.tr_mul(&DVector::from_column_slice(y)).data.into()
+    }
+
+    fn factor(&self) -> Result<LuFactor, String> {
+        LuFactor::factor(self)
+    }
+
     // TODO: This is a very naive implementation, and will need to be improved for performance depending on the matrix shape and sparsity.
     fn mldivide(&self, rhs: &mut [f64]) -> Result<(), String> {
+        // nalgebra's LU panics on an empty system, which has the empty solution.
+        if rhs.is_empty() && self.inner.is_empty() {
+            return Ok(());
+        }
         let a = &self.inner;
         let b = DMatrix::from_column_slice(rhs.len(), 1, rhs);
         match a.clone().lu().solve(&b) {
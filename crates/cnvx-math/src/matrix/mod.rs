@@ -2,7 +2,23 @@ mod dense;
 mod sparse;
 
 pub use dense::ExposedDenseMatrix as DenseMatrix;
-pub use sparse::ExposedSparseMatrix as SparseMatrix;
+pub use sparse::{CsrMatrix, ExposedSparseMatrix as SparseMatrix};
+
+/// A factorisation of a square matrix `A` that can be reused to solve several
+/// systems with `A` or `Aᵀ`, as returned by [`MatrixWrapper::factor`].
+pub trait Factorisation {
+    /// Solve `A x = rhs`, overwriting `rhs` with `x`.
+    ///
+    /// # Panics
+    /// Panics if `rhs.len()` differs from the dimension of the factorisation.
+    fn solve(&self, rhs: &mut [f64]);
+
+    /// Solve `Aᵀ x = rhs`, overwriting `rhs` with `x`.
+    ///
+    /// # Panics
+    /// Panics if `rhs.len()` differs from the dimension of the factorisation.
+    fn solve_transpose(&self, rhs: &mut [f64]);
+}
 
 /// A generic matrix trait for linear algebra operations.
 ///
@@ -10,8 +26,13 @@ pub use sparse::ExposedSparseMatrix as SparseMatrix;
 /// including creation, element access, and solving linear systems.
 ///
 /// Implementors must provide row/column indexing, element access, and
-///  a method for solving linear systems.
+///  a factorisation for solving linear systems. The non-zero iterators and
+/// matrix-vector products have default implementations in terms of
+/// [`get`](Self::get), which backends override with efficient kernels.
 pub trait MatrixWrapper: Clone {
+    /// The factorisation returned by [`factor`](Self::factor).
+    type Factor: Factorisation;
+
     /// Create a new matrix with the given number of rows and columns,
     /// initialized with zeros.
     ///
@@ -26,6 +47,33 @@ pub trait MatrixWrapper: Clone {
     where
         Self: Sized;
 
+    /// Build a `rows × cols` matrix from `(row, col, value)` triplets,
+    /// summing duplicate entries.
+    ///
+    /// The default implementation sets the entries one at a time; sparse
+    /// formats override it to assemble their storage in one pass.
+    ///
+    /// # Panics
+    /// Panics if a triplet lies outside the matrix.
+    ///
+    /// # Example
+    /// ```
+    /// # use cnvx_math::{DenseMatrix, Matrix};
+    /// let m = DenseMatrix::from_triplets(2, 2, &[(0, 1, 1.0), (1, 0, 2.0), (0, 1, 3.0)]);
+    /// assert_eq!(m.get(0, 1), 4.0);
+    /// assert_eq!(m.get(1, 1), 0.0);
+    /// ```
+    fn from_triplets(rows: usize, cols: usize, triplets: &[(usize, usize, f64)]) -> Self
+    where
+        Self: Sized,
+    {
+        let mut m = Self::new(rows, cols);
+        for &(i, j, v) in triplets {
+            m.set(i, j, m.get(i, j) + v);
+        }
+        m
+    }
+
     /// Return the number of rows in the matrix.
     fn rows(&self) -> usize;
 
@@ -44,6 +92,93 @@ pub trait MatrixWrapper: Clone {
     /// Panics if `row` or `col` are out of bounds.
     fn set(&mut self, row: usize, col: usize, value: f64);
 
+    /// Iterate over the non-zero entries of column `j` as `(row, value)`
+    /// pairs, in row order.
+    ///
+    /// # Panics
+    /// Panics if `j` is out of bounds.
+    ///
+    /// # Example
+    /// ```
+    /// # use cnvx_math::{Matrix, SparseMatrix};
+    /// let m = SparseMatrix::from_triplets(3, 2, &[(0, 1, 1.0), (2, 1, 5.0)]);
+    /// assert_eq!(m.col(1).collect::<Vec<_>>(), vec![(0, 1.0), (2, 5.0)]);
+    /// assert_eq!(m.col(0).count(), 0);
+    /// ```
+    fn col(&self, j: usize) -> impl Iterator<Item = (usize, f64)> + '_ {
+        assert!(j < self.cols(), "index out of bounds");
+        (0..self.rows())
+            .map(move |i| (i, self.get(i, j)))
+            .filter(|&(_, v)| v != 0.0)
+    }
+
+    /// Iterate over the non-zero entries of row `i` as `(column, value)`
+    /// pairs, in column order.
+    ///
+    /// # Panics
+    /// Panics if `i` is out of bounds.
+    fn row(&self, i: usize) -> impl Iterator<Item = (usize, f64)> + '_ {
+        assert!(i < self.rows(), "index out of bounds");
+        (0..self.cols())
+            .map(move |j| (j, self.get(i, j)))
+            .filter(|&(_, v)| v != 0.0)
+    }
+
+    /// Compute the product `A x`.
+    ///
+    /// # Panics
+    /// Panics if `x.len()` differs from the number of columns.
+    ///
+    /// # Example
+    /// ```
+    /// # use cnvx_math::{DenseMatrix, Matrix};
+    /// let m = DenseMatrix::from_triplets(2, 2, &[(0, 0, 1.0), (0, 1, 2.0), (1, 1, 3.0)]);
+    /// assert_eq!(m.mul_vec(&[1.0, 1.0]), vec![3.0, 3.0]);
+    /// assert_eq!(m.tr_mul_vec(&[1.0, 1.0]), vec![1.0, 5.0]);
+    /// ```
+    fn mul_vec(&self, x: &[f64]) -> Vec<f64> {
+        assert_eq!(x.len(), self.cols(), "vector has the wrong dimension");
+        let mut y = vec![0.0; self.rows()];
+        for (j, &xj) in x.iter().enumerate().filter(|&(_, &xj)| xj != 0.0) {
+            for (i, v) in self.col(j) {
+                y[i] += v * xj;
+            }
+        }
+        y
+    }
+
+    /// Compute the product `Aᵀ y` without forming the transpose.
+    ///
+    /// # Panics
+    /// Panics if `y.len()` differs from the number of rows.
+    fn tr_mul_vec(&self, y: &[f64]) -> Vec<f64> {
+        assert_eq!(y.len(), self.rows(), "vector has the wrong dimension");
+        (0..self.cols())
+            .map(|j| self.col(j).map(|(i, v)| v * y[i]).sum())
+            .collect()
+    }
+
+    /// Factor a square matrix, for solving several systems with it.
+    ///
+    /// # Errors
+    /// Returns an `Err(String)` if the matrix is not square or is singular.
+    ///
+    /// # Example
+    /// ```
+    /// # use cnvx_math::{Factorisation, Matrix, SparseMatrix};
+    /// let a = SparseMatrix::from_triplets(2, 2, &[(0, 0, 2.0), (0, 1, 1.0), (1, 1, 4.0)]);
+    /// let lu = a.factor().unwrap();
+    ///
+    /// let mut x = vec![3.0, 4.0];
+    /// lu.solve(&mut x);
+    /// assert_eq!(x, vec![1.0, 1.0]);
+    ///
+    /// let mut y = vec![2.0, 5.0];
+    /// lu.solve_transpose(&mut y);
+    /// assert_eq!(y, vec![1.0, 1.0]);
+    /// ```
+    fn factor(&self) -> Result<Self::Factor, String>;
+
     /// Solve a square linear system `Ax = rhs`
     ///
     /// On success, `rhs` is overwritten with the solution vector `x`.
@@ -68,5 +203,24 @@ pub trait MatrixWrapper: Clone {
     /// ```
     fn mldivide(&self, rhs: &mut [f64]) -> Result<(), String>
     where
-        Self: Sized;
+        Self: Sized,
+    {
+        self.factor()?.solve(rhs);
+        Ok(())
+    }
+
+    /// Solve the transposed square linear system `Aᵀx = rhs`.
+    ///
+    /// On success, `rhs` is overwritten with the solution vector `x`.
+    ///
+    /// # Errors
+    /// Returns an `Err(String)` if the system cannot be solved, e.g., if the matrix
+    /// is singular.
+    fn mldivide_transpose(&self, rhs: &mut [f64]) -> Result<(), String>
+    where
+        Self: Sized,
+    {
+        self.factor()?.solve_transpose(rhs);
+        Ok(())
+    }
 }
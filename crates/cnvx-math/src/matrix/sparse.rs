@@ -1,32 +1,332 @@
 use crate::matrix::MatrixWrapper;
+use crate::sparse_lu::SparseLu;
 
-// TODO: This will either use sprs or nalgebra_sparse csr format
+/// A sparse matrix in compressed sparse column (CSC) format.
+///
+/// Only the non-zero entries are stored, column by column: the entries of
+/// column `j` are `row_idx[col_ptr[j]..col_ptr[j + 1]]` (sorted by row) with
+/// the matching `values`. Build it with [`from_triplets`](Self::from_triplets);
+/// [`set`](MatrixWrapper::set) is supported but inserting into the middle of
+/// the storage costs `O(nnz)`.
+///
+/// # Examples
+///
+/// ```
+/// # use cnvx_math::{Matrix, SparseMatrix};
+/// let a = SparseMatrix::from_triplets(2, 3, &[(0, 0, 1.0), (1, 2, 4.0), (0, 2, 2.0)]);
+/// assert_eq!(a.nnz(), 3);
+/// assert_eq!(a.get(0, 2), 2.0);
+/// assert_eq!(a.col(2).collect::<Vec<_>>(), vec![(0, 2.0), (1, 4.0)]);
+///
+/// assert_eq!(a.mul_vec(&[1.0, 1.0, 1.0]), vec![3.0, 4.0]);
+/// assert_eq!(a.tr_mul_vec(&[1.0, 1.0]), vec![1.0, 0.0, 6.0]);
+/// ```
 #[derive(Debug, Clone)]
-pub struct ExposedSparseMatrix {}
+pub struct ExposedSparseMatrix {
+    rows: usize,
+    cols: usize,
+    /// Start of each column in `row_idx` and `values`, with a final entry of `nnz`.
+    col_ptr: Vec<usize>,
+    /// Row index of each stored entry.
+    row_idx: Vec<usize>,
+    /// Value of each stored entry.
+    values: Vec<f64>,
+}
+
+/// A sparse matrix in compressed sparse row (CSR) format.
+///
+/// The row-major counterpart of [`SparseMatrix`](ExposedSparseMatrix), for
+/// algorithms that walk the rows of a matrix. Obtain one with
+/// [`SparseMatrix::to_csr`](ExposedSparseMatrix::to_csr) or
+/// [`from_triplets`](Self::from_triplets).
+///
+/// # Examples
+///
+/// ```
+/// # use cnvx_math::{CsrMatrix, Matrix, SparseMatrix};
+/// let a = SparseMatrix::from_triplets(2, 3, &[(0, 0, 1.0), (1, 2, 4.0), (0, 2, 2.0)]);
+/// let csr = a.to_csr();
+/// assert_eq!(csr.row(0).collect::<Vec<_>>(), vec![(0, 1.0), (2, 2.0)]);
+/// assert_eq!(csr.to_csc().get(1, 2), 4.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CsrMatrix {
+    rows: usize,
+    cols: usize,
+    /// Start of each row in `col_idx` and `values`, with a final entry of `nnz`.
+    row_ptr: Vec<usize>,
+    /// Column index of each stored entry.
+    col_idx: Vec<usize>,
+    /// Value of each stored entry.
+    values: Vec<f64>,
+}
+
+impl ExposedSparseMatrix {
+    /// Number of stored non-zero entries.
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    /// The transpose `Aᵀ`, also in CSC format.
+    pub fn transpose(&self) -> Self {
+        let csr = self.to_csr();
+        Self {
+            rows: self.cols,
+            cols: self.rows,
+            col_ptr: csr.row_ptr,
+            row_idx: csr.col_idx,
+            values: csr.values,
+        }
+    }
+
+    /// The stored values, column by column in the order of
+    /// [`col`](MatrixWrapper::col).
+    ///
+    /// Entries assigned zero stay stored, so this refills the values of a
+    /// matrix with a fixed pattern, e.g. before a
+    /// [`SparseCholesky::refactor`](crate::SparseCholesky::refactor).
+    pub fn values_mut(&mut self) -> &mut [f64] {
+        &mut self.values
+    }
+
+    /// Convert to compressed sparse row format.
+    pub fn to_csr(&self) -> CsrMatrix {
+        let mut row_ptr = vec![0; self.rows + 1];
+        for &i in &self.row_idx {
+            row_ptr[i + 1] += 1;
+        }
+        for i in 0..self.rows {
+            row_ptr[i + 1] += row_ptr[i];
+        }
+
+        // Columns are visited in order, so every row comes out sorted.
+        let mut next = row_ptr.clone();
+        let mut col_idx = vec![0; self.nnz()];
+        let mut values = vec![0.0; self.nnz()];
+        for j in 0..self.cols {
+            for (i, v) in self.col(j) {
+                col_idx[next[i]] = j;
+                values[next[i]] = v;
+                next[i] += 1;
+            }
+        }
+
+        CsrMatrix {
+            rows: self.rows,
+            cols: self.cols,
+            row_ptr,
+            col_idx,
+            values,
+        }
+    }
+
+    /// Position of entry `(row, col)` in the storage, or where it would be
+    /// inserted.
+    fn find(&self, row: usize, col: usize) -> Result<usize, usize> {
+        assert!(row < self.rows && col < self.cols, "index out of bounds");
+        let start = self.col_ptr[col];
+        self.row_idx[start..self.col_ptr[col + 1]]
+            .binary_search(&row)
+            .map(|k| start + k)
+            .map_err(|k| start + k)
+    }
+}
+
+impl CsrMatrix {
+    /// Build a `rows × cols` matrix from `(row, col, value)` triplets.
+    ///
+    /// Duplicate entries are summed and zeros are dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a triplet lies outside the matrix.
+    pub fn from_triplets(
+        rows: usize,
+        cols: usize,
+        triplets: &[(usize, usize, f64)],
+    ) -> Self {
+        let (row_ptr, col_idx, values) = compress(rows, cols, triplets, |&t| t);
+        Self { rows, cols, row_ptr, col_idx, values }
+    }
+
+    /// Return the number of rows in the matrix.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Return the number of columns in the matrix.
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Number of stored non-zero entries.
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    /// The non-zero entries of row `i` as `(column, value)` pairs, in column
+    /// order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is out of bounds.
+    pub fn row(&self, i: usize) -> impl Iterator<Item = (usize, f64)> + '_ {
+        let range = self.row_ptr[i]..self.row_ptr[i + 1];
+        self.col_idx[range.clone()]
+            .iter()
+            .copied()
+            .zip(self.values[range].iter().copied())
+    }
+
+    /// Compute `A x`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x.len()` differs from the number of columns.
+    pub fn mul_vec(&self, x: &[f64]) -> Vec<f64> {
+        assert_eq!(x.len(), self.cols, "vector has the wrong dimension");
+        (0..self.rows)
+            .map(|i| self.row(i).map(|(j, v)| v * x[j]).sum())
+            .collect()
+    }
+
+    /// Compute `Aᵀ y` without forming the transpose.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `y.len()` differs from the number of rows.
+    pub fn tr_mul_vec(&self, y: &[f64]) -> Vec<f64> {
+        assert_eq!(y.len(), self.rows, "vector has the wrong dimension");
+        let mut x = vec![0.0; self.cols];
+        for (i, &yi) in y.iter().enumerate().filter(|&(_, &yi)| yi != 0.0) {
+            for (j, v) in self.row(i) {
+                x[j] += v * yi;
+            }
+        }
+        x
+    }
+
+    /// Convert to compressed sparse column format.
+    pub fn to_csc(&self) -> ExposedSparseMatrix {
+        // The CSR arrays of A are the CSC arrays of Aᵀ.
+        ExposedSparseMatrix {
+            rows: self.cols,
+            cols: self.rows,
+            col_ptr: self.row_ptr.clone(),
+            row_idx: self.col_idx.clone(),
+            values: self.values.clone(),
+        }
+        .transpose()
+    }
+}
 
-#[allow(unused)]
 impl MatrixWrapper for ExposedSparseMatrix {
+    type Factor = SparseLu;
+
     fn new(rows: usize, cols: usize) -> Self {
-        unimplemented!()
+        Self {
+            rows,
+            cols,
+            col_ptr: vec![0; cols + 1],
+            row_idx: Vec::new(),
+            values: Vec::new(),
+        }
+    }
+
+    /// Build a `rows × cols` matrix from `(row, col, value)` triplets.
+    ///
+    /// Duplicate entries are summed and zeros are dropped.
+    fn from_triplets(rows: usize, cols: usize, triplets: &[(usize, usize, f64)]) -> Self {
+        let (col_ptr, row_idx, values) =
+            compress(cols, rows, triplets, |&(i, j, v)| (j, i, v));
+        Self { rows, cols, col_ptr, row_idx, values }
     }
 
     fn rows(&self) -> usize {
-        unimplemented!()
+        self.rows
     }
 
     fn cols(&self) -> usize {
-        unimplemented!()
+        self.cols
     }
 
     fn get(&self, row: usize, col: usize) -> f64 {
-        unimplemented!()
+        self.find(row, col).map_or(0.0, |k| self.values[k])
     }
 
     fn set(&mut self, row: usize, col: usize, value: f64) {
-        unimplemented!()
+        match self.find(row, col) {
+            Ok(k) if value == 0.0 => {
+                self.row_idx.remove(k);
+                self.values.remove(k);
+                self.col_ptr[col + 1..].iter_mut().for_each(|p| *p -= 1);
+            }
+            Ok(k) => self.values[k] = value,
+            Err(_) if value == 0.0 => {}
+            Err(k) => {
+                self.row_idx.insert(k, row);
+                self.values.insert(k, value);
+                self.col_ptr[col + 1..].iter_mut().for_each(|p| *p += 1);
+            }
+        }
+    }
+
+    fn col(&self, j: usize) -> impl Iterator<Item = (usize, f64)> + '_ {
+        let range = self.col_ptr[j]..self.col_ptr[j + 1];
+        self.row_idx[range.clone()]
+            .iter()
+            .copied()
+            .zip(self.values[range].iter().copied())
+    }
+
+    fn factor(&self) -> Result<SparseLu, String> {
+        SparseLu::factor(self)
+    }
+}
+
+/// Compress `(major, minor, value)` triplets (as extracted by `key`) into the
+/// pointer, index and value arrays of a compressed format with `majors` major
+/// lines of length `minors`, summing duplicates and dropping zeros.
+fn compress<T>(
+    majors: usize,
+    minors: usize,
+    triplets: &[T],
+    key: impl Fn(&T) -> (usize, usize, f64),
+) -> (Vec<usize>, Vec<usize>, Vec<f64>) {
+    let mut entries = triplets.iter().map(key).collect::<Vec<_>>();
+    for &(major, minor, _) in &entries {
+        assert!(major < majors && minor < minors, "triplet out of bounds");
+    }
+    entries.sort_unstable_by_key(|&(major, minor, _)| (major, minor));
+
+    let mut ptr = vec![0; majors + 1];
+    let mut idx: Vec<usize> = Vec::with_capacity(entries.len());
+    let mut values: Vec<f64> = Vec::with_capacity(entries.len());
+    let mut last = None;
+    for (major, minor, v) in entries {
+        if last == Some((major, minor)) {
+            *values.last_mut().unwrap() += v;
+        } else {
+            // Drop the previous entry if its duplicates cancelled out.
+            if values.last() == Some(&0.0) {
+                idx.pop();
+                values.pop();
+                ptr[last.unwrap().0 + 1] -= 1;
+            }
+            idx.push(minor);
+            values.push(v);
+            ptr[major + 1] += 1;
+            last = Some((major, minor));
+        }
+    }
+    if values.last() == Some(&0.0) {
+        idx.pop();
+        values.pop();
+        ptr[last.unwrap().0 + 1] -= 1;
     }
 
-    fn mldivide(&self, rhs: &mut [f64]) -> Result<(), String> {
-        unimplemented!()
+    for k in 0..majors {
+        ptr[k + 1] += ptr[k];
     }
+    (ptr, idx, values)
 }
@@ -0,0 +1,666 @@
+//! Sparse Cholesky and `LDLᵀ` factorisation of symmetric matrices.
+//!
+//! Interior-point methods factor a matrix with the same sparsity pattern in
+//! every iteration: the normal equations `A Θ Aᵀ` of an LP, or the
+//! quasi-definite KKT matrix of a QP. The work is therefore split in two:
+//!
+//! - [`SymbolicCholesky::analyse`] reads only the pattern. It computes an
+//!   approximate minimum degree ordering, the elimination tree and the pattern
+//!   of the factor, and groups consecutive columns with the same pattern into
+//!   supernodes.
+//! - [`SparseCholesky`] factors the values as `P A Pᵀ = L D Lᵀ`, one supernode
+//!   at a time with dense kernels, and [`SparseCholesky::refactor`] repeats
+//!   this for new values with the same pattern.
+//!
+//! The `LDLᵀ` form covers positive definite matrices (all pivots positive,
+//! which is a square-root-free Cholesky factorisation) and quasi-definite ones
+//! (a known sign for each pivot). Pivots that collapse to zero or come out with
+//! the wrong sign can be replaced by dynamic regularisation (see
+//! [`Regularisation`]) instead of failing.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use crate::matrix::{Factorisation, MatrixWrapper};
+
+/// Default [`Regularisation::pivot_tol`].
+const PIVOT_TOL: f64 = 1e-13;
+
+/// Default [`Regularisation::delta`].
+const DELTA: f64 = 1e-7;
+
+/// Dynamic regularisation of the pivots of a [`SparseCholesky`] factorisation.
+///
+/// Each pivot has an expected sign and a reference magnitude: the diagonal
+/// entry of its row of `A`, or the largest diagonal entry if that one is zero.
+/// A pivot whose signed value falls below `pivot_tol` times its reference is
+/// replaced by `sign * delta` times its reference. A `delta` of infinity drops
+/// the pivot, which sets the corresponding solution component to zero (as
+/// [`Cholesky::factor_regularised`](crate::Cholesky::factor_regularised)
+/// does).
+///
+/// # Examples
+///
+/// ```
+/// # use cnvx_math::{Regularisation, SparseCholesky, SparseMatrix, SymbolicCholesky};
+/// # use cnvx_math::Matrix;
+/// // A quasi-definite matrix whose second pivot is zero.
+/// let a = SparseMatrix::from_triplets(2, 2, &[(0, 0, 1.0)]);
+/// let symbolic = SymbolicCholesky::analyse(&a).unwrap();
+/// assert!(SparseCholesky::factor(&symbolic, &a).is_err());
+///
+/// let reg = Regularisation::quasi_definite(1, 1);
+/// let ldl = SparseCholesky::factor_regularised(&symbolic, &a, reg).unwrap();
+/// assert_eq!(ldl.regularised_pivots(), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Regularisation {
+    /// Expected sign of the pivot of each row of `A`, `1.0` or `-1.0`.
+    pub signs: Vec<f64>,
+    /// Pivots below this fraction of their reference are regularised.
+    pub pivot_tol: f64,
+    /// Magnitude of a regularised pivot, as a fraction of its reference.
+    pub delta: f64,
+}
+
+impl Regularisation {
+    /// Regularisation of an `n × n` positive definite matrix.
+    pub fn positive_definite(n: usize) -> Self {
+        Self::quasi_definite(n, 0)
+    }
+
+    /// Regularisation of a quasi-definite matrix whose first `positive` pivots
+    /// are positive and whose last `negative` pivots are negative, such as the
+    /// KKT matrix `[H + δI, Aᵀ; A, -δI]`.
+    pub fn quasi_definite(positive: usize, negative: usize) -> Self {
+        let mut signs = vec![1.0; positive];
+        signs.resize(positive + negative, -1.0);
+        Self { signs, pivot_tol: PIVOT_TOL, delta: DELTA }
+    }
+}
+
+/// The symbolic analysis of a symmetric sparsity pattern: a fill-reducing
+/// ordering, and the supernodes and pattern of the factor it produces.
+///
+/// Only the lower triangle of the analysed matrix is read.
+#[derive(Debug, Clone)]
+pub struct SymbolicCholesky {
+    /// Row of `A` pivoted at each step.
+    perm: Vec<usize>,
+    /// Step at which each row of `A` is pivoted.
+    pinv: Vec<usize>,
+    /// First column of each supernode, with a final entry of `n`.
+    first: Vec<usize>,
+    /// Supernode of each column.
+    supernode: Vec<usize>,
+    /// Row pattern of each supernode: its own columns, then the rows below
+    /// them in increasing order.
+    rows: Vec<Vec<usize>>,
+}
+
+impl SymbolicCholesky {
+    /// Analyse the pattern of the symmetric matrix `a`.
+    ///
+    /// Entries that are stored but zero are not part of the pattern, so a
+    /// pattern that must accommodate several matrices should be analysed with
+    /// non-zero placeholders.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err(String)` if `a` is not square.
+    pub fn analyse<M: MatrixWrapper>(a: &M) -> Result<Self, String> {
+        let n = a.rows();
+        if a.cols() != n {
+            return Err("Matrix is not square".to_string());
+        }
+
+        let mut adj = vec![Vec::new(); n];
+        for j in 0..n {
+            for (i, _) in a.col(j).filter(|&(i, _)| i > j) {
+                adj[i].push(j);
+                adj[j].push(i);
+            }
+        }
+        let perm = minimum_degree(&adj);
+        let mut pinv = vec![0; n];
+        for (k, &i) in perm.iter().enumerate() {
+            pinv[i] = k;
+        }
+
+        // Strictly lower pattern of `P A Pᵀ`, by column and by row.
+        let mut lower = vec![Vec::new(); n];
+        let mut upper = vec![Vec::new(); n];
+        for (i, neighbours) in adj.iter().enumerate() {
+            for &j in neighbours.iter().filter(|&&j| j < i) {
+                let (pi, pj) = (pinv[i], pinv[j]);
+                lower[pi.min(pj)].push(pi.max(pj));
+                upper[pi.max(pj)].push(pi.min(pj));
+            }
+        }
+
+        // Elimination tree, with path compression through `ancestor`.
+        let mut parent = vec![None; n];
+        let mut ancestor: Vec<Option<usize>> = vec![None; n];
+        for (k, row) in upper.iter().enumerate() {
+            for &start in row {
+                let mut i = start;
+                while i < k {
+                    let next = ancestor[i].replace(k);
+                    match next {
+                        Some(next) => i = next,
+                        None => {
+                            parent[i] = Some(k);
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+
+        // Off-diagonal count of each column of L: row k of L is the union of
+        // the tree paths from the entries of row k of A up to k.
+        let mut count = vec![0; n];
+        let mut flag = vec![usize::MAX; n];
+        for (k, row) in upper.iter().enumerate() {
+            flag[k] = k;
+            for &start in row {
+                let mut i = start;
+                while flag[i] != k {
+                    flag[i] = k;
+                    count[i] += 1;
+                    i = parent[i].unwrap_or(k);
+                }
+            }
+        }
+
+        // Fundamental supernodes: column j joins the supernode of j - 1 if j - 1
+        // is its only child and the patterns match below it.
+        let mut children = vec![0; n];
+        for &p in parent.iter().flatten() {
+            children[p] += 1;
+        }
+        let mut first = Vec::new();
+        let mut supernode = vec![0; n];
+        for j in 0..n {
+            let merge = j > 0
+                && parent[j - 1] == Some(j)
+                && children[j] == 1
+                && count[j] + 1 == count[j - 1];
+            if !merge {
+                first.push(j);
+            }
+            supernode[j] = first.len() - 1;
+        }
+        first.push(n);
+
+        // The pattern of a supernode below its columns is the union of those of
+        // its columns in A and of its children in the supernodal tree.
+        let supernodes = first.len() - 1;
+        let mut child_nodes = vec![Vec::new(); supernodes];
+        for s in 0..supernodes {
+            if let Some(p) = parent[first[s + 1] - 1] {
+                child_nodes[supernode[p]].push(s);
+            }
+        }
+        let mut rows: Vec<Vec<usize>> = Vec::with_capacity(supernodes);
+        let mut mark = vec![usize::MAX; n];
+        for s in 0..supernodes {
+            let (f, l) = (first[s], first[s + 1]);
+            let mut pattern: Vec<usize> = (f..l).collect();
+            let below = lower[f..l]
+                .iter()
+                .flatten()
+                .chain(child_nodes[s].iter().flat_map(|&c| &rows[c]));
+            for &i in below {
+                if i >= l && mark[i] != s {
+                    mark[i] = s;
+                    pattern.push(i);
+                }
+            }
+            pattern[l - f..].sort_unstable();
+            debug_assert_eq!(pattern.len(), count[f] + 1);
+            rows.push(pattern);
+        }
+
+        Ok(Self { perm, pinv, first, supernode, rows })
+    }
+
+    /// Dimension of the analysed matrix.
+    pub fn dim(&self) -> usize {
+        self.perm.len()
+    }
+
+    /// The fill-reducing ordering: row `perm()[k]` of `A` is pivoted at step
+    /// `k`.
+    pub fn perm(&self) -> &[usize] {
+        &self.perm
+    }
+
+    /// Number of supernodes.
+    pub fn supernodes(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Number of non-zeros below the diagonal of `L`, including the fill-in.
+    pub fn nnz(&self) -> usize {
+        self.rows
+            .iter()
+            .enumerate()
+            .map(|(s, rows)| {
+                let w = self.first[s + 1] - self.first[s];
+                w * rows.len() - w * (w + 1) / 2
+            })
+            .sum()
+    }
+}
+
+/// A supernodal factorisation `P A Pᵀ = L D Lᵀ` of a symmetric matrix, with
+/// `L` unit lower triangular and `D` diagonal.
+///
+/// The factorisation keeps its [`SymbolicCholesky`] analysis, so matrices with
+/// the same pattern are factored again with [`refactor`](Self::refactor).
+/// Only the lower triangle of the factored matrix is read.
+///
+/// # Examples
+///
+/// ```
+/// # use cnvx_math::{Factorisation, Matrix, SparseCholesky, SparseMatrix, SymbolicCholesky};
+/// let a = SparseMatrix::from_triplets(
+///     3,
+///     3,
+///     &[(0, 0, 4.0), (1, 0, 1.0), (1, 1, 3.0), (2, 0, 1.0), (2, 2, 2.0)],
+/// );
+/// let symbolic = SymbolicCholesky::analyse(&a).unwrap();
+/// let mut chol = SparseCholesky::factor(&symbolic, &a).unwrap();
+///
+/// let mut rhs = vec![6.0, 4.0, 3.0];
+/// chol.solve(&mut rhs);
+/// assert!(rhs.iter().all(|x| (x - 1.0).abs() < 1e-12));
+///
+/// // New values with the same pattern reuse the analysis.
+/// let b = SparseMatrix::from_triplets(
+///     3,
+///     3,
+///     &[(0, 0, 8.0), (1, 0, 2.0), (1, 1, 6.0), (2, 0, 2.0), (2, 2, 4.0)],
+/// );
+/// chol.refactor(&b).unwrap();
+/// let mut rhs = vec![6.0, 4.0, 3.0];
+/// chol.solve(&mut rhs);
+/// assert!(rhs.iter().all(|x| (x - 0.5).abs() < 1e-12));
+/// ```
+#[derive(Debug, Clone)]
+pub struct SparseCholesky {
+    /// The symbolic analysis of the pattern.
+    symbolic: SymbolicCholesky,
+    /// Regularisation of the pivots, or `None` for a positive definite matrix
+    /// that must factor without it.
+    regularisation: Option<Regularisation>,
+    /// Dense block of each supernode, column-major with the rows of its
+    /// pattern; entries below the diagonal hold `L`.
+    blocks: Vec<Vec<f64>>,
+    /// The pivots `D`.
+    d: Vec<f64>,
+    /// Number of regularised pivots.
+    regularised: usize,
+}
+
+impl SparseCholesky {
+    /// Factor the symmetric positive definite matrix `a`, whose pattern was
+    /// analysed by `symbolic`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err(String)` if `a` has entries outside the analysed pattern
+    /// or is not positive definite.
+    pub fn factor<M: MatrixWrapper>(
+        symbolic: &SymbolicCholesky,
+        a: &M,
+    ) -> Result<Self, String> {
+        Self::new(symbolic, a, None)
+    }
+
+    /// Factor the symmetric matrix `a`, whose pattern was analysed by
+    /// `symbolic`, regularising pivots as described by `regularisation`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err(String)` if `a` has entries outside the analysed
+    /// pattern, if `regularisation` has the wrong dimension, or if a pivot is
+    /// not finite.
+    pub fn factor_regularised<M: MatrixWrapper>(
+        symbolic: &SymbolicCholesky,
+        a: &M,
+        regularisation: Regularisation,
+    ) -> Result<Self, String> {
+        if regularisation.signs.len() != symbolic.dim() {
+            return Err("Regularisation has the wrong dimension".to_string());
+        }
+        Self::new(symbolic, a, Some(regularisation))
+    }
+
+    /// Factor new values `a` with the pattern (and regularisation) of the
+    /// current factorisation.
+    ///
+    /// # Errors
+    ///
+    /// As for [`factor`](Self::factor) or
+    /// [`factor_regularised`](Self::factor_regularised). The factorisation is
+    /// unusable after an error.
+    pub fn refactor<M: MatrixWrapper>(&mut self, a: &M) -> Result<(), String> {
+        let sym = &self.symbolic;
+        let n = sym.dim();
+        if a.rows() != n || a.cols() != n {
+            return Err("Matrix has the wrong dimension".to_string());
+        }
+
+        // Lower triangle of `P A Pᵀ` by column, and the diagonal of `A`.
+        let mut cols: Vec<Vec<(usize, f64)>> = vec![Vec::new(); n];
+        let mut diag = vec![0.0_f64; n];
+        for j in 0..n {
+            for (i, v) in a.col(j).filter(|&(i, _)| i >= j) {
+                let (pi, pj) = (sym.pinv[i], sym.pinv[j]);
+                cols[pi.min(pj)].push((pi.max(pj), v));
+                if i == j {
+                    diag[pi] = v.abs();
+                }
+            }
+        }
+        let max_diag = diag.iter().copied().fold(0.0, f64::max);
+        let reference = |k: usize| match (diag[k], max_diag) {
+            (d, _) if d > 0.0 => d,
+            (_, m) if m > 0.0 => m,
+            _ => 1.0,
+        };
+
+        self.regularised = 0;
+        // Position of each row in the pattern of the current supernode.
+        let mut pos = vec![usize::MAX; n];
+        // Updates owed to each supernode by earlier ones, as
+        // `(supernode, first row of its pattern in the target)` pairs.
+        let mut pending: Vec<Vec<(usize, usize)>> = vec![Vec::new(); sym.supernodes()];
+
+        for s in 0..sym.supernodes() {
+            let (f, l) = (sym.first[s], sym.first[s + 1]);
+            let rows = &sym.rows[s];
+            let r = rows.len();
+            for (k, &i) in rows.iter().enumerate() {
+                pos[i] = k;
+            }
+            let (done, rest) = self.blocks.split_at_mut(s);
+            let block = &mut rest[0];
+            block.fill(0.0);
+
+            for (j, col) in cols[f..l].iter().enumerate() {
+                for &(i, v) in col {
+                    if pos[i] == usize::MAX {
+                        return Err(
+                            "Matrix has entries outside the analysed pattern".to_string()
+                        );
+                    }
+                    block[j * r + pos[i]] += v;
+                }
+            }
+
+            // Left-looking updates from the descendants whose patterns meet
+            // the columns of this supernode.
+            for (t, p) in std::mem::take(&mut pending[s]) {
+                let tf = sym.first[t];
+                let trows = &sym.rows[t];
+                let tr = trows.len();
+                let q = p + trows[p..].iter().take_while(|&&i| i < l).count();
+                for (k, lk) in done[t].chunks_exact(tr).enumerate() {
+                    let dk = self.d[tf + k];
+                    if dk.is_infinite() {
+                        continue;
+                    }
+                    for jj in p..q {
+                        let ld = lk[jj] * dk;
+                        if ld == 0.0 {
+                            continue;
+                        }
+                        let col = &mut block[(trows[jj] - f) * r..];
+                        for ii in jj..tr {
+                            col[pos[trows[ii]]] -= lk[ii] * ld;
+                        }
+                    }
+                }
+                if q < tr {
+                    pending[sym.supernode[trows[q]]].push((t, q));
+                }
+            }
+
+            // Dense right-looking LDLᵀ of the supernode's columns.
+            for j in 0..l - f {
+                let k = f + j;
+                let mut pivot = block[j * r + j];
+                if !pivot.is_finite() {
+                    return Err("Matrix is not finite".to_string());
+                }
+                match &self.regularisation {
+                    Some(reg) => {
+                        let sign = reg.signs[sym.perm[k]];
+                        let reference = reference(k);
+                        if sign * pivot <= reg.pivot_tol * reference
+                            || sign * pivot <= f64::MIN_POSITIVE
+                        {
+                            pivot = sign * reg.delta * reference;
+                            self.regularised += 1;
+                        }
+                    }
+                    None if pivot <= 0.0 => {
+                        return Err("Matrix is not positive definite".to_string());
+                    }
+                    None => {}
+                }
+                self.d[k] = pivot;
+
+                let (left, right) = block.split_at_mut((j + 1) * r);
+                let lj = &mut left[j * r..];
+                lj[j] = 1.0;
+                lj[j + 1..].iter_mut().for_each(|v| *v /= pivot);
+                if pivot.is_infinite() {
+                    continue;
+                }
+                for (jj, col) in (j + 1..l - f).zip(right.chunks_exact_mut(r)) {
+                    let ld = lj[jj] * pivot;
+                    if ld != 0.0 {
+                        for ii in jj..r {
+                            col[ii] -= lj[ii] * ld;
+                        }
+                    }
+                }
+            }
+
+            for &i in rows {
+                pos[i] = usize::MAX;
+            }
+            if l - f < r {
+                pending[sym.supernode[rows[l - f]]].push((s, l - f));
+            }
+        }
+        Ok(())
+    }
+
+    /// Dimension of the factored matrix.
+    pub fn dim(&self) -> usize {
+        self.symbolic.dim()
+    }
+
+    /// The symbolic analysis used by the factorisation.
+    pub fn symbolic(&self) -> &SymbolicCholesky {
+        &self.symbolic
+    }
+
+    /// The pivots `D`, in the order of [`SymbolicCholesky::perm`].
+    pub fn pivots(&self) -> &[f64] {
+        &self.d
+    }
+
+    /// Number of pivots replaced by the regularisation in the latest
+    /// factorisation.
+    pub fn regularised_pivots(&self) -> usize {
+        self.regularised
+    }
+
+    /// Allocate the blocks for `symbolic` and factor `a`.
+    fn new<M: MatrixWrapper>(
+        symbolic: &SymbolicCholesky,
+        a: &M,
+        regularisation: Option<Regularisation>,
+    ) -> Result<Self, String> {
+        let blocks = symbolic
+            .rows
+            .iter()
+            .enumerate()
+            .map(|(s, rows)| {
+                vec![0.0; (symbolic.first[s + 1] - symbolic.first[s]) * rows.len()]
+            })
+            .collect();
+        let mut chol = Self {
+            symbolic: symbolic.clone(),
+            regularisation,
+            blocks,
+            d: vec![0.0; symbolic.dim()],
+            regularised: 0,
+        };
+        chol.refactor(a)?;
+        Ok(chol)
+    }
+}
+
+impl Factorisation for SparseCholesky {
+    fn solve(&self, rhs: &mut [f64]) {
+        let sym = &self.symbolic;
+        assert_eq!(rhs.len(), sym.dim(), "right-hand side has the wrong dimension");
+
+        let mut x: Vec<f64> = sym.perm.iter().map(|&i| rhs[i]).collect();
+
+        // Forward substitution with L, one supernode column at a time.
+        for (s, block) in self.blocks.iter().enumerate() {
+            let (f, rows) = (sym.first[s], &sym.rows[s]);
+            for (j, lj) in block.chunks_exact(rows.len()).enumerate() {
+                let xj = x[f + j];
+                if xj != 0.0 {
+                    for (&i, l) in rows[j + 1..].iter().zip(&lj[j + 1..]) {
+                        x[i] -= l * xj;
+                    }
+                }
+            }
+        }
+
+        // A dropped (infinite) pivot sets its component to zero.
+        x.iter_mut().zip(&self.d).for_each(|(x, d)| *x /= d);
+
+        // Backward substitution with Lᵀ.
+        for (s, block) in self.blocks.iter().enumerate().rev() {
+            let (f, rows) = (sym.first[s], &sym.rows[s]);
+            for (j, lj) in block.chunks_exact(rows.len()).enumerate().rev() {
+                let dot = rows[j + 1..]
+                    .iter()
+                    .zip(&lj[j + 1..])
+                    .map(|(&i, l)| l * x[i])
+                    .sum::<f64>();
+                x[f + j] -= dot;
+            }
+        }
+
+        for (k, &i) in sym.perm.iter().enumerate() {
+            rhs[i] = x[k];
+        }
+    }
+
+    /// The matrix is symmetric, so this is the same as
+    /// [`solve`](Factorisation::solve).
+    fn solve_transpose(&self, rhs: &mut [f64]) {
+        self.solve(rhs);
+    }
+}
+
+/// Approximate minimum degree ordering of the graph with adjacency lists
+/// `adj`.
+///
+/// Elimination is simulated on the quotient graph: an eliminated vertex
+/// becomes an element standing for the clique of its neighbours, and absorbs
+/// the elements adjacent to it. The degree of a vertex is bounded, as in AMD,
+/// by its variable neighbours plus, for each adjacent element, the members not
+/// already counted through the newest element.
+fn minimum_degree(adj: &[Vec<usize>]) -> Vec<usize> {
+    let n = adj.len();
+    let mut vars = adj.to_vec();
+    let mut elems: Vec<Vec<usize>> = vec![Vec::new(); n];
+    let mut members: Vec<Vec<usize>> = vec![Vec::new(); n];
+    let mut eliminated = vec![false; n];
+    let mut absorbed = vec![false; n];
+    let mut degree: Vec<usize> = vars.iter().map(Vec::len).collect();
+    let mut heap: BinaryHeap<_> = (0..n).map(|i| Reverse((degree[i], i))).collect();
+
+    // `mark[i] == stamp` flags the members of the new element, and `outside[e]`
+    // counts the members of element `e` outside it when `seen[e] == stamp`.
+    let mut mark = vec![usize::MAX; n];
+    let mut seen = vec![usize::MAX; n];
+    let mut outside = vec![0; n];
+
+    let mut order = Vec::with_capacity(n);
+    while let Some(Reverse((d, p))) = heap.pop() {
+        if eliminated[p] || d != degree[p] {
+            continue;
+        }
+        let stamp = order.len();
+        order.push(p);
+        eliminated[p] = true;
+        mark[p] = stamp;
+
+        // The new element: p's variable neighbours and the members of the
+        // elements it absorbs.
+        let mut lp = Vec::new();
+        for &v in &vars[p] {
+            if !eliminated[v] && mark[v] != stamp {
+                mark[v] = stamp;
+                lp.push(v);
+            }
+        }
+        for e in std::mem::take(&mut elems[p]) {
+            if absorbed[e] {
+                continue;
+            }
+            absorbed[e] = true;
+            for v in std::mem::take(&mut members[e]) {
+                if !eliminated[v] && mark[v] != stamp {
+                    mark[v] = stamp;
+                    lp.push(v);
+                }
+            }
+        }
+        vars[p].clear();
+
+        for &i in &lp {
+            for &e in elems[i].iter().filter(|&&e| !absorbed[e]) {
+                if seen[e] != stamp {
+                    seen[e] = stamp;
+                    outside[e] = members[e].len();
+                }
+                outside[e] -= 1;
+            }
+        }
+
+        let remaining = n - order.len();
+        for &i in &lp {
+            // Elements lying entirely inside the new one are absorbed by it.
+            elems[i].retain(|&e| {
+                if !absorbed[e] && outside[e] == 0 {
+                    absorbed[e] = true;
+                    members[e].clear();
+                }
+                !absorbed[e]
+            });
+            vars[i].retain(|&v| !eliminated[v] && mark[v] != stamp);
+
+            let external = elems[i].iter().map(|&e| outside[e]).sum::<usize>();
+            degree[i] = (vars[i].len() + lp.len() - 1 + external).min(remaining - 1);
+            elems[i].push(p);
+            heap.push(Reverse((degree[i], i)));
+        }
+        members[p] = lp;
+    }
+    order
+}
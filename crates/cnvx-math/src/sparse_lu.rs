@@ -0,0 +1,355 @@
+//! Sparse LU factorisation with Markowitz pivoting.
+//!
+//! [`SparseLu`] factors a sparse square matrix as `P A Q = L U`, choosing each
+//! pivot to keep `L` and `U` sparse. Among the entries of the active submatrix
+//! that pass a threshold test for stability, it picks the one with the smallest
+//! Markowitz count `(r_i - 1)(c_j - 1)`, an upper bound on the fill-in the
+//! elimination step creates.
+//!
+//! In rank-revealing mode ([`SparseLu::factor_rank_revealing`]) a column that
+//! turns out to depend on the columns pivoted before it is replaced by the unit
+//! column of a row left without a pivot. The factorisation still succeeds and
+//! reports the replaced columns, which lets a simplex solver repair a singular
+//! basis by swapping them for slack columns.
+
+use crate::lu::EtaFile;
+use crate::matrix::{Factorisation, MatrixWrapper};
+
+/// An entry is only accepted as a pivot if its magnitude is at least this
+/// fraction of the largest magnitude in its column of the active submatrix.
+const THRESHOLD: f64 = 0.1;
+
+/// A column whose active entries are all at most this fraction of the largest
+/// magnitude in `A` is treated as dependent.
+const PIVOT_TOL: f64 = 1e-11;
+
+/// Number of active columns with the fewest entries searched for each pivot.
+const SEARCH_COLUMNS: usize = 4;
+
+/// A sparse LU factorisation `P A Q = L U` with Markowitz pivot selection and
+/// threshold partial pivoting, followed by a product of eta matrices for the
+/// columns replaced since.
+///
+/// # Examples
+///
+/// ```
+/// # use cnvx_math::{Factorisation, Matrix, SparseLu, SparseMatrix};
+/// // The third column is the sum of the first two.
+/// let a = SparseMatrix::from_triplets(
+///     3,
+///     3,
+///     &[(0, 0, 1.0), (1, 1, 2.0), (0, 2, 1.0), (1, 2, 2.0), (2, 1, 1.0), (2, 2, 1.0)],
+/// );
+/// assert!(SparseLu::factor(&a).is_err());
+///
+/// // Column 2 is replaced by the unit column of row 2, the row left without a
+/// // pivot; the factorisation solves with that matrix instead.
+/// let lu = SparseLu::factor_rank_revealing(&a).unwrap();
+/// assert_eq!(lu.dependent_columns(), &[(2, 2)]);
+///
+/// let mut rhs = vec![1.0, 2.0, 4.0];
+/// lu.solve(&mut rhs);
+/// assert_eq!(rhs, vec![1.0, 1.0, 3.0]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SparseLu {
+    /// Columns of `L` below the diagonal, one per pivot step, as
+    /// `(row of A, multiplier)` pairs; the diagonal is one.
+    l: Vec<Vec<(usize, f64)>>,
+    /// Rows of `U`, one per pivot step, as `(column of A, value)` pairs with
+    /// the pivot first.
+    u: Vec<Vec<(usize, f64)>>,
+    /// Row of `A` pivoted at each step.
+    pivot_row: Vec<usize>,
+    /// Dependent columns of `A` and the rows whose unit columns replaced them.
+    dependent: Vec<(usize, usize)>,
+    /// Eta matrices of the column replacements since the factorisation.
+    etas: EtaFile,
+}
+
+impl SparseLu {
+    /// Factor the square matrix `a`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err(String)` if `a` is not square or is (numerically)
+    /// singular.
+    pub fn factor<M: MatrixWrapper>(a: &M) -> Result<Self, String> {
+        Self::factorise(a, false)
+    }
+
+    /// Factor the square matrix `a`, replacing each dependent column by the
+    /// unit column of a row without a pivot.
+    ///
+    /// The result factors the repaired matrix; the replacements are reported by
+    /// [`dependent_columns`](Self::dependent_columns).
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err(String)` if `a` is not square.
+    pub fn factor_rank_revealing<M: MatrixWrapper>(a: &M) -> Result<Self, String> {
+        Self::factorise(a, true)
+    }
+
+    /// Dimension of the factored matrix.
+    pub fn dim(&self) -> usize {
+        self.pivot_row.len()
+    }
+
+    /// Number of non-zeros stored in `L` and `U`, including the fill-in.
+    pub fn nnz(&self) -> usize {
+        self.l.iter().chain(&self.u).map(Vec::len).sum()
+    }
+
+    /// The dependent columns found in rank-revealing mode, as `(column, row)`
+    /// pairs: column `column` of `A` was replaced by the unit column `e_row`.
+    pub fn dependent_columns(&self) -> &[(usize, usize)] {
+        &self.dependent
+    }
+
+    /// Number of column replacements since the matrix was factored.
+    pub fn updates(&self) -> usize {
+        self.etas.len()
+    }
+
+    /// Replace column `col` of the factored matrix `A` with a new column `a`,
+    /// given `d = A⁻¹ a` (as computed by [`solve`](Factorisation::solve) before
+    /// the replacement).
+    ///
+    /// # Errors
+    ///
+    /// Returns an `Err(String)` if `d[col]` is zero, i.e. the new matrix would
+    /// be singular.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `d.len()` differs from the dimension of the factorisation.
+    pub fn replace_column(&mut self, col: usize, d: &[f64]) -> Result<(), String> {
+        assert_eq!(d.len(), self.dim(), "column has the wrong dimension");
+        self.etas.push(col, d)
+    }
+
+    /// Right-looking elimination on the active submatrix, stored by rows (with
+    /// values) and by columns (pattern only).
+    fn factorise<M: MatrixWrapper>(a: &M, rank_revealing: bool) -> Result<Self, String> {
+        let n = a.rows();
+        if a.cols() != n {
+            return Err("Matrix is not square".to_string());
+        }
+
+        let mut rows: Vec<Vec<(usize, f64)>> = vec![Vec::new(); n];
+        let mut cols: Vec<Vec<usize>> = vec![Vec::new(); n];
+        let mut amax = 0.0_f64;
+        for (j, col) in cols.iter_mut().enumerate() {
+            for (i, v) in a.col(j) {
+                rows[i].push((j, v));
+                col.push(i);
+                amax = amax.max(v.abs());
+            }
+        }
+        let tol = PIVOT_TOL * amax;
+
+        let mut l = Vec::with_capacity(n);
+        let mut u = Vec::with_capacity(n);
+        let mut pivot_row = Vec::with_capacity(n);
+        let mut row_done = vec![false; n];
+        let mut active: Vec<usize> = (0..n).collect();
+        let mut dependent_cols = Vec::new();
+        // Position of each column in the row being updated.
+        let mut pos = vec![usize::MAX; n];
+
+        while !active.is_empty() {
+            let Some((p, q, pv)) = Self::choose_pivot(&rows, &cols, &active, tol) else {
+                // The sparsest remaining column has no acceptable pivot.
+                let q = Self::sparsest(&cols, &active, 1)[0];
+                if !rank_revealing {
+                    return Err("Matrix is singular".to_string());
+                }
+                for i in std::mem::take(&mut cols[q]) {
+                    rows[i].retain(|&(j, _)| j != q);
+                }
+                active.retain(|&j| j != q);
+                dependent_cols.push(q);
+                continue;
+            };
+
+            let mut prow = std::mem::take(&mut rows[p]);
+            for &(j, _) in &prow {
+                cols[j].retain(|&i| i != p);
+            }
+
+            // Eliminate column q from the other rows, creating fill-in.
+            let mut lcol = Vec::new();
+            for i in std::mem::take(&mut cols[q]) {
+                let k = rows[i].iter().position(|&(j, _)| j == q).unwrap();
+                let (_, aiq) = rows[i].swap_remove(k);
+                let m = aiq / pv;
+                lcol.push((i, m));
+
+                for (k, &(j, _)) in rows[i].iter().enumerate() {
+                    pos[j] = k;
+                }
+                for &(j, v) in prow.iter().filter(|&&(j, _)| j != q) {
+                    match pos[j] {
+                        usize::MAX => {
+                            rows[i].push((j, -m * v));
+                            cols[j].push(i);
+                        }
+                        k => rows[i][k].1 -= m * v,
+                    }
+                }
+                for &(j, _) in &rows[i] {
+                    pos[j] = usize::MAX;
+                }
+            }
+
+            let k = prow.iter().position(|&(j, _)| j == q).unwrap();
+            prow.swap(0, k);
+            l.push(lcol);
+            u.push(prow);
+            pivot_row.push(p);
+            row_done[p] = true;
+            active.retain(|&j| j != q);
+        }
+
+        // Drop the dependent columns from the rows of U and pivot their unit
+        // replacements on the rows left over.
+        if !dependent_cols.is_empty() {
+            let mut is_dependent = vec![false; n];
+            for &q in &dependent_cols {
+                is_dependent[q] = true;
+            }
+            for row in &mut u {
+                row.retain(|&(j, _)| !is_dependent[j]);
+            }
+        }
+        let spare_rows = (0..n).filter(|&i| !row_done[i]);
+        let dependent = dependent_cols.into_iter().zip(spare_rows).collect::<Vec<_>>();
+        for &(q, r) in &dependent {
+            l.push(Vec::new());
+            u.push(vec![(q, 1.0)]);
+            pivot_row.push(r);
+        }
+
+        Ok(Self {
+            l,
+            u,
+            pivot_row,
+            dependent,
+            etas: EtaFile::default(),
+        })
+    }
+
+    /// Up to `count` active columns with the fewest entries, ties broken by
+    /// column index.
+    fn sparsest(cols: &[Vec<usize>], active: &[usize], count: usize) -> Vec<usize> {
+        let mut by_count = active.to_vec();
+        if by_count.len() > count {
+            by_count.select_nth_unstable_by_key(count - 1, |&j| (cols[j].len(), j));
+            by_count.truncate(count);
+        }
+        by_count.sort_unstable_by_key(|&j| (cols[j].len(), j));
+        by_count
+    }
+
+    /// Choose a pivot `(row, column, value)` among the sparsest active columns:
+    /// the entry with the smallest Markowitz count that passes the threshold
+    /// test, preferring larger magnitudes on ties. Returns `None` if the
+    /// sparsest column has no entry larger than `tol`.
+    fn choose_pivot(
+        rows: &[Vec<(usize, f64)>],
+        cols: &[Vec<usize>],
+        active: &[usize],
+        tol: f64,
+    ) -> Option<(usize, usize, f64)> {
+        let value = |i: usize, j: usize| {
+            rows[i].iter().find(|&&(k, _)| k == j).map_or(0.0, |&(_, v)| v)
+        };
+
+        let mut best: Option<(usize, usize, f64, usize)> = None;
+        for (rank, j) in
+            Self::sparsest(cols, active, SEARCH_COLUMNS).into_iter().enumerate()
+        {
+            let col_max = cols[j].iter().map(|&i| value(i, j).abs()).fold(0.0, f64::max);
+            if col_max <= tol {
+                if rank == 0 {
+                    return None;
+                }
+                continue;
+            }
+            for &i in &cols[j] {
+                let v = value(i, j);
+                if v.abs() < THRESHOLD * col_max {
+                    continue;
+                }
+                let cost = (rows[i].len() - 1) * (cols[j].len() - 1);
+                let better = best.is_none_or(|(_, _, bv, bc)| {
+                    cost < bc || cost == bc && v.abs() > bv.abs()
+                });
+                if better {
+                    best = Some((i, j, v, cost));
+                }
+            }
+            if best.is_some_and(|(_, _, _, cost)| cost == 0) {
+                break;
+            }
+        }
+        best.map(|(i, j, v, _)| (i, j, v))
+    }
+}
+
+impl Factorisation for SparseLu {
+    fn solve(&self, rhs: &mut [f64]) {
+        let n = self.dim();
+        assert_eq!(rhs.len(), n, "right-hand side has the wrong dimension");
+
+        // Forward substitution with L, in the row numbering of A.
+        let mut y = vec![0.0; n];
+        for (k, col) in self.l.iter().enumerate() {
+            let v = rhs[self.pivot_row[k]];
+            y[k] = v;
+            if v != 0.0 {
+                for &(i, m) in col {
+                    rhs[i] -= m * v;
+                }
+            }
+        }
+
+        // Backward substitution with U, into the column numbering of A.
+        let mut x = vec![0.0; n];
+        for (k, row) in self.u.iter().enumerate().rev() {
+            let (q, pivot) = row[0];
+            let s = row[1..].iter().map(|&(j, v)| v * x[j]).sum::<f64>();
+            x[q] = (y[k] - s) / pivot;
+        }
+
+        self.etas.solve(&mut x);
+        rhs.copy_from_slice(&x);
+    }
+
+    fn solve_transpose(&self, rhs: &mut [f64]) {
+        let n = self.dim();
+        assert_eq!(rhs.len(), n, "right-hand side has the wrong dimension");
+        self.etas.solve_transpose(rhs);
+
+        // Forward substitution with Uᵀ, one row of U per step.
+        let mut w = vec![0.0; n];
+        for (k, row) in self.u.iter().enumerate() {
+            let (q, pivot) = row[0];
+            let v = rhs[q] / pivot;
+            w[k] = v;
+            if v != 0.0 {
+                for &(j, uv) in &row[1..] {
+                    rhs[j] -= uv * v;
+                }
+            }
+        }
+
+        // Backward substitution with Lᵀ, into the row numbering of A. Every
+        // row of L holds rows pivoted later, which are already solved for.
+        for (k, col) in self.l.iter().enumerate().rev() {
+            let s = col.iter().map(|&(i, m)| m * rhs[i]).sum::<f64>();
+            rhs[self.pivot_row[k]] = w[k] - s;
+        }
+    }
+}
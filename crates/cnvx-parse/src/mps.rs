@@ -73,7 +73,7 @@ impl LanguageParser for MPSLanguage {
                     let col = parts[idx].to_string();
                     let varid = *var_map
                         .entry(col.clone())
-                        .or_insert_with(|| model.add_var().finish());
+                        .or_insert_with(|| model.add_var().name(&col).finish());
                     let mut i = idx + 1;
                     while i + 1 < parts.len() {
                         let row = parts[i].to_string();
@@ -122,7 +122,7 @@ impl LanguageParser for MPSLanguage {
                     let varname = parts[idx + 2].to_string();
                     let varid = *var_map
                         .entry(varname.clone())
-                        .or_insert_with(|| model.add_var().finish());
+                        .or_insert_with(|| model.add_var().name(&varname).finish());
                     match btype {
                         "UP" => {
                             if parts.len() >= idx + 4
@@ -173,13 +173,13 @@ impl LanguageParser for MPSLanguage {
                     model.add_objective(Objective::minimize(expr).name("Z"));
                 }
                 'L' => {
-                    model += expr.leq(rhs);
+                    model += expr.leq(rhs).named(rname);
                 }
                 'G' => {
-                    model += expr.geq(rhs);
+                    model += expr.geq(rhs).named(rname);
                 }
                 'E' => {
-                    model += expr.eq(rhs);
+                    model += expr.eq(rhs).named(rname);
                 }
                 _ => {}
             }
@@ -74,9 +74,14 @@ pub mod prelude {
 pub mod solvers {
     #[cfg(feature = "lp")]
     pub use crate::lp::{
+        AdmmSolver,
+        ConicSolver,
         DualSimplexSolver,
+        InteriorPointSolver,
         LpSolver,
+        MipSolver,
         PrimalSimplexSolver,
+        QpSolver,
         Solver, // Trait
     };
 }
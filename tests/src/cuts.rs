@@ -0,0 +1,196 @@
+use cnvx_core::SolveStatus;
+use cnvx_lp::mip::cuts::{self, Cut, CutFamily};
+use cnvx_lp::{
+    LinExpr, LpModel, MipSolver, Objective, PrimalSimplexSolver, PrimalSimplexState,
+    Solver, VarId,
+};
+use cnvx_math::DenseMatrix;
+
+// Tolerance for cut violations and objective comparison
+const TOL: f64 = 1e-6;
+
+// Build `Σ coeff * x_j` from `(j, coeff)` pairs
+fn expr(vars: &[VarId], terms: &[(usize, f64)]) -> LinExpr {
+    terms.iter().fold(LinExpr::default(), |e, &(j, c)| e + c * vars[j])
+}
+
+// Integer variables in `[0, ub]`.
+fn integer_vars(model: &mut LpModel, ub: &[f64]) -> Vec<VarId> {
+    ub.iter()
+        .map(|&ub| model.add_var().integer().upper_bound(ub).finish())
+        .collect()
+}
+
+// A pure integer program whose LP optimum (1, 1.5) has a fractional basic
+// variable.
+fn gomory_lp() -> LpModel {
+    let mut model = LpModel::new();
+    let x = integer_vars(&mut model, &[3.0, 3.0]);
+    model += expr(&x, &[(0, 3.0), (1, 2.0)]).leq(6.0);
+    model += expr(&x, &[(0, -3.0), (1, 2.0)]).leq(0.0);
+    model.add_objective(Objective::maximize(expr(&x, &[(1, 1.0)])).name("y"));
+    model
+}
+
+// A mixed-integer program whose row `2x - s <= 3` gives the MIR cut
+// `x - s <= 1`, violated by the LP optimum (1.5, 0).
+fn mir_lp() -> LpModel {
+    let mut model = LpModel::new();
+    let x = vec![
+        model.add_var().integer().upper_bound(5.0).finish(),
+        model.add_var().finish(),
+    ];
+    model += expr(&x, &[(0, 2.0), (1, -1.0)]).leq(3.0);
+    model.add_objective(
+        Objective::maximize(expr(&x, &[(0, 1.0), (1, -0.6)])).name("profit"),
+    );
+    model
+}
+
+// A binary knapsack whose LP optimum takes item 0 fractionally.
+fn knapsack_lp() -> LpModel {
+    let mut model = LpModel::new();
+    let x = integer_vars(&mut model, &[1.0; 4]);
+    model += expr(&x, &[(0, 4.0), (1, 3.0), (2, 3.0), (3, 2.0)]).leq(6.0);
+    model += expr(&x, &[(0, 2.0), (2, -3.0), (3, 4.0)]).leq(3.0);
+    let value = [(0, 5.0), (1, 4.0), (2, 3.0), (3, 2.0)];
+    model.add_objective(Objective::maximize(expr(&x, &value)).name("value"));
+    model
+}
+
+// A general integer program with equality and `>=` rows that needs branching.
+fn production_lp() -> LpModel {
+    let mut model = LpModel::new();
+    let mut x = integer_vars(&mut model, &[4.0, 4.0, 4.0, 4.0]);
+    x.push(model.add_var().upper_bound(3.0).finish());
+    model += expr(&x, &[(0, 3.0), (1, 5.0), (2, 2.0), (3, 4.0), (4, 1.0)]).leq(14.5);
+    model += expr(&x, &[(0, 2.0), (1, 1.0), (3, 3.0)]).geq(2.5);
+    model += expr(&x, &[(1, 1.0), (2, 1.0), (4, -1.0)]).eq(2.25);
+    model += expr(&x, &[(0, 4.0), (2, 3.0), (3, 1.0), (4, 2.0)]).leq(11.0);
+    let profit = [(0, 6.0), (1, 7.0), (2, 3.0), (3, 5.0), (4, 1.5)];
+    model.add_objective(Objective::maximize(expr(&x, &profit)).name("profit"));
+    model
+}
+
+// The LP relaxation optimum of `model` and its final simplex state.
+fn lp_optimum(model: &LpModel) -> (Vec<f64>, PrimalSimplexState<DenseMatrix>) {
+    let mut state = PrimalSimplexState::<DenseMatrix>::new(model);
+    state.solve_lp(1000, 1e-9).expect("LP relaxation failed");
+    assert_eq!(state.status, SolveStatus::Optimal);
+    let (mut x, _) = state.solution();
+    x.truncate(model.vars().len());
+    (x, state)
+}
+
+// Every assignment of the integer variables of `model` within their bounds.
+fn integer_points(model: &LpModel) -> Vec<Vec<(usize, f64)>> {
+    model.vars().iter().enumerate().filter(|(_, v)| v.is_integer).fold(
+        vec![Vec::new()],
+        |points, (j, v)| {
+            let (lb, ub) = (v.lb.unwrap_or(0.0), v.ub.expect("unbounded integer"));
+            points
+                .iter()
+                .flat_map(|p| {
+                    (lb as i64..=ub as i64).map(move |k| {
+                        let mut p = p.clone();
+                        p.push((j, k as f64));
+                        p
+                    })
+                })
+                .collect()
+        },
+    )
+}
+
+// Assert that no point of `model` with integral integer variables violates
+// `cut`: for each integer assignment, the largest value of the cut's left-hand
+// side over the continuous variables is at most its right-hand side.
+fn assert_valid(model: &LpModel, cut: &Cut) {
+    for point in integer_points(model) {
+        let mut fixed = model.clone();
+        for &(j, v) in &point {
+            fixed.vars[j].lb = Some(v);
+            fixed.vars[j].ub = Some(v);
+            fixed.vars[j].is_integer = false;
+        }
+        fixed.objectives.clear();
+        fixed.add_objective(Objective::maximize(cut.to_constraint().expr).name("cut"));
+
+        let solution = PrimalSimplexSolver::new().solve(&fixed).expect("simplex failed");
+        match solution.status {
+            SolveStatus::Infeasible => {}
+            SolveStatus::Optimal => {
+                let lhs = solution.objective_value.unwrap();
+                assert!(
+                    lhs <= cut.rhs + TOL,
+                    "{cut:?} cuts off {point:?}: left-hand side {lhs}"
+                );
+            }
+            status => panic!("{cut:?} at {point:?}: {status}"),
+        }
+    }
+}
+
+// Separate the cuts of `family` at the LP optimum of `model`, and check that
+// each one is valid and cuts off that optimum.
+fn check_family(model: &LpModel, family: CutFamily) -> usize {
+    let (x, state) = lp_optimum(model);
+    let found = match family {
+        CutFamily::Gomory => {
+            cuts::gomory_cuts(&state, model).expect("Gomory cuts failed")
+        }
+        CutFamily::Mir => cuts::mir_cuts(model, &x),
+        CutFamily::KnapsackCover => cuts::cover_cuts(model, &x),
+    };
+    for cut in &found {
+        assert_eq!(cut.family, family);
+        assert!(cut.violation(&x) > TOL, "{cut:?} does not cut off {x:?}");
+        assert_valid(model, cut);
+    }
+    found.len()
+}
+
+fn models() -> Vec<LpModel> {
+    vec![gomory_lp(), mir_lp(), knapsack_lp(), production_lp()]
+}
+
+#[test]
+fn gomory_cuts_are_valid() {
+    let found: Vec<usize> =
+        models().iter().map(|m| check_family(m, CutFamily::Gomory)).collect();
+    assert!(found[0] > 0, "no Gomory cut for gomory_lp");
+}
+
+#[test]
+fn mir_cuts_are_valid() {
+    let found: Vec<usize> =
+        models().iter().map(|m| check_family(m, CutFamily::Mir)).collect();
+    assert!(found[1] > 0, "no MIR cut for mir_lp");
+}
+
+#[test]
+fn cover_cuts_are_valid() {
+    let found: Vec<usize> = models()
+        .iter()
+        .map(|m| check_family(m, CutFamily::KnapsackCover))
+        .collect();
+    assert!(found[2] > 0, "no cover cut for knapsack_lp");
+}
+
+#[test]
+fn branch_and_bound_with_cuts() {
+    for model in models() {
+        let mut plain = MipSolver::new();
+        plain.cut_rounds = 0;
+        let expected = plain.solve(&model).expect("MIP solver failed");
+        assert_eq!(expected.status, SolveStatus::Optimal);
+
+        let mut solver = MipSolver::new();
+        let solution = solver.solve(&model).expect("MIP solver failed");
+        assert_eq!(solution.status, SolveStatus::Optimal);
+        assert!(solver.stats().cuts.total() > 0, "no cuts added");
+        let (a, b) =
+            (expected.objective_value.unwrap(), solution.objective_value.unwrap());
+        assert!((a - b).abs() < TOL, "objective {b}, without cuts {a}");
+    }
+}
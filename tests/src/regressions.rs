@@ -0,0 +1,311 @@
+use cnvx_core::{SolveError, SolveStatus};
+use cnvx_lp::{
+    AdmmSolver, ConicSolver, DualSimplexSolver, InteriorPointSolver, LinExpr, LpModel,
+    LpSolver, Objective, PrimalSimplexSolver, QpSolver, Scaling, Solver, VarId,
+};
+
+// Tolerance for objective comparison
+const TOL: f64 = 1e-6;
+
+// Build `Σ coeff * x_j` from `(j, coeff)` pairs
+fn expr(vars: &[VarId], terms: &[(usize, f64)]) -> LinExpr {
+    terms.iter().fold(LinExpr::default(), |e, &(j, c)| e + c * vars[j])
+}
+
+// An LP whose free columns made the first KKT factorisation of the QP
+// interior-point method break down.
+fn free_columns_lp() -> LpModel {
+    let mut model = LpModel::new();
+    let x: Vec<VarId> = vec![
+        model.add_var().free().finish(),
+        model.add_var().free().finish(),
+        model.add_var().upper_bound(5.1035).finish(),
+        model.add_var().upper_bound(5.828).finish(),
+        model.add_var().free().finish(),
+        model.add_var().finish(),
+    ];
+    model += expr(&x, &[(0, 1.9312), (2, -0.1684), (4, -0.0324), (5, 0.5688)]).leq(0.952);
+    model += expr(&x, &[(0, 0.848), (1, -0.1148), (4, 1.7728)]).eq(1.111);
+    model += expr(&x, &[(1, 1.5232), (2, 1.4924), (4, 1.206), (5, 1.3464)]).leq(5.595);
+    model += expr(&x, &[(0, 1.4396), (1, -0.2676), (4, 2.3712)]).eq(1.265);
+    model += expr(&x, &[(3, -0.4532), (5, -0.64)]).leq(3.989);
+    model +=
+        expr(&x, &[(0, 2.1696), (1, 1.4152), (2, -0.8512), (4, 2.1308), (5, 0.8804)])
+            .geq(2.551);
+    let profit =
+        [(0, 0.688), (1, 0.7692), (2, 0.6898), (3, 0.0434), (4, 0.1294), (5, -0.7388)];
+    model.add_objective(Objective::maximize(expr(&x, &profit)).name("profit"));
+    model
+}
+
+// An LP on which the KKT solve of the QP interior-point method returned a
+// non-finite direction, leaving the next iterate undefined.
+fn non_finite_direction_lp() -> LpModel {
+    let mut model = LpModel::new();
+    let x: Vec<VarId> = (0..8)
+        .map(|j| match j {
+            0 | 4 | 6 | 7 => model.add_var().free().finish(),
+            _ => model.add_var().finish(),
+        })
+        .collect();
+    model += expr(&x, &[(1, 0.9064), (5, 1.5292), (7, -0.8412)]).leq(7.452);
+    model += expr(
+        &x,
+        &[(0, -0.6836), (1, 0.408), (2, 1.4332), (3, 1.1412), (4, 1.9112), (7, 1.9856)],
+    )
+    .leq(3.649);
+    model +=
+        expr(&x, &[(2, -0.754), (3, 1.6924), (4, 2.1112), (5, -0.6764), (6, -0.994)])
+            .leq(6.593);
+    model += expr(&x, &[(2, -0.2676), (3, -0.3528), (5, 2.624)]).leq(5.587);
+    model +=
+        expr(&x, &[(0, 1.8468), (2, 2.5868), (3, -0.5664), (4, 2.0348), (7, -0.666)])
+            .geq(6.508);
+    model += expr(&x, &[(0, -0.8516), (2, 2.1848), (4, -0.2196), (7, 1.2236)]).leq(0.899);
+    model += expr(&x, &[(0, 2.8548), (3, -0.4188), (7, 0.3356)]).eq(7.116);
+    model += expr(&x, &[(0, 2.35), (1, -0.9124), (2, -0.1112), (6, 1.7612)]).geq(5.105);
+    let cost = [
+        (0, -0.6088),
+        (1, -0.5758),
+        (2, -0.5436),
+        (3, 0.9724),
+        (4, -0.586),
+        (5, 0.1942),
+        (6, 0.988),
+        (7, -0.6998),
+    ];
+    model.add_objective(Objective::minimize(expr(&x, &cost)).name("cost"));
+    model
+}
+
+// An infeasible LP on which the step length of the conic interior-point method
+// vanished before its iterates met the infeasibility test.
+fn infeasible_lp() -> LpModel {
+    let mut model = LpModel::new();
+    let x: Vec<VarId> = vec![
+        model.add_var().lower_bound(1.0).upper_bound(1.0).finish(),
+        model.add_var().free().finish(),
+        model.add_var().finish(),
+        model.add_var().free().finish(),
+    ];
+    model += expr(&x, &[(0, 2.7268), (1, -0.4872), (2, 0.096), (3, 0.4368)]).eq(4.616);
+    model += expr(&x, &[(0, -0.7764), (2, 2.7104)]).leq(-0.029);
+    model += expr(&x, &[(0, 1.2556), (1, 2.9208), (2, 1.4264), (3, 1.6216)]).leq(6.536);
+    model += expr(&x, &[(0, 2.904), (2, 2.5644), (3, 0.9196)]).eq(-0.251);
+    model += expr(&x, &[(0, 2.6288), (1, 1.9928), (2, -0.0788), (3, 2.3288)]).geq(-1.716);
+    model += expr(&x, &[(0, 0.582), (2, 2.3204), (3, 1.9112)]).leq(6.781);
+    model += expr(&x, &[(0, -0.5116), (1, 1.6564), (2, -0.206), (3, 2.8728)]).geq(4.205);
+    let profit = [(0, -0.055), (1, 0.179), (2, -0.5492), (3, -0.9134)];
+    model.add_objective(Objective::maximize(expr(&x, &profit)).name("profit"));
+    model
+}
+
+// An unbounded LP on which the conic interior-point method failed to
+// factorise the KKT matrix once its iterates grew along the ray.
+fn unbounded_lp() -> LpModel {
+    let mut model = LpModel::new();
+    let x: Vec<VarId> = (0..8)
+        .map(|j| match j {
+            0 | 5 | 6 => model.add_var().free().finish(),
+            7 => model.add_var().upper_bound(3.858).finish(),
+            _ => model.add_var().finish(),
+        })
+        .collect();
+    model += expr(&x, &[(1, 1.582), (4, 2.88), (7, 2.9148)]).eq(3.624);
+    model += expr(&x, &[(1, 1.6216), (2, 0.0076), (4, 1.1468), (5, 0.186), (7, -0.6036)])
+        .leq(1.478);
+    model +=
+        expr(&x, &[(0, -0.0912), (1, 0.0848), (2, -0.2576), (4, 1.8516), (6, -0.5488)])
+            .leq(2.657);
+    model += expr(&x, &[(1, 2.8948), (4, 2.668), (7, 1.2824)]).leq(3.72);
+    model += expr(
+        &x,
+        &[(1, 0.838), (2, 0.2332), (3, -0.5836), (5, 2.7576), (6, 1.5936), (7, 0.9812)],
+    )
+    .leq(5.136);
+    model +=
+        expr(&x, &[(0, 1.3636), (2, -0.1656), (4, 0.0436), (6, 0.7672), (7, 1.8548)])
+            .leq(-0.656);
+    model += expr(&x, &[(0, 1.696), (1, 2.0796), (2, 2.1328), (6, 0.2348), (7, 1.0376)])
+        .eq(3.993);
+    let cost = [
+        (0, -0.9292),
+        (1, -0.0064),
+        (2, 0.8994),
+        (3, -0.1168),
+        (4, -0.875),
+        (5, 0.5186),
+        (6, 0.3748),
+        (7, 0.2886),
+    ];
+    model.add_objective(Objective::minimize(expr(&x, &cost)).name("cost"));
+    model
+}
+
+#[test]
+fn qp_solver_lp_with_free_columns() {
+    let solution = QpSolver::new().solve(&free_columns_lp()).expect("QP solver failed");
+    assert_eq!(solution.status, SolveStatus::Optimal);
+    let obj = solution.objective_value.expect("no objective value");
+    assert!((obj - 3.107939200499899).abs() < TOL, "objective {obj}");
+}
+
+#[test]
+fn qp_solver_lp_with_non_finite_direction() {
+    let solution = QpSolver::new()
+        .solve(&non_finite_direction_lp())
+        .expect("QP solver failed");
+    assert_eq!(solution.status, SolveStatus::Optimal);
+    let obj = solution.objective_value.expect("no objective value");
+    assert!((obj - -4.80370670574899).abs() < TOL, "objective {obj}");
+}
+
+#[test]
+fn conic_solver_infeasible_lp() {
+    let model = infeasible_lp();
+    let solution = ConicSolver::new().solve(&model).expect("conic solver failed");
+    assert_eq!(solution.status, SolveStatus::Infeasible);
+    let certificate = solution.certificate.expect("no certificate");
+    assert!(certificate.verify(&model, TOL).is_ok());
+}
+
+#[test]
+fn conic_solver_unbounded_lp() {
+    let model = unbounded_lp();
+    let solution = ConicSolver::new().solve(&model).expect("conic solver failed");
+    assert_eq!(solution.status, SolveStatus::Unbounded);
+    let certificate = solution.certificate.expect("no certificate");
+    assert!(certificate.verify(&model, TOL).is_ok());
+}
+
+#[test]
+fn sensitivity_of_model_edited_after_solve() {
+    let mut model = LpModel::new();
+    let x = model.add_var().finish();
+    let y = model.add_var().finish();
+    model += (x + y).leq(4.0);
+    model.add_objective(Objective::maximize(3.0 * x + 2.0 * y).name("profit"));
+
+    let mut solver = LpSolver::new();
+    solver.solve(&model).expect("LP solver failed");
+    assert!(solver.sensitivity(&model).is_ok());
+
+    model += (x + -1.0 * y).leq(1.0);
+    let err = solver
+        .sensitivity(&model)
+        .expect_err("sensitivity of an edited model");
+    assert!(matches!(err, SolveError::InvalidModel(_)), "{err}");
+}
+
+#[test]
+fn primal_simplex_infeasible_lp_has_no_objective_value() {
+    let mut model = LpModel::new();
+    let x = model.add_var().finish();
+    let y = model.add_var().finish();
+    model += (x + y).leq(1.0);
+    model += (x + y).geq(2.0);
+    model.add_objective(Objective::maximize(3.0 * x + 2.0 * y).name("profit"));
+
+    let solution = PrimalSimplexSolver::new().solve(&model).expect("simplex failed");
+    assert_eq!(solution.status, SolveStatus::Infeasible);
+    assert!(solution.objective_value.is_none(), "{solution}");
+}
+
+#[test]
+fn dual_simplex_scaling_keeps_solution() {
+    // Rows whose coefficients differ by six orders of magnitude.
+    let mut model = LpModel::new();
+    let x = model.add_var().finish();
+    let y = model.add_var().finish();
+    model += (1000.0 * x + 2000.0 * y).geq(3000.0);
+    model += (0.001 * x + 0.004 * y).geq(0.002);
+    model.add_objective(Objective::minimize(3.0 * x + 5.0 * y).name("cost"));
+
+    let solve = |scaling| {
+        let mut solver = DualSimplexSolver::new();
+        solver.scaling = scaling;
+        solver.solve(&model).expect("dual simplex failed")
+    };
+    let plain = solve(Scaling::None);
+    for scaling in [Scaling::GeometricMean, Scaling::Equilibration] {
+        let scaled = solve(scaling);
+        assert_eq!(scaled.status, SolveStatus::Optimal);
+        let (a, b) = (plain.objective_value.unwrap(), scaled.objective_value.unwrap());
+        assert!((a - b).abs() < TOL, "objective {b}, unscaled {a}");
+        for (a, b) in plain.duals.iter().zip(&scaled.duals) {
+            assert!((a - b).abs() < TOL, "dual {b}, unscaled {a}");
+        }
+    }
+}
+
+#[test]
+fn admm_solver_iteration_limit() {
+    let model = free_columns_lp();
+    let mut solver = AdmmSolver::new();
+    solver.max_iter = 10;
+    let solution = solver.solve(&model).expect("ADMM solver failed");
+    assert!(matches!(solution.status, SolveStatus::Other(_)), "{}", solution.status);
+    assert_eq!(solution.values.len(), model.vars().len());
+}
+
+#[test]
+fn admm_solver_certificates() {
+    for (model, status) in [
+        (infeasible_lp(), SolveStatus::Infeasible),
+        (unbounded_lp(), SolveStatus::Unbounded),
+    ] {
+        let solution = AdmmSolver::new().solve(&model).expect("ADMM solver failed");
+        assert_eq!(solution.status, status);
+        let certificate = solution.certificate.expect("no certificate");
+        assert!(certificate.verify(&model, TOL).is_ok());
+    }
+}
+
+#[test]
+fn dual_simplex_sparse_model() {
+    // A transportation-like LP with 5% nonzero coefficients, stored sparse.
+    let n = 40;
+    let mut model = LpModel::new();
+    let x: Vec<VarId> = (0..n).map(|_| model.add_var().finish()).collect();
+    for i in 0..n {
+        let terms = [(i, 1.0), ((i + 1) % n, 2.0)];
+        model += expr(&x, &terms).geq(1.0 + (i % 3) as f64);
+    }
+    let cost: Vec<(usize, f64)> = (0..n).map(|j| (j, 1.0 + (j % 5) as f64)).collect();
+    model.add_objective(Objective::minimize(expr(&x, &cost)).name("cost"));
+
+    let mut primal = PrimalSimplexSolver::new();
+    let mut dual = DualSimplexSolver::new();
+    for _ in 0..2 {
+        let expected = primal.resolve(&model).expect("primal simplex failed");
+        let solution = dual.resolve(&model).expect("dual simplex failed");
+        assert_eq!(solution.status, SolveStatus::Optimal);
+        let (a, b) =
+            (expected.objective_value.unwrap(), solution.objective_value.unwrap());
+        assert!((a - b).abs() < TOL, "objective {b}, primal simplex {a}");
+        model += expr(&x, &[(0, 1.0), (n / 2, 1.0)]).geq(5.0);
+    }
+    assert!(dual.has_warm_start());
+}
+
+#[test]
+fn interior_point_crossover_size_limit() {
+    let n = 100;
+    let mut model = LpModel::new();
+    let x: Vec<VarId> = (0..n).map(|_| model.add_var().finish()).collect();
+    for i in 0..7001 {
+        model += expr(&x, &[(i % n, 1.0)]).leq(1.0 + i as f64);
+    }
+    let profit: Vec<(usize, f64)> = (0..n).map(|j| (j, 1.0)).collect();
+    model.add_objective(Objective::maximize(expr(&x, &profit)).name("profit"));
+
+    let mut solver = InteriorPointSolver::new();
+    assert!(solver.supports(&model));
+    solver.crossover = true;
+    assert!(!solver.supports(&model));
+    let err = solver.capabilities().check(&model).expect_err("oversized model");
+    assert!(err.contains("7001 constraints"), "{err}");
+
+    let selection = LpSolver::new().selection_for(&model).expect("no solver");
+    assert_ne!(selection.solver, "interior-point");
+}
@@ -0,0 +1,135 @@
+use cnvx_core::{SolveError, SolveStatus};
+use cnvx_lp::{
+    LinExpr, LpModel, Objective, PricingRule, PrimalSimplexSolver, PrimalSimplexState,
+    Solver, TieBreak, VarId,
+};
+use cnvx_math::DenseMatrix;
+
+// Tolerance for objective comparison
+const TOL: f64 = 1e-9;
+
+// Build `Σ coeff * x_j` from `(j, coeff)` pairs
+fn expr(vars: &[VarId], terms: &[(usize, f64)]) -> LinExpr {
+    terms.iter().fold(LinExpr::default(), |e, &(j, c)| e + c * vars[j])
+}
+
+// Beale's example, with its second row scaled by 1/4 so that the largest-pivot
+// tie-break leaves from the first row like the textbook cycle. Optimum -5/4.
+fn beale_lp() -> LpModel {
+    let mut model = LpModel::new();
+    let x: Vec<VarId> = (0..4).map(|_| model.add_var().finish()).collect();
+    model += expr(&x, &[(0, 0.25), (1, -8.0), (2, -1.0), (3, 9.0)]).leq(0.0);
+    model += expr(&x, &[(0, 0.125), (1, -3.0), (2, -0.125), (3, 0.75)]).leq(0.0);
+    model += expr(&x, &[(2, 1.0)]).leq(1.0);
+    let cost = [(0, -0.75), (1, 20.0), (2, -0.5), (3, 6.0)];
+    model.add_objective(Objective::minimize(expr(&x, &cost)).name("cost"));
+    model
+}
+
+// Kuhn's cycling example. Optimum -2.
+fn kuhn_lp() -> LpModel {
+    let mut model = LpModel::new();
+    let x: Vec<VarId> = (0..4).map(|_| model.add_var().finish()).collect();
+    model += expr(&x, &[(0, -2.0), (1, -9.0), (2, 1.0), (3, 9.0)]).leq(0.0);
+    model += expr(&x, &[(0, 1.0 / 3.0), (1, 1.0), (2, -1.0 / 3.0), (3, -2.0)]).leq(0.0);
+    model += expr(&x, &[(0, 2.0), (1, 3.0), (2, -1.0), (3, -12.0)]).leq(2.0);
+    let cost = [(0, -2.0), (1, -3.0), (2, 1.0), (3, 12.0)];
+    model.add_objective(Objective::minimize(expr(&x, &cost)).name("cost"));
+    model
+}
+
+// A degenerate 5 × 6 transportation problem.
+fn transportation_lp() -> LpModel {
+    let (supply, demand) = ([4.0, 6.0, 3.0, 5.0, 2.0], [3.0, 2.0, 5.0, 4.0, 3.0, 3.0]);
+    let mut model = LpModel::new();
+    let x: Vec<Vec<VarId>> = supply
+        .iter()
+        .map(|_| demand.iter().map(|_| model.add_var().finish()).collect())
+        .collect();
+    for (i, &s) in supply.iter().enumerate() {
+        model +=
+            expr(&x[i], &(0..demand.len()).map(|j| (j, 1.0)).collect::<Vec<_>>()).leq(s);
+    }
+    for (j, &d) in demand.iter().enumerate() {
+        let column: Vec<VarId> = x.iter().map(|row| row[j]).collect();
+        model +=
+            expr(&column, &(0..supply.len()).map(|i| (i, 1.0)).collect::<Vec<_>>()).eq(d);
+    }
+    let mut cost = LinExpr::default();
+    for (i, row) in x.iter().enumerate() {
+        for (j, &var) in row.iter().enumerate() {
+            cost += ((3 * i + 5 * j) % 7 + 1) as f64 * var;
+        }
+    }
+    model.add_objective(Objective::minimize(cost).name("cost"));
+    model
+}
+
+// Solve `model` with the simplex state configured by `configure`.
+fn solve(
+    model: &LpModel,
+    configure: impl Fn(&mut PrimalSimplexState<DenseMatrix>),
+) -> Result<f64, SolveError> {
+    let mut state = PrimalSimplexState::<DenseMatrix>::new(model);
+    configure(&mut state);
+    let (_, obj) = state.solve_lp(1000, 1e-9)?;
+    assert_eq!(state.status, SolveStatus::Optimal);
+    Ok(obj)
+}
+
+#[test]
+fn cycling_lps_cycle_without_anti_cycling() {
+    for model in [beale_lp(), kuhn_lp()] {
+        let result = solve(&model, |state| {
+            state.tie_break = TieBreak::Pivot;
+            state.perturb_after = usize::MAX;
+            state.bland_after = usize::MAX;
+        });
+        assert!(result.is_err(), "no cycle: {result:?}");
+    }
+}
+
+#[test]
+fn tie_breaks_on_cycling_lps() {
+    // Each tie-break, with only the safeguard it relies on, if any.
+    let cases = [
+        (TieBreak::Lexicographic, usize::MAX, usize::MAX),
+        (TieBreak::Bland, usize::MAX, 0),
+        (TieBreak::Pivot, 20, usize::MAX),
+        (TieBreak::Pivot, usize::MAX, 50),
+    ];
+    for (model, optimum) in [(beale_lp(), -1.25), (kuhn_lp(), -2.0)] {
+        for (tie_break, perturb_after, bland_after) in cases {
+            let obj = solve(&model, |state| {
+                state.tie_break = tie_break;
+                state.perturb_after = perturb_after;
+                state.bland_after = bland_after;
+            })
+            .unwrap_or_else(|e| {
+                panic!("{tie_break:?}, {perturb_after}, {bland_after}: {e}")
+            });
+            assert!((obj - optimum).abs() < TOL, "{tie_break:?}: objective {obj}");
+        }
+    }
+}
+
+#[test]
+fn pricing_rules_match_dantzig() {
+    for model in [beale_lp(), kuhn_lp(), transportation_lp()] {
+        let solve = |pricing| {
+            let mut solver = PrimalSimplexSolver::new();
+            solver.pricing = pricing;
+            let solution = solver.solve(&model).expect("simplex failed");
+            assert_eq!(solution.status, SolveStatus::Optimal);
+            solution.objective_value.unwrap()
+        };
+        let expected = solve(PricingRule::Dantzig);
+        for pricing in [PricingRule::Devex, PricingRule::SteepestEdge] {
+            let obj = solve(pricing);
+            assert!(
+                (obj - expected).abs() < TOL,
+                "{pricing:?}: {obj}, Dantzig {expected}"
+            );
+        }
+    }
+}
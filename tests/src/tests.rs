@@ -1 +1,4 @@
+pub mod cuts;
 pub mod netlib;
+pub mod regressions;
+pub mod simplex;